@@ -0,0 +1,115 @@
+//!
+//! The EVM address validation.
+//!
+
+use sha3::Digest;
+
+///
+/// The EVM address validation.
+///
+/// The malformed addresses are rejected at compile time, since they otherwise surface as
+/// linking or deployment failures. The mixed-case addresses must have the valid EIP-55
+/// checksum, whereas the all-lowercase and all-uppercase ones are accepted as is.
+///
+#[derive(Debug)]
+pub struct Address;
+
+impl Address {
+    /// The address length in hexadecimal digits.
+    pub const HEX_LENGTH: usize = 40;
+
+    ///
+    /// Validates the `0x`-prefixed `address`, returning its hexadecimal digits.
+    ///
+    pub fn validate(address: &str) -> anyhow::Result<&str> {
+        let digits = address
+            .strip_prefix("0x")
+            .ok_or_else(|| anyhow::anyhow!("the `0x` prefix is missing"))?;
+        if let Some(character) = digits
+            .chars()
+            .find(|character| !character.is_ascii_hexdigit())
+        {
+            anyhow::bail!("found the non-hexadecimal character `{}`", character);
+        }
+        if digits.len() != Self::HEX_LENGTH {
+            anyhow::bail!(
+                "expected {} hexadecimal digits, found {}",
+                Self::HEX_LENGTH,
+                digits.len()
+            );
+        }
+
+        let is_mixed_case = digits
+            .chars()
+            .any(|character| character.is_ascii_lowercase())
+            && digits
+                .chars()
+                .any(|character| character.is_ascii_uppercase());
+        if is_mixed_case {
+            let checksummed = Self::checksum(digits);
+            if digits != checksummed {
+                anyhow::bail!(
+                    "the EIP-55 checksum is invalid, the correctly checksummed address is `0x{}`",
+                    checksummed
+                );
+            }
+        }
+
+        Ok(digits)
+    }
+
+    ///
+    /// Returns the EIP-55 checksummed hexadecimal digits of the address.
+    ///
+    pub fn checksum(digits: &str) -> String {
+        let lowercase = digits.to_ascii_lowercase();
+        let hash = hex::encode(sha3::Keccak256::digest(lowercase.as_bytes()));
+        lowercase
+            .chars()
+            .zip(hash.chars())
+            .map(|(character, nibble)| {
+                if nibble >= '8' {
+                    character.to_ascii_uppercase()
+                } else {
+                    character
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Address;
+
+    #[test]
+    fn valid() {
+        for address in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED",
+        ] {
+            assert!(Address::validate(address).is_ok(), "{address}");
+        }
+    }
+
+    #[test]
+    fn invalid_checksum() {
+        let error = Address::validate("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD")
+            .expect_err("Always invalid");
+        assert!(error
+            .to_string()
+            .contains("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+    }
+
+    #[test]
+    fn malformed() {
+        for address in [
+            "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea",
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beazz",
+        ] {
+            assert!(Address::validate(address).is_err(), "{address}");
+        }
+    }
+}
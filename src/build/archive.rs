@@ -0,0 +1,124 @@
+//!
+//! The compressed artifact archive.
+//!
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::manifest::Manifest;
+
+///
+/// The compressed artifact archive.
+///
+/// A gzip-compressed tarball of the artifacts attested by the build manifest, the manifest itself
+/// and, if the build is signed, its detached signature, so the build can be passed between CI
+/// stages or attached to a release as a single file, and verified on the other side. The
+/// verification bundles are written along with the archived artifacts, so they are attested and
+/// archived as well.
+///
+#[derive(Debug)]
+pub struct Archive;
+
+impl Archive {
+    /// The archive file name.
+    pub const FILE_NAME: &'static str = "artifacts.zkbuild";
+
+    ///
+    /// Packs the `manifest` subjects and the manifest files in `output_directory` into the
+    /// archive, which is written to the same directory. Returns the archive path.
+    ///
+    /// The other files in the directory, like the stale artifacts of earlier runs, are left out.
+    ///
+    pub fn write(output_directory: &Path, manifest: &Manifest) -> anyhow::Result<PathBuf> {
+        let mut names: Vec<&str> = manifest
+            .statement
+            .subject
+            .iter()
+            .map(|subject| subject.name.as_str())
+            .collect();
+        names.push(Manifest::FILE_NAME);
+        if manifest.public_key.is_some() {
+            names.push(Manifest::SIGNATURE_FILE_NAME);
+        }
+        names.sort_unstable();
+
+        let archive_path = output_directory.join(Self::FILE_NAME);
+        let file = std::fs::File::create(archive_path.as_path()).map_err(|error| {
+            anyhow::anyhow!("Archive {:?} creating error: {}", archive_path, error)
+        })?;
+        let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::best());
+        let mut builder = tar::Builder::new(encoder);
+        builder.mode(tar::HeaderMode::Deterministic);
+
+        for name in names.into_iter() {
+            let path = output_directory.join(name);
+            builder
+                .append_path_with_name(path.as_path(), name)
+                .map_err(|error| {
+                    anyhow::anyhow!("Archive {:?} writing error: {}", archive_path, error)
+                })?;
+        }
+        builder
+            .into_inner()
+            .and_then(|encoder| encoder.finish())
+            .map_err(|error| {
+                anyhow::anyhow!("Archive {:?} writing error: {}", archive_path, error)
+            })?;
+
+        Ok(archive_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::Archive;
+    use crate::build::manifest::Manifest;
+
+    #[test]
+    fn write() {
+        let directory = std::env::temp_dir().join(format!(
+            "zksolc-archive-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        std::fs::create_dir_all(directory.join("Test.sol")).expect("Always valid");
+        let artifact = directory.join("Test.sol").join("Test.zbin");
+        std::fs::write(artifact.as_path(), b"bytecode").expect("Always valid");
+        std::fs::write(directory.join("Test.sol").join("Stale.zbin"), b"stale")
+            .expect("Always valid");
+        let manifest = Manifest::try_new(
+            directory.as_path(),
+            &[artifact],
+            &BTreeMap::new(),
+            BTreeMap::new(),
+            BTreeMap::new(),
+            None,
+        )
+        .expect("Always valid");
+        manifest
+            .write(directory.as_path(), None)
+            .expect("Always valid");
+
+        let archive_path = Archive::write(directory.as_path(), &manifest).expect("Always valid");
+        let file = std::fs::File::open(archive_path).expect("Always valid");
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+        let mut names: Vec<String> = archive
+            .entries()
+            .expect("Always valid")
+            .map(|entry| {
+                entry
+                    .expect("Always valid")
+                    .path()
+                    .expect("Always valid")
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        names.sort();
+        std::fs::remove_dir_all(directory.as_path()).expect("Always valid");
+
+        assert_eq!(names, vec![Manifest::FILE_NAME, "Test.sol/Test.zbin"]);
+    }
+}
@@ -0,0 +1,407 @@
+//!
+//! The zkEVM assembly verification pass.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+///
+/// The zkEVM assembly verification pass.
+///
+/// Checks the invariants the translator must uphold: every referenced label is defined, no
+/// function falls through its end into the next one, and the stack pointer adjustments agree
+/// at the block joins and never pop more than was pushed. The violations are reported with
+/// the function names and assembly line numbers, which is easier to act upon than a failure
+/// of the backend assembler or a misbehaving contract.
+///
+/// The checks are heuristic, so the pass only runs in the debug builds of the compiler, which
+/// the tests are run with, like the other internal assertions.
+///
+#[derive(Debug, Default)]
+pub struct AssemblyCheck {
+    /// The violations found, in the assembly order.
+    pub violations: Vec<String>,
+}
+
+///
+/// The assembly instruction.
+///
+#[derive(Debug)]
+struct Instruction<'a> {
+    /// The one-based assembly line number.
+    line: usize,
+    /// The mnemonic, including the modifiers, e.g. `jump.ne`.
+    mnemonic: &'a str,
+    /// The operands.
+    operands: Vec<&'a str>,
+}
+
+///
+/// The basic block, starting either at a function or at a local label.
+///
+#[derive(Debug, Default)]
+struct Block<'a> {
+    /// The local label, if any.
+    label: Option<&'a str>,
+    /// The instructions.
+    instructions: Vec<Instruction<'a>>,
+}
+
+impl AssemblyCheck {
+    /// The labels defined by the assembler itself.
+    pub const BUILTIN_LABELS: [&'static str; 3] =
+        ["DEFAULT_UNWIND", "DEFAULT_FAR_RETURN", "DEFAULT_FAR_REVERT"];
+
+    /// The mnemonics ending the control flow, unless they are conditional.
+    const TERMINATORS: [&'static str; 8] = [
+        "ret", "rev", "revert", "pncl", "panic", "jump", "retl", "revl",
+    ];
+
+    /// The condition modifiers.
+    const CONDITIONS: [&'static str; 7] = ["eq", "ne", "lt", "le", "gt", "ge", "gtlt"];
+
+    ///
+    /// Verifies the `assembly_text` of the contract at `path`.
+    ///
+    pub fn check(path: &str, assembly_text: &str) -> anyhow::Result<()> {
+        let check = Self::new(assembly_text);
+        if check.violations.is_empty() {
+            return Ok(());
+        }
+
+        anyhow::bail!(
+            "The contract `{}` assembly verification failed:\n{}",
+            path,
+            check
+                .violations
+                .iter()
+                .map(|violation| format!("  {violation}"))
+                .collect::<Vec<String>>()
+                .join("\n")
+        );
+    }
+
+    ///
+    /// Runs the verification pass over the assembly.
+    ///
+    pub fn new(assembly_text: &str) -> Self {
+        let mut check = Self::default();
+
+        let mut labels = BTreeSet::new();
+        let mut references = Vec::new();
+        let mut functions: Vec<(&str, Vec<Block>)> = Vec::new();
+        let mut is_text = true;
+
+        for (index, line) in assembly_text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.split(';').next().unwrap_or_default();
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            if let Some(label) = trimmed.strip_suffix(':') {
+                labels.insert(label);
+                if !is_text {
+                    continue;
+                }
+                if label.starts_with('.') {
+                    if let Some((_name, blocks)) = functions.last_mut() {
+                        blocks.push(Block {
+                            label: Some(label),
+                            instructions: Vec::new(),
+                        });
+                    }
+                } else if !line.starts_with(char::is_whitespace) {
+                    functions.push((label, vec![Block::default()]));
+                }
+                continue;
+            }
+
+            if trimmed.starts_with('.') {
+                let mut directive = trimmed.split_whitespace();
+                match directive.next() {
+                    Some(".text") => is_text = true,
+                    Some(".section") => {
+                        is_text = directive
+                            .next()
+                            .map(|name| name.trim_end_matches(',').starts_with(".text"))
+                            .unwrap_or_default();
+                    }
+                    Some(".data") | Some(".rodata") | Some(".bss") => is_text = false,
+                    Some(name) if name.starts_with(".rodata") => is_text = false,
+                    Some(".cell") => {
+                        references
+                            .extend(Self::references(trimmed).map(|label| (line_number, label)));
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            references.extend(Self::references(trimmed).map(|label| (line_number, label)));
+            if !is_text {
+                continue;
+            }
+
+            let (mnemonic, operands) = trimmed
+                .split_once(char::is_whitespace)
+                .unwrap_or((trimmed, ""));
+            let instruction = Instruction {
+                line: line_number,
+                mnemonic,
+                operands: operands
+                    .split(',')
+                    .map(|operand| operand.trim())
+                    .filter(|operand| !operand.is_empty())
+                    .collect(),
+            };
+            match functions
+                .last_mut()
+                .and_then(|(_name, blocks)| blocks.last_mut())
+            {
+                Some(block) => block.instructions.push(instruction),
+                None => check.violations.push(format!(
+                    "line {line_number}: instruction `{trimmed}` is outside of any function"
+                )),
+            }
+        }
+
+        for (line, label) in references.into_iter() {
+            if !labels.contains(label) && !Self::BUILTIN_LABELS.contains(&label) {
+                check
+                    .violations
+                    .push(format!("line {line}: label `{label}` is not defined"));
+            }
+        }
+
+        for (name, blocks) in functions.iter() {
+            check.check_fallthrough(name, blocks.as_slice());
+            check.check_stack(name, blocks.as_slice());
+        }
+
+        check
+    }
+
+    ///
+    /// Checks that the last instruction of the function does not fall through its end.
+    ///
+    /// A trailing `near_call` is accepted, as LLVM only emits it for the callees that never
+    /// return, such as the revert and panic helpers.
+    ///
+    fn check_fallthrough(&mut self, name: &str, blocks: &[Block]) {
+        let last = blocks
+            .iter()
+            .rev()
+            .find_map(|block| block.instructions.last());
+        match last {
+            Some(instruction) if Self::is_terminator(instruction) => {}
+            Some(instruction) if instruction.mnemonic == "near_call" => {}
+            Some(instruction) => self.violations.push(format!(
+                "line {}: function `{}` falls through its end after `{}`",
+                instruction.line, name, instruction.mnemonic
+            )),
+            None => {}
+        }
+    }
+
+    ///
+    /// Checks that the stack pointer adjustments agree at the block joins and never make the
+    /// function frame negative.
+    ///
+    /// The functions with the adjustments by a register value are skipped.
+    ///
+    fn check_stack(&mut self, name: &str, blocks: &[Block]) {
+        let block_indexes: BTreeMap<&str, usize> = blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(index, block)| block.label.map(|label| (label, index)))
+            .collect();
+
+        let mut entry_depths: BTreeMap<usize, i64> = BTreeMap::new();
+        let mut queue = vec![(0, 0)];
+        while let Some((index, depth)) = queue.pop() {
+            match entry_depths.get(&index) {
+                Some(known) if *known == depth => continue,
+                Some(known) => {
+                    self.violations.push(format!(
+                        "function `{}`: stack depth at `{}` is {} on one path and {} on another",
+                        name,
+                        blocks[index].label.unwrap_or(name),
+                        known,
+                        depth
+                    ));
+                    return;
+                }
+                None => {
+                    entry_depths.insert(index, depth);
+                }
+            }
+
+            let mut depth = depth;
+            let mut falls_through = true;
+            for instruction in blocks[index].instructions.iter() {
+                let base = instruction.mnemonic.split('.').next().unwrap_or_default();
+                if base == "jump" || base == "near_call" {
+                    for operand in instruction.operands.iter() {
+                        if let Some(target) = operand
+                            .strip_prefix('@')
+                            .and_then(|label| block_indexes.get(label))
+                        {
+                            queue.push((*target, depth));
+                        }
+                    }
+                }
+
+                for operand in instruction.operands.iter() {
+                    let (sign, offset) = if let Some(offset) = operand.strip_prefix("stack+=") {
+                        (1, offset)
+                    } else if let Some(offset) = operand.strip_prefix("stack-=") {
+                        (-1, offset)
+                    } else {
+                        continue;
+                    };
+                    match Self::constant_offset(offset) {
+                        Some(offset) => depth += sign * offset,
+                        None => return,
+                    }
+                    if depth < 0 {
+                        self.violations.push(format!(
+                            "line {}: function `{}` pops {} stack slots more than it pushed",
+                            instruction.line, name, -depth
+                        ));
+                        return;
+                    }
+                }
+
+                if Self::is_terminator(instruction) {
+                    falls_through = false;
+                    break;
+                }
+            }
+            if falls_through && index + 1 < blocks.len() {
+                queue.push((index + 1, depth));
+            }
+        }
+    }
+
+    ///
+    /// Whether the instruction unconditionally ends the control flow.
+    ///
+    fn is_terminator(instruction: &Instruction) -> bool {
+        let mut modifiers = instruction.mnemonic.split('.');
+        let base = modifiers.next().unwrap_or_default();
+        Self::TERMINATORS.contains(&base)
+            && !modifiers.any(|modifier| Self::CONDITIONS.contains(&modifier))
+    }
+
+    ///
+    /// Parses the stack offset like `[2]` or `[1 + r0]`, returning `None` for the register ones.
+    ///
+    fn constant_offset(offset: &str) -> Option<i64> {
+        let offset = offset.strip_prefix('[')?.strip_suffix(']')?;
+        let mut sum = 0;
+        for term in offset.split('+').map(|term| term.trim()) {
+            match term {
+                "r0" => {}
+                term => sum += term.parse::<i64>().ok()?,
+            }
+        }
+        Some(sum)
+    }
+
+    ///
+    /// Returns the labels referenced by the instruction or directive.
+    ///
+    fn references(line: &str) -> impl Iterator<Item = &str> {
+        line.split(|character: char| {
+            character.is_whitespace()
+                || character == ','
+                || character == '['
+                || character == ']'
+                || character == '+'
+        })
+        .filter_map(|operand| operand.strip_prefix('@'))
+        .filter(|label| !label.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AssemblyCheck;
+
+    #[test]
+    fn valid() {
+        let assembly = r#"	.text
+	.file	"Test.sol:Test"
+	.globl	__entry
+__entry:                                ; @__entry
+.func_begin0:
+	nop	stack+=[2 + r0]
+	add	@CPI0_0[0], r0, stack+=[1]
+	jump.ne	@.BB0_2
+	add	stack-=[1], r0, r1
+	near_call	r0, @__deploy, @DEFAULT_UNWIND
+	ret
+.BB0_2:
+	add	stack-=[1], r0, r1
+	ret
+.func_end0:
+__deploy:
+	ret
+	.rodata.cst32
+CPI0_0:
+	.cell 42
+"#;
+        assert!(AssemblyCheck::new(assembly).violations.is_empty());
+    }
+
+    #[test]
+    fn code_reference() {
+        let assembly =
+            "__entry:\n\tadd\tcode[@CPI0_0], r0, r1\n\tret\n\t.rodata\nCPI0_0:\n\t.cell 42\n";
+        assert!(AssemblyCheck::new(assembly).violations.is_empty());
+    }
+
+    #[test]
+    fn noreturn_call() {
+        let assembly = "__entry:\n\tnear_call\tr0, @__revert, @DEFAULT_UNWIND\n__revert:\n\trev\n";
+        assert!(AssemblyCheck::new(assembly).violations.is_empty());
+    }
+
+    #[test]
+    fn undefined_label() {
+        let assembly = "__entry:\n\tjump\t@.BB0_7\n";
+        assert_eq!(
+            AssemblyCheck::new(assembly).violations,
+            vec!["line 2: label `.BB0_7` is not defined"]
+        );
+    }
+
+    #[test]
+    fn fallthrough() {
+        let assembly = "__entry:\n\tret\n.BB0_1:\n\tadd\tr1, r0, r2\n__deploy:\n\tret\n";
+        assert_eq!(
+            AssemblyCheck::new(assembly).violations,
+            vec!["line 4: function `__entry` falls through its end after `add`"]
+        );
+    }
+
+    #[test]
+    fn stack_mismatch() {
+        let assembly = "__entry:\n\tjump.ne\t@.BB0_2\n\tadd\tr1, r0, stack+=[1]\n.BB0_2:\n\tret\n";
+        assert_eq!(
+            AssemblyCheck::new(assembly).violations,
+            vec!["function `__entry`: stack depth at `.BB0_2` is 1 on one path and 0 on another"]
+        );
+    }
+
+    #[test]
+    fn stack_underflow() {
+        let assembly = "__entry:\n\tnop\tstack+=[1 + r0]\n\tadd\tstack-=[2], r0, r1\n\tret\n";
+        assert_eq!(
+            AssemblyCheck::new(assembly).violations,
+            vec!["line 3: function `__entry` pops 1 stack slots more than it pushed"]
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//!
+//! The example calldata of the contract functions.
+//!
+
+use std::collections::BTreeMap;
+
+use sha3::Digest;
+
+///
+/// The example calldata of the contract functions.
+///
+/// Encodes a call of each function from the ABI with the default argument values: zero
+/// numbers and addresses, `false`, and empty dynamic arrays, bytes, and strings. The payloads
+/// are valid according to the ABI, so they can be used to smoke-test the deployed contracts.
+///
+#[derive(Debug)]
+pub struct Calldata;
+
+///
+/// The ABI type, reduced to what matters for the default value encoding.
+///
+#[derive(Debug, Clone)]
+enum Type {
+    /// The static elementary type, e.g. `uint256`, `address`, or `bytes32`.
+    Elementary,
+    /// The `bytes` or `string` type.
+    Bytes,
+    /// The array type with the optional static length.
+    Array(Box<Type>, Option<usize>),
+    /// The tuple type.
+    Tuple(Vec<Type>),
+}
+
+impl Calldata {
+    ///
+    /// Returns the `0x`-prefixed calldata keyed by the function signatures.
+    ///
+    /// The functions with the types that cannot be parsed are skipped.
+    ///
+    pub fn from_abi(abi: &serde_json::Value) -> BTreeMap<String, String> {
+        let mut calldata = BTreeMap::new();
+        for entry in abi.as_array().into_iter().flatten() {
+            if entry.get("type").and_then(|r#type| r#type.as_str()) != Some("function") {
+                continue;
+            }
+            let name = match entry.get("name").and_then(|name| name.as_str()) {
+                Some(name) => name,
+                None => continue,
+            };
+            let inputs = entry
+                .get("inputs")
+                .and_then(|inputs| inputs.as_array())
+                .cloned()
+                .unwrap_or_default();
+
+            let parsed: Option<Vec<(String, Type)>> = inputs.iter().map(Self::parse).collect();
+            let (signatures, types): (Vec<String>, Vec<Type>) = match parsed {
+                Some(parsed) => parsed.into_iter().unzip(),
+                None => continue,
+            };
+
+            let signature = format!("{name}({})", signatures.join(","));
+            let selector = sha3::Keccak256::digest(signature.as_bytes());
+            let mut data = selector[..4].to_vec();
+            data.extend(Self::encode_sequence(types.as_slice()));
+            calldata.insert(signature, format!("0x{}", hex::encode(data)));
+        }
+        calldata
+    }
+
+    ///
+    /// Parses the ABI parameter into its canonical signature type and the encoding type.
+    ///
+    fn parse(parameter: &serde_json::Value) -> Option<(String, Type)> {
+        let r#type = parameter.get("type")?.as_str()?;
+        let base_end = r#type.find('[').unwrap_or(r#type.len());
+        let (base, suffixes) = r#type.split_at(base_end);
+
+        let (mut signature, mut encoding) = match base {
+            "tuple" => {
+                let components: Option<Vec<(String, Type)>> = parameter
+                    .get("components")?
+                    .as_array()?
+                    .iter()
+                    .map(Self::parse)
+                    .collect();
+                let (signatures, types): (Vec<String>, Vec<Type>) = components?.into_iter().unzip();
+                (format!("({})", signatures.join(",")), Type::Tuple(types))
+            }
+            "bytes" | "string" => (base.to_owned(), Type::Bytes),
+            base if !base.is_empty() => (base.to_owned(), Type::Elementary),
+            _ => return None,
+        };
+
+        for suffix in suffixes.split_inclusive(']') {
+            let length = suffix.strip_prefix('[')?.strip_suffix(']')?;
+            let length = if length.is_empty() {
+                None
+            } else {
+                Some(length.parse::<usize>().ok()?)
+            };
+            encoding = Type::Array(Box::new(encoding), length);
+        }
+        signature.push_str(suffixes);
+
+        Some((signature, encoding))
+    }
+
+    ///
+    /// Encodes the default values of a sequence of types with the head-tail layout.
+    ///
+    fn encode_sequence(types: &[Type]) -> Vec<u8> {
+        let heads_size: usize = types.iter().map(Type::head_size).sum();
+        let mut heads = Vec::with_capacity(heads_size);
+        let mut tails = Vec::new();
+        for r#type in types.iter() {
+            if r#type.is_dynamic() {
+                heads.extend(Self::word(heads_size + tails.len()));
+                tails.extend(Self::encode(r#type));
+            } else {
+                heads.extend(Self::encode(r#type));
+            }
+        }
+        heads.extend(tails);
+        heads
+    }
+
+    ///
+    /// Encodes the default value of the type.
+    ///
+    fn encode(r#type: &Type) -> Vec<u8> {
+        match r#type {
+            Type::Elementary | Type::Bytes | Type::Array(_, None) => Self::word(0),
+            Type::Array(element, Some(length)) => {
+                Self::encode_sequence(vec![element.as_ref().to_owned(); *length].as_slice())
+            }
+            Type::Tuple(types) => Self::encode_sequence(types.as_slice()),
+        }
+    }
+
+    ///
+    /// Encodes the value as a big-endian 32-byte word.
+    ///
+    fn word(value: usize) -> Vec<u8> {
+        let mut word = vec![0; compiler_common::BYTE_LENGTH_FIELD];
+        let bytes = (value as u64).to_be_bytes();
+        word[compiler_common::BYTE_LENGTH_FIELD - bytes.len()..].copy_from_slice(&bytes);
+        word
+    }
+}
+
+impl Type {
+    ///
+    /// Whether the type is encoded in the tail.
+    ///
+    fn is_dynamic(&self) -> bool {
+        match self {
+            Self::Elementary => false,
+            Self::Bytes | Self::Array(_, None) => true,
+            Self::Array(element, Some(_)) => element.is_dynamic(),
+            Self::Tuple(types) => types.iter().any(Self::is_dynamic),
+        }
+    }
+
+    ///
+    /// Returns the size of the type encoding in the head.
+    ///
+    fn head_size(&self) -> usize {
+        match self {
+            r#type if r#type.is_dynamic() => compiler_common::BYTE_LENGTH_FIELD,
+            Self::Array(element, Some(length)) => element.head_size() * length,
+            Self::Tuple(types) => types.iter().map(Self::head_size).sum(),
+            _ => compiler_common::BYTE_LENGTH_FIELD,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Calldata;
+
+    #[test]
+    fn from_abi() {
+        let abi = serde_json::json!([
+            { "type": "constructor", "inputs": [{ "type": "uint256" }] },
+            { "type": "function", "name": "totalSupply", "inputs": [] },
+            {
+                "type": "function",
+                "name": "transfer",
+                "inputs": [{ "type": "address" }, { "type": "uint256" }]
+            },
+            {
+                "type": "function",
+                "name": "submit",
+                "inputs": [
+                    { "type": "tuple", "components": [{ "type": "bool" }, { "type": "string" }] },
+                    { "type": "uint8[2]" }
+                ]
+            }
+        ]);
+
+        let calldata = Calldata::from_abi(&abi);
+        assert_eq!(
+            calldata.keys().collect::<Vec<&String>>(),
+            vec![
+                "submit((bool,string),uint8[2])",
+                "totalSupply()",
+                "transfer(address,uint256)"
+            ]
+        );
+        assert_eq!(calldata["totalSupply()"], "0x18160ddd");
+        assert_eq!(
+            calldata["transfer(address,uint256)"],
+            format!("0xa9059cbb{}", "0".repeat(128))
+        );
+
+        let submit = calldata["submit((bool,string),uint8[2])"]
+            .strip_prefix("0x")
+            .expect("Always valid");
+        let words: Vec<&str> = (8..submit.len())
+            .step_by(64)
+            .map(|offset| &submit[offset..offset + 64])
+            .collect();
+        assert_eq!(words.len(), 6);
+        assert_eq!(words[0], format!("{:064x}", 96));
+        assert_eq!(words[3], "0".repeat(64));
+        assert_eq!(words[4], format!("{:064x}", 64));
+        assert_eq!(words[5], "0".repeat(64));
+    }
+}
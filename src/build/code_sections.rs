@@ -0,0 +1,251 @@
+//!
+//! The contract deploy and runtime code sections.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The contract deploy and runtime code sections.
+///
+/// zkEVM contracts are deployed as a single bytecode blob, but the functions reachable only
+/// from the deploy code entry are never executed after the construction, and vice versa.
+/// The functions reachable from both entries, the dispatcher, and the unreachable ones are
+/// reported as shared.
+///
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeSections {
+    /// The functions reachable only from the deploy code.
+    pub deploy: Vec<CodeRange>,
+    /// The functions reachable only from the runtime code.
+    pub runtime: Vec<CodeRange>,
+    /// The functions reachable from both entries or from neither of them.
+    pub shared: Vec<CodeRange>,
+}
+
+///
+/// The bytecode range of a function.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeRange {
+    /// The function name.
+    pub function: String,
+    /// The byte offset in the bytecode.
+    pub offset: usize,
+    /// The byte size in the bytecode.
+    pub size: usize,
+}
+
+///
+/// The function parsed from the assembly.
+///
+#[derive(Debug)]
+struct Function {
+    /// The first instruction index.
+    start: usize,
+    /// The number of instructions.
+    length: usize,
+    /// The labels referenced by the function instructions.
+    references: BTreeSet<String>,
+}
+
+impl CodeSections {
+    /// The deploy code function name.
+    pub const FUNCTION_DEPLOY_CODE: &'static str = "__deploy";
+
+    /// The runtime code function name.
+    pub const FUNCTION_RUNTIME_CODE: &'static str = "__runtime";
+
+    /// The zkEVM instruction size in bytes.
+    pub const INSTRUCTION_SIZE: usize = 8;
+
+    ///
+    /// Splits the functions of the assembly into the deploy, runtime, and shared sections.
+    ///
+    /// The offsets assume that the assembler lays out the instructions in the assembly order.
+    ///
+    pub fn new(assembly_text: &str) -> Self {
+        let functions = Self::parse(assembly_text);
+
+        let deploy = Self::reachable(&functions, Self::FUNCTION_DEPLOY_CODE);
+        let runtime = Self::reachable(&functions, Self::FUNCTION_RUNTIME_CODE);
+
+        let mut sections = Self::default();
+        let mut functions: Vec<(String, Function)> = functions.into_iter().collect();
+        functions.sort_by_key(|(_name, function)| function.start);
+        for (name, function) in functions.into_iter() {
+            let section = match (deploy.contains(&name), runtime.contains(&name)) {
+                (true, false) => &mut sections.deploy,
+                (false, true) => &mut sections.runtime,
+                _ => &mut sections.shared,
+            };
+            section.push(CodeRange {
+                function: name,
+                offset: function.start * Self::INSTRUCTION_SIZE,
+                size: function.length * Self::INSTRUCTION_SIZE,
+            });
+        }
+        sections
+    }
+
+    ///
+    /// Parses the functions of the text section with their instruction ranges and references.
+    ///
+    fn parse(assembly_text: &str) -> BTreeMap<String, Function> {
+        let mut functions = BTreeMap::new();
+        let mut current: Option<(String, Function)> = None;
+        let mut is_text = true;
+        let mut index = 0;
+
+        for line in assembly_text.lines() {
+            let line = line.split(';').next().unwrap_or_default();
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            if trimmed.starts_with('.') && !trimmed.ends_with(':') {
+                let mut directive = trimmed.split_whitespace();
+                match directive.next() {
+                    Some(".text") => is_text = true,
+                    Some(".section") => {
+                        is_text = directive
+                            .next()
+                            .map(|name| name.trim_end_matches(',').starts_with(".text"))
+                            .unwrap_or_default();
+                    }
+                    Some(".data") | Some(".rodata") | Some(".bss") => is_text = false,
+                    Some(name) if name.starts_with(".rodata") => is_text = false,
+                    _ => {}
+                }
+                continue;
+            }
+            if !is_text {
+                continue;
+            }
+
+            if !line.starts_with(char::is_whitespace) && trimmed.ends_with(':') {
+                if trimmed.starts_with('.') {
+                    continue;
+                }
+                if let Some((name, function)) = current.take() {
+                    functions.insert(name, function);
+                }
+                current = Some((
+                    trimmed.trim_end_matches(':').to_owned(),
+                    Function {
+                        start: index,
+                        length: 0,
+                        references: BTreeSet::new(),
+                    },
+                ));
+                continue;
+            }
+
+            if let Some((_name, function)) = current.as_mut() {
+                function.length += 1;
+                for operand in trimmed.split(|character: char| {
+                    character.is_whitespace() || character == ',' || character == '['
+                }) {
+                    if let Some(label) = operand.strip_prefix('@') {
+                        if !label.starts_with('.') {
+                            function.references.insert(label.to_owned());
+                        }
+                    }
+                }
+            }
+            index += 1;
+        }
+        if let Some((name, function)) = current.take() {
+            functions.insert(name, function);
+        }
+
+        functions
+    }
+
+    ///
+    /// Returns the functions reachable from the `entry` one, including itself.
+    ///
+    fn reachable(functions: &BTreeMap<String, Function>, entry: &str) -> BTreeSet<String> {
+        let mut visited = BTreeSet::new();
+        if !functions.contains_key(entry) {
+            return visited;
+        }
+
+        let mut queue = vec![entry.to_owned()];
+        while let Some(name) = queue.pop() {
+            if !visited.insert(name.clone()) {
+                continue;
+            }
+            if let Some(function) = functions.get(name.as_str()) {
+                queue.extend(
+                    function
+                        .references
+                        .iter()
+                        .filter(|reference| functions.contains_key(reference.as_str()))
+                        .cloned(),
+                );
+            }
+        }
+        visited
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CodeSections;
+
+    #[test]
+    fn split() {
+        let assembly = r#"	.text
+	.file	"Test.sol:Test"
+	.globl	__entry
+__entry:                                ; @__entry
+.func_begin0:
+	jump.ne	@.BB0_2
+	near_call	r0, @__deploy, @DEFAULT_UNWIND
+	ret
+.BB0_2:
+	near_call	r0, @__runtime, @DEFAULT_UNWIND
+	ret
+.func_end0:
+__deploy:
+	near_call	r0, @abi_decode, @DEFAULT_UNWIND
+	near_call	r0, @checked_add, @DEFAULT_UNWIND
+	ret
+__runtime:
+	add	@CPI0_0[0], r0, r1
+	near_call	r0, @checked_add, @DEFAULT_UNWIND
+	ret
+abi_decode:
+	ret
+checked_add:
+	ret
+	.rodata.cst32
+CPI0_0:
+	.cell 42
+"#;
+        let sections = CodeSections::new(assembly);
+
+        let names = |ranges: &[super::CodeRange]| {
+            ranges
+                .iter()
+                .map(|range| range.function.as_str())
+                .collect::<Vec<&str>>()
+        };
+        assert_eq!(names(&sections.deploy), vec!["__deploy", "abi_decode"]);
+        assert_eq!(names(&sections.runtime), vec!["__runtime"]);
+        assert_eq!(names(&sections.shared), vec!["__entry", "checked_add"]);
+        assert_eq!(sections.shared[0].offset, 0);
+        assert_eq!(sections.shared[0].size, 5 * CodeSections::INSTRUCTION_SIZE);
+        assert_eq!(
+            sections.deploy[0].offset,
+            5 * CodeSections::INSTRUCTION_SIZE
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//!
+//! The contract constant-sized loop.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The contract constant-sized loop.
+///
+/// The loops with a compile-time-known trip count are reported with the estimated tradeoff of
+/// unrolling them, so the unrolling threshold can be tuned without comparing the bytecode.
+///
+/// The estimates are heuristic and are not measured from the compiled code: they assume one
+/// EraVM instruction per Yul statement or expression node and a single cycle per instruction,
+/// while the actual code depends on the LLVM optimizations.
+///
+/// Only the unrolling done by zksolc is reported. LLVM may unroll or vectorize any of the loops
+/// that zksolc has not unrolled, which is not reported, as zksolc does not inspect the optimized
+/// LLVM IR.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConstantLoop {
+    /// The code type, which is either `deploy` or `runtime`.
+    pub code: String,
+    /// The enclosing Yul function name, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    /// The Yul source location in the `line:column` format.
+    pub location: String,
+    /// The number of iterations.
+    pub trip_count: usize,
+    /// The loop body size in Yul statements and expression nodes.
+    pub body_size: usize,
+    /// Whether the loop has been unrolled by zksolc. `false` does not mean that the loop is kept
+    /// in the bytecode, as LLVM may still unroll it.
+    pub unrolled: bool,
+    /// The heuristic code size increase of unrolling in instructions, negative for short loops.
+    pub estimated_size_delta: i64,
+    /// The heuristic ergs saved by unrolling, as the loop condition and back jump are eliminated.
+    pub estimated_ergs_saved: u64,
+}
+
+impl ConstantLoop {
+    /// The estimated size of the loop condition, increment, and jumps in instructions.
+    pub const CONTROL_SIZE: i64 = 4;
+
+    /// The estimated number of instructions eliminated from each iteration by unrolling.
+    pub const ITERATION_OVERHEAD: u64 = 2;
+
+    /// The assumed ergs cost of an EraVM instruction, that is, of a single VM cycle.
+    pub const CYCLE_ERGS: u64 = 4;
+
+    ///
+    /// A shortcut constructor, which estimates the unrolling tradeoff heuristically.
+    ///
+    pub fn new(
+        code: &str,
+        function: Option<String>,
+        location: String,
+        trip_count: usize,
+        body_size: usize,
+        unrolled: bool,
+    ) -> Self {
+        let trips = i64::try_from(trip_count).unwrap_or(i64::MAX);
+        let body = i64::try_from(body_size).unwrap_or(i64::MAX);
+        let estimated_size_delta = trips
+            .saturating_mul(body)
+            .saturating_add((trips - 1).max(0))
+            .saturating_sub(body)
+            .saturating_sub(Self::CONTROL_SIZE);
+        let estimated_ergs_saved = u64::try_from(trip_count)
+            .unwrap_or(u64::MAX)
+            .saturating_mul(Self::ITERATION_OVERHEAD)
+            .saturating_add(1)
+            .saturating_mul(Self::CYCLE_ERGS);
+
+        Self {
+            code: code.to_owned(),
+            function,
+            location,
+            trip_count,
+            body_size,
+            unrolled,
+            estimated_size_delta,
+            estimated_ergs_saved,
+        }
+    }
+}
@@ -2,16 +2,28 @@
 //! The Solidity contract build.
 //!
 
+use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
+use std::path::PathBuf;
 
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::build::calldata::Calldata;
+use crate::build::code_sections::CodeSections;
+use crate::build::constant_loop::ConstantLoop;
+use crate::build::gas_usage::GasUsage;
+use crate::build::histogram::Histogram;
+use crate::build::immutable::Immutable;
+use crate::build::statistics::Statistics;
+use crate::format_version::FormatVersion;
+use crate::output_layout::OutputLayout;
 use crate::solc::combined_json::contract::Contract as CombinedJsonContract;
 use crate::solc::standard_json::output::contract::Contract as StandardJsonOutputContract;
+use crate::target::Target;
 
 ///
 /// The Solidity contract build.
@@ -22,21 +34,45 @@ pub struct Contract {
     pub path: String,
     /// The auxiliary identifier. Used to identify Yul objects.
     pub identifier: String,
+    /// The compilation target.
+    pub target: Target,
     /// The LLVM module build.
     pub build: compiler_llvm_context::Build,
     /// The metadata JSON.
     pub metadata_json: serde_json::Value,
     /// The factory dependencies.
     pub factory_dependencies: HashSet<String>,
+    /// The `solc` EVM bytecode, preserved on demand for comparison with that of zkEVM.
+    pub evm_bytecode: Option<String>,
+    /// The Yul source code produced by `solc`, preserved on demand for auditing.
+    pub yul: Option<String>,
+    /// The compilation statistics.
+    pub statistics: Option<Statistics>,
+    /// The source and zkEVM instruction histogram.
+    pub histogram: Option<Histogram>,
+    /// The deploy and runtime code sections.
+    pub code_sections: Option<CodeSections>,
+    /// The immutable slots and the constructor parameters assigned to them.
+    pub immutables: Option<Vec<Immutable>>,
+    /// The `gas()` and `gasleft()` usages.
+    pub gas_usages: Option<Vec<GasUsage>>,
+    /// The revert messages keyed by the identifiers substituted for them.
+    pub revert_string_ids: Option<BTreeMap<String, String>>,
+    /// The constant-sized loops with their estimated unrolling tradeoffs.
+    pub constant_loops: Option<Vec<ConstantLoop>>,
 }
 
 impl Contract {
+    /// The maximum number of assembly lines reported in a determinism check diff.
+    const DETERMINISM_DIFF_LINES: usize = 16;
+
     ///
     /// A shortcut constructor.
     ///
     pub fn new(
         path: String,
         identifier: String,
+        target: Target,
         build: compiler_llvm_context::Build,
         metadata_json: serde_json::Value,
         factory_dependencies: HashSet<String>,
@@ -44,23 +80,87 @@ impl Contract {
         Self {
             path,
             identifier,
+            target,
             build,
             metadata_json,
             factory_dependencies,
+            evm_bytecode: None,
+            yul: None,
+            statistics: None,
+            histogram: None,
+            code_sections: None,
+            immutables: None,
+            gas_usages: None,
+            revert_string_ids: None,
+            constant_loops: None,
         }
     }
 
+    ///
+    /// Checks whether the `reference` build of the same contract is identical to this one.
+    ///
+    /// If the bytecode differs, the first diverging lines of the assembly are reported.
+    ///
+    pub fn check_determinism(&self, reference: &Self) -> anyhow::Result<()> {
+        if self.build.bytecode == reference.build.bytecode {
+            return Ok(());
+        }
+
+        let expected: Vec<&str> = reference.build.assembly_text.lines().collect();
+        let found: Vec<&str> = self.build.assembly_text.lines().collect();
+        let first_mismatch = expected
+            .iter()
+            .zip(found.iter())
+            .position(|(expected, found)| expected != found)
+            .unwrap_or_else(|| std::cmp::min(expected.len(), found.len()));
+
+        let mut diff = String::with_capacity(4096);
+        for line in expected
+            .iter()
+            .skip(first_mismatch)
+            .take(Self::DETERMINISM_DIFF_LINES)
+        {
+            diff.push_str(format!("-{line}\n").as_str());
+        }
+        for line in found
+            .iter()
+            .skip(first_mismatch)
+            .take(Self::DETERMINISM_DIFF_LINES)
+        {
+            diff.push_str(format!("+{line}\n").as_str());
+        }
+
+        anyhow::bail!(
+            "Contract `{}` compilation is not deterministic: bytecode hashes `{}` and `{}` differ.\nThe assembly diverges at line {}:\n{}",
+            self.path,
+            reference.build.bytecode_hash,
+            self.build.bytecode_hash,
+            first_mismatch + 1,
+            diff,
+        );
+    }
+
     ///
     /// Writes the contract text assembly and bytecode to files.
     ///
+    /// Returns the paths of the written files, leaving out the existing ones that are not
+    /// overwritten.
+    ///
+    #[allow(clippy::too_many_arguments)]
     pub fn write_to_directory(
         self,
         path: &Path,
         output_assembly: bool,
         output_binary: bool,
+        output_histogram: bool,
+        output_metadata: bool,
+        output_calldata: bool,
+        format_version: FormatVersion,
+        output_layout: OutputLayout,
         overwrite: bool,
-    ) -> anyhow::Result<()> {
-        let file_name = Self::short_path(self.path.as_str());
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        let mut written = Vec::new();
+        let file_name = output_layout.file_name(self.path.as_str());
 
         if output_assembly {
             let file_name = format!(
@@ -84,11 +184,25 @@ impl Contract {
                     .map_err(|error| {
                         anyhow::anyhow!("File {:?} writing error: {}", file_path, error)
                     })?;
+                written.push(file_path);
             }
         }
 
         if output_binary {
-            let file_name = format!("{}.{}", file_name, compiler_common::EXTENSION_ZKEVM_BINARY);
+            let hexadecimal_bytecode;
+            let (file_name, bytecode) = match output_layout {
+                OutputLayout::Zksolc => (
+                    format!("{}.{}", file_name, compiler_common::EXTENSION_ZKEVM_BINARY),
+                    self.build.bytecode.as_slice(),
+                ),
+                OutputLayout::Solc => {
+                    hexadecimal_bytecode = hex::encode(self.build.bytecode.as_slice());
+                    (
+                        format!("{}.{}", file_name, crate::r#const::EXTENSION_EVM_BINARY),
+                        hexadecimal_bytecode.as_bytes(),
+                    )
+                }
+            };
             let mut file_path = path.to_owned();
             file_path.push(file_name);
 
@@ -101,14 +215,191 @@ impl Contract {
                     .map_err(|error| {
                         anyhow::anyhow!("File {:?} creating error: {}", file_path, error)
                     })?
-                    .write_all(self.build.bytecode.as_slice())
+                    .write_all(bytecode)
                     .map_err(|error| {
                         anyhow::anyhow!("File {:?} writing error: {}", file_path, error)
                     })?;
+                written.push(file_path);
+            }
+
+            if let Some(evm_bytecode) = self.evm_bytecode.as_ref() {
+                let file_name = match output_layout {
+                    OutputLayout::Zksolc => {
+                        format!("{}.{}", file_name, crate::r#const::EXTENSION_EVM_BINARY)
+                    }
+                    OutputLayout::Solc => format!(
+                        "{}{}.{}",
+                        file_name,
+                        OutputLayout::SOLC_EVM_BYTECODE_FILE_NAME_SUFFIX,
+                        crate::r#const::EXTENSION_EVM_BINARY
+                    ),
+                };
+                let mut file_path = path.to_owned();
+                file_path.push(file_name);
+
+                if file_path.exists() && !overwrite {
+                    eprintln!(
+                        "Refusing to overwrite an existing file {file_path:?} (use --overwrite to force)."
+                    );
+                } else {
+                    File::create(&file_path)
+                        .map_err(|error| {
+                            anyhow::anyhow!("File {:?} creating error: {}", file_path, error)
+                        })?
+                        .write_all(evm_bytecode.as_bytes())
+                        .map_err(|error| {
+                            anyhow::anyhow!("File {:?} writing error: {}", file_path, error)
+                        })?;
+                    written.push(file_path);
+                }
             }
         }
 
-        Ok(())
+        if let Some(yul) = self.yul.as_ref() {
+            let file_name = format!("{}.{}", file_name, crate::r#const::EXTENSION_YUL);
+            let mut file_path = path.to_owned();
+            file_path.push(file_name);
+
+            if file_path.exists() && !overwrite {
+                eprintln!(
+                    "Refusing to overwrite an existing file {file_path:?} (use --overwrite to force)."
+                );
+            } else {
+                File::create(&file_path)
+                    .map_err(|error| {
+                        anyhow::anyhow!("File {:?} creating error: {}", file_path, error)
+                    })?
+                    .write_all(yul.as_bytes())
+                    .map_err(|error| {
+                        anyhow::anyhow!("File {:?} writing error: {}", file_path, error)
+                    })?;
+                written.push(file_path);
+            }
+        }
+
+        if output_metadata {
+            let file_name = match output_layout {
+                OutputLayout::Zksolc => format!(
+                    "{}.{}.{}",
+                    file_name,
+                    crate::r#const::METADATA_FILE_NAME_SUFFIX,
+                    compiler_common::EXTENSION_JSON
+                ),
+                OutputLayout::Solc => format!(
+                    "{}{}.{}",
+                    file_name,
+                    OutputLayout::SOLC_METADATA_FILE_NAME_SUFFIX,
+                    compiler_common::EXTENSION_JSON
+                ),
+            };
+            let mut file_path = path.to_owned();
+            file_path.push(file_name);
+
+            if file_path.exists() && !overwrite {
+                eprintln!(
+                    "Refusing to overwrite an existing file {file_path:?} (use --overwrite to force)."
+                );
+            } else {
+                let json = serde_json::to_vec_pretty(&self.metadata_json).expect("Always valid");
+                File::create(&file_path)
+                    .map_err(|error| {
+                        anyhow::anyhow!("File {:?} creating error: {}", file_path, error)
+                    })?
+                    .write_all(json.as_slice())
+                    .map_err(|error| {
+                        anyhow::anyhow!("File {:?} writing error: {}", file_path, error)
+                    })?;
+                written.push(file_path);
+            }
+        }
+
+        if let (OutputLayout::Solc, Some(abi)) = (output_layout, self.abi()) {
+            let file_name = format!("{}.{}", file_name, OutputLayout::EXTENSION_ABI);
+            let mut file_path = path.to_owned();
+            file_path.push(file_name);
+
+            if file_path.exists() && !overwrite {
+                eprintln!(
+                    "Refusing to overwrite an existing file {file_path:?} (use --overwrite to force)."
+                );
+            } else {
+                let json = serde_json::to_vec(&abi).expect("Always valid");
+                File::create(&file_path)
+                    .map_err(|error| {
+                        anyhow::anyhow!("File {:?} creating error: {}", file_path, error)
+                    })?
+                    .write_all(json.as_slice())
+                    .map_err(|error| {
+                        anyhow::anyhow!("File {:?} writing error: {}", file_path, error)
+                    })?;
+                written.push(file_path);
+            }
+        }
+
+        if output_calldata {
+            let file_name = format!(
+                "{}.{}.{}",
+                file_name,
+                crate::r#const::CALLDATA_FILE_NAME_SUFFIX,
+                compiler_common::EXTENSION_JSON
+            );
+            let mut file_path = path.to_owned();
+            file_path.push(file_name);
+
+            if file_path.exists() && !overwrite {
+                eprintln!(
+                    "Refusing to overwrite an existing file {file_path:?} (use --overwrite to force)."
+                );
+            } else {
+                let calldata = self
+                    .abi()
+                    .map(|abi| Calldata::from_abi(&abi))
+                    .unwrap_or_default();
+                let json = serde_json::to_vec_pretty(&calldata).expect("Always valid");
+                File::create(&file_path)
+                    .map_err(|error| {
+                        anyhow::anyhow!("File {:?} creating error: {}", file_path, error)
+                    })?
+                    .write_all(json.as_slice())
+                    .map_err(|error| {
+                        anyhow::anyhow!("File {:?} writing error: {}", file_path, error)
+                    })?;
+                written.push(file_path);
+            }
+        }
+
+        if output_histogram {
+            if let Some(histogram) = self.histogram.as_ref() {
+                let file_name = format!(
+                    "{}.{}.{}",
+                    file_name,
+                    crate::r#const::HISTOGRAM_FILE_NAME_SUFFIX,
+                    compiler_common::EXTENSION_JSON
+                );
+                let mut file_path = path.to_owned();
+                file_path.push(file_name);
+
+                if file_path.exists() && !overwrite {
+                    eprintln!(
+                        "Refusing to overwrite an existing file {file_path:?} (use --overwrite to force)."
+                    );
+                } else {
+                    let json = serde_json::to_vec_pretty(&format_version.to_value(histogram))
+                        .expect("Always valid");
+                    File::create(&file_path)
+                        .map_err(|error| {
+                            anyhow::anyhow!("File {:?} creating error: {}", file_path, error)
+                        })?
+                        .write_all(json.as_slice())
+                        .map_err(|error| {
+                            anyhow::anyhow!("File {:?} writing error: {}", file_path, error)
+                        })?;
+                    written.push(file_path);
+                }
+            }
+        }
+
+        Ok(written)
     }
 
     ///
@@ -166,10 +457,30 @@ impl Contract {
 
         standard_json_contract.factory_dependencies = Some(self.build.factory_dependencies);
         standard_json_contract.hash = Some(self.build.bytecode_hash);
+        standard_json_contract.statistics = self.statistics;
+        standard_json_contract.histogram = self.histogram;
+        standard_json_contract.code_sections = self.code_sections;
+        standard_json_contract.immutables = self.immutables;
+        standard_json_contract.gas_usages = self.gas_usages;
+        standard_json_contract.revert_string_ids = self.revert_string_ids;
+        standard_json_contract.constant_loops = self.constant_loops;
 
         Ok(())
     }
 
+    ///
+    /// Returns the ABI from the `solc` metadata, which is either an object or its serialized string.
+    ///
+    pub fn abi(&self) -> Option<serde_json::Value> {
+        let solc_metadata = match self.metadata_json.get("solc_metadata")? {
+            serde_json::Value::String(metadata) => {
+                serde_json::from_str::<serde_json::Value>(metadata).ok()?
+            }
+            metadata => metadata.to_owned(),
+        };
+        solc_metadata.get("output")?.get("abi").cloned()
+    }
+
     ///
     /// Converts the full path to a short one.
     ///
@@ -0,0 +1,177 @@
+//!
+//! The contract `gas()` and `gasleft()` usage.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The contract `gas()` and `gasleft()` usage.
+///
+/// Gas-dependent logic is the most common source of behavioral differences when porting
+/// contracts to EraVM, so every usage is reported with the function it belongs to.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GasUsage {
+    /// The construct, which is either `gasleft` or the `gas` assembly instruction.
+    pub construct: String,
+    /// The enclosing function or modifier name, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    /// The `solc` source location in the `start:length:index` format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub src: Option<String>,
+    /// The note about the EraVM semantics.
+    pub note: String,
+}
+
+impl GasUsage {
+    /// The note about the EraVM gas semantics.
+    pub const NOTE: &'static str = "EraVM measures gas in ergs, which are priced differently \
+        from the EVM gas, and the cost of the instructions, storage writes, and calls differs \
+        as well. Hardcoded gas thresholds and stipends ported from the EVM may be insufficient \
+        or excessive.";
+
+    ///
+    /// Collects the `gas()` and `gasleft()` usages in the AST contract definition.
+    ///
+    pub fn from_contract_definition(contract: &serde_json::Value) -> Vec<Self> {
+        let mut usages = Vec::new();
+        Self::collect(contract, None, &mut usages);
+        usages
+    }
+
+    ///
+    /// Collects the usages in the AST node and its children.
+    ///
+    fn collect(ast: &serde_json::Value, function: Option<&str>, usages: &mut Vec<Self>) {
+        match ast {
+            serde_json::Value::Array(array) => {
+                for element in array.iter() {
+                    Self::collect(element, function, usages);
+                }
+            }
+            serde_json::Value::Object(object) => {
+                let node_type = object
+                    .get("nodeType")
+                    .and_then(|node_type| node_type.as_str());
+                let function = match node_type {
+                    Some("FunctionDefinition") | Some("ModifierDefinition") => object
+                        .get("name")
+                        .and_then(|name| name.as_str())
+                        .map(|name| {
+                            match (name, object.get("kind").and_then(|kind| kind.as_str())) {
+                                ("", Some(kind)) => kind,
+                                (name, _) => name,
+                            }
+                        }),
+                    _ => function,
+                };
+
+                let construct = match node_type {
+                    Some("Identifier")
+                        if object.get("name").and_then(|name| name.as_str()) == Some("gasleft") =>
+                    {
+                        Some("gasleft")
+                    }
+                    Some("YulFunctionCall")
+                        if object
+                            .get("functionName")
+                            .and_then(|name| name.get("name"))
+                            .and_then(|name| name.as_str())
+                            == Some("gas") =>
+                    {
+                        Some("gas")
+                    }
+                    _ => None,
+                };
+                if let Some(construct) = construct {
+                    usages.push(Self {
+                        construct: construct.to_owned(),
+                        function: function.map(|function| function.to_owned()),
+                        src: object
+                            .get("src")
+                            .and_then(|src| src.as_str())
+                            .map(|src| src.to_owned()),
+                        note: Self::NOTE.to_owned(),
+                    });
+                }
+
+                for (_key, value) in object.iter() {
+                    Self::collect(value, function, usages);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GasUsage;
+
+    #[test]
+    fn collect() {
+        let contract = serde_json::json!({
+            "nodeType": "ContractDefinition",
+            "name": "Test",
+            "nodes": [
+                {
+                    "nodeType": "FunctionDefinition",
+                    "name": "f",
+                    "kind": "function",
+                    "body": {
+                        "nodeType": "Block",
+                        "statements": [
+                            {
+                                "nodeType": "FunctionCall",
+                                "expression": { "nodeType": "Identifier", "name": "gasleft", "src": "10:7:0" }
+                            },
+                            {
+                                "nodeType": "InlineAssembly",
+                                "AST": {
+                                    "nodeType": "YulBlock",
+                                    "statements": [{
+                                        "nodeType": "YulFunctionCall",
+                                        "functionName": { "nodeType": "YulIdentifier", "name": "gas" },
+                                        "src": "30:5:0"
+                                    }]
+                                }
+                            }
+                        ]
+                    }
+                },
+                {
+                    "nodeType": "FunctionDefinition",
+                    "name": "",
+                    "kind": "receive",
+                    "body": {
+                        "nodeType": "Block",
+                        "statements": [{ "nodeType": "Identifier", "name": "gasleft", "src": "50:7:0" }]
+                    }
+                }
+            ]
+        });
+
+        let usages = GasUsage::from_contract_definition(&contract);
+        let summary: Vec<(&str, Option<&str>, Option<&str>)> = usages
+            .iter()
+            .map(|usage| {
+                (
+                    usage.construct.as_str(),
+                    usage.function.as_deref(),
+                    usage.src.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("gasleft", Some("f"), Some("10:7:0")),
+                ("gas", Some("f"), Some("30:5:0")),
+                ("gasleft", Some("receive"), Some("50:7:0")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+//!
+//! The contract instruction histogram.
+//!
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The contract instruction histogram.
+///
+/// Shows how many times each source EVM instruction has been consumed and each zkEVM instruction
+/// has been produced.
+///
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Histogram {
+    /// The source EVM instruction or Yul built-in function counts.
+    pub source: BTreeMap<String, usize>,
+    /// The zkEVM instruction counts.
+    pub target: BTreeMap<String, usize>,
+}
+
+impl Histogram {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(source: BTreeMap<String, usize>, assembly_text: &str) -> Self {
+        Self {
+            source,
+            target: Self::count_target(assembly_text),
+        }
+    }
+
+    ///
+    /// Counts the zkEVM instructions by their mnemonics, including the modifiers.
+    ///
+    /// Labels, directives, and comments are skipped.
+    ///
+    pub fn count_target(assembly_text: &str) -> BTreeMap<String, usize> {
+        let mut histogram = BTreeMap::new();
+        for line in assembly_text
+            .lines()
+            .filter(|line| line.starts_with(char::is_whitespace))
+            .map(|line| line.trim())
+        {
+            if line.starts_with('.') || line.starts_with(';') || line.ends_with(':') {
+                continue;
+            }
+            if let Some(mnemonic) = line.split_whitespace().next() {
+                *histogram.entry(mnemonic.to_owned()).or_insert(0) += 1;
+            }
+        }
+        histogram
+    }
+}
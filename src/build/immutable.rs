@@ -0,0 +1,321 @@
+//!
+//! The contract immutable variable.
+//!
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The contract immutable variable.
+///
+/// Describes the immutable slot of the variable and the constructor parameter it is
+/// assigned from, so deployment tools can verify the deployed immutable values against the
+/// constructor arguments.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Immutable {
+    /// The immutable key used in the IR, which is the AST identifier of the variable.
+    pub key: String,
+    /// The variable name.
+    pub name: String,
+    /// The byte offset of the immutable in the contract immutables array, if it is used in the code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+    /// The constructor parameter assigned to the variable as is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameter: Option<Parameter>,
+}
+
+///
+/// The constructor parameter.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Parameter {
+    /// The parameter name.
+    pub name: String,
+    /// The parameter position in the constructor signature.
+    pub index: usize,
+    /// The parameter type.
+    pub r#type: String,
+}
+
+impl Immutable {
+    ///
+    /// Collects the immutables declared by the AST contract definition and its base contracts.
+    ///
+    /// The base contracts are looked up in `definitions`, keyed by their AST identifiers. Only the
+    /// direct assignments of the parameters in the contract's own constructor are traced. The
+    /// immutables computed from expressions or assigned by the base contracts are reported
+    /// without a parameter.
+    ///
+    pub fn from_contract_definition(
+        contract: &serde_json::Value,
+        definitions: &BTreeMap<u64, &serde_json::Value>,
+    ) -> Vec<Self> {
+        let mut immutables = BTreeMap::new();
+        let id = contract.get("id").and_then(|id| id.as_u64());
+        for base in contract
+            .get("linearizedBaseContracts")
+            .and_then(|bases| bases.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|base| base.as_u64())
+            .filter(|base| Some(*base) != id)
+        {
+            if let Some(definition) = definitions.get(&base) {
+                Self::collect_declarations(definition, &mut immutables);
+            }
+        }
+        Self::collect_declarations(contract, &mut immutables);
+
+        let nodes = match contract.get("nodes").and_then(|nodes| nodes.as_array()) {
+            Some(nodes) => nodes,
+            None => return immutables.into_values().collect(),
+        };
+
+        let mut parameters = BTreeMap::new();
+        let mut constructor_body = None;
+        for node in nodes.iter() {
+            if node
+                .get("nodeType")
+                .and_then(|node_type| node_type.as_str())
+                != Some("FunctionDefinition")
+                || node.get("kind").and_then(|kind| kind.as_str()) != Some("constructor")
+            {
+                continue;
+            }
+
+            for (index, parameter) in node
+                .get("parameters")
+                .and_then(|parameters| parameters.get("parameters"))
+                .and_then(|parameters| parameters.as_array())
+                .into_iter()
+                .flatten()
+                .enumerate()
+            {
+                let id = match parameter.get("id").and_then(|id| id.as_u64()) {
+                    Some(id) => id,
+                    None => continue,
+                };
+                parameters.insert(
+                    id,
+                    Parameter {
+                        name: parameter
+                            .get("name")
+                            .and_then(|name| name.as_str())
+                            .unwrap_or_default()
+                            .to_owned(),
+                        index,
+                        r#type: parameter
+                            .get("typeDescriptions")
+                            .and_then(|type_descriptions| type_descriptions.get("typeString"))
+                            .and_then(|type_string| type_string.as_str())
+                            .unwrap_or_default()
+                            .to_owned(),
+                    },
+                );
+            }
+            constructor_body = node.get("body");
+        }
+
+        if let Some(body) = constructor_body {
+            Self::trace_assignments(body, &mut immutables, &parameters);
+        }
+
+        immutables.into_values().collect()
+    }
+
+    ///
+    /// Collects the immutable variables declared directly in the AST contract definition.
+    ///
+    fn collect_declarations(contract: &serde_json::Value, immutables: &mut BTreeMap<u64, Self>) {
+        for node in contract
+            .get("nodes")
+            .and_then(|nodes| nodes.as_array())
+            .into_iter()
+            .flatten()
+        {
+            if node
+                .get("nodeType")
+                .and_then(|node_type| node_type.as_str())
+                != Some("VariableDeclaration")
+                || node
+                    .get("mutability")
+                    .and_then(|mutability| mutability.as_str())
+                    != Some("immutable")
+            {
+                continue;
+            }
+
+            let id = match node.get("id").and_then(|id| id.as_u64()) {
+                Some(id) => id,
+                None => continue,
+            };
+            let name = node
+                .get("name")
+                .and_then(|name| name.as_str())
+                .unwrap_or_default();
+            immutables.insert(
+                id,
+                Self {
+                    key: id.to_string(),
+                    name: name.to_owned(),
+                    offset: None,
+                    parameter: None,
+                },
+            );
+        }
+    }
+
+    ///
+    /// Finds the assignments of the constructor parameters to the immutables.
+    ///
+    fn trace_assignments(
+        ast: &serde_json::Value,
+        immutables: &mut BTreeMap<u64, Self>,
+        parameters: &BTreeMap<u64, Parameter>,
+    ) {
+        match ast {
+            serde_json::Value::Array(array) => {
+                for element in array.iter() {
+                    Self::trace_assignments(element, immutables, parameters);
+                }
+            }
+            serde_json::Value::Object(object) => {
+                if object
+                    .get("nodeType")
+                    .and_then(|node_type| node_type.as_str())
+                    == Some("Assignment")
+                    && object
+                        .get("operator")
+                        .and_then(|operator| operator.as_str())
+                        == Some("=")
+                {
+                    let referenced = |side: &str| {
+                        let side = object.get(side)?;
+                        if side.get("nodeType")?.as_str()? != "Identifier" {
+                            return None;
+                        }
+                        side.get("referencedDeclaration")?.as_u64()
+                    };
+                    if let (Some(left), Some(right)) =
+                        (referenced("leftHandSide"), referenced("rightHandSide"))
+                    {
+                        if let (Some(immutable), Some(parameter)) =
+                            (immutables.get_mut(&left), parameters.get(&right))
+                        {
+                            immutable.parameter = Some(parameter.to_owned());
+                        }
+                    }
+                }
+
+                for (_key, value) in object.iter() {
+                    Self::trace_assignments(value, immutables, parameters);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::Immutable;
+
+    #[test]
+    fn constructor_parameter() {
+        let contract = serde_json::json!({
+            "nodeType": "ContractDefinition",
+            "name": "Test",
+            "nodes": [
+                { "nodeType": "VariableDeclaration", "id": 3, "name": "owner", "mutability": "immutable" },
+                { "nodeType": "VariableDeclaration", "id": 5, "name": "created", "mutability": "immutable" },
+                { "nodeType": "VariableDeclaration", "id": 7, "name": "counter", "mutability": "mutable" },
+                {
+                    "nodeType": "FunctionDefinition",
+                    "kind": "constructor",
+                    "parameters": {
+                        "parameters": [
+                            { "id": 10, "name": "value", "typeDescriptions": { "typeString": "uint256" } },
+                            { "id": 12, "name": "_owner", "typeDescriptions": { "typeString": "address" } }
+                        ]
+                    },
+                    "body": {
+                        "nodeType": "Block",
+                        "statements": [
+                            {
+                                "nodeType": "ExpressionStatement",
+                                "expression": {
+                                    "nodeType": "Assignment",
+                                    "operator": "=",
+                                    "leftHandSide": { "nodeType": "Identifier", "referencedDeclaration": 3 },
+                                    "rightHandSide": { "nodeType": "Identifier", "referencedDeclaration": 12 }
+                                }
+                            },
+                            {
+                                "nodeType": "ExpressionStatement",
+                                "expression": {
+                                    "nodeType": "Assignment",
+                                    "operator": "=",
+                                    "leftHandSide": { "nodeType": "Identifier", "referencedDeclaration": 5 },
+                                    "rightHandSide": { "nodeType": "FunctionCall" }
+                                }
+                            }
+                        ]
+                    }
+                }
+            ]
+        });
+
+        let immutables = Immutable::from_contract_definition(&contract, &BTreeMap::new());
+        assert_eq!(immutables.len(), 2);
+        assert_eq!(immutables[0].key, "3");
+        let parameter = immutables[0].parameter.as_ref().expect("Always exists");
+        assert_eq!(parameter.name, "_owner");
+        assert_eq!(parameter.index, 1);
+        assert_eq!(parameter.r#type, "address");
+        assert_eq!(immutables[1].name, "created");
+        assert!(immutables[1].parameter.is_none());
+    }
+
+    #[test]
+    fn inherited() {
+        let base = serde_json::json!({
+            "nodeType": "ContractDefinition",
+            "id": 20,
+            "name": "Base",
+            "linearizedBaseContracts": [20],
+            "nodes": [
+                { "nodeType": "VariableDeclaration", "id": 2, "name": "owner", "mutability": "immutable" }
+            ]
+        });
+        let contract = serde_json::json!({
+            "nodeType": "ContractDefinition",
+            "id": 30,
+            "name": "Test",
+            "linearizedBaseContracts": [30, 20],
+            "nodes": [
+                { "nodeType": "VariableDeclaration", "id": 5, "name": "created", "mutability": "immutable" }
+            ]
+        });
+        let definitions = BTreeMap::from([(20, &base), (30, &contract)]);
+
+        let immutables = Immutable::from_contract_definition(&contract, &definitions);
+        assert_eq!(
+            immutables
+                .iter()
+                .map(|immutable| immutable.name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["owner", "created"]
+        );
+        assert!(immutables
+            .iter()
+            .all(|immutable| immutable.parameter.is_none()));
+    }
+}
@@ -0,0 +1,423 @@
+//!
+//! The signed build manifest.
+//!
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use k256::ecdsa::signature::Signer;
+use k256::ecdsa::signature::Verifier;
+use serde::Deserialize;
+use serde::Serialize;
+use sha3::Digest;
+
+///
+/// The signed build manifest.
+///
+/// Attests the artifacts written by the build with an in-toto statement carrying a
+/// SLSA-style provenance predicate, which describes the source files and the toolchain versions.
+/// If a secp256k1 key is provided, each artifact and the manifest itself are signed with it.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    /// The manifest format version.
+    pub version: usize,
+    /// The provenance statement.
+    pub statement: Statement,
+    /// The `0x`-prefixed compressed SEC1 public key of the signer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    /// The `0x`-prefixed artifact signatures, keyed by the artifact paths.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub signatures: BTreeMap<String, String>,
+}
+
+///
+/// The in-toto statement.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Statement {
+    /// The statement type.
+    #[serde(rename = "_type")]
+    pub r#type: String,
+    /// The attested artifacts.
+    pub subject: Vec<ResourceDescriptor>,
+    /// The predicate type.
+    pub predicate_type: String,
+    /// The provenance predicate.
+    pub predicate: Predicate,
+}
+
+///
+/// The SLSA provenance predicate.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Predicate {
+    /// The build inputs.
+    pub build_definition: BuildDefinition,
+    /// The builder description.
+    pub run_details: RunDetails,
+}
+
+///
+/// The SLSA build definition.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildDefinition {
+    /// The build type URI.
+    pub build_type: String,
+    /// The build parameters verifiers must know to reproduce the artifacts, e.g. the metadata
+    /// hash placement.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub external_parameters: BTreeMap<String, String>,
+    /// The source files with their hashes.
+    pub resolved_dependencies: Vec<ResourceDescriptor>,
+}
+
+///
+/// The SLSA run details.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RunDetails {
+    /// The builder.
+    pub builder: Builder,
+}
+
+///
+/// The SLSA builder.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Builder {
+    /// The builder identifier.
+    pub id: String,
+    /// The toolchain component versions, keyed by the component names.
+    pub version: BTreeMap<String, String>,
+}
+
+///
+/// The in-toto resource descriptor.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceDescriptor {
+    /// The resource name.
+    pub name: String,
+    /// The resource digests, keyed by the algorithm names.
+    pub digest: BTreeMap<String, String>,
+}
+
+///
+/// The detached manifest signature.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestSignature {
+    /// The signature algorithm.
+    pub algorithm: String,
+    /// The `0x`-prefixed compressed SEC1 public key of the signer.
+    pub public_key: String,
+    /// The `0x`-prefixed signature of the manifest file contents.
+    pub signature: String,
+}
+
+impl Manifest {
+    /// The current manifest format version.
+    pub const VERSION: usize = 1;
+
+    /// The manifest file name.
+    pub const FILE_NAME: &'static str = "manifest.json";
+
+    /// The detached manifest signature file name.
+    pub const SIGNATURE_FILE_NAME: &'static str = "manifest.json.sig";
+
+    /// The in-toto statement type.
+    pub const STATEMENT_TYPE: &'static str = "https://in-toto.io/Statement/v1";
+
+    /// The SLSA provenance predicate type.
+    pub const PREDICATE_TYPE: &'static str = "https://slsa.dev/provenance/v1";
+
+    /// The build type URI.
+    pub const BUILD_TYPE: &'static str = "https://github.com/matter-labs/era-compiler-solidity";
+
+    /// The builder identifier.
+    pub const BUILDER_ID: &'static str = "zksolc";
+
+    /// The digest algorithm name.
+    pub const DIGEST_ALGORITHM: &'static str = "keccak256";
+
+    /// The signature algorithm name.
+    pub const SIGNATURE_ALGORITHM: &'static str = "ecdsa-secp256k1-sha256";
+
+    ///
+    /// Attests and optionally signs the `artifacts` written to `output_directory` by the build.
+    ///
+    /// The other files in the directory, like the stale artifacts of earlier runs, are left out.
+    /// `source_hashes` are the `0x`-prefixed keccak256 hashes of the sources, `parameters` are
+    /// the build parameters, and `toolchain` are the toolchain component versions.
+    ///
+    pub fn try_new(
+        output_directory: &Path,
+        artifacts: &[PathBuf],
+        source_hashes: &BTreeMap<String, String>,
+        parameters: BTreeMap<String, String>,
+        toolchain: BTreeMap<String, String>,
+        key: Option<&k256::ecdsa::SigningKey>,
+    ) -> anyhow::Result<Self> {
+        let mut paths = artifacts.to_vec();
+        paths.sort();
+        paths.dedup();
+
+        let mut subject = Vec::with_capacity(paths.len());
+        let mut signatures = BTreeMap::new();
+        for path in paths.into_iter() {
+            let name = Self::artifact_name(output_directory, path.as_path());
+            let content = std::fs::read(path.as_path())
+                .map_err(|error| anyhow::anyhow!("Artifact {:?} reading error: {}", path, error))?;
+            subject.push(ResourceDescriptor::new(
+                name.clone(),
+                Self::hash(content.as_slice()),
+            ));
+            if let Some(key) = key {
+                signatures.insert(name, Self::sign(key, content.as_slice()));
+            }
+        }
+
+        let resolved_dependencies = source_hashes
+            .iter()
+            .map(|(name, hash)| ResourceDescriptor::new(name.to_owned(), hash.to_owned()))
+            .collect();
+
+        Ok(Self {
+            version: Self::VERSION,
+            statement: Statement {
+                r#type: Self::STATEMENT_TYPE.to_owned(),
+                subject,
+                predicate_type: Self::PREDICATE_TYPE.to_owned(),
+                predicate: Predicate {
+                    build_definition: BuildDefinition {
+                        build_type: Self::BUILD_TYPE.to_owned(),
+                        external_parameters: parameters,
+                        resolved_dependencies,
+                    },
+                    run_details: RunDetails {
+                        builder: Builder {
+                            id: Self::BUILDER_ID.to_owned(),
+                            version: toolchain,
+                        },
+                    },
+                },
+            },
+            public_key: key.map(Self::public_key),
+            signatures,
+        })
+    }
+
+    ///
+    /// Reads the hex-encoded secp256k1 private key, optionally `0x`-prefixed, from the file.
+    ///
+    pub fn read_key(path: &Path) -> anyhow::Result<k256::ecdsa::SigningKey> {
+        let key = std::fs::read_to_string(path)
+            .map_err(|error| anyhow::anyhow!("Signing key {:?} reading error: {}", path, error))?;
+        let key = key.trim();
+        let key = hex::decode(key.strip_prefix("0x").unwrap_or(key))
+            .map_err(|error| anyhow::anyhow!("Signing key {:?} decoding error: {}", path, error))?;
+        k256::ecdsa::SigningKey::from_bytes(key.as_slice())
+            .map_err(|error| anyhow::anyhow!("Signing key {:?} is invalid: {}", path, error))
+    }
+
+    ///
+    /// Writes the manifest to `output_directory`.
+    ///
+    /// If `key` is provided, the detached manifest signature is written as well.
+    ///
+    pub fn write(
+        &self,
+        output_directory: &Path,
+        key: Option<&k256::ecdsa::SigningKey>,
+    ) -> anyhow::Result<()> {
+        let mut json = serde_json::to_vec_pretty(self).expect("Always valid");
+        json.push(b'\n');
+        let mut files = Vec::with_capacity(2);
+        if let Some(key) = key {
+            let signature = ManifestSignature {
+                algorithm: Self::SIGNATURE_ALGORITHM.to_owned(),
+                public_key: Self::public_key(key),
+                signature: Self::sign(key, json.as_slice()),
+            };
+            let mut signature_json = serde_json::to_vec_pretty(&signature).expect("Always valid");
+            signature_json.push(b'\n');
+            files.push((Self::SIGNATURE_FILE_NAME, signature_json));
+        }
+        files.push((Self::FILE_NAME, json));
+
+        for (name, content) in files.into_iter() {
+            let path = output_directory.join(name);
+            std::fs::write(path.as_path(), content)
+                .map_err(|error| anyhow::anyhow!("File {:?} writing error: {}", path, error))?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Verifies the artifact signature against the manifest public key.
+    ///
+    pub fn verify(&self, name: &str, content: &[u8]) -> anyhow::Result<()> {
+        let (public_key, signature) = self
+            .public_key
+            .as_ref()
+            .zip(self.signatures.get(name))
+            .ok_or_else(|| anyhow::anyhow!("Artifact `{}` is not signed", name))?;
+        Self::verify_signature(public_key.as_str(), signature.as_str(), content)
+            .map_err(|error| anyhow::anyhow!("Artifact `{}` {}", name, error))
+    }
+
+    ///
+    /// Verifies the `0x`-prefixed `signature` of `content` with the `0x`-prefixed `public_key`.
+    ///
+    pub fn verify_signature(
+        public_key: &str,
+        signature: &str,
+        content: &[u8],
+    ) -> anyhow::Result<()> {
+        let public_key = hex::decode(public_key.strip_prefix("0x").unwrap_or(public_key))
+            .map_err(|error| anyhow::anyhow!("public key decoding error: {}", error))?;
+        let public_key = k256::ecdsa::VerifyingKey::from_sec1_bytes(public_key.as_slice())
+            .map_err(|error| anyhow::anyhow!("public key is invalid: {}", error))?;
+        let signature = hex::decode(signature.strip_prefix("0x").unwrap_or(signature))
+            .map_err(|error| anyhow::anyhow!("signature decoding error: {}", error))?;
+        let signature = k256::ecdsa::Signature::try_from(signature.as_slice())
+            .map_err(|error| anyhow::anyhow!("signature is invalid: {}", error))?;
+        public_key
+            .verify(content, &signature)
+            .map_err(|_error| anyhow::anyhow!("signature verification failed"))
+    }
+
+    ///
+    /// Returns the hex-encoded keccak256 hash of `content`.
+    ///
+    fn hash(content: &[u8]) -> String {
+        hex::encode(sha3::Keccak256::digest(content))
+    }
+
+    ///
+    /// Signs `content`, returning the `0x`-prefixed signature.
+    ///
+    fn sign(key: &k256::ecdsa::SigningKey, content: &[u8]) -> String {
+        let signature: k256::ecdsa::Signature = key.sign(content);
+        format!("0x{}", hex::encode(signature))
+    }
+
+    ///
+    /// Returns the `0x`-prefixed compressed SEC1 public key of `key`.
+    ///
+    fn public_key(key: &k256::ecdsa::SigningKey) -> String {
+        format!(
+            "0x{}",
+            hex::encode(key.verifying_key().to_encoded_point(true).as_bytes())
+        )
+    }
+
+    ///
+    /// Returns the artifact name, which is its `/`-separated path relative to the output directory.
+    ///
+    fn artifact_name(output_directory: &Path, path: &Path) -> String {
+        path.strip_prefix(output_directory)
+            .unwrap_or(path)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<String>>()
+            .join("/")
+    }
+}
+
+impl ResourceDescriptor {
+    ///
+    /// A shortcut constructor for a resource with the keccak256 digest.
+    ///
+    /// The `0x` prefix of the `hash` is removed, as in-toto digests are plain hex strings.
+    ///
+    pub fn new(name: String, hash: String) -> Self {
+        let mut digest = BTreeMap::new();
+        digest.insert(
+            Manifest::DIGEST_ALGORITHM.to_owned(),
+            hash.strip_prefix("0x").unwrap_or(hash.as_str()).to_owned(),
+        );
+        Self { name, digest }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::Manifest;
+
+    #[test]
+    fn sign_and_verify() {
+        let directory = std::env::temp_dir().join(format!(
+            "zksolc-manifest-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        std::fs::create_dir_all(directory.join("Test.sol")).expect("Always valid");
+        let artifact = directory.join("Test.sol").join("Test.zbin");
+        std::fs::write(artifact.as_path(), b"bytecode").expect("Always valid");
+        std::fs::write(directory.join("Test.sol").join("Stale.zbin"), b"stale")
+            .expect("Always valid");
+
+        let key = k256::ecdsa::SigningKey::from_bytes(&[0x42; 32]).expect("Always valid");
+        let mut toolchain = BTreeMap::new();
+        toolchain.insert("zksolc".to_owned(), "1.3.13".to_owned());
+        let mut source_hashes = BTreeMap::new();
+        source_hashes.insert("Test.sol".to_owned(), "0x01".to_owned());
+
+        let mut parameters = BTreeMap::new();
+        parameters.insert("metadataHashPlacement".to_owned(), "appended".to_owned());
+
+        let manifest = Manifest::try_new(
+            directory.as_path(),
+            &[artifact.clone()],
+            &source_hashes,
+            parameters,
+            toolchain,
+            Some(&key),
+        )
+        .expect("Always valid");
+        manifest
+            .write(directory.as_path(), Some(&key))
+            .expect("Always valid");
+        let rebuilt = Manifest::try_new(
+            directory.as_path(),
+            &[artifact],
+            &source_hashes,
+            BTreeMap::new(),
+            BTreeMap::new(),
+            None,
+        )
+        .expect("Always valid");
+        std::fs::remove_dir_all(directory.as_path()).expect("Always valid");
+
+        assert_eq!(manifest.statement.subject.len(), 1);
+        assert_eq!(manifest.statement.subject, rebuilt.statement.subject);
+        assert!(rebuilt.public_key.is_none() && rebuilt.signatures.is_empty());
+        assert_eq!(manifest.statement.subject[0].name, "Test.sol/Test.zbin");
+        assert_eq!(
+            manifest
+                .statement
+                .predicate
+                .build_definition
+                .resolved_dependencies[0]
+                .digest[Manifest::DIGEST_ALGORITHM],
+            "01"
+        );
+        assert!(manifest.verify("Test.sol/Test.zbin", b"bytecode").is_ok());
+        assert!(manifest.verify("Test.sol/Test.zbin", b"tampered").is_err());
+    }
+}
@@ -0,0 +1,389 @@
+//!
+//! The metadata hash placement.
+//!
+
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The metadata hash placement.
+///
+/// EraVM bytecode must consist of an odd number of 32-byte words, and cannot exceed 2^16 words.
+/// The assembler appends the metadata hash as the last word, padding the bytecode if needed.
+/// Alternatively, the hash is prepended to the constant section of the assembly as a cell, which
+/// keeps it at a fixed place relative to the code, regardless of the padding.
+///
+/// The LLVM context only returns the assembly text along with the assembled bytecode, so in the
+/// Solidity, Yul and LLVM IR modes the text with the hash cell is assembled once more, while in
+/// the zkEVM assembly mode the source is patched before its only assembly.
+///
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataHashPlacement {
+    /// Appended to the bytecode by the assembler.
+    #[default]
+    #[serde(rename = "appended")]
+    Appended,
+    /// Prepended to the constant section of the assembly.
+    #[serde(rename = "constant-section")]
+    ConstantSection,
+}
+
+impl MetadataHashPlacement {
+    /// The label of the metadata hash cell in the constant section.
+    pub const LABEL: &'static str = "__metadata_hash";
+
+    /// The constant section name, which is also the prefix of its specialized variants, e.g. the
+    /// `.rodata.cst32` section of the 32-byte constant pool emitted by the EraVM backend.
+    pub const SECTION_CONSTANTS: &'static str = ".rodata";
+
+    /// The generic section switching directive.
+    pub const DIRECTIVE_SECTION: &'static str = ".section";
+
+    /// The constant cell directive.
+    pub const DIRECTIVE_CELL: &'static str = ".cell";
+
+    /// The alignment directive, which is a no-op between the word-sized cells.
+    pub const DIRECTIVE_ALIGN: &'static str = ".p2align";
+
+    /// The maximal bytecode size in words.
+    pub const MAX_WORDS: usize = (1 << 16) - 1;
+
+    ///
+    /// Places the metadata hash into the `assembly_text`, if required.
+    ///
+    /// Returns the assembly text and the metadata hash to be appended by the assembler.
+    ///
+    pub fn place(
+        &self,
+        assembly_text: &str,
+        metadata_hash: Option<[u8; compiler_common::BYTE_LENGTH_FIELD]>,
+    ) -> (String, Option<[u8; compiler_common::BYTE_LENGTH_FIELD]>) {
+        match (self, metadata_hash) {
+            (Self::ConstantSection, Some(metadata_hash)) => {
+                (Self::prepend(assembly_text, &metadata_hash), None)
+            }
+            (_, metadata_hash) => (assembly_text.to_owned(), metadata_hash),
+        }
+    }
+
+    ///
+    /// Validates the EraVM bytecode size and the metadata hash placement.
+    ///
+    /// At the constant section placement, the hash must occur in the bytecode exactly once and be
+    /// followed by the cells that follow it in the constant section of the `assembly_text`.
+    ///
+    pub fn validate(
+        &self,
+        bytecode: &[u8],
+        metadata_hash: Option<&[u8; compiler_common::BYTE_LENGTH_FIELD]>,
+        assembly_text: &str,
+    ) -> anyhow::Result<()> {
+        if bytecode.len() % compiler_common::BYTE_LENGTH_FIELD != 0 {
+            anyhow::bail!(
+                "bytecode size of {} bytes is not a multiple of the {}-byte word",
+                bytecode.len(),
+                compiler_common::BYTE_LENGTH_FIELD
+            );
+        }
+        let words: Vec<&[u8]> = bytecode
+            .chunks(compiler_common::BYTE_LENGTH_FIELD)
+            .collect();
+        if words.len() % 2 == 0 {
+            anyhow::bail!(
+                "bytecode consists of an even number of words {}, while EraVM requires an odd one",
+                words.len()
+            );
+        }
+        if words.len() > Self::MAX_WORDS {
+            anyhow::bail!(
+                "bytecode size of {} words exceeds the EraVM limit of {} words",
+                words.len(),
+                Self::MAX_WORDS
+            );
+        }
+
+        let metadata_hash = match metadata_hash {
+            Some(metadata_hash) => metadata_hash.as_slice(),
+            None => return Ok(()),
+        };
+        let is_placed = match self {
+            Self::Appended => match words.as_slice() {
+                [.., last] if *last == metadata_hash => true,
+                [.., hash, padding] => {
+                    *hash == metadata_hash && padding.iter().all(|byte| *byte == 0)
+                }
+                _ => false,
+            },
+            Self::ConstantSection => {
+                let positions: Vec<usize> = words
+                    .iter()
+                    .enumerate()
+                    .filter(|(_index, word)| **word == metadata_hash)
+                    .map(|(index, _word)| index)
+                    .collect();
+                let constants = Self::constants_after_hash(assembly_text);
+                match positions.as_slice() {
+                    [index] => {
+                        let following = &words[index + 1..];
+                        following.len() >= constants.len()
+                            && following
+                                .iter()
+                                .zip(constants.iter())
+                                .all(|(word, constant)| *word == constant.as_slice())
+                    }
+                    _ => false,
+                }
+            }
+        };
+        if !is_placed {
+            anyhow::bail!("metadata hash is not found in the bytecode at the {self} placement");
+        }
+        Ok(())
+    }
+
+    ///
+    /// Prepends the metadata hash cell to the constant section of the `assembly_text`.
+    ///
+    /// The section is created at the end of the assembly if there are no constants.
+    ///
+    fn prepend(
+        assembly_text: &str,
+        metadata_hash: &[u8; compiler_common::BYTE_LENGTH_FIELD],
+    ) -> String {
+        let cell = format!(
+            "{}:\n\t.cell {}\n",
+            Self::LABEL,
+            num::BigInt::from_signed_bytes_be(metadata_hash.as_slice())
+        );
+
+        let mut result = String::with_capacity(assembly_text.len() + cell.len() * 2);
+        let mut is_placed = false;
+        for line in assembly_text.lines() {
+            result.push_str(line);
+            result.push('\n');
+            if !is_placed && Self::is_constant_section(line) {
+                result.push_str(cell.as_str());
+                is_placed = true;
+            }
+        }
+        if !is_placed {
+            result.push('\t');
+            result.push_str(Self::SECTION_CONSTANTS);
+            result.push('\n');
+            result.push_str(cell.as_str());
+        }
+        result
+    }
+
+    ///
+    /// Checks whether the assembly `line` switches to the constant section, either with the
+    /// section name as the directive, or with the generic section directive.
+    ///
+    fn is_constant_section(line: &str) -> bool {
+        let line = line.trim();
+        let name = match line.strip_prefix(Self::DIRECTIVE_SECTION) {
+            Some(arguments) => arguments
+                .split(',')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .trim_matches('"'),
+            None => line,
+        };
+        name == Self::SECTION_CONSTANTS
+            || name
+                .strip_prefix(Self::SECTION_CONSTANTS)
+                .map_or(false, |suffix| suffix.starts_with('.'))
+    }
+
+    ///
+    /// Returns the cells following the metadata hash cell in the constant section of the
+    /// `assembly_text`, stopping at the first line that is neither a cell, nor a label, nor an
+    /// alignment.
+    ///
+    fn constants_after_hash(assembly_text: &str) -> Vec<[u8; compiler_common::BYTE_LENGTH_FIELD]> {
+        let label = format!("{}:", Self::LABEL);
+        let mut constants = Vec::new();
+        for line in assembly_text
+            .lines()
+            .map(|line| line.trim())
+            .skip_while(|line| *line != label.as_str())
+            .skip(2)
+        {
+            if line.ends_with(':') || line.starts_with(Self::DIRECTIVE_ALIGN) {
+                continue;
+            }
+            match line
+                .strip_prefix(Self::DIRECTIVE_CELL)
+                .and_then(|value| Self::cell_word(value.trim()))
+            {
+                Some(word) => constants.push(word),
+                None => break,
+            }
+        }
+        constants
+    }
+
+    ///
+    /// Converts the decimal cell `value` into the bytecode word.
+    ///
+    fn cell_word(value: &str) -> Option<[u8; compiler_common::BYTE_LENGTH_FIELD]> {
+        let value = num::BigInt::parse_bytes(value.as_bytes(), compiler_common::BASE_DECIMAL)?;
+        let bytes = value.to_signed_bytes_be();
+        let bytes = match bytes.as_slice() {
+            [0, rest @ ..] if rest.len() == compiler_common::BYTE_LENGTH_FIELD => rest,
+            bytes if bytes.len() <= compiler_common::BYTE_LENGTH_FIELD => bytes,
+            _ => return None,
+        };
+
+        let fill = if value.sign() == num::bigint::Sign::Minus {
+            0xff
+        } else {
+            0x00
+        };
+        let mut word = [fill; compiler_common::BYTE_LENGTH_FIELD];
+        word[compiler_common::BYTE_LENGTH_FIELD - bytes.len()..].copy_from_slice(bytes);
+        Some(word)
+    }
+}
+
+impl FromStr for MetadataHashPlacement {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "appended" => Ok(Self::Appended),
+            "constant-section" => Ok(Self::ConstantSection),
+            string => anyhow::bail!(
+                "Unknown metadata hash placement `{}`. Supported placements: `appended`, `constant-section`",
+                string
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for MetadataHashPlacement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Appended => write!(f, "appended"),
+            Self::ConstantSection => write!(f, "constant-section"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MetadataHashPlacement;
+
+    #[test]
+    fn prepend() {
+        let mut metadata_hash = [0u8; compiler_common::BYTE_LENGTH_FIELD];
+        metadata_hash[compiler_common::BYTE_LENGTH_FIELD - 1] = 42;
+
+        let (assembly_text, appended) = MetadataHashPlacement::ConstantSection.place(
+            "\t.text\n__entry:\n\tret\n\t.rodata\nCPI0_0:\n\t.cell 255\n",
+            Some(metadata_hash),
+        );
+        assert_eq!(appended, None);
+        assert_eq!(
+            assembly_text,
+            "\t.text\n__entry:\n\tret\n\t.rodata\n__metadata_hash:\n\t.cell 42\nCPI0_0:\n\t.cell 255\n"
+        );
+    }
+
+    #[test]
+    fn prepend_constant_pool() {
+        let mut metadata_hash = [0u8; compiler_common::BYTE_LENGTH_FIELD];
+        metadata_hash[compiler_common::BYTE_LENGTH_FIELD - 1] = 42;
+
+        for directive in [
+            "\t.rodata.cst32",
+            "\t.section\t.rodata.cst32,\"aM\",@progbits,32",
+        ] {
+            let (assembly_text, _appended) = MetadataHashPlacement::ConstantSection.place(
+                format!(
+                    "\t.text\n__entry:\n\tret\n\t.section\t.note.GNU-stack\n{directive}\n\t.p2align\t5\nCPI0_0:\n\t.cell\t-1\n"
+                )
+                .as_str(),
+                Some(metadata_hash),
+            );
+            assert_eq!(
+                assembly_text,
+                format!("\t.text\n__entry:\n\tret\n\t.section\t.note.GNU-stack\n{directive}\n__metadata_hash:\n\t.cell 42\n\t.p2align\t5\nCPI0_0:\n\t.cell\t-1\n")
+            );
+        }
+
+        let (assembly_text, _appended) = MetadataHashPlacement::ConstantSection.place(
+            "\t.text\n__entry:\n\tret\n\t.rodatax\n",
+            Some(metadata_hash),
+        );
+        assert!(assembly_text.ends_with("\t.rodatax\n\t.rodata\n__metadata_hash:\n\t.cell 42\n"));
+    }
+
+    #[test]
+    fn validate() {
+        let metadata_hash = [0xffu8; compiler_common::BYTE_LENGTH_FIELD];
+        let mut bytecode = vec![1u8; compiler_common::BYTE_LENGTH_FIELD * 2];
+        bytecode.extend_from_slice(metadata_hash.as_slice());
+
+        assert!(MetadataHashPlacement::Appended
+            .validate(bytecode.as_slice(), Some(&metadata_hash), "")
+            .is_ok());
+        assert!(MetadataHashPlacement::Appended
+            .validate(
+                &bytecode[..compiler_common::BYTE_LENGTH_FIELD * 2],
+                None,
+                ""
+            )
+            .is_err());
+        assert!(MetadataHashPlacement::Appended
+            .validate(&bytecode[1..], None, "")
+            .is_err());
+
+        bytecode.rotate_right(compiler_common::BYTE_LENGTH_FIELD);
+        assert!(MetadataHashPlacement::Appended
+            .validate(bytecode.as_slice(), Some(&metadata_hash), "")
+            .is_err());
+    }
+
+    #[test]
+    fn validate_constant_section() {
+        let metadata_hash = [0xffu8; compiler_common::BYTE_LENGTH_FIELD];
+        let mut constant = [0u8; compiler_common::BYTE_LENGTH_FIELD];
+        constant[compiler_common::BYTE_LENGTH_FIELD - 1] = 255;
+        let (assembly_text, _appended) = MetadataHashPlacement::ConstantSection.place(
+            "\t.text\n__entry:\n\tret\n\t.rodata.cst32\n\t.p2align\t5\nCPI0_0:\n\t.cell 255\n",
+            Some(metadata_hash),
+        );
+
+        let code = [1u8; compiler_common::BYTE_LENGTH_FIELD];
+        let bytecode = [code, metadata_hash, constant].concat();
+        assert!(MetadataHashPlacement::ConstantSection
+            .validate(
+                bytecode.as_slice(),
+                Some(&metadata_hash),
+                assembly_text.as_str()
+            )
+            .is_ok());
+
+        let bytecode = [code, metadata_hash, code].concat();
+        assert!(MetadataHashPlacement::ConstantSection
+            .validate(
+                bytecode.as_slice(),
+                Some(&metadata_hash),
+                assembly_text.as_str()
+            )
+            .is_err());
+
+        let bytecode = [metadata_hash, metadata_hash, constant].concat();
+        assert!(MetadataHashPlacement::ConstantSection
+            .validate(
+                bytecode.as_slice(),
+                Some(&metadata_hash),
+                assembly_text.as_str()
+            )
+            .is_err());
+    }
+}
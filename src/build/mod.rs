@@ -2,16 +2,41 @@
 //! The Solidity project build.
 //!
 
+pub mod archive;
+pub mod assembly_check;
+pub mod calldata;
+pub mod code_sections;
+pub mod constant_loop;
 pub mod contract;
+pub mod gas_usage;
+pub mod histogram;
+pub mod immutable;
+pub mod manifest;
+pub mod metadata_hash_placement;
+pub mod recommendation;
+pub mod shared_constant;
+pub mod skipped;
+pub mod source_bundle;
+pub mod source_hash;
+pub mod statistics;
+pub mod store;
+pub mod verify_bundle;
 
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
 
+use crate::format_version::FormatVersion;
+use crate::output_layout::OutputLayout;
 use crate::solc::combined_json::CombinedJson;
 use crate::solc::standard_json::output::Output as StandardJsonOutput;
 use crate::solc::version::Version as SolcVersion;
 
 use self::contract::Contract;
+use self::shared_constant::SharedConstant;
+use self::skipped::Skipped;
+use self::source_hash::SourceHash;
 
 ///
 /// The Solidity project build.
@@ -20,29 +45,160 @@ use self::contract::Contract;
 pub struct Build {
     /// The contract data,
     pub contracts: BTreeMap<String, Contract>,
+    /// The source code hashes, if the sources are available.
+    pub source_hashes: BTreeMap<String, SourceHash>,
+    /// The source unit names of the input files and the imports resolved by `solc`.
+    pub source_names: Vec<String>,
+    /// The source code of the input files, as read into memory and compiled, keyed by the
+    /// source unit names.
+    pub source_code: BTreeMap<String, Arc<str>>,
+    /// The keccak256 hashes of the compiled input files and the imports resolved by `solc`,
+    /// keyed by the source unit names.
+    pub compiled_sources: BTreeMap<String, String>,
+    /// The mutant builds, keyed by the mutant name.
+    pub mutants: BTreeMap<String, Build>,
+    /// The contracts recompiled on a build cache miss, with the changed transitive sources.
+    /// The sources are empty if the contract is built for the first time or the settings changed.
+    pub invalidated: BTreeMap<String, Vec<String>>,
+    /// The contracts skipped by the build, with the reasons.
+    pub skipped: BTreeMap<String, Skipped>,
 }
 
 impl Build {
+    /// The source code hashes file name, without the extension.
+    pub const SOURCE_HASHES_FILE_NAME: &'static str = "source_hashes";
+    /// The mutant builds directory name.
+    pub const MUTANTS_DIRECTORY_NAME: &'static str = "mutants";
+
     ///
     /// Writes all contracts to the specified directory.
     ///
+    /// Returns the paths of the written files.
+    ///
+    #[allow(clippy::too_many_arguments)]
     pub fn write_to_directory(
         self,
         output_directory: &Path,
         output_assembly: bool,
         output_binary: bool,
+        output_histogram: bool,
+        output_metadata: bool,
+        output_calldata: bool,
+        format_version: FormatVersion,
+        output_layout: OutputLayout,
         overwrite: bool,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        self.check_name_collisions(output_layout)?;
+
+        let mut written = Vec::new();
         for (_path, contract) in self.contracts.into_iter() {
-            contract.write_to_directory(
+            written.extend(contract.write_to_directory(
                 output_directory,
                 output_assembly,
                 output_binary,
+                output_histogram,
+                output_metadata,
+                output_calldata,
+                format_version,
+                output_layout,
                 overwrite,
-            )?;
+            )?);
         }
 
-        Ok(())
+        Ok(written)
+    }
+
+    ///
+    /// Checks that no two contracts are written to the same artifact files.
+    ///
+    /// The artifacts are named after the source file name and the contract name, or only after
+    /// the contract name in the `solc` layout, so the contracts of the same name declared in
+    /// different files could silently overwrite each other.
+    ///
+    pub fn check_name_collisions(&self, output_layout: OutputLayout) -> anyhow::Result<()> {
+        let collisions =
+            Self::name_collisions(self.contracts.keys().map(String::as_str), output_layout);
+        if collisions.is_empty() {
+            return Ok(());
+        }
+
+        let mut message = String::from("Contract artifact names collide:");
+        for (short_path, paths) in collisions.into_iter() {
+            message.push_str(format!("\n  `{short_path}` is declared in").as_str());
+            for path in paths.into_iter() {
+                let source = path
+                    .rsplit_once(':')
+                    .map(|(source, _name)| source)
+                    .unwrap_or(path);
+                message
+                    .push_str(format!("\n    {source} (fully qualified name `{path}`)").as_str());
+            }
+        }
+        message.push_str(
+            "\nRename the contracts or source files, or select them by their fully qualified names \
+             and compile them into separate output directories.",
+        );
+        anyhow::bail!("{}", message);
+    }
+
+    ///
+    /// Returns the contract full paths grouped by the artifact names they are written to.
+    ///
+    /// Only the groups of at least two contracts are returned.
+    ///
+    pub fn name_collisions<'a>(
+        paths: impl Iterator<Item = &'a str>,
+        output_layout: OutputLayout,
+    ) -> BTreeMap<&'a str, Vec<&'a str>> {
+        let mut groups: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for path in paths {
+            groups
+                .entry(output_layout.file_name(path))
+                .or_default()
+                .push(path);
+        }
+        groups.retain(|_short_path, paths| paths.len() > 1);
+        groups
+    }
+
+    ///
+    /// Writes the source code hashes to the specified directory.
+    ///
+    /// Since the format version 2, the hashes are nested into the `sources` field.
+    ///
+    /// Returns the path of the written file, or `None` if the existing file is not overwritten.
+    ///
+    pub fn write_source_hashes_to_directory(
+        &self,
+        output_directory: &Path,
+        format_version: FormatVersion,
+        overwrite: bool,
+    ) -> anyhow::Result<Option<PathBuf>> {
+        let mut file_path = output_directory.to_owned();
+        file_path.push(format!(
+            "{}.{}",
+            Self::SOURCE_HASHES_FILE_NAME,
+            compiler_common::EXTENSION_JSON
+        ));
+
+        if file_path.exists() && !overwrite {
+            eprintln!(
+                "Refusing to overwrite an existing file {file_path:?} (use --overwrite to force)."
+            );
+            return Ok(None);
+        }
+
+        let json = match format_version {
+            FormatVersion::V1 => serde_json::to_vec_pretty(&self.source_hashes),
+            FormatVersion::V2 => serde_json::to_vec_pretty(
+                &format_version.to_value(&serde_json::json!({ "sources": self.source_hashes })),
+            ),
+        }
+        .expect("Always valid");
+        std::fs::write(file_path.as_path(), json)
+            .map_err(|error| anyhow::anyhow!("File {:?} writing error: {}", file_path, error))?;
+
+        Ok(Some(file_path))
     }
 
     ///
@@ -104,4 +260,62 @@ impl Build {
 
         Ok(())
     }
+
+    ///
+    /// Returns the groups of contracts with identical bytecode, keyed by the bytecode hash.
+    ///
+    /// Only the groups of at least two contracts are returned.
+    ///
+    pub fn duplicates(&self) -> BTreeMap<String, Vec<String>> {
+        let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (path, contract) in self.contracts.iter() {
+            groups
+                .entry(contract.build.bytecode_hash.to_owned())
+                .or_default()
+                .push(path.to_owned());
+        }
+        groups.retain(|_hash, paths| paths.len() > 1);
+        groups
+    }
+
+    ///
+    /// Returns the large constants shared by several contracts, ordered by the saving potential.
+    ///
+    pub fn shared_constants(&self) -> Vec<SharedConstant> {
+        SharedConstant::find_all(
+            self.contracts
+                .iter()
+                .map(|(path, contract)| (path.as_str(), contract.build.assembly_text.as_str())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Build;
+    use crate::output_layout::OutputLayout;
+
+    #[test]
+    fn name_collisions() {
+        let paths = [
+            "contracts/token/Token.sol:Token",
+            "contracts/legacy/Token.sol:Token",
+            "contracts/token/Token.sol:TokenFactory",
+            "contracts/Vault.sol:Vault",
+        ];
+
+        let collisions = Build::name_collisions(paths.into_iter(), OutputLayout::Zksolc);
+        assert_eq!(collisions.len(), 1);
+        assert_eq!(
+            collisions.get("Token.sol:Token"),
+            Some(&vec![
+                "contracts/token/Token.sol:Token",
+                "contracts/legacy/Token.sol:Token"
+            ])
+        );
+
+        let collisions = Build::name_collisions(paths.into_iter(), OutputLayout::Solc);
+        assert_eq!(collisions.len(), 1);
+        assert_eq!(collisions.get("Token").map(Vec::len), Some(2));
+    }
 }
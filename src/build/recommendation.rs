@@ -0,0 +1,135 @@
+//!
+//! The contract optimization mode recommendation.
+//!
+
+use std::collections::BTreeMap;
+
+///
+/// The contract optimization mode recommendation.
+///
+/// Compares the bytecode sizes of the contract built in the candidate modes. The runtime cost
+/// is not measured, so the size mode is only recommended if it saves a substantial share of
+/// the bytecode, and the performance mode is kept otherwise.
+///
+#[derive(Debug, Clone)]
+pub struct Recommendation {
+    /// The contract full path.
+    pub path: String,
+    /// The bytecode sizes in bytes, keyed by the optimization mode.
+    pub sizes: BTreeMap<char, usize>,
+    /// The recommended optimization mode.
+    pub mode: char,
+}
+
+impl Recommendation {
+    /// The candidate optimization modes.
+    pub const MODES: [char; 3] = ['3', 's', 'z'];
+
+    /// The performance mode, recommended by default.
+    pub const MODE_PERFORMANCE: char = '3';
+
+    /// The minimal size saving in percent, which justifies the runtime cost of a size mode.
+    pub const SIZE_SAVING_THRESHOLD: usize = 10;
+
+    ///
+    /// A shortcut constructor.
+    ///
+    /// Picks the smallest size mode if it saves at least the threshold share of the bytecode
+    /// size of the performance mode.
+    ///
+    pub fn new(path: String, sizes: BTreeMap<char, usize>) -> Self {
+        let mode = match (
+            sizes.get(&Self::MODE_PERFORMANCE),
+            sizes
+                .iter()
+                .filter(|(mode, _)| **mode != Self::MODE_PERFORMANCE)
+                .min_by_key(|(_, size)| **size),
+        ) {
+            (Some(performance_size), Some((mode, size)))
+                if Self::saving(*performance_size, *size) >= Self::SIZE_SAVING_THRESHOLD =>
+            {
+                *mode
+            }
+            _ => Self::MODE_PERFORMANCE,
+        };
+
+        Self { path, sizes, mode }
+    }
+
+    ///
+    /// Returns the size saving of `size` relative to `reference` in percent.
+    ///
+    fn saving(reference: usize, size: usize) -> usize {
+        if reference == 0 || size >= reference {
+            return 0;
+        }
+        (reference - size) * 100 / reference
+    }
+}
+
+impl std::fmt::Display for Recommendation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sizes = self
+            .sizes
+            .iter()
+            .map(|(mode, size)| format!("`{mode}`: {size} B"))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let performance_size = self
+            .sizes
+            .get(&Self::MODE_PERFORMANCE)
+            .copied()
+            .unwrap_or_default();
+
+        if self.mode == Self::MODE_PERFORMANCE {
+            write!(
+                f,
+                "Contract `{}`: keep `{}`, as the size modes save less than {}% ({}).",
+                self.path,
+                Self::MODE_PERFORMANCE,
+                Self::SIZE_SAVING_THRESHOLD,
+                sizes,
+            )
+        } else {
+            let size = self.sizes.get(&self.mode).copied().unwrap_or_default();
+            write!(
+                f,
+                "Contract `{}`: `{}` saves {}% of the size of `{}` ({}). Consider it if the runtime cost is acceptable.",
+                self.path,
+                self.mode,
+                Self::saving(performance_size, size),
+                Self::MODE_PERFORMANCE,
+                sizes,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::Recommendation;
+
+    #[test]
+    fn size_mode_recommended() {
+        let recommendation = Recommendation::new(
+            "Test.sol:Test".to_owned(),
+            BTreeMap::from([('3', 10000), ('s', 8800), ('z', 8200)]),
+        );
+        assert_eq!(recommendation.mode, 'z');
+        assert_eq!(
+            recommendation.to_string(),
+            "Contract `Test.sol:Test`: `z` saves 18% of the size of `3` (`3`: 10000 B, `s`: 8800 B, `z`: 8200 B). Consider it if the runtime cost is acceptable."
+        );
+    }
+
+    #[test]
+    fn performance_mode_kept() {
+        let recommendation = Recommendation::new(
+            "Test.sol:Test".to_owned(),
+            BTreeMap::from([('3', 10000), ('s', 9500), ('z', 9400)]),
+        );
+        assert_eq!(recommendation.mode, '3');
+    }
+}
@@ -0,0 +1,131 @@
+//!
+//! The constant shared by several contracts.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+///
+/// The constant shared by several contracts.
+///
+/// Large constants, such as revert message chunks, hashes, and initialization tables, are
+/// stored in the constant pool of each contract using them. The shared ones are candidates for
+/// factoring into a common library to reduce the aggregate deployment cost.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedConstant {
+    /// The decimal constant value.
+    pub value: String,
+    /// The full paths of the contracts using the constant.
+    pub contracts: Vec<String>,
+}
+
+impl SharedConstant {
+    /// The constant pool cell size in bytes.
+    pub const CELL_SIZE: usize = compiler_common::BYTE_LENGTH_FIELD;
+
+    /// The minimal number of decimal digits of a reported constant.
+    ///
+    /// It is the number of digits of `u64::MAX`, so the values with fewer digits fit in 64 bits
+    /// and are cheap to materialize in each contract.
+    pub const MIN_DIGITS: usize = 20;
+
+    ///
+    /// Finds the constants shared by at least two contracts, ordered by the saving potential.
+    ///
+    /// The `assembly` is the zkEVM assembly text keyed by the contract path.
+    ///
+    pub fn find_all<'a>(assembly: impl Iterator<Item = (&'a str, &'a str)>) -> Vec<Self> {
+        let mut usages: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (path, assembly_text) in assembly {
+            for value in Self::constants(assembly_text).into_iter() {
+                usages.entry(value).or_default().push(path.to_owned());
+            }
+        }
+
+        let mut shared: Vec<Self> = usages
+            .into_iter()
+            .filter(|(_, contracts)| contracts.len() > 1)
+            .map(|(value, contracts)| Self { value, contracts })
+            .collect();
+        shared.sort_by(|a, b| {
+            b.redundant_size()
+                .cmp(&a.redundant_size())
+                .then_with(|| a.value.cmp(&b.value))
+        });
+        shared
+    }
+
+    ///
+    /// Returns the total size of the redundant copies of the constant in bytes.
+    ///
+    pub fn redundant_size(&self) -> usize {
+        (self.contracts.len() - 1) * Self::CELL_SIZE
+    }
+
+    ///
+    /// Returns the large constant pool values of the contract.
+    ///
+    fn constants(assembly_text: &str) -> BTreeSet<String> {
+        assembly_text
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix(".cell"))
+            .map(|value| value.trim())
+            .filter(|value| {
+                let digits = value.strip_prefix('-').unwrap_or(value);
+                digits.len() >= Self::MIN_DIGITS
+                    && digits.chars().all(|character| character.is_ascii_digit())
+            })
+            .map(|value| value.to_owned())
+            .collect()
+    }
+}
+
+impl std::fmt::Display for SharedConstant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Constant 0x{} is used by {} contracts ({} redundant bytes): {}",
+            num::BigInt::parse_bytes(self.value.as_bytes(), compiler_common::BASE_DECIMAL)
+                .map(|value| value.to_str_radix(compiler_common::BASE_HEXADECIMAL))
+                .unwrap_or_default(),
+            self.contracts.len(),
+            self.redundant_size(),
+            self.contracts
+                .iter()
+                .map(|path| format!("`{path}`"))
+                .collect::<Vec<String>>()
+                .join(", "),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SharedConstant;
+
+    #[test]
+    fn found() {
+        let large = "4835703278458516698824704";
+        let a = format!("CPI0_0:\n\t.cell {large}\nCPI0_1:\n\t.cell 255\n");
+        let b = format!("CPI0_0:\n\t.cell 255\nCPI0_1:\n\t.cell {large}\n");
+        let c = "CPI0_0:\n\t.cell 255\n".to_owned();
+
+        let shared = SharedConstant::find_all(
+            [
+                ("A.sol:A", a.as_str()),
+                ("B.sol:B", b.as_str()),
+                ("C.sol:C", c.as_str()),
+            ]
+            .into_iter(),
+        );
+        assert_eq!(
+            shared,
+            vec![SharedConstant {
+                value: large.to_owned(),
+                contracts: vec!["A.sol:A".to_owned(), "B.sol:B".to_owned()],
+            }]
+        );
+        assert_eq!(shared[0].redundant_size(), 32);
+    }
+}
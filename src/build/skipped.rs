@@ -0,0 +1,44 @@
+//!
+//! The contract skipped by the build.
+//!
+
+use crate::process::timeout::Timeout;
+
+///
+/// The contract skipped by the build.
+///
+/// The contracts exceeding the compile time budget are skipped instead of failing the build,
+/// along with the contracts depending on them, whose bytecode cannot be built without the
+/// dependency hashes.
+///
+#[derive(Debug, Clone)]
+pub enum Skipped {
+    /// The contract has exceeded the compile time budget.
+    Timeout(Timeout),
+    /// The full path of the skipped factory dependency of the contract.
+    Dependency(String),
+}
+
+impl std::fmt::Display for Skipped {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Timeout(timeout) => write!(f, "{timeout}"),
+            Self::Dependency(path) => {
+                write!(f, "its factory dependency `{path}` has been skipped")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Skipped;
+
+    #[test]
+    fn dependency() {
+        assert_eq!(
+            Skipped::Dependency("Child.sol:Child".to_owned()).to_string(),
+            "its factory dependency `Child.sol:Child` has been skipped"
+        );
+    }
+}
@@ -0,0 +1,362 @@
+//!
+//! The published source bundle.
+//!
+
+use std::collections::BTreeMap;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::build::source_hash::SourceHash;
+use crate::lockfile::Lockfile;
+
+///
+/// The published source bundle.
+///
+/// The input files and every import resolved by `solc`, exactly as compiled, written to the `sources` subdirectory
+/// of the output directory for publishing along with the artifacts. A project-configured banner
+/// can be prepended to each file, while the SPDX license identifier of the file stays on its
+/// first line, and the identifiers are additionally listed in `licenses.json`.
+///
+#[derive(Debug)]
+pub struct SourceBundle {
+    /// The source code, keyed by the source unit names.
+    pub sources: BTreeMap<String, String>,
+}
+
+impl SourceBundle {
+    /// The source bundle subdirectory name.
+    pub const DIRECTORY_NAME: &'static str = "sources";
+
+    /// The SPDX license identifiers file name.
+    pub const LICENSES_FILE_NAME: &'static str = "licenses.json";
+
+    /// The SPDX license identifier comment tag.
+    pub const SPDX_TAG: &'static str = "SPDX-License-Identifier:";
+
+    ///
+    /// Collects the compiled sources.
+    ///
+    /// The input files are taken from `source_code`, as they were read into memory and compiled.
+    /// The imports resolved by `solc` are read from the file system the way `solc` resolves them,
+    /// and are checked against the keccak256 hashes in `compiled_sources`, so a file changed
+    /// after the compilation is never published.
+    ///
+    pub fn try_new(
+        source_names: &[String],
+        source_code: &BTreeMap<String, Arc<str>>,
+        compiled_sources: &BTreeMap<String, String>,
+        base_path: Option<&str>,
+        include_paths: &[String],
+    ) -> anyhow::Result<Self> {
+        let mut sources = BTreeMap::new();
+        for name in source_names.iter() {
+            if let Some(content) = source_code.get(name.as_str()) {
+                sources.insert(name.to_owned(), content.to_string());
+                continue;
+            }
+
+            let path =
+                Lockfile::resolve(name.as_str(), base_path, include_paths).ok_or_else(|| {
+                    anyhow::anyhow!("Source `{}` is not found on the file system", name)
+                })?;
+            let content = std::fs::read_to_string(path.as_path())
+                .map_err(|error| anyhow::anyhow!("Source {:?} reading error: {}", path, error))?;
+            let compiled_hash = compiled_sources.get(name.as_str()).ok_or_else(|| {
+                anyhow::anyhow!("Source `{}` hash is not reported by `solc`", name)
+            })?;
+            if SourceHash::new(content.as_bytes()).keccak256 != *compiled_hash {
+                anyhow::bail!(
+                    "Source {:?} has changed on the file system since it was compiled",
+                    path
+                );
+            }
+            sources.insert(name.to_owned(), content);
+        }
+
+        let bundle = Self { sources };
+        bundle.paths()?;
+        Ok(bundle)
+    }
+
+    ///
+    /// Reads the banner text file and converts it to line comments.
+    ///
+    /// The banner must not contain an SPDX license identifier, as `solc` rejects the files
+    /// with several of them, and it would shadow the license of the file.
+    ///
+    pub fn read_banner(path: &Path) -> anyhow::Result<String> {
+        let banner = std::fs::read_to_string(path)
+            .map_err(|error| anyhow::anyhow!("Banner {:?} reading error: {}", path, error))?;
+        if banner.contains(Self::SPDX_TAG) {
+            anyhow::bail!(
+                "Banner {:?} must not contain `{}`, as the license identifier of each source file is kept",
+                path,
+                Self::SPDX_TAG
+            );
+        }
+
+        let mut comment = String::with_capacity(banner.len() * 2);
+        for line in banner.trim_end().lines() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                comment.push_str("//\n");
+            } else {
+                comment.push_str(format!("// {line}\n").as_str());
+            }
+        }
+        Ok(comment)
+    }
+
+    ///
+    /// Returns the SPDX license identifier of the source code, if any.
+    ///
+    pub fn license(source: &str) -> Option<String> {
+        let line = source
+            .lines()
+            .find_map(|line| line.split_once(Self::SPDX_TAG))?
+            .1;
+        let license = line.trim().trim_end_matches("*/").trim();
+        if license.is_empty() {
+            None
+        } else {
+            Some(license.to_owned())
+        }
+    }
+
+    ///
+    /// Prepends the `banner` to the source code, keeping the SPDX license identifier line first.
+    ///
+    pub fn with_banner(source: &str, banner: &str) -> String {
+        let mut result = String::with_capacity(source.len() + banner.len() + 1);
+        let rest = match source.split_once('\n') {
+            Some((first_line, rest)) if first_line.contains(Self::SPDX_TAG) => {
+                result.push_str(first_line);
+                result.push('\n');
+                rest
+            }
+            _ if !source.contains('\n') && source.contains(Self::SPDX_TAG) => {
+                result.push_str(source);
+                result.push('\n');
+                ""
+            }
+            _ => source,
+        };
+        result.push_str(banner);
+        result.push('\n');
+        result.push_str(rest);
+        result
+    }
+
+    ///
+    /// Writes the sources and the license identifiers to the `sources` subdirectory.
+    ///
+    /// Returns the paths of the written files.
+    ///
+    pub fn write(
+        &self,
+        output_directory: &Path,
+        banner: Option<&str>,
+        overwrite: bool,
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        let directory = output_directory.join(Self::DIRECTORY_NAME);
+        let mut written = Vec::with_capacity(self.sources.len() + 1);
+
+        let paths = self.paths()?;
+        let mut licenses = BTreeMap::new();
+        for (name, source) in self.sources.iter() {
+            licenses.insert(name.to_owned(), Self::license(source.as_str()));
+
+            let file_path = directory.join(paths[name.as_str()].as_path());
+            if file_path.exists() && !overwrite {
+                eprintln!(
+                    "Refusing to overwrite an existing file {file_path:?} (use --overwrite to force)."
+                );
+                continue;
+            }
+            if let Some(parent) = file_path.parent() {
+                std::fs::create_dir_all(parent).map_err(|error| {
+                    anyhow::anyhow!("Directory {:?} creating error: {}", parent, error)
+                })?;
+            }
+            let content = match banner {
+                Some(banner) => Self::with_banner(source.as_str(), banner),
+                None => source.to_owned(),
+            };
+            std::fs::write(file_path.as_path(), content).map_err(|error| {
+                anyhow::anyhow!("File {:?} writing error: {}", file_path, error)
+            })?;
+            written.push(file_path);
+        }
+
+        std::fs::create_dir_all(directory.as_path()).map_err(|error| {
+            anyhow::anyhow!("Directory {:?} creating error: {}", directory, error)
+        })?;
+        let file_path = directory.join(Self::LICENSES_FILE_NAME);
+        let json = serde_json::to_vec_pretty(&licenses).expect("Always valid");
+        std::fs::write(file_path.as_path(), json)
+            .map_err(|error| anyhow::anyhow!("File {:?} writing error: {}", file_path, error))?;
+        written.push(file_path);
+
+        Ok(written)
+    }
+
+    ///
+    /// Returns the paths of the sources inside the bundle directory, keyed by the source unit
+    /// names.
+    ///
+    /// Fails if several sources are mapped to the same path, so none of them is overwritten.
+    ///
+    fn paths(&self) -> anyhow::Result<BTreeMap<&str, PathBuf>> {
+        let mut names = BTreeMap::new();
+        for name in self.sources.keys() {
+            let path = Self::relative_path(name.as_str())?;
+            if let Some(other) = names.insert(path.clone(), name.as_str()) {
+                anyhow::bail!(
+                    "Sources `{}` and `{}` are both bundled as {:?}",
+                    other,
+                    name,
+                    path
+                );
+            }
+        }
+        Ok(names.into_iter().map(|(path, name)| (name, path)).collect())
+    }
+
+    ///
+    /// Converts the source unit name to a path inside the bundle directory.
+    ///
+    /// The path is normalized, with the root component dropped, so the absolute source unit
+    /// names are written inside the bundle as well. The names referring outside of the root
+    /// with the parent directory components are rejected.
+    ///
+    pub fn relative_path(name: &str) -> anyhow::Result<PathBuf> {
+        let mut path = PathBuf::new();
+        for component in Path::new(name).components() {
+            match component {
+                Component::Normal(component) => path.push(component),
+                Component::ParentDir => {
+                    if !path.pop() {
+                        anyhow::bail!("Source `{}` refers outside of the bundle directory", name);
+                    }
+                }
+                Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+            }
+        }
+        if path.as_os_str().is_empty() {
+            anyhow::bail!("Source `{}` has no file name", name);
+        }
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use crate::build::source_hash::SourceHash;
+
+    use super::SourceBundle;
+
+    #[test]
+    fn license() {
+        assert_eq!(
+            SourceBundle::license("// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\n"),
+            Some("MIT".to_owned())
+        );
+        assert_eq!(
+            SourceBundle::license("/* SPDX-License-Identifier: GPL-3.0-or-later */\n"),
+            Some("GPL-3.0-or-later".to_owned())
+        );
+        assert_eq!(SourceBundle::license("pragma solidity ^0.8.0;\n"), None);
+    }
+
+    #[test]
+    fn with_banner() {
+        let banner = "// Copyright Example Ltd.\n";
+        assert_eq!(
+            SourceBundle::with_banner(
+                "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\n",
+                banner
+            ),
+            "// SPDX-License-Identifier: MIT\n// Copyright Example Ltd.\n\npragma solidity ^0.8.0;\n"
+        );
+        assert_eq!(
+            SourceBundle::with_banner("pragma solidity ^0.8.0;\n", banner),
+            "// Copyright Example Ltd.\n\npragma solidity ^0.8.0;\n"
+        );
+    }
+
+    #[test]
+    fn relative_path() {
+        assert_eq!(
+            SourceBundle::relative_path("/home/user/../lib/Token.sol").expect("Always valid"),
+            PathBuf::from("home/lib/Token.sol")
+        );
+        assert_eq!(
+            SourceBundle::relative_path("@openzeppelin/contracts/./token/ERC20.sol")
+                .expect("Always valid"),
+            PathBuf::from("@openzeppelin/contracts/token/ERC20.sol")
+        );
+        assert!(SourceBundle::relative_path("../lib/A.sol").is_err());
+    }
+
+    #[test]
+    fn collision() {
+        let mut sources = BTreeMap::new();
+        sources.insert("/lib/A.sol".to_owned(), "contract A {}".to_owned());
+        sources.insert("lib/A.sol".to_owned(), "contract B {}".to_owned());
+        let error = SourceBundle { sources }
+            .paths()
+            .expect_err("Always fails")
+            .to_string();
+        assert!(error.contains("are both bundled as"));
+    }
+
+    #[test]
+    fn in_memory() {
+        let directory = std::env::temp_dir().join(format!(
+            "zksolc-source-bundle-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        std::fs::create_dir_all(directory.join("lib")).expect("Always valid");
+        std::fs::write(directory.join("lib").join("B.sol"), "contract B {}").expect("Always valid");
+        let base_path = directory.to_string_lossy().to_string();
+
+        let source_names = vec!["A.sol".to_owned(), "lib/B.sol".to_owned()];
+        let mut source_code = BTreeMap::new();
+        source_code.insert("A.sol".to_owned(), Arc::from("contract A {}"));
+        let mut compiled_sources = BTreeMap::new();
+        compiled_sources.insert(
+            "lib/B.sol".to_owned(),
+            SourceHash::new(b"contract B {}").keccak256,
+        );
+
+        let bundle = SourceBundle::try_new(
+            source_names.as_slice(),
+            &source_code,
+            &compiled_sources,
+            Some(base_path.as_str()),
+            &[],
+        )
+        .expect("Always valid");
+
+        std::fs::write(directory.join("lib").join("B.sol"), "contract C {}").expect("Always valid");
+        let changed = SourceBundle::try_new(
+            source_names.as_slice(),
+            &source_code,
+            &compiled_sources,
+            Some(base_path.as_str()),
+            &[],
+        );
+        std::fs::remove_dir_all(directory.as_path()).expect("Always valid");
+
+        assert_eq!(bundle.sources["A.sol"], "contract A {}");
+        assert_eq!(bundle.sources["lib/B.sol"], "contract B {}");
+        assert!(changed.is_err());
+    }
+}
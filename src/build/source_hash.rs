@@ -0,0 +1,51 @@
+//!
+//! The source code hash.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+use sha3::Digest;
+
+///
+/// The source code hash.
+///
+/// The keccak256 hash is the one that appears in the metadata, whereas the sha256 hash is used
+/// by the audit and provenance tooling.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SourceHash {
+    /// The `0x`-prefixed keccak256 hash.
+    pub keccak256: String,
+    /// The `0x`-prefixed sha256 hash.
+    pub sha256: String,
+}
+
+impl SourceHash {
+    ///
+    /// Hashes the source code `content`.
+    ///
+    pub fn new(content: &[u8]) -> Self {
+        Self {
+            keccak256: format!("0x{}", hex::encode(sha3::Keccak256::digest(content))),
+            sha256: format!("0x{}", hex::encode(sha2::Sha256::digest(content))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SourceHash;
+
+    #[test]
+    fn empty() {
+        let hash = SourceHash::new(b"");
+        assert_eq!(
+            hash.keccak256,
+            "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(
+            hash.sha256,
+            "0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+}
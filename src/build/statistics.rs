@@ -0,0 +1,71 @@
+//!
+//! The contract compilation statistics.
+//!
+
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The contract compilation statistics.
+///
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Statistics {
+    /// The compilation time in milliseconds.
+    pub compile_time: u64,
+    /// The input IR size in bytes.
+    pub ir_size: usize,
+    /// The zkEVM assembly size in bytes.
+    pub assembly_size: usize,
+    /// The zkEVM bytecode size in bytes.
+    pub bytecode_size: usize,
+    /// The number of zkEVM assembly instructions.
+    pub instruction_count: usize,
+    /// The stringified optimizer settings.
+    pub optimization_mode: String,
+    /// Whether the build has been taken from a cache.
+    pub cache_hit: bool,
+}
+
+impl Statistics {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        compile_time: Duration,
+        ir_size: usize,
+        build: &compiler_llvm_context::Build,
+        optimizer_settings: &compiler_llvm_context::OptimizerSettings,
+    ) -> Self {
+        Self {
+            compile_time: compile_time.as_millis() as u64,
+            ir_size,
+            assembly_size: build.assembly_text.len(),
+            bytecode_size: build.bytecode.len(),
+            instruction_count: Self::count_instructions(build.assembly_text.as_str()),
+            optimization_mode: optimizer_settings.to_string(),
+            cache_hit: false,
+        }
+    }
+
+    ///
+    /// Counts the instructions in the zkEVM assembly text.
+    ///
+    /// Labels, directives, and comments are skipped.
+    ///
+    pub fn count_instructions(assembly_text: &str) -> usize {
+        assembly_text
+            .lines()
+            .filter(|line| line.starts_with(char::is_whitespace))
+            .map(|line| line.trim())
+            .filter(|line| {
+                !line.is_empty()
+                    && !line.starts_with('.')
+                    && !line.starts_with(';')
+                    && !line.ends_with(':')
+            })
+            .count()
+    }
+}
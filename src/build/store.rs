@@ -0,0 +1,518 @@
+//!
+//! The content-addressed artifact store.
+//!
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use sha3::Digest;
+
+use crate::build::contract::Contract as ContractBuild;
+use crate::build::Build;
+use crate::http::Endpoint as HttpEndpoint;
+use crate::project::contract::ir::IR as ProjectContractIR;
+use crate::project::contract::Contract as ProjectContract;
+
+///
+/// The content-addressed artifact store.
+///
+/// The bytecode of each contract is written to `objects/<bytecode hash>`, and the mapping
+/// from full contract paths to bytecode hashes is written to `index.json`.
+///
+/// The store also serves as the incremental build cache. The contract builds are written to
+/// `builds/<key>.json`, where the key is the hash of the compiler settings and the `solc`
+/// metadata, which pins the hashes of all sources the contract transitively imports. Thus,
+/// editing a source only invalidates the contracts importing it. The transitive sources of
+/// each contract are written to `sources.json`, so the invalidation can be explained.
+///
+/// The builds are pushed to the remote cache as `builds/<key>.json` along with the objects, so
+/// the stores sharing it reuse the contract builds of each other on a local cache miss.
+///
+#[derive(Debug, Clone)]
+pub struct Store {
+    /// The store root directory.
+    pub root: PathBuf,
+    /// The optional remote cache endpoint.
+    pub cache: Option<HttpEndpoint>,
+}
+
+impl Store {
+    /// The index file name.
+    pub const INDEX_FILE_NAME: &'static str = "index.json";
+
+    /// The objects directory name.
+    pub const OBJECTS_DIRECTORY_NAME: &'static str = "objects";
+
+    /// The cached builds directory name.
+    pub const BUILDS_DIRECTORY_NAME: &'static str = "builds";
+
+    /// The contract transitive sources file name.
+    pub const SOURCES_FILE_NAME: &'static str = "sources.json";
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(root: PathBuf, cache_url: Option<&str>) -> anyhow::Result<Self> {
+        let cache = match cache_url {
+            Some(url) => Some(HttpEndpoint::try_from_url(url)?),
+            None => None,
+        };
+
+        Ok(Self { root, cache })
+    }
+
+    ///
+    /// Writes the build artifacts to the store, pushing the new objects to the remote cache.
+    ///
+    /// The existing index entries of other contracts are preserved.
+    ///
+    pub fn write(&self, build: &Build) -> anyhow::Result<()> {
+        let objects_directory = self.root.join(Self::OBJECTS_DIRECTORY_NAME);
+        std::fs::create_dir_all(objects_directory.as_path()).map_err(|error| {
+            anyhow::anyhow!(
+                "Directory {:?} creating error: {}",
+                objects_directory,
+                error
+            )
+        })?;
+
+        let mut index = self.read_index()?;
+        for (path, contract) in build.contracts.iter() {
+            let hash = contract.build.bytecode_hash.as_str();
+            let object_path = self.object_path(hash);
+            if !object_path.exists() {
+                std::fs::write(object_path.as_path(), contract.build.bytecode.as_slice()).map_err(
+                    |error| anyhow::anyhow!("File {:?} writing error: {}", object_path, error),
+                )?;
+
+                if let Some(cache) = self.cache.as_ref() {
+                    cache.put(hash, contract.build.bytecode.as_slice())?;
+                }
+            }
+            index.insert(path.to_owned(), hash.to_owned());
+        }
+
+        let index_path = self.root.join(Self::INDEX_FILE_NAME);
+        let index_json = serde_json::to_vec_pretty(&index).expect("Always valid");
+        std::fs::write(index_path.as_path(), index_json)
+            .map_err(|error| anyhow::anyhow!("File {:?} writing error: {}", index_path, error))?;
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the object with the specified hash.
+    ///
+    /// If the object is missing locally, it is pulled from the remote cache and saved.
+    ///
+    pub fn get(&self, hash: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        self.pull(self.object_path(hash).as_path(), hash)
+    }
+
+    ///
+    /// Reads the index, returning an empty one if it does not exist yet.
+    ///
+    pub fn read_index(&self) -> anyhow::Result<BTreeMap<String, String>> {
+        let index_path = self.root.join(Self::INDEX_FILE_NAME);
+        if !index_path.exists() {
+            return Ok(BTreeMap::new());
+        }
+
+        let index = std::fs::read(index_path.as_path())
+            .map_err(|error| anyhow::anyhow!("File {:?} reading error: {}", index_path, error))?;
+        serde_json::from_slice(index.as_slice())
+            .map_err(|error| anyhow::anyhow!("File {:?} parsing error: {}", index_path, error))
+    }
+
+    ///
+    /// Returns the build cache key of the contract, if it can be cached.
+    ///
+    /// Only the contracts with the `solc` metadata listing their transitive sources are cached.
+    /// The key includes the IR the contract is compiled from, as the same sources produce
+    /// different bytecode via the Yul and EVM legacy assembly pipelines.
+    ///
+    pub fn build_key(contract: &ProjectContract, settings: &serde_json::Value) -> Option<String> {
+        Self::contract_sources(contract)?;
+        let pipeline = match contract.ir {
+            ProjectContractIR::Yul(_) => "Yul",
+            ProjectContractIR::EVMLA(_) => "EVMLA",
+            ProjectContractIR::LLVMIR(_) => "LLVMIR",
+            ProjectContractIR::ZKASM(_) => "ZKASM",
+        };
+        let key = serde_json::json!({
+            "path": contract.path,
+            "pipeline": pipeline,
+            "metadata": contract.metadata_json,
+            "settings": settings,
+        });
+        let key = serde_json::to_vec(&key).expect("Always valid");
+        Some(hex::encode(sha3::Keccak256::digest(key.as_slice())))
+    }
+
+    ///
+    /// Returns the cached build with the specified key.
+    ///
+    /// If the build is missing locally, it is pulled from the remote cache and saved.
+    /// The unreadable builds are treated as missing, so they are recompiled and overwritten.
+    ///
+    pub fn get_build(&self, key: &str) -> anyhow::Result<Option<ContractBuild>> {
+        let build = self.pull(
+            self.build_path(key).as_path(),
+            Self::build_cache_path(key).as_str(),
+        )?;
+        Ok(build.and_then(|build| serde_json::from_slice(build.as_slice()).ok()))
+    }
+
+    ///
+    /// Writes the build with the specified key to the cache, pushing it to the remote cache.
+    ///
+    pub fn put_build(&self, key: &str, build: &ContractBuild) -> anyhow::Result<()> {
+        let build = serde_json::to_vec(build).expect("Always valid");
+        self.push(
+            self.build_path(key).as_path(),
+            Self::build_cache_path(key).as_str(),
+            build.as_slice(),
+        )
+    }
+
+    ///
+    /// Returns the transitive sources of the contract with their keccak256 hashes, as listed
+    /// in the `solc` metadata, which is either a JSON object or its serialized string.
+    ///
+    pub fn contract_sources(contract: &ProjectContract) -> Option<BTreeMap<String, String>> {
+        let metadata: serde_json::Value = match contract.metadata_json {
+            serde_json::Value::String(ref metadata) => serde_json::from_str(metadata).ok()?,
+            ref metadata => metadata.to_owned(),
+        };
+        let sources = metadata.get("sources")?.as_object()?;
+        Some(
+            sources
+                .iter()
+                .map(|(path, source)| {
+                    let hash = source
+                        .get("keccak256")
+                        .and_then(|hash| hash.as_str())
+                        .unwrap_or_default();
+                    (path.to_owned(), hash.to_owned())
+                })
+                .collect(),
+        )
+    }
+
+    ///
+    /// Reads the transitive sources of the contracts built previously, keyed by the full
+    /// contract paths. Returns an empty map if there are none.
+    ///
+    pub fn read_sources(&self) -> BTreeMap<String, BTreeMap<String, String>> {
+        std::fs::read(self.root.join(Self::SOURCES_FILE_NAME))
+            .ok()
+            .and_then(|sources| serde_json::from_slice(sources.as_slice()).ok())
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Writes the transitive sources of the contracts, preserving the entries of other contracts.
+    ///
+    pub fn write_sources(
+        &self,
+        sources: BTreeMap<String, BTreeMap<String, String>>,
+    ) -> anyhow::Result<()> {
+        let mut all_sources = self.read_sources();
+        all_sources.extend(sources);
+        let sources_json = serde_json::to_vec_pretty(&all_sources).expect("Always valid");
+        Self::write_object(
+            self.root.join(Self::SOURCES_FILE_NAME).as_path(),
+            sources_json.as_slice(),
+        )
+    }
+
+    ///
+    /// Returns the sources changed between the `previous` and `current` transitive sources
+    /// of a contract.
+    ///
+    pub fn changed_sources(
+        previous: &BTreeMap<String, String>,
+        current: &BTreeMap<String, String>,
+    ) -> Vec<String> {
+        let mut changed: Vec<String> = current
+            .iter()
+            .filter(|(path, hash)| previous.get(path.as_str()) != Some(hash))
+            .map(|(path, _hash)| path.to_owned())
+            .collect();
+        changed.extend(
+            previous
+                .keys()
+                .filter(|path| !current.contains_key(path.as_str()))
+                .cloned(),
+        );
+        changed.sort();
+        changed
+    }
+
+    ///
+    /// Returns the path to the cached build with the specified key.
+    ///
+    fn build_path(&self, key: &str) -> PathBuf {
+        let mut path = self.root.join(Self::BUILDS_DIRECTORY_NAME);
+        path.push(format!("{key}.{}", compiler_common::EXTENSION_JSON));
+        path
+    }
+
+    ///
+    /// Returns the remote cache path to the build with the specified key.
+    ///
+    fn build_cache_path(key: &str) -> String {
+        format!(
+            "{}/{key}.{}",
+            Self::BUILDS_DIRECTORY_NAME,
+            compiler_common::EXTENSION_JSON
+        )
+    }
+
+    ///
+    /// Returns the path to the object with the specified hash.
+    ///
+    fn object_path(&self, hash: &str) -> PathBuf {
+        let mut path = self.root.join(Self::OBJECTS_DIRECTORY_NAME);
+        path.push(hash);
+        path
+    }
+
+    ///
+    /// Reads the file at the `local_path`, pulling it from the `remote_path` of the remote cache
+    /// and saving it if it is missing locally.
+    ///
+    fn pull(&self, local_path: &Path, remote_path: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        if local_path.exists() {
+            let object = std::fs::read(local_path).map_err(|error| {
+                anyhow::anyhow!("File {:?} reading error: {}", local_path, error)
+            })?;
+            return Ok(Some(object));
+        }
+
+        let cache = match self.cache.as_ref() {
+            Some(cache) => cache,
+            None => return Ok(None),
+        };
+        let object = match cache.get(remote_path)? {
+            Some(object) => object,
+            None => return Ok(None),
+        };
+        Self::write_object(local_path, object.as_slice())?;
+        Ok(Some(object))
+    }
+
+    ///
+    /// Writes the file to the `local_path`, pushing it to the `remote_path` of the remote cache.
+    ///
+    fn push(&self, local_path: &Path, remote_path: &str, object: &[u8]) -> anyhow::Result<()> {
+        Self::write_object(local_path, object)?;
+        if let Some(cache) = self.cache.as_ref() {
+            cache.put(remote_path, object)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Writes an object to the specified path, creating the parent directory if necessary.
+    ///
+    fn write_object(path: &Path, object: &[u8]) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|error| {
+                anyhow::anyhow!("Directory {:?} creating error: {}", parent, error)
+            })?;
+        }
+        std::fs::write(path, object)
+            .map_err(|error| anyhow::anyhow!("File {:?} writing error: {}", path, error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::collections::HashMap;
+    use std::io::BufRead;
+    use std::io::BufReader;
+    use std::io::Read;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+
+    use crate::project::contract::ir::IR as ProjectContractIR;
+    use crate::project::contract::Contract as ProjectContract;
+
+    use super::Store;
+
+    ///
+    /// Serves the in-memory remote cache, returning its URL.
+    ///
+    fn serve_cache() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Always valid");
+        let port = listener.local_addr().expect("Always valid").port();
+        std::thread::spawn(move || {
+            let mut resources: HashMap<String, Vec<u8>> = HashMap::new();
+            for stream in listener.incoming() {
+                let mut stream = BufReader::new(stream.expect("Always valid"));
+                let mut request_line = String::new();
+                stream.read_line(&mut request_line).expect("Always valid");
+                let mut request_line = request_line.split_whitespace();
+                let method = request_line.next().unwrap_or_default().to_owned();
+                let target = request_line.next().unwrap_or_default().to_owned();
+
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    stream.read_line(&mut header).expect("Always valid");
+                    let header = header.trim_end();
+                    if header.is_empty() {
+                        break;
+                    }
+                    if let Some(length) = header.strip_prefix("Content-Length: ") {
+                        content_length = length.parse().expect("Always valid");
+                    }
+                }
+                let mut body = vec![0u8; content_length];
+                stream
+                    .read_exact(body.as_mut_slice())
+                    .expect("Always valid");
+
+                let mut stream = stream.into_inner();
+                match (method.as_str(), resources.get(target.as_str())) {
+                    ("PUT", _) => {
+                        resources.insert(target, body);
+                        let _ =
+                            stream.write_all(b"HTTP/1.1 201 Created\r\nContent-Length: 0\r\n\r\n");
+                    }
+                    ("GET", Some(resource)) => {
+                        let head = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+                            resource.len()
+                        );
+                        let _ = stream.write_all(head.as_bytes());
+                        let _ = stream.write_all(resource.as_slice());
+                    }
+                    _ => {
+                        let _ = stream
+                            .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n");
+                    }
+                }
+            }
+        });
+        format!("http://127.0.0.1:{port}/cache")
+    }
+
+    fn root(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "zksolc-store-{name}-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ))
+    }
+
+    fn sources(sources: &[(&str, &str)]) -> BTreeMap<String, String> {
+        sources
+            .iter()
+            .map(|(path, hash)| (path.to_string(), hash.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn changed_sources() {
+        let previous = sources(&[
+            ("A.sol", "0x01"),
+            ("lib/B.sol", "0x02"),
+            ("lib/C.sol", "0x03"),
+        ]);
+        let current = sources(&[
+            ("A.sol", "0x01"),
+            ("lib/B.sol", "0x04"),
+            ("lib/D.sol", "0x05"),
+        ]);
+        assert_eq!(
+            Store::changed_sources(&previous, &current),
+            vec!["lib/B.sol", "lib/C.sol", "lib/D.sol"]
+        );
+        assert!(Store::changed_sources(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn build_key_pipeline() {
+        let metadata = serde_json::json!({
+            "sources": { "Test.sol": { "keccak256": "0x01" } },
+        });
+        let contract = |ir| {
+            ProjectContract::new(
+                "Test.sol:Test".to_owned(),
+                [0; compiler_common::BYTE_LENGTH_FIELD],
+                semver::Version::new(0, 8, 20),
+                ir,
+                Some(metadata.clone()),
+            )
+        };
+        let llvm_ir = contract(ProjectContractIR::new_llvm_ir(
+            "Test.sol:Test".to_owned(),
+            String::new(),
+        ));
+        let zkasm = contract(ProjectContractIR::new_zkasm(
+            "Test.sol:Test".to_owned(),
+            String::new(),
+        ));
+
+        let settings = serde_json::json!({});
+        assert_ne!(
+            Store::build_key(&llvm_ir, &settings),
+            Store::build_key(&zkasm, &settings)
+        );
+        assert_ne!(
+            Store::build_key(&llvm_ir, &settings),
+            Store::build_key(&llvm_ir, &serde_json::json!({ "evmlaTraceTag": 1 }))
+        );
+    }
+
+    #[test]
+    fn shared_cache() {
+        let cache_url = serve_cache();
+        let first = Store::new(root("first"), Some(cache_url.as_str())).expect("Always valid");
+        let second = Store::new(root("second"), Some(cache_url.as_str())).expect("Always valid");
+
+        let key = "01".repeat(32);
+        first
+            .push(
+                first.build_path(key.as_str()).as_path(),
+                Store::build_cache_path(key.as_str()).as_str(),
+                b"{}",
+            )
+            .expect("Always valid");
+        assert_eq!(
+            second
+                .pull(
+                    second.build_path(key.as_str()).as_path(),
+                    Store::build_cache_path(key.as_str()).as_str(),
+                )
+                .expect("Always valid"),
+            Some(b"{}".to_vec())
+        );
+        assert!(second.build_path(key.as_str()).exists());
+
+        let hash = "02".repeat(32);
+        first
+            .push(
+                first.object_path(hash.as_str()).as_path(),
+                hash.as_str(),
+                b"object",
+            )
+            .expect("Always valid");
+        assert_eq!(
+            second.get(hash.as_str()).expect("Always valid"),
+            Some(b"object".to_vec())
+        );
+        assert_eq!(
+            second.get("03".repeat(32).as_str()).expect("Always valid"),
+            None
+        );
+
+        let _ = std::fs::remove_dir_all(first.root.as_path());
+        let _ = std::fs::remove_dir_all(second.root.as_path());
+    }
+}
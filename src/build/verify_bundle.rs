@@ -0,0 +1,360 @@
+//!
+//! The contract verification bundle.
+//!
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::build::source_bundle::SourceBundle;
+use crate::build::Build;
+use crate::cache::Cache;
+use crate::debug::filter::Filter as DebugFilter;
+use crate::solc::Compiler as SolcCompiler;
+
+///
+/// The contract verification bundle.
+///
+/// Pins everything the build depends on: the compiler versions, the flags outside of the
+/// standard JSON input, and the input itself with the sources and settings. Replaying it
+/// reproduces the build and compares the contract bytecode hash to the expected one, which is
+/// what the block explorers do to verify the deployed contracts.
+///
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyBundle {
+    /// The zksolc version.
+    pub zksolc_version: semver::Version,
+    /// The `solc` version.
+    pub solc_version: semver::Version,
+    /// Whether the EVM legacy assembly pipeline is forced.
+    #[serde(default)]
+    pub force_evmla: bool,
+    /// Whether the system mode is enabled.
+    #[serde(default)]
+    pub system_mode: bool,
+    /// The full path of the contract to verify.
+    pub contract: String,
+    /// The `0x`-prefixed expected bytecode hash.
+    pub bytecode_hash: String,
+    /// The standard JSON input.
+    pub input: serde_json::Value,
+}
+
+///
+/// The verification bundle replay result.
+///
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Replay {
+    /// The full path of the verified contract.
+    pub contract: String,
+    /// The `0x`-prefixed expected bytecode hash.
+    pub expected_bytecode_hash: String,
+    /// The `0x`-prefixed reproduced bytecode hash.
+    pub actual_bytecode_hash: String,
+    /// Whether the hashes match.
+    pub matches: bool,
+}
+
+impl VerifyBundle {
+    /// The bundle file name extension.
+    pub const EXTENSION: &'static str = "verify.json";
+
+    ///
+    /// Creates the bundles of all the contracts with bytecode in the standard JSON `output`
+    /// compiled from the standard JSON `input`.
+    ///
+    /// The input is pinned as is, so it must have all the sources inline and no sandbox, as the
+    /// bundles are replayed in hermetic mode.
+    ///
+    pub fn new_all(
+        input: &serde_json::Value,
+        output: &serde_json::Value,
+        solc_version: &semver::Version,
+        force_evmla: bool,
+        system_mode: bool,
+    ) -> anyhow::Result<Vec<Self>> {
+        if !Cache::is_cacheable(input) || input.pointer("/settings/sandbox").is_some() {
+            anyhow::bail!(
+                "The verification bundles require the input with all the sources inline and no sandbox"
+            );
+        }
+        let zksolc_version =
+            semver::Version::parse(env!("CARGO_PKG_VERSION")).expect("Always valid");
+
+        let mut bundles = Vec::new();
+        for (path, contracts) in output
+            .get("contracts")
+            .and_then(|files| files.as_object())
+            .into_iter()
+            .flatten()
+        {
+            for (name, contract) in contracts.as_object().into_iter().flatten() {
+                let bytecode_hash = match contract.get("hash").and_then(|hash| hash.as_str()) {
+                    Some(hash) => hash,
+                    None => continue,
+                };
+                bundles.push(Self {
+                    zksolc_version: zksolc_version.to_owned(),
+                    solc_version: solc_version.to_owned(),
+                    force_evmla,
+                    system_mode,
+                    contract: format!("{path}:{name}"),
+                    bytecode_hash: Self::normalize_hash(bytecode_hash),
+                    input: input.to_owned(),
+                });
+            }
+        }
+        Ok(bundles)
+    }
+
+    ///
+    /// Creates the bundles of all the contracts in the `build` compiled in the default Solidity
+    /// mode.
+    ///
+    /// The pinned standard JSON input is assembled from the `sources`, which are all the compiled
+    /// sources including the resolved imports keyed by their source unit names, and the standard
+    /// JSON `settings` equivalent to the command line options the `build` was compiled with.
+    ///
+    pub fn new_all_from_build(
+        build: &Build,
+        sources: &BTreeMap<String, String>,
+        mut settings: serde_json::Value,
+        solc_version: &semver::Version,
+        force_evmla: bool,
+        system_mode: bool,
+    ) -> Vec<Self> {
+        settings["outputSelection"] = serde_json::json!({ "*": { "*": ["abi"] } });
+        let input = serde_json::json!({
+            "language": "Solidity",
+            "sources": sources
+                .iter()
+                .map(|(path, content)| (path.to_owned(), serde_json::json!({ "content": content })))
+                .collect::<serde_json::Map<String, serde_json::Value>>(),
+            "settings": settings,
+        });
+        let zksolc_version =
+            semver::Version::parse(env!("CARGO_PKG_VERSION")).expect("Always valid");
+
+        build
+            .contracts
+            .iter()
+            .map(|(path, contract)| Self {
+                zksolc_version: zksolc_version.to_owned(),
+                solc_version: solc_version.to_owned(),
+                force_evmla,
+                system_mode,
+                contract: path.to_owned(),
+                bytecode_hash: Self::normalize_hash(contract.build.bytecode_hash.as_str()),
+                input: input.to_owned(),
+            })
+            .collect()
+    }
+
+    ///
+    /// Writes the bundle into `directory`, at the contract source path, and returns the file path.
+    ///
+    pub fn write(&self, directory: &Path) -> anyhow::Result<PathBuf> {
+        let (path, name) = self.contract.rsplit_once(':').ok_or_else(|| {
+            anyhow::anyhow!(
+                "The contract path `{}` must have the `<path>:<name>` format",
+                self.contract
+            )
+        })?;
+        let directory = directory.join(SourceBundle::relative_path(path)?);
+        std::fs::create_dir_all(directory.as_path()).map_err(|error| {
+            anyhow::anyhow!("Directory {:?} creating error: {}", directory, error)
+        })?;
+
+        let file_path = directory.join(format!("{name}.{}", Self::EXTENSION));
+        let json = serde_json::to_vec_pretty(self).expect("Always valid");
+        std::fs::write(file_path.as_path(), json)
+            .map_err(|error| anyhow::anyhow!("File {:?} writing error: {}", file_path, error))?;
+        Ok(file_path)
+    }
+
+    ///
+    /// Reads the bundle from the JSON file at `path`.
+    ///
+    pub fn try_from_path(path: &Path) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(path).map_err(|error| {
+            anyhow::anyhow!("Verification bundle {:?} reading error: {}", path, error)
+        })?;
+        serde_json::from_reader(std::io::BufReader::new(file)).map_err(|error| {
+            anyhow::anyhow!("Verification bundle {:?} parsing error: {}", path, error)
+        })
+    }
+
+    ///
+    /// Checks that the running zksolc and the `solc` executable match the pinned versions.
+    ///
+    pub fn check_versions(&self, solc: &mut SolcCompiler) -> anyhow::Result<()> {
+        let zksolc_version =
+            semver::Version::parse(env!("CARGO_PKG_VERSION")).expect("Always valid");
+        if zksolc_version != self.zksolc_version {
+            anyhow::bail!(
+                "The bundle was built with zksolc v{}, but this is v{}",
+                self.zksolc_version,
+                zksolc_version
+            );
+        }
+
+        let solc_version = solc.version()?.default;
+        if solc_version != self.solc_version {
+            anyhow::bail!(
+                "The bundle was built with solc v{}, but `{}` is v{}. Pass the matching executable with `--solc`",
+                self.solc_version,
+                solc.executable,
+                solc_version
+            );
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Reproduces the build and compares the contract bytecode hash to the expected one.
+    ///
+    pub fn replay(&self, solc: &mut SolcCompiler) -> anyhow::Result<Replay> {
+        self.check_versions(solc)?;
+
+        let input = serde_json::to_vec(&self.input).expect("Always valid");
+        let output = crate::standard_json_output(
+            input.as_slice(),
+            solc,
+            self.force_evmla,
+            self.system_mode,
+            false,
+            false,
+            None,
+            None,
+            vec![],
+            None,
+            true,
+            None,
+            DebugFilter::default(),
+        )?;
+
+        if let Some(errors) = output.errors.as_ref() {
+            let errors: Vec<String> = errors
+                .iter()
+                .filter(|error| error.severity.as_str() == "error")
+                .map(|error| error.to_string())
+                .collect();
+            if !errors.is_empty() {
+                anyhow::bail!("The bundle compilation failed:\n{}", errors.join("\n"));
+            }
+        }
+
+        let (path, name) = self.contract.rsplit_once(':').ok_or_else(|| {
+            anyhow::anyhow!(
+                "The contract path `{}` must have the `<path>:<name>` format",
+                self.contract
+            )
+        })?;
+        let actual_bytecode_hash = output
+            .contracts
+            .as_ref()
+            .and_then(|files| files.get(path))
+            .and_then(|contracts| contracts.get(name))
+            .and_then(|contract| contract.hash.as_deref())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "The contract `{}` has no bytecode in the build",
+                    self.contract
+                )
+            })?;
+
+        let expected_bytecode_hash = Self::normalize_hash(self.bytecode_hash.as_str());
+        let actual_bytecode_hash = Self::normalize_hash(actual_bytecode_hash);
+        Ok(Replay {
+            contract: self.contract.to_owned(),
+            matches: expected_bytecode_hash == actual_bytecode_hash,
+            expected_bytecode_hash,
+            actual_bytecode_hash,
+        })
+    }
+
+    ///
+    /// Normalizes the hash to the lowercase `0x`-prefixed form.
+    ///
+    fn normalize_hash(hash: &str) -> String {
+        format!(
+            "0x{}",
+            hash.strip_prefix("0x").unwrap_or(hash).to_ascii_lowercase()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::VerifyBundle;
+
+    #[test]
+    fn deserialize() {
+        let bundle: VerifyBundle = serde_json::from_value(serde_json::json!({
+            "zksolcVersion": "1.3.13",
+            "solcVersion": "0.8.20",
+            "contract": "contracts/Token.sol:Token",
+            "bytecodeHash": "0x0100ABCD",
+            "input": { "language": "Solidity", "sources": {}, "settings": {} }
+        }))
+        .expect("Always valid");
+
+        assert!(!bundle.force_evmla);
+        assert!(!bundle.system_mode);
+        assert_eq!(bundle.solc_version, semver::Version::new(0, 8, 20));
+        assert_eq!(
+            VerifyBundle::normalize_hash(bundle.bytecode_hash.as_str()),
+            "0x0100abcd"
+        );
+    }
+
+    #[test]
+    fn new_all() {
+        let input = serde_json::json!({
+            "language": "Solidity",
+            "sources": { "contracts/Token.sol": { "content": "contract Token {}" } },
+        });
+        let output = serde_json::json!({
+            "contracts": {
+                "contracts/Token.sol": {
+                    "IToken": { "abi": [] },
+                    "Token": { "hash": "0100ABCD" },
+                },
+            },
+        });
+        let version = semver::Version::new(0, 8, 20);
+
+        let bundles =
+            VerifyBundle::new_all(&input, &output, &version, false, false).expect("Always valid");
+        assert_eq!(bundles.len(), 1);
+        assert_eq!(bundles[0].contract, "contracts/Token.sol:Token");
+        assert_eq!(bundles[0].bytecode_hash, "0x0100abcd");
+
+        let directory = std::env::temp_dir().join(format!(
+            "zksolc-verify-bundle-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        let path = bundles[0].write(directory.as_path()).expect("Always valid");
+        let bundle = VerifyBundle::try_from_path(path.as_path()).expect("Always valid");
+        std::fs::remove_dir_all(directory.as_path()).expect("Always valid");
+
+        assert!(path.ends_with(Path::new("contracts/Token.verify.json")));
+        assert_eq!(bundle.contract, bundles[0].contract);
+        assert_eq!(bundle.input, input);
+
+        let input = serde_json::json!({
+            "language": "Solidity",
+            "sources": { "contracts/Token.sol": { "urls": ["contracts/Token.sol"] } },
+        });
+        assert!(VerifyBundle::new_all(&input, &output, &version, false, false).is_err());
+    }
+}
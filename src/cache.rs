@@ -0,0 +1,95 @@
+//!
+//! The standard JSON output cache.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+
+use sha3::Digest;
+
+///
+/// The standard JSON output cache.
+///
+/// Keeps the serialized outputs of the most recent self-contained requests, evicting the oldest
+/// ones once the capacity is reached. Used by the compile daemon and the batch mode.
+///
+#[derive(Debug, Default)]
+pub struct Cache {
+    /// The cached outputs, keyed by the keccak256 hashes of the requests.
+    outputs: BTreeMap<String, Vec<u8>>,
+    /// The cached output keys in the insertion order, used for eviction.
+    order: VecDeque<String>,
+}
+
+impl Cache {
+    /// The maximal number of cached outputs.
+    const CAPACITY: usize = 64;
+
+    ///
+    /// Returns the cache key of the serialized `request`.
+    ///
+    pub fn key(request: &[u8]) -> String {
+        hex::encode(sha3::Keccak256::digest(request))
+    }
+
+    ///
+    /// Whether the output only depends on the standard JSON `input`, that is, all sources
+    /// are inline.
+    ///
+    /// The sources resolved from the file system or network may change between requests.
+    ///
+    pub fn is_cacheable(input: &serde_json::Value) -> bool {
+        input
+            .get("sources")
+            .and_then(|sources| sources.as_object())
+            .map(|sources| {
+                sources
+                    .values()
+                    .all(|source| source.get("content").is_some())
+            })
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Returns the cached output.
+    ///
+    pub fn get(&self, key: &str) -> Option<&[u8]> {
+        self.outputs.get(key).map(Vec::as_slice)
+    }
+
+    ///
+    /// Caches the output, evicting the oldest one if the capacity is reached.
+    ///
+    pub fn insert(&mut self, key: String, output: Vec<u8>) {
+        if self.outputs.contains_key(key.as_str()) {
+            return;
+        }
+        if self.order.len() >= Self::CAPACITY {
+            if let Some(evicted) = self.order.pop_front() {
+                self.outputs.remove(evicted.as_str());
+            }
+        }
+        self.order.push_back(key.clone());
+        self.outputs.insert(key, output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cache;
+
+    #[test]
+    fn eviction() {
+        let mut cache = Cache::default();
+        for index in 0..=Cache::CAPACITY {
+            cache.insert(index.to_string(), vec![index as u8]);
+        }
+
+        assert_eq!(cache.get("0"), None);
+        assert_eq!(cache.get("1"), Some([1].as_slice()));
+        assert_eq!(
+            cache.get(Cache::CAPACITY.to_string().as_str()),
+            Some([Cache::CAPACITY as u8].as_slice())
+        );
+    }
+}
@@ -0,0 +1,311 @@
+//!
+//! The compiler capabilities.
+//!
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::evmla::assembly::instruction::support::Support as EVMLAInstructionSupport;
+use crate::solc::standard_json::input::language::Language as SolcStandardJsonInputLanguage;
+use crate::solc::standard_json::input::schema::INPUT as SOLC_STANDARD_JSON_INPUT_SCHEMA;
+use crate::solc::standard_json::input::settings::selection::file::flag::Flag as SolcStandardJsonInputSettingsSelectionFileFlag;
+use crate::solc::Compiler as SolcCompiler;
+use crate::target::Target;
+
+///
+/// The compiler capabilities.
+///
+/// Lets the tooling detect the supported features instead of comparing the compiler versions.
+/// Every list is taken from the definitions the compiler itself uses to parse and validate its
+/// input. The lists of the enum variants and the settings keys are checked against the enums
+/// and the settings structure by the tests, which stop compiling when a variant or a field is
+/// added, so they cannot drift from the actual behavior.
+///
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    /// The compiler version.
+    pub version: String,
+    /// The supported `solc` version range.
+    pub solc_versions: SolcVersions,
+    /// The standard JSON input languages.
+    pub languages: Vec<String>,
+    /// The compilation targets supported by this build.
+    pub targets: Vec<String>,
+    /// The dot-separated standard JSON input keys, where `*` stands for an arbitrary key.
+    pub input_keys: Vec<String>,
+    /// The standard JSON output selection flags.
+    pub output_selection: Vec<String>,
+    /// The EVM legacy assembly instruction policies.
+    pub instructions: BTreeMap<String, EVMLAInstructionSupport>,
+}
+
+///
+/// The supported `solc` version range.
+///
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolcVersions {
+    /// The first supported version.
+    pub first: semver::Version,
+    /// The last supported version.
+    pub last: semver::Version,
+}
+
+impl Capabilities {
+    ///
+    /// Collects the capabilities of this compiler build.
+    ///
+    pub fn new() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            solc_versions: SolcVersions {
+                first: SolcCompiler::FIRST_SUPPORTED_VERSION,
+                last: SolcCompiler::LAST_SUPPORTED_VERSION,
+            },
+            languages: SolcStandardJsonInputLanguage::ALL
+                .iter()
+                .map(|language| language.to_string())
+                .collect(),
+            targets: Target::ALL
+                .iter()
+                .filter(|target| target.validate().is_ok())
+                .map(|target| target.to_string())
+                .collect(),
+            input_keys: SOLC_STANDARD_JSON_INPUT_SCHEMA.keys(),
+            output_selection: SolcStandardJsonInputSettingsSelectionFileFlag::ALL
+                .iter()
+                .map(|flag| flag.to_string())
+                .collect(),
+            instructions: EVMLAInstructionSupport::INSTRUCTIONS
+                .iter()
+                .map(|name| (name.to_string(), EVMLAInstructionSupport::of(name)))
+                .collect(),
+        }
+    }
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solc::standard_json::input::language::Language as SolcStandardJsonInputLanguage;
+    use crate::solc::standard_json::input::schema::INPUT as SOLC_STANDARD_JSON_INPUT_SCHEMA;
+    use crate::solc::standard_json::input::settings::selection::file::flag::Flag as SolcStandardJsonInputSettingsSelectionFileFlag;
+    use crate::solc::standard_json::input::settings::Settings as SolcStandardJsonInputSettings;
+    use crate::target::Target;
+
+    use super::Capabilities;
+
+    #[test]
+    fn default() {
+        let capabilities = serde_json::to_value(Capabilities::new()).expect("Always valid");
+
+        assert_eq!(
+            capabilities["languages"],
+            serde_json::json!(["Solidity", "Yul"])
+        );
+        assert!(capabilities["inputKeys"]
+            .as_array()
+            .expect("Always valid")
+            .contains(&serde_json::json!("settings.optimizer.mode")));
+        assert!(capabilities["outputSelection"]
+            .as_array()
+            .expect("Always valid")
+            .contains(&serde_json::json!("irOptimized")));
+        assert_eq!(
+            capabilities["instructions"]["PC"]["status"],
+            serde_json::json!("rejected")
+        );
+    }
+
+    ///
+    /// Checks that the `all` list contains every variant exactly once, in the declaration order,
+    /// and is named as in the serde definitions.
+    ///
+    /// `index` must match the variants exhaustively, so adding a variant fails the compilation
+    /// until it is added to both the `index` and the `all` list.
+    ///
+    fn check_variants<T>(all: &[T], index: fn(&T) -> usize)
+    where
+        T: serde::Serialize + std::fmt::Display,
+    {
+        for (expected, variant) in all.iter().enumerate() {
+            assert_eq!(index(variant), expected);
+            assert_eq!(
+                serde_json::to_value(variant).expect("Always valid"),
+                serde_json::json!(variant.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn languages() {
+        check_variants(
+            &SolcStandardJsonInputLanguage::ALL,
+            |language| match language {
+                SolcStandardJsonInputLanguage::Solidity => 0,
+                SolcStandardJsonInputLanguage::Yul => 1,
+            },
+        );
+    }
+
+    #[test]
+    fn targets() {
+        check_variants(&Target::ALL, |target| match target {
+            Target::EraVM => 0,
+            Target::EVM => 1,
+        });
+    }
+
+    #[test]
+    fn output_selection() {
+        check_variants(
+            &SolcStandardJsonInputSettingsSelectionFileFlag::ALL,
+            |flag| match flag {
+                SolcStandardJsonInputSettingsSelectionFileFlag::ABI => 0,
+                SolcStandardJsonInputSettingsSelectionFileFlag::Metadata => 1,
+                SolcStandardJsonInputSettingsSelectionFileFlag::Devdoc => 2,
+                SolcStandardJsonInputSettingsSelectionFileFlag::Userdoc => 3,
+                SolcStandardJsonInputSettingsSelectionFileFlag::MethodIdentifiers => 4,
+                SolcStandardJsonInputSettingsSelectionFileFlag::StorageLayout => 5,
+                SolcStandardJsonInputSettingsSelectionFileFlag::AST => 6,
+                SolcStandardJsonInputSettingsSelectionFileFlag::Yul => 7,
+                SolcStandardJsonInputSettingsSelectionFileFlag::EVMLA => 8,
+                SolcStandardJsonInputSettingsSelectionFileFlag::EVMBytecode => 9,
+                SolcStandardJsonInputSettingsSelectionFileFlag::Statistics => 10,
+            },
+        );
+    }
+
+    ///
+    /// Checks that the settings schema lists every settings field, and that the other keys in
+    /// the schema are either ignored or passed to `solc` as is.
+    ///
+    #[test]
+    fn settings_keys() {
+        // Destructured without `..`, so adding a field fails the compilation until its key is
+        // listed below.
+        let SolcStandardJsonInputSettings {
+            libraries: _,
+            output_selection: _,
+            via_ir: _,
+            optimizer: _,
+            metadata: _,
+            debug: _,
+            target: _,
+            output_evm_bytecode: _,
+            output_statistics: _,
+            output_histogram: _,
+            output_code_sections: _,
+            output_immutables: _,
+            output_gas_usages: _,
+            output_source_hashes: _,
+            output_constant_loops: _,
+            normalize_sources: _,
+            evmla_stack_spill_threshold: _,
+            reentrancy_guard: _,
+            order_by_selector: _,
+            loop_unroll_threshold: _,
+            strict: _,
+            strict_gas: _,
+            erc_checks: _,
+            sandbox: _,
+            empty_contracts: _,
+            warnings_as_errors: _,
+            severity_overrides: _,
+            other: _,
+        } = SolcStandardJsonInputSettings::new(
+            Default::default(),
+            Default::default(),
+            false,
+            serde_json::from_value(serde_json::json!({ "enabled": true })).expect("Always valid"),
+            None,
+        );
+        let fields = [
+            "libraries",
+            "outputSelection",
+            "optimizer",
+            "metadata",
+            "debug",
+            "target",
+            "outputEvmBytecode",
+            "outputHistogram",
+            "outputCodeSections",
+            "outputImmutables",
+            "outputGasUsages",
+            "outputSourceHashes",
+            "outputConstantLoops",
+            "normalizeSources",
+            "evmlaStackSpillThreshold",
+            "reentrancyGuard",
+            "orderBySelector",
+            "loopUnrollThreshold",
+            "contractTimeBudget",
+            "strict",
+            "strictGas",
+            "ercChecks",
+            "sandbox",
+            "emptyContracts",
+            "warningsAsErrors",
+            "severityOverrides",
+        ];
+        let passed_to_solc = ["remappings", "evmVersion"];
+
+        let schema_keys: Vec<String> = SOLC_STANDARD_JSON_INPUT_SCHEMA
+            .keys()
+            .into_iter()
+            .filter_map(|key| {
+                let key = key.strip_prefix("settings.")?;
+                (!key.contains('.')).then(|| key.to_owned())
+            })
+            .collect();
+        for key in fields
+            .iter()
+            .chain(passed_to_solc.iter())
+            .chain(SolcStandardJsonInputSettings::IGNORED.iter())
+        {
+            assert!(
+                schema_keys.iter().any(|schema_key| schema_key == key),
+                "`settings.{key}` is missing in the schema"
+            );
+        }
+        for key in schema_keys.iter() {
+            let is_field = fields.contains(&key.as_str());
+            let is_ignored = SolcStandardJsonInputSettings::IGNORED.contains(&key.as_str());
+            assert!(
+                is_field || is_ignored || passed_to_solc.contains(&key.as_str()),
+                "`settings.{key}` is neither a field, ignored, nor passed to `solc`"
+            );
+
+            // A field key is consumed by the deserializer, either successfully or failing on
+            // the value type, whereas the other keys are collected, and the ignored ones are
+            // removed before passing the rest to `solc`.
+            let settings = serde_json::from_value::<SolcStandardJsonInputSettings>(
+                serde_json::json!({ "optimizer": { "enabled": true }, key.as_str(): "probe" }),
+            );
+            let (is_collected, is_passed) = match settings {
+                Ok(mut settings) => {
+                    let is_collected = settings.other.contains_key(key);
+                    settings.normalize();
+                    (is_collected, settings.other.contains_key(key))
+                }
+                Err(_) => (false, false),
+            };
+            assert_eq!(
+                is_field, !is_collected,
+                "`settings.{key}` is not deserialized as expected"
+            );
+            assert_eq!(
+                is_field || is_ignored,
+                !is_passed,
+                "`settings.{key}` is not passed to `solc` as expected"
+            );
+        }
+    }
+}
@@ -7,6 +7,15 @@
 /// The default executable name.
 pub static DEFAULT_EXECUTABLE_NAME: &str = "zksolc";
 
+/// The `solc` EVM bytecode file extension.
+pub static EXTENSION_EVM_BINARY: &str = "bin";
+
+/// The error code of the `blobbasefee` instruction, which has no meaningful value on EraVM.
+pub static ERROR_CODE_BLOB_BASE_FEE: &str = "ZK4844";
+
+/// The error code of the transient storage instructions, which are not lowered for EraVM yet.
+pub static ERROR_CODE_TRANSIENT_STORAGE: &str = "ZK1153";
+
 /// The `keccak256` scratch space offset.
 pub const OFFSET_SCRATCH_SPACE: usize = 0;
 
@@ -18,3 +27,21 @@ pub const OFFSET_EMPTY_SLOT: usize = 3 * compiler_common::BYTE_LENGTH_FIELD;
 
 /// The non-reserved memory offset.
 pub const OFFSET_NON_RESERVED: usize = 4 * compiler_common::BYTE_LENGTH_FIELD;
+
+/// The instruction histogram file name suffix.
+pub static HISTOGRAM_FILE_NAME_SUFFIX: &str = "histogram";
+
+/// The example calldata file name suffix.
+pub static CALLDATA_FILE_NAME_SUFFIX: &str = "calldata";
+
+/// The metadata file name suffix.
+pub static METADATA_FILE_NAME_SUFFIX: &str = "metadata";
+
+/// The EVM legacy assembly block tag to LLVM basic block mapping file name suffix.
+pub static EVMLA_LLVM_BLOCK_MAP_FILE_NAME_SUFFIX: &str = "llvm_blocks";
+
+/// The Yul file extension.
+pub static EXTENSION_YUL: &str = "yul";
+
+/// The EVM legacy assembly translation trace file extension.
+pub static EXTENSION_EVMLA_TRACE: &str = "trace";
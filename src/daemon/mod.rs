@@ -0,0 +1,378 @@
+//!
+//! The compile daemon.
+//!
+
+pub mod request;
+pub mod response;
+
+use std::io::Read;
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+#[cfg(unix)]
+use std::path::Path;
+#[cfg(unix)]
+use std::path::PathBuf;
+#[cfg(unix)]
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::cache::Cache;
+use crate::debug::filter::Filter as DebugFilter;
+use crate::solc::Compiler as SolcCompiler;
+
+use self::request::Request;
+use self::response::Response;
+
+///
+/// The compile daemon.
+///
+/// A long-running local process the standard JSON invocations of `zksolc` delegate to with
+/// `--daemon`, if it is running. The `solc` version is queried only once, the LLVM target is
+/// initialized only once, and the outputs are cached, which cuts the latency of the repeated
+/// compilations in interactive workflows.
+///
+/// The daemon listens on a Unix socket in a directory only accessible to the current user, so
+/// no other user can submit requests or impersonate the daemon. It compiles only the inputs with
+/// all the sources inline, with its own `solc` executable, and never reads the sources from the
+/// file system or network. The invocations using another `solc` executable compile on their own.
+///
+/// `solc` itself does not support persistent processes, so it is still run once per
+/// compilation. The compilations are serialized, as they share the `solc` compiler state, and
+/// each of them is parallelized on its own.
+///
+pub struct Daemon {
+    /// The compilation state.
+    state: Mutex<State>,
+}
+
+///
+/// The compile daemon state.
+///
+struct State {
+    /// The `solc` compiler with its cached version.
+    solc: SolcCompiler,
+    /// The cached outputs.
+    outputs: Cache,
+}
+
+impl Daemon {
+    /// The maximal request size in bytes.
+    const MAX_REQUEST_SIZE: usize = 256 * 1024 * 1024;
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(solc: SolcCompiler) -> Self {
+        Self {
+            state: Mutex::new(State {
+                solc,
+                outputs: Cache::default(),
+            }),
+        }
+    }
+
+    ///
+    /// Returns the socket path.
+    ///
+    /// The socket is placed into the directory of the current user, and its name depends on the
+    /// compiler version, so the invocations only delegate to the daemon of the same version.
+    ///
+    #[cfg(unix)]
+    pub fn socket_path() -> PathBuf {
+        // SAFETY: `getuid` is always successful and has no side effects.
+        let uid = unsafe { libc::getuid() };
+        std::env::temp_dir()
+            .join(format!("zksolc-{uid}"))
+            .join(format!("daemon-{}.sock", env!("CARGO_PKG_VERSION")))
+    }
+
+    ///
+    /// Accepts the connections until the process is terminated.
+    ///
+    #[cfg(unix)]
+    pub fn run(self) -> anyhow::Result<()> {
+        self.state.lock().expect("Sync").solc.version()?;
+
+        let socket_path = Self::socket_path();
+        let directory = socket_path.parent().expect("Always exists");
+        if !directory.exists() {
+            use std::os::unix::fs::DirBuilderExt;
+
+            std::fs::DirBuilder::new()
+                .mode(0o700)
+                .create(directory)
+                .map_err(|error| {
+                    anyhow::anyhow!("Directory {:?} creating error: {}", directory, error)
+                })?;
+        }
+        Self::check_directory(directory)?;
+        if UnixStream::connect(socket_path.as_path()).is_ok() {
+            anyhow::bail!("The daemon is already listening on {:?}", socket_path);
+        }
+        if socket_path.exists() {
+            std::fs::remove_file(socket_path.as_path()).map_err(|error| {
+                anyhow::anyhow!("Stale socket {:?} removing error: {}", socket_path, error)
+            })?;
+        }
+
+        let listener = UnixListener::bind(socket_path.as_path())
+            .map_err(|error| anyhow::anyhow!("Binding to {:?} error: {}", socket_path, error))?;
+        eprintln!("Listening on {socket_path:?}");
+
+        let daemon = Arc::new(self);
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(error) => {
+                    eprintln!("Connection accepting error: {error}");
+                    continue;
+                }
+            };
+
+            let daemon = daemon.clone();
+            std::thread::spawn(move || {
+                if let Err(error) = daemon.handle(stream) {
+                    eprintln!("Request handling error: {error}");
+                }
+            });
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Accepts the connections until the process is terminated.
+    ///
+    #[cfg(not(unix))]
+    pub fn run(self) -> anyhow::Result<()> {
+        anyhow::bail!("The compile daemon is only supported on Unix");
+    }
+
+    ///
+    /// Compiles the standard JSON input with the running daemon.
+    ///
+    /// Returns `None` if the daemon is not running or uses another `solc` executable, so the
+    /// invocation must compile on its own. Returns the standard JSON output otherwise, or the
+    /// error of the compiler itself.
+    ///
+    #[cfg(unix)]
+    pub fn delegate(request: &Request) -> anyhow::Result<Option<serde_json::Value>> {
+        let socket_path = Self::socket_path();
+        let directory = socket_path.parent().expect("Always exists");
+        if !directory.exists() {
+            return Ok(None);
+        }
+        Self::check_directory(directory)?;
+        let mut stream = match UnixStream::connect(socket_path.as_path()) {
+            Ok(stream) => stream,
+            Err(_) => return Ok(None),
+        };
+
+        serde_json::to_writer(&mut stream, request)
+            .map_err(|error| anyhow::anyhow!("The daemon request writing error: {}", error))?;
+        stream.shutdown(std::net::Shutdown::Write)?;
+        let mut body = Vec::new();
+        stream
+            .read_to_end(&mut body)
+            .map_err(|error| anyhow::anyhow!("The daemon response reading error: {}", error))?;
+        let response: Response = serde_json::from_slice(body.as_slice())
+            .map_err(|error| anyhow::anyhow!("The daemon response parsing error: {}", error))?;
+        match response {
+            Response::Output(output) => Ok(Some(output)),
+            Response::Declined(_reason) => Ok(None),
+            Response::Error(error) => anyhow::bail!("{}", error),
+        }
+    }
+
+    ///
+    /// Compiles the standard JSON input with the running daemon.
+    ///
+    #[cfg(not(unix))]
+    pub fn delegate(_request: &Request) -> anyhow::Result<Option<serde_json::Value>> {
+        anyhow::bail!("The compile daemon is only supported on Unix");
+    }
+
+    ///
+    /// Checks that the socket `directory` is owned by the current user and is not accessible to
+    /// the others, so the socket in it cannot be reached or replaced by them.
+    ///
+    #[cfg(unix)]
+    fn check_directory(directory: &Path) -> anyhow::Result<()> {
+        use std::os::unix::fs::MetadataExt;
+
+        let metadata = std::fs::symlink_metadata(directory).map_err(|error| {
+            anyhow::anyhow!("Directory {:?} reading error: {}", directory, error)
+        })?;
+        // SAFETY: `getuid` is always successful and has no side effects.
+        let uid = unsafe { libc::getuid() };
+        if !metadata.is_dir() || metadata.uid() != uid || metadata.mode() & 0o077 != 0 {
+            anyhow::bail!(
+                "The daemon directory {:?} must be owned by the current user and not accessible to the others",
+                directory
+            );
+        }
+        Ok(())
+    }
+
+    ///
+    /// Reads the request from the `stream` and writes the response.
+    ///
+    fn handle<S>(&self, mut stream: S) -> anyhow::Result<()>
+    where
+        S: Read + Write,
+    {
+        let mut body = Vec::new();
+        (&mut stream)
+            .take(Self::MAX_REQUEST_SIZE as u64 + 1)
+            .read_to_end(&mut body)?;
+        let response = if body.len() > Self::MAX_REQUEST_SIZE {
+            Response::Error(format!(
+                "The request exceeds the size limit of {} bytes",
+                Self::MAX_REQUEST_SIZE
+            ))
+        } else {
+            self.compile(body.as_slice())
+                .unwrap_or_else(|error| Response::Error(error.to_string()))
+        };
+        serde_json::to_writer(&mut stream, &response)?;
+        stream.flush()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Compiles the request `body`.
+    ///
+    fn compile(&self, body: &[u8]) -> anyhow::Result<Response> {
+        let request: Request = serde_json::from_slice(body)?;
+        request.validate()?;
+
+        let mut state = self.state.lock().expect("Sync");
+        if request.solc != state.solc.executable {
+            return Ok(Response::Declined(format!(
+                "The daemon uses the `solc` executable `{}`",
+                state.solc.executable
+            )));
+        }
+
+        let identity = format!(
+            "{}\n{}\n",
+            state.solc.executable,
+            state.solc.version()?.long
+        );
+        let key = Cache::key([identity.as_bytes(), body].concat().as_slice());
+        if let Some(output) = state.outputs.get(key.as_str()) {
+            return Ok(Response::Output(serde_json::from_slice(output)?));
+        }
+
+        let input = serde_json::to_vec(&request.input).expect("Always valid");
+        let output = crate::standard_json_output(
+            input.as_slice(),
+            &mut state.solc,
+            request.force_evmla,
+            request.is_system_mode,
+            request.determinism_check,
+            false,
+            None,
+            None,
+            vec![],
+            None,
+            true,
+            None,
+            DebugFilter::default(),
+        )?;
+        let output = serde_json::to_value(&output).expect("Always valid");
+
+        state
+            .outputs
+            .insert(key, serde_json::to_vec(&output).expect("Always valid"));
+        Ok(Response::Output(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solc::Compiler as SolcCompiler;
+
+    use super::request::Request;
+    use super::response::Response;
+    use super::Daemon;
+
+    fn respond(request: &serde_json::Value) -> Response {
+        let daemon = Daemon::new(SolcCompiler::new("/opt/solc".to_owned()));
+        let body = serde_json::to_vec(request).expect("Always valid");
+        let mut stream = std::io::Cursor::new(body);
+        daemon.handle(&mut stream).expect("Always valid");
+
+        let position = stream.position() as usize;
+        serde_json::from_slice(&stream.into_inner()[position..]).expect("Always valid")
+    }
+
+    #[test]
+    fn declined() {
+        let request = Request {
+            solc: "solc".to_owned(),
+            force_evmla: false,
+            is_system_mode: false,
+            determinism_check: false,
+            input: serde_json::json!({
+                "language": "Solidity",
+                "sources": { "Test.sol": { "content": "contract Test {}" } },
+            }),
+        };
+        let response = respond(&serde_json::to_value(&request).expect("Always valid"));
+        assert!(matches!(response, Response::Declined(_)));
+    }
+
+    #[test]
+    fn rejected() {
+        let request = serde_json::json!({
+            "directory": "/",
+            "solc": "/opt/solc",
+            "forceEvmla": false,
+            "isSystemMode": false,
+            "determinismCheck": false,
+            "input": {},
+        });
+        assert!(matches!(respond(&request), Response::Error(_)));
+
+        let request = serde_json::json!({
+            "solc": "/opt/solc",
+            "forceEvmla": false,
+            "isSystemMode": false,
+            "determinismCheck": false,
+            "input": {
+                "language": "Solidity",
+                "sources": { "Test.sol": { "urls": ["/etc/passwd"] } },
+            },
+        });
+        assert!(matches!(respond(&request), Response::Error(_)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn directory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let directory = std::env::temp_dir().join(format!(
+            "zksolc-daemon-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        std::fs::create_dir_all(directory.as_path()).expect("Always valid");
+
+        std::fs::set_permissions(directory.as_path(), std::fs::Permissions::from_mode(0o755))
+            .expect("Always valid");
+        let shared = Daemon::check_directory(directory.as_path());
+        std::fs::set_permissions(directory.as_path(), std::fs::Permissions::from_mode(0o700))
+            .expect("Always valid");
+        let private = Daemon::check_directory(directory.as_path());
+        std::fs::remove_dir_all(directory.as_path()).expect("Always valid");
+
+        assert!(shared.is_err());
+        assert!(private.is_ok());
+    }
+}
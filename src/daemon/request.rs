@@ -0,0 +1,79 @@
+//!
+//! The compile daemon request.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::cache::Cache;
+
+///
+/// The compile daemon request.
+///
+/// Carries the standard JSON input along with the command line options of the delegating
+/// `zksolc` invocation, so the daemon compiles it exactly as the invocation itself would.
+///
+/// The request cannot choose the `solc` executable or the working directory of the daemon.
+/// The `solc` executable path is only compared with the one of the daemon.
+///
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Request {
+    /// The `solc` executable path of the delegating invocation.
+    pub solc: String,
+    /// Whether to force the EVM legacy assembly pipeline.
+    pub force_evmla: bool,
+    /// Whether to enable the system contract compilation mode.
+    pub is_system_mode: bool,
+    /// Whether to run the determinism check.
+    pub determinism_check: bool,
+    /// The standard JSON input.
+    pub input: serde_json::Value,
+}
+
+impl Request {
+    ///
+    /// Whether the request can be delegated, that is, all sources are inline.
+    ///
+    /// The daemon does not resolve the sources from the file system or network, as it may run
+    /// in another directory and must not read the files on behalf of the requests. The sandboxed
+    /// inputs are not delegated either, as the daemon does not enforce the sandbox.
+    ///
+    pub fn is_delegable(input: &serde_json::Value) -> bool {
+        Cache::is_cacheable(input) && input.pointer("/settings/sandbox").is_none()
+    }
+
+    ///
+    /// Checks that the request can be compiled by the daemon.
+    ///
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !Self::is_delegable(&self.input) {
+            anyhow::bail!(
+                "The daemon only compiles the inputs with all the sources inline and no sandbox"
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Request;
+
+    #[test]
+    fn delegable() {
+        assert!(Request::is_delegable(&serde_json::json!({
+            "language": "Solidity",
+            "sources": { "Test.sol": { "content": "contract Test {}" } },
+        })));
+        assert!(!Request::is_delegable(&serde_json::json!({
+            "language": "Solidity",
+            "sources": { "Test.sol": { "urls": ["Test.sol"] } },
+        })));
+        assert!(!Request::is_delegable(&serde_json::json!({
+            "language": "Solidity",
+            "sources": { "Test.sol": { "content": "contract Test {}" } },
+            "settings": { "sandbox": {} },
+        })));
+    }
+}
@@ -0,0 +1,20 @@
+//!
+//! The compile daemon response.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The compile daemon response.
+///
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Response {
+    /// The standard JSON output.
+    Output(serde_json::Value),
+    /// The request is valid, but must be compiled by the invocation itself, with the reason.
+    Declined(String),
+    /// The error of the compiler itself.
+    Error(String),
+}
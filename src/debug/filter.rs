@@ -0,0 +1,95 @@
+//!
+//! The debug dump filter.
+//!
+
+use std::collections::BTreeSet;
+
+use super::stage::Stage;
+
+///
+/// The debug dump filter.
+///
+/// Restricts the dumps to the selected contracts and compilation stages.
+///
+#[derive(Debug, Default, Clone)]
+pub struct Filter {
+    /// The contract names or paths to dump. All contracts are dumped if empty.
+    pub contracts: Vec<String>,
+    /// The stages to dump. All stages are dumped if `None`.
+    pub stages: Option<BTreeSet<Stage>>,
+}
+
+impl Filter {
+    ///
+    /// A shortcut constructor.
+    ///
+    /// The stage names are `yul`, `evmla`, `ethir`, `trace`, `llvm`, and `asm`, where `ethir` also
+    /// stands for the block map dumped along with the Ethereal IR, and `llvm` stands for the LLVM IR
+    /// both before and after optimizations.
+    ///
+    pub fn try_new(contracts: Vec<String>, stages: Vec<String>) -> anyhow::Result<Self> {
+        let stages = if stages.is_empty() {
+            None
+        } else {
+            let mut set = BTreeSet::new();
+            for stage in stages.into_iter() {
+                match stage.as_str() {
+                    "yul" => {
+                        set.insert(Stage::Yul);
+                    }
+                    "evmla" => {
+                        set.insert(Stage::EVMLA);
+                    }
+                    "ethir" => {
+                        set.insert(Stage::EthIR);
+                        set.insert(Stage::BlockMap);
+                    }
+                    "trace" => {
+                        set.insert(Stage::Trace);
+                    }
+                    "llvm" => {
+                        set.insert(Stage::LLVMIRUnoptimized);
+                        set.insert(Stage::LLVMIROptimized);
+                    }
+                    "asm" => {
+                        set.insert(Stage::Assembly);
+                    }
+                    stage => anyhow::bail!(
+                        "Unknown debug stage `{}`. Supported stages: `yul`, `evmla`, `ethir`, `trace`, `llvm`, `asm`",
+                        stage
+                    ),
+                }
+            }
+            Some(set)
+        };
+
+        Ok(Self { contracts, stages })
+    }
+
+    ///
+    /// Whether the contract must be dumped.
+    ///
+    /// The contract is matched by its full path, its name, or its path suffix.
+    ///
+    pub fn is_contract_selected(&self, contract_path: &str) -> bool {
+        if self.contracts.is_empty() {
+            return true;
+        }
+
+        self.contracts.iter().any(|contract| {
+            contract_path == contract
+                || contract_path.ends_with(format!(":{contract}").as_str())
+                || contract_path.ends_with(format!("/{contract}").as_str())
+        })
+    }
+
+    ///
+    /// Whether the stage must be dumped.
+    ///
+    pub fn is_stage_selected(&self, stage: Stage) -> bool {
+        self.stages
+            .as_ref()
+            .map(|stages| stages.contains(&stage))
+            .unwrap_or(true)
+    }
+}
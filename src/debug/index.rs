@@ -0,0 +1,123 @@
+//!
+//! The debug dump index.
+//!
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::stage::Stage;
+
+///
+/// The debug dump index.
+///
+/// Describes every dump in the debug output directory, grouped by contract.
+///
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Index {
+    /// The contract dumps, keyed by the full contract path.
+    pub contracts: BTreeMap<String, Contract>,
+}
+
+///
+/// The debug dump index contract entry.
+///
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Contract {
+    /// The contract subdirectory, relative to the debug output directory.
+    pub directory: String,
+    /// The dumps, ordered by stage.
+    pub dumps: Vec<Dump>,
+}
+
+///
+/// The debug dump index file entry.
+///
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Dump {
+    /// The compilation stage.
+    pub stage: Stage,
+    /// The file name, relative to the contract subdirectory.
+    pub file: String,
+}
+
+impl Index {
+    /// The index file name.
+    pub const FILE_NAME: &'static str = "index.json";
+
+    ///
+    /// Reads the index from the debug output directory, returning an empty one if it does not exist.
+    ///
+    pub fn read(output_directory: &Path) -> anyhow::Result<Self> {
+        let path = output_directory.join(Self::FILE_NAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let json = std::fs::read(path.as_path())
+            .map_err(|error| anyhow::anyhow!("File {:?} reading error: {}", path, error))?;
+        serde_json::from_slice(json.as_slice())
+            .map_err(|error| anyhow::anyhow!("File {:?} parsing error: {}", path, error))
+    }
+
+    ///
+    /// Writes the index to the debug output directory.
+    ///
+    pub fn write(&self, output_directory: &Path) -> anyhow::Result<()> {
+        let path = output_directory.join(Self::FILE_NAME);
+        let json = serde_json::to_vec_pretty(self).expect("Always valid");
+        std::fs::write(path.as_path(), json)
+            .map_err(|error| anyhow::anyhow!("File {:?} writing error: {}", path, error))
+    }
+
+    ///
+    /// Prefixes the new dumps in the contract subdirectory with their stage numbers and
+    /// records all of them in the index.
+    ///
+    pub fn insert_contract(
+        &mut self,
+        output_directory: &Path,
+        contract_path: &str,
+        directory: String,
+    ) -> anyhow::Result<()> {
+        let contract_directory = output_directory.join(directory.as_str());
+        if !contract_directory.exists() {
+            return Ok(());
+        }
+
+        let mut dumps = Vec::new();
+        for entry in std::fs::read_dir(contract_directory.as_path()).map_err(|error| {
+            anyhow::anyhow!(
+                "Directory {:?} reading error: {}",
+                contract_directory,
+                error
+            )
+        })? {
+            let entry = entry?;
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let stage = match Stage::from_file_name(file_name.as_str()) {
+                Some(stage) => stage,
+                None => continue,
+            };
+
+            let prefix = format!("{:02}_", stage.number());
+            let file = if file_name.starts_with(prefix.as_str()) {
+                file_name
+            } else {
+                let file = format!("{prefix}{file_name}");
+                std::fs::rename(entry.path(), contract_directory.join(file.as_str())).map_err(
+                    |error| anyhow::anyhow!("File {:?} renaming error: {}", entry.path(), error),
+                )?;
+                file
+            };
+            dumps.push(Dump { stage, file });
+        }
+        dumps.sort_by(|a, b| (a.stage, a.file.as_str()).cmp(&(b.stage, b.file.as_str())));
+
+        self.contracts
+            .insert(contract_path.to_owned(), Contract { directory, dumps });
+        Ok(())
+    }
+}
@@ -0,0 +1,304 @@
+//!
+//! The debug output directory layout.
+//!
+
+pub mod filter;
+pub mod index;
+pub mod stage;
+
+use std::path::Path;
+
+use crate::evmla::ethereal_ir::block_map::BlockMapping;
+
+use self::filter::Filter;
+use self::index::Index;
+use self::stage::Stage;
+
+/// The maximum length of a contract subdirectory name.
+///
+/// Longer names are truncated and suffixed with a hash to keep them unique.
+pub const CONTRACT_DIRECTORY_MAX_LENGTH: usize = 96;
+
+///
+/// Returns the debug configuration writing to the contract subdirectory, which is created if
+/// it does not exist.
+///
+/// Returns `None` if the contract is excluded by the `filter`. If the `filter` selects stages,
+/// the dumps are written to a staging subdirectory instead, since the LLVM IR and assembly are
+/// dumped unconditionally, and the selected ones are moved to the contract subdirectory by
+/// `write_index`.
+///
+pub fn contract_config(
+    debug_config: &compiler_llvm_context::DebugConfig,
+    filter: &Filter,
+    contract_path: &str,
+) -> anyhow::Result<Option<compiler_llvm_context::DebugConfig>> {
+    if !filter.is_contract_selected(contract_path) {
+        return Ok(None);
+    }
+
+    let mut directory = contract_directory(contract_path);
+    if filter.stages.is_some() {
+        directory = staging_directory(directory.as_str());
+    }
+    let directory = debug_config.output_directory.join(directory);
+    std::fs::create_dir_all(directory.as_path())
+        .map_err(|error| anyhow::anyhow!("Directory {:?} creating error: {}", directory, error))?;
+    Ok(Some(compiler_llvm_context::DebugConfig::new(directory)))
+}
+
+///
+/// Dumps the mapping of the EVM legacy assembly block tags to the LLVM basic blocks of the
+/// contract at `contract_path`.
+///
+/// The `debug_config` is the one returned by `contract_config`, so the map is written to the
+/// contract subdirectory, and is prefixed and indexed by `write_index` as the other dumps.
+///
+pub fn dump_llvm_block_map(
+    debug_config: &compiler_llvm_context::DebugConfig,
+    contract_path: &str,
+    block_map: &[BlockMapping],
+) -> anyhow::Result<()> {
+    let mut path = debug_config.output_directory.to_owned();
+    path.push(format!(
+        "{}.{}.{}",
+        contract_directory(contract_path),
+        crate::r#const::EVMLA_LLVM_BLOCK_MAP_FILE_NAME_SUFFIX,
+        compiler_common::EXTENSION_JSON
+    ));
+    let json = serde_json::to_vec_pretty(block_map).expect("Always valid");
+    std::fs::write(path.as_path(), json)
+        .map_err(|error| anyhow::anyhow!("File {:?} writing error: {}", path, error))
+}
+
+///
+/// Prefixes the dumps of the contracts with their stage numbers and updates the index file.
+///
+/// If the `filter` selects stages, the dumps of the selected ones are moved from the staging
+/// subdirectories first, and the staging subdirectories are removed. The existing dumps in the
+/// contract subdirectories are left intact.
+///
+pub fn write_index<'a>(
+    debug_config: &compiler_llvm_context::DebugConfig,
+    filter: &Filter,
+    contract_paths: impl Iterator<Item = &'a String>,
+) -> anyhow::Result<()> {
+    let output_directory = debug_config.output_directory.as_path();
+
+    let mut index = Index::read(output_directory)?;
+    for contract_path in contract_paths {
+        let directory = contract_directory(contract_path.as_str());
+        if filter.stages.is_some() {
+            collect_staged(output_directory, directory.as_str(), filter)?;
+        }
+        index.insert_contract(output_directory, contract_path.as_str(), directory)?;
+    }
+    index.write(output_directory)
+}
+
+///
+/// Moves the dumps of the stages selected by the `filter` from the staging subdirectory to the
+/// contract subdirectory, and removes the staging subdirectory.
+///
+fn collect_staged(output_directory: &Path, directory: &str, filter: &Filter) -> anyhow::Result<()> {
+    let staging_directory = output_directory.join(staging_directory(directory));
+    if !staging_directory.exists() {
+        return Ok(());
+    }
+    let contract_directory = output_directory.join(directory);
+    std::fs::create_dir_all(contract_directory.as_path()).map_err(|error| {
+        anyhow::anyhow!(
+            "Directory {:?} creating error: {}",
+            contract_directory,
+            error
+        )
+    })?;
+
+    for entry in std::fs::read_dir(staging_directory.as_path()).map_err(|error| {
+        anyhow::anyhow!("Directory {:?} reading error: {}", staging_directory, error)
+    })? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        if let Some(stage) = Stage::from_file_name(file_name.to_string_lossy().as_ref()) {
+            if !filter.is_stage_selected(stage) {
+                continue;
+            }
+        }
+        let path = contract_directory.join(file_name);
+        std::fs::rename(entry.path(), path.as_path())
+            .map_err(|error| anyhow::anyhow!("File {:?} moving error: {}", entry.path(), error))?;
+    }
+
+    std::fs::remove_dir_all(staging_directory.as_path()).map_err(|error| {
+        anyhow::anyhow!(
+            "Directory {:?} removing error: {}",
+            staging_directory,
+            error
+        )
+    })
+}
+
+///
+/// Returns the name of the staging subdirectory for the contract subdirectory.
+///
+fn staging_directory(directory: &str) -> String {
+    format!(".{directory}.staging")
+}
+
+///
+/// Converts the full contract path to a subdirectory name safe for all common filesystems.
+///
+/// All characters except ASCII alphanumerics, `.`, `-`, and `_` are replaced with `_`.
+/// A leading `.` is escaped to avoid hidden and special directories.
+///
+pub fn contract_directory(contract_path: &str) -> String {
+    let mut directory: String = contract_path
+        .chars()
+        .map(|character| match character {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => character,
+            _ => '_',
+        })
+        .collect();
+    if directory.starts_with('.') {
+        directory.insert(0, '_');
+    }
+
+    if directory.len() > CONTRACT_DIRECTORY_MAX_LENGTH {
+        let hash = compiler_llvm_context::keccak256(contract_path.as_bytes());
+        let hash = &hash[hash.len() - 16..];
+        directory.truncate(CONTRACT_DIRECTORY_MAX_LENGTH - hash.len() - 1);
+        directory.push('_');
+        directory.push_str(hash);
+    }
+
+    directory
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::evmla::ethereal_ir::block_map::BlockMapping;
+
+    use super::filter::Filter;
+    use super::index::Index;
+    use super::stage::Stage;
+
+    #[test]
+    fn contract_directory_sanitized() {
+        assert_eq!(
+            super::contract_directory("/home/user/src/Test.sol:Test"),
+            "_home_user_src_Test.sol_Test"
+        );
+        assert_eq!(super::contract_directory("../A.sol:A"), "_.._A.sol_A");
+    }
+
+    #[test]
+    fn contract_directory_truncated() {
+        let path = format!("{}.sol:Test", "a".repeat(256));
+        let directory = super::contract_directory(path.as_str());
+        assert_eq!(directory.len(), super::CONTRACT_DIRECTORY_MAX_LENGTH);
+        assert_ne!(
+            directory,
+            super::contract_directory(format!("{}.sol:Other", "a".repeat(256)).as_str())
+        );
+    }
+
+    #[test]
+    fn stages_filtered() {
+        let output_directory = std::env::temp_dir().join(format!(
+            "zksolc-debug-filter-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        let debug_config = compiler_llvm_context::DebugConfig::new(output_directory.clone());
+        let filter = Filter::try_new(vec![], vec!["yul".to_owned()]).expect("Always valid");
+        let contract_path = "Test.sol:Test".to_owned();
+
+        let contract_directory = output_directory.join(super::contract_directory("Test.sol:Test"));
+        std::fs::create_dir_all(contract_directory.as_path()).expect("Always valid");
+        std::fs::write(contract_directory.join("notes.txt"), "user file").expect("Always valid");
+        std::fs::write(contract_directory.join("05_Test.optimized.ll"), "").expect("Always valid");
+
+        let contract_config =
+            super::contract_config(&debug_config, &filter, contract_path.as_str())
+                .expect("Always valid")
+                .expect("Always selected");
+        assert_ne!(contract_config.output_directory, contract_directory);
+        for file_name in ["Test.yul", "Test.unoptimized.ll", "Test.zasm"] {
+            std::fs::write(contract_config.output_directory.join(file_name), "")
+                .expect("Always valid");
+        }
+
+        super::write_index(&debug_config, &filter, std::iter::once(&contract_path))
+            .expect("Always valid");
+
+        assert!(contract_directory.join("01_Test.yul").exists());
+        assert!(!contract_directory.join("04_Test.unoptimized.ll").exists());
+        assert!(!contract_directory.join("06_Test.zasm").exists());
+        assert!(contract_directory.join("notes.txt").exists());
+        assert!(contract_directory.join("05_Test.optimized.ll").exists());
+        assert!(!contract_config.output_directory.exists());
+
+        std::fs::remove_dir_all(output_directory).expect("Always valid");
+    }
+
+    #[test]
+    fn llvm_block_map() {
+        let output_directory = std::env::temp_dir().join(format!(
+            "zksolc-debug-block-map-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        let debug_config = compiler_llvm_context::DebugConfig::new(output_directory.clone());
+        let filter = Filter::try_new(vec![], vec![]).expect("Always valid");
+        let contract_path = "Test.sol:Test".to_owned();
+        let contract_config =
+            super::contract_config(&debug_config, &filter, contract_path.as_str())
+                .expect("Always valid")
+                .expect("Always selected");
+
+        let key = compiler_llvm_context::FunctionBlockKey::new(
+            compiler_llvm_context::CodeType::Runtime,
+            num::BigUint::from(42u32),
+        );
+        let block_map = vec![BlockMapping::new(&key, "main", 1)];
+        super::dump_llvm_block_map(
+            &contract_config,
+            contract_path.as_str(),
+            block_map.as_slice(),
+        )
+        .expect("Always valid");
+        super::write_index(&debug_config, &filter, std::iter::once(&contract_path))
+            .expect("Always valid");
+
+        let index = Index::read(output_directory.as_path()).expect("Always valid");
+        let dumps = &index.contracts[contract_path.as_str()].dumps;
+        assert_eq!(dumps.len(), 1);
+        assert_eq!(dumps[0].stage, Stage::BlockMap);
+        assert_eq!(dumps[0].file, "02_Test.sol_Test.llvm_blocks.json");
+
+        let path = contract_config
+            .output_directory
+            .join(dumps[0].file.as_str());
+        let json: serde_json::Value = serde_json::from_slice(
+            std::fs::read(path.as_path())
+                .expect("Always valid")
+                .as_slice(),
+        )
+        .expect("Always valid");
+        std::fs::remove_dir_all(output_directory).expect("Always valid");
+
+        assert_eq!(json[0]["tag"], "42");
+        assert_eq!(json[0]["function"], "main");
+    }
+
+    #[test]
+    fn contracts_filtered() {
+        let debug_config = compiler_llvm_context::DebugConfig::new(std::env::temp_dir());
+        let filter = Filter::try_new(vec!["Other".to_owned()], vec![]).expect("Always valid");
+        assert!(
+            super::contract_config(&debug_config, &filter, "Test.sol:Test")
+                .expect("Always valid")
+                .is_none()
+        );
+    }
+}
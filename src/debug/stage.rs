@@ -0,0 +1,100 @@
+//!
+//! The debug dump stage.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The debug dump stage.
+///
+/// The stages are ordered as they appear in the compilation pipeline.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    /// The Yul source code.
+    Yul,
+    /// The EVM legacy assembly.
+    EVMLA,
+    /// The Ethereal IR.
+    EthIR,
+    /// The mapping of the EVM legacy assembly block tags to the LLVM basic blocks.
+    BlockMap,
+    /// The EVM legacy assembly translation trace.
+    Trace,
+    /// The LLVM IR before optimizations.
+    LLVMIRUnoptimized,
+    /// The LLVM IR after optimizations.
+    LLVMIROptimized,
+    /// The zkEVM assembly.
+    Assembly,
+}
+
+impl Stage {
+    ///
+    /// Detects the stage by the dump file name.
+    ///
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        if file_name.ends_with(".yul") {
+            Some(Self::Yul)
+        } else if file_name.ends_with(".evmla") {
+            Some(Self::EVMLA)
+        } else if file_name.ends_with(".ethir") {
+            Some(Self::EthIR)
+        } else if file_name.ends_with(
+            format!(
+                ".{}.{}",
+                crate::r#const::EVMLA_LLVM_BLOCK_MAP_FILE_NAME_SUFFIX,
+                compiler_common::EXTENSION_JSON
+            )
+            .as_str(),
+        ) {
+            Some(Self::BlockMap)
+        } else if file_name.ends_with(".trace") {
+            Some(Self::Trace)
+        } else if file_name.ends_with(".unoptimized.ll") {
+            Some(Self::LLVMIRUnoptimized)
+        } else if file_name.ends_with(".optimized.ll") {
+            Some(Self::LLVMIROptimized)
+        } else if file_name.ends_with(".zasm") {
+            Some(Self::Assembly)
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Returns the stage number used as the dump file name prefix.
+    ///
+    /// The Yul and EVM legacy assembly share the number, as they belong to different pipelines.
+    /// The block map shares the number with the Ethereal IR it is dumped along with.
+    ///
+    pub fn number(&self) -> usize {
+        match self {
+            Self::Yul => 1,
+            Self::EVMLA => 1,
+            Self::EthIR => 2,
+            Self::BlockMap => 2,
+            Self::Trace => 3,
+            Self::LLVMIRUnoptimized => 4,
+            Self::LLVMIROptimized => 5,
+            Self::Assembly => 6,
+        }
+    }
+}
+
+impl std::fmt::Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Yul => write!(f, "yul"),
+            Self::EVMLA => write!(f, "evmla"),
+            Self::EthIR => write!(f, "ethir"),
+            Self::BlockMap => write!(f, "llvm_blocks"),
+            Self::Trace => write!(f, "trace"),
+            Self::LLVMIRUnoptimized => write!(f, "llvm_unoptimized"),
+            Self::LLVMIROptimized => write!(f, "llvm_optimized"),
+            Self::Assembly => write!(f, "assembly"),
+        }
+    }
+}
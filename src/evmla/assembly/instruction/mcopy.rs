@@ -0,0 +1,209 @@
+//!
+//! Translates the MCOPY instruction.
+//!
+
+///
+/// Translates the heap memory copying.
+///
+/// The ranges may overlap, so the copying direction is chosen like in `memmove`: forwards if the
+/// destination is below the source, and backwards otherwise. The whole words are copied with
+/// the heap loads and stores, and the trailing bytes, which do not form a whole word, are copied
+/// one by one, so no byte outside of the destination range is overwritten.
+///
+pub fn copy<'ctx, D>(
+    context: &mut compiler_llvm_context::Context<'ctx, D>,
+    destination: inkwell::values::IntValue<'ctx>,
+    source: inkwell::values::IntValue<'ctx>,
+    size: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<()>
+where
+    D: compiler_llvm_context::Dependency + Clone,
+{
+    let word_size = context.field_const(compiler_common::BYTE_LENGTH_FIELD as u64);
+    let tail_size = compiler_llvm_context::bitwise::and(
+        context,
+        size,
+        context.field_const((compiler_common::BYTE_LENGTH_FIELD - 1) as u64),
+    )?
+    .into_int_value();
+    let words_size =
+        compiler_llvm_context::arithmetic::subtraction(context, size, tail_size)?.into_int_value();
+
+    let index_pointer = context.build_alloca(context.field_type(), "mcopy_index_pointer");
+
+    let forward_block = context.append_basic_block("mcopy_forward");
+    let forward_words_condition_block = context.append_basic_block("mcopy_forward_words_condition");
+    let forward_words_body_block = context.append_basic_block("mcopy_forward_words_body");
+    let forward_bytes_condition_block = context.append_basic_block("mcopy_forward_bytes_condition");
+    let forward_bytes_body_block = context.append_basic_block("mcopy_forward_bytes_body");
+    let backward_block = context.append_basic_block("mcopy_backward");
+    let backward_bytes_condition_block =
+        context.append_basic_block("mcopy_backward_bytes_condition");
+    let backward_bytes_body_block = context.append_basic_block("mcopy_backward_bytes_body");
+    let backward_words_condition_block =
+        context.append_basic_block("mcopy_backward_words_condition");
+    let backward_words_body_block = context.append_basic_block("mcopy_backward_words_body");
+    let join_block = context.append_basic_block("mcopy_join");
+
+    let is_forward = context.builder().build_int_compare(
+        inkwell::IntPredicate::ULE,
+        destination,
+        source,
+        "mcopy_is_forward",
+    );
+    context.build_conditional_branch(is_forward, forward_block, backward_block);
+
+    context.set_basic_block(forward_block);
+    context.build_store(index_pointer, context.field_const(0));
+    context.build_unconditional_branch(forward_words_condition_block);
+
+    context.set_basic_block(forward_words_condition_block);
+    let index = context
+        .build_load(index_pointer, "mcopy_forward_words_index")
+        .into_int_value();
+    let condition = context.builder().build_int_compare(
+        inkwell::IntPredicate::ULT,
+        index,
+        words_size,
+        "mcopy_forward_words_condition",
+    );
+    context.build_conditional_branch(
+        condition,
+        forward_words_body_block,
+        forward_bytes_condition_block,
+    );
+
+    context.set_basic_block(forward_words_body_block);
+    copy_word(context, destination, source, index)?;
+    let index = context
+        .builder()
+        .build_int_add(index, word_size, "mcopy_forward_words_index_next");
+    context.build_store(index_pointer, index);
+    context.build_unconditional_branch(forward_words_condition_block);
+
+    context.set_basic_block(forward_bytes_condition_block);
+    let index = context
+        .build_load(index_pointer, "mcopy_forward_bytes_index")
+        .into_int_value();
+    let condition = context.builder().build_int_compare(
+        inkwell::IntPredicate::ULT,
+        index,
+        size,
+        "mcopy_forward_bytes_condition",
+    );
+    context.build_conditional_branch(condition, forward_bytes_body_block, join_block);
+
+    context.set_basic_block(forward_bytes_body_block);
+    copy_byte(context, destination, source, index)?;
+    let index = context.builder().build_int_add(
+        index,
+        context.field_const(1),
+        "mcopy_forward_bytes_index_next",
+    );
+    context.build_store(index_pointer, index);
+    context.build_unconditional_branch(forward_bytes_condition_block);
+
+    context.set_basic_block(backward_block);
+    context.build_store(index_pointer, size);
+    context.build_unconditional_branch(backward_bytes_condition_block);
+
+    context.set_basic_block(backward_bytes_condition_block);
+    let index = context
+        .build_load(index_pointer, "mcopy_backward_bytes_index")
+        .into_int_value();
+    let condition = context.builder().build_int_compare(
+        inkwell::IntPredicate::UGT,
+        index,
+        words_size,
+        "mcopy_backward_bytes_condition",
+    );
+    context.build_conditional_branch(
+        condition,
+        backward_bytes_body_block,
+        backward_words_condition_block,
+    );
+
+    context.set_basic_block(backward_bytes_body_block);
+    let index =
+        compiler_llvm_context::arithmetic::subtraction(context, index, context.field_const(1))?
+            .into_int_value();
+    context.build_store(index_pointer, index);
+    copy_byte(context, destination, source, index)?;
+    context.build_unconditional_branch(backward_bytes_condition_block);
+
+    context.set_basic_block(backward_words_condition_block);
+    let index = context
+        .build_load(index_pointer, "mcopy_backward_words_index")
+        .into_int_value();
+    let condition = context.builder().build_int_compare(
+        inkwell::IntPredicate::NE,
+        index,
+        context.field_const(0),
+        "mcopy_backward_words_condition",
+    );
+    context.build_conditional_branch(condition, backward_words_body_block, join_block);
+
+    context.set_basic_block(backward_words_body_block);
+    let index =
+        compiler_llvm_context::arithmetic::subtraction(context, index, word_size)?.into_int_value();
+    context.build_store(index_pointer, index);
+    copy_word(context, destination, source, index)?;
+    context.build_unconditional_branch(backward_words_condition_block);
+
+    context.set_basic_block(join_block);
+
+    Ok(())
+}
+
+///
+/// Copies the word at `index` from the `source` to the `destination`.
+///
+fn copy_word<'ctx, D>(
+    context: &mut compiler_llvm_context::Context<'ctx, D>,
+    destination: inkwell::values::IntValue<'ctx>,
+    source: inkwell::values::IntValue<'ctx>,
+    index: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<()>
+where
+    D: compiler_llvm_context::Dependency + Clone,
+{
+    let source = context
+        .builder()
+        .build_int_add(source, index, "mcopy_source_word");
+    let destination = context
+        .builder()
+        .build_int_add(destination, index, "mcopy_destination_word");
+    let value = compiler_llvm_context::memory::load(context, source)?;
+    compiler_llvm_context::memory::store(context, destination, value.into_int_value())?;
+
+    Ok(())
+}
+
+///
+/// Copies the byte at `index` from the `source` to the `destination`.
+///
+fn copy_byte<'ctx, D>(
+    context: &mut compiler_llvm_context::Context<'ctx, D>,
+    destination: inkwell::values::IntValue<'ctx>,
+    source: inkwell::values::IntValue<'ctx>,
+    index: inkwell::values::IntValue<'ctx>,
+) -> anyhow::Result<()>
+where
+    D: compiler_llvm_context::Dependency + Clone,
+{
+    let source = context
+        .builder()
+        .build_int_add(source, index, "mcopy_source_byte");
+    let destination = context
+        .builder()
+        .build_int_add(destination, index, "mcopy_destination_byte");
+    let word = compiler_llvm_context::memory::load(context, source)?;
+    let value = compiler_llvm_context::bitwise::shift_right(
+        context,
+        context.field_const(((compiler_common::BYTE_LENGTH_FIELD - 1) * 8) as u64),
+        word.into_int_value(),
+    )?;
+    compiler_llvm_context::memory::store_byte(context, destination, value.into_int_value())?;
+
+    Ok(())
+}
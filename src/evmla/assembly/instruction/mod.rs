@@ -4,8 +4,10 @@
 
 pub mod codecopy;
 pub mod jump;
+pub mod mcopy;
 pub mod name;
 pub mod stack;
+pub mod support;
 
 use std::collections::BTreeMap;
 
@@ -23,6 +25,15 @@ pub struct Instruction {
     pub name: Name,
     /// The optional value argument.
     pub value: Option<String>,
+    /// The source code index in the `solc` source list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<isize>,
+    /// The source code location start offset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub begin: Option<isize>,
+    /// The source code location end offset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<isize>,
 }
 
 impl Instruction {
@@ -70,9 +81,12 @@ impl Instruction {
             Name::MLOAD => 1,
             Name::MSTORE => 2,
             Name::MSTORE8 => 2,
+            Name::MCOPY => 3,
 
             Name::SLOAD => 1,
             Name::SSTORE => 2,
+            Name::TLOAD => 1,
+            Name::TSTORE => 2,
             Name::PUSHIMMUTABLE => 0,
             Name::ASSIGNIMMUTABLE => {
                 if version.minor >= 8 {
@@ -97,6 +111,7 @@ impl Instruction {
             Name::RETURN => 2,
             Name::REVERT => 2,
             Name::SELFDESTRUCT => 1,
+            Name::BLOBHASH => 1,
 
             Name::LOG0 => 2,
             Name::LOG1 => 3,
@@ -220,6 +235,7 @@ impl Instruction {
             Name::MLOAD => 1,
 
             Name::SLOAD => 1,
+            Name::TLOAD => 1,
             Name::PUSHIMMUTABLE => 1,
 
             Name::CALLDATALOAD => 1,
@@ -262,6 +278,8 @@ impl Instruction {
             Name::MSIZE => 1,
 
             Name::BASEFEE => 1,
+            Name::BLOBHASH => 1,
+            Name::BLOBBASEFEE => 1,
             Name::PC => 1,
 
             Name::RecursiveCall { output_size, .. } => output_size,
@@ -308,6 +326,18 @@ impl Instruction {
         Ok(())
     }
 
+    ///
+    /// Returns the `solc` source code location prefix for the error messages, if it is known.
+    ///
+    pub fn location_prefix(&self) -> String {
+        match (self.source, self.begin, self.end) {
+            (Some(source), Some(begin), Some(end)) => {
+                format!("Source #{source}, bytes {begin}..{end}: ")
+            }
+            _ => String::new(),
+        }
+    }
+
     ///
     /// Initializes an `INVALID` instruction to terminate an invalid unreachable block part.
     ///
@@ -315,6 +345,9 @@ impl Instruction {
         Self {
             name: Name::INVALID,
             value: None,
+            source: None,
+            begin: None,
+            end: None,
         }
     }
 
@@ -335,6 +368,9 @@ impl Instruction {
                 return_address,
             },
             value: None,
+            source: None,
+            begin: None,
+            end: None,
         }
     }
 
@@ -345,6 +381,9 @@ impl Instruction {
         Self {
             name: Name::RecursiveReturn { input_size },
             value: None,
+            source: None,
+            begin: None,
+            end: None,
         }
     }
 }
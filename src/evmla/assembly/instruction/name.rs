@@ -237,12 +237,18 @@ pub enum Name {
     MSTORE,
     /// The eponymous EVM instruction.
     MSTORE8,
+    /// The eponymous EVM instruction.
+    MCOPY,
 
     /// The eponymous EVM instruction.
     SLOAD,
     /// The eponymous EVM instruction.
     SSTORE,
     /// The eponymous EVM instruction.
+    TLOAD,
+    /// The eponymous EVM instruction.
+    TSTORE,
+    /// The eponymous EVM instruction.
     PUSHIMMUTABLE,
     /// The eponymous EVM instruction.
     ASSIGNIMMUTABLE,
@@ -349,6 +355,10 @@ pub enum Name {
     /// The eponymous EVM instruction.
     BASEFEE,
     /// The eponymous EVM instruction.
+    BLOBHASH,
+    /// The eponymous EVM instruction.
+    BLOBBASEFEE,
+    /// The eponymous EVM instruction.
     MSIZE,
 
     /// The eponymous EVM instruction.
@@ -382,7 +392,13 @@ pub enum Name {
 
 impl From<Name> for Instruction {
     fn from(name: Name) -> Self {
-        Self { name, value: None }
+        Self {
+            name,
+            value: None,
+            source: None,
+            begin: None,
+            end: None,
+        }
     }
 }
 
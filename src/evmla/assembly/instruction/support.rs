@@ -0,0 +1,362 @@
+//!
+//! The EVM legacy assembly instruction support.
+//!
+
+use serde::Serialize;
+
+use crate::solc::Compiler as SolcCompiler;
+
+use super::name::Name;
+
+///
+/// Declares the list of the instructions `solc` may emit.
+///
+/// The test-only match over the list has no wildcard, so adding an instruction to `Name` fails
+/// the test build until it is listed here.
+///
+macro_rules! instructions {
+    ($($name:ident),+ $(,)?) => {
+        /// The instructions `solc` may emit in the EVM legacy assembly, in the translator order.
+        pub const INSTRUCTIONS: &'static [Name] = &[$(Name::$name),+];
+
+        ///
+        /// Returns whether the instruction `name` is listed, or is a translator-internal one.
+        ///
+        #[cfg(test)]
+        fn is_listed(name: &Name) -> bool {
+            match name {
+                $(Name::$name)|+ => true,
+                Name::RecursiveCall { .. } | Name::RecursiveReturn { .. } => false,
+            }
+        }
+    };
+}
+
+///
+/// The EVM legacy assembly instruction support.
+///
+/// Must be kept in sync with the EVM legacy assembly translator, which rejects the instructions
+/// classified as rejected here.
+///
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum Support {
+    /// Translated for all supported `solc` versions.
+    Supported,
+    /// Translated, with the semantics changed since the specified `solc` version.
+    Since {
+        /// The first `solc` version with the changed semantics.
+        version: semver::Version,
+        /// The change description.
+        description: &'static str,
+    },
+    /// Rejected by the translator.
+    Rejected {
+        /// The rejection reason.
+        reason: &'static str,
+    },
+}
+
+impl Support {
+    instructions! {
+        PUSH,
+        PUSH_Tag,
+        PUSH_Data,
+        PUSH_ContractHashSize,
+        PUSH_ContractHash,
+        PUSH1,
+        PUSH2,
+        PUSH3,
+        PUSH4,
+        PUSH5,
+        PUSH6,
+        PUSH7,
+        PUSH8,
+        PUSH9,
+        PUSH10,
+        PUSH11,
+        PUSH12,
+        PUSH13,
+        PUSH14,
+        PUSH15,
+        PUSH16,
+        PUSH17,
+        PUSH18,
+        PUSH19,
+        PUSH20,
+        PUSH21,
+        PUSH22,
+        PUSH23,
+        PUSH24,
+        PUSH25,
+        PUSH26,
+        PUSH27,
+        PUSH28,
+        PUSH29,
+        PUSH30,
+        PUSH31,
+        PUSH32,
+        DUP1,
+        DUP2,
+        DUP3,
+        DUP4,
+        DUP5,
+        DUP6,
+        DUP7,
+        DUP8,
+        DUP9,
+        DUP10,
+        DUP11,
+        DUP12,
+        DUP13,
+        DUP14,
+        DUP15,
+        DUP16,
+        SWAP1,
+        SWAP2,
+        SWAP3,
+        SWAP4,
+        SWAP5,
+        SWAP6,
+        SWAP7,
+        SWAP8,
+        SWAP9,
+        SWAP10,
+        SWAP11,
+        SWAP12,
+        SWAP13,
+        SWAP14,
+        SWAP15,
+        SWAP16,
+        POP,
+        Tag,
+        JUMP,
+        JUMPI,
+        JUMPDEST,
+        ADD,
+        SUB,
+        MUL,
+        DIV,
+        MOD,
+        SDIV,
+        SMOD,
+        LT,
+        GT,
+        EQ,
+        ISZERO,
+        SLT,
+        SGT,
+        OR,
+        XOR,
+        NOT,
+        AND,
+        SHL,
+        SHR,
+        SAR,
+        BYTE,
+        ADDMOD,
+        MULMOD,
+        EXP,
+        SIGNEXTEND,
+        SHA3,
+        KECCAK256,
+        MLOAD,
+        MSTORE,
+        MSTORE8,
+        MCOPY,
+        SLOAD,
+        SSTORE,
+        TLOAD,
+        TSTORE,
+        PUSHIMMUTABLE,
+        ASSIGNIMMUTABLE,
+        CALLDATALOAD,
+        CALLDATASIZE,
+        CALLDATACOPY,
+        CODESIZE,
+        CODECOPY,
+        PUSHSIZE,
+        EXTCODESIZE,
+        EXTCODEHASH,
+        RETURNDATASIZE,
+        RETURNDATACOPY,
+        RETURN,
+        REVERT,
+        STOP,
+        INVALID,
+        LOG0,
+        LOG1,
+        LOG2,
+        LOG3,
+        LOG4,
+        CALL,
+        STATICCALL,
+        DELEGATECALL,
+        CREATE,
+        CREATE2,
+        ZK_CREATE,
+        ZK_CREATE2,
+        ADDRESS,
+        CALLER,
+        CALLVALUE,
+        GAS,
+        BALANCE,
+        SELFBALANCE,
+        PUSHLIB,
+        PUSHDEPLOYADDRESS,
+        GASLIMIT,
+        GASPRICE,
+        ORIGIN,
+        CHAINID,
+        TIMESTAMP,
+        NUMBER,
+        BLOCKHASH,
+        DIFFICULTY,
+        PREVRANDAO,
+        COINBASE,
+        BASEFEE,
+        BLOBHASH,
+        BLOBBASEFEE,
+        MSIZE,
+        CALLCODE,
+        PC,
+        EXTCODECOPY,
+        SELFDESTRUCT,
+    }
+
+    ///
+    /// Returns the translator support of the instruction `name`.
+    ///
+    pub fn of(name: &Name) -> Self {
+        match name {
+            Name::ASSIGNIMMUTABLE => Self::Since {
+                version: SolcCompiler::FIRST_YUL_VERSION,
+                description: "takes the memory offset argument",
+            },
+            Name::MCOPY => Self::Since {
+                version: SolcCompiler::FIRST_MCOPY_VERSION,
+                description: "emitted for the memory copying with EVM version `cancun`",
+            },
+            Name::TLOAD | Name::TSTORE => Self::Since {
+                version: SolcCompiler::FIRST_TRANSIENT_STORAGE_VERSION,
+                description: "emitted for the transient storage access with EVM version `cancun`",
+            },
+            Name::BASEFEE => Self::Since {
+                version: SolcCompiler::FIRST_BASEFEE_VERSION,
+                description: "emitted for `block.basefee` with EVM version `london`",
+            },
+            Name::PREVRANDAO => Self::Since {
+                version: SolcCompiler::FIRST_PREVRANDAO_VERSION,
+                description: "emitted instead of `DIFFICULTY`",
+            },
+            Name::BLOBHASH => Self::Since {
+                version: SolcCompiler::FIRST_BLOBHASH_VERSION,
+                description: "always `0`, since EraVM transactions do not carry blobs",
+            },
+
+            Name::BLOBBASEFEE => Self::Rejected {
+                reason: "EraVM transactions do not carry blobs",
+            },
+            Name::CALLCODE => Self::Rejected {
+                reason: "deprecated in favor of `DELEGATECALL`",
+            },
+            Name::PC => Self::Rejected {
+                reason: "the zkEVM bytecode layout is not observable",
+            },
+            Name::EXTCODECOPY => Self::Rejected {
+                reason: "the zkEVM bytecode is not accessible as data",
+            },
+            Name::SELFDESTRUCT => Self::Rejected {
+                reason: "not supported by the zkEVM",
+            },
+
+            _ => Self::Supported,
+        }
+    }
+
+    ///
+    /// Returns the human-readable report of the instruction support.
+    ///
+    pub fn report() -> String {
+        let mut report = format!(
+            "EVM legacy assembly instructions, `solc` v{} to v{}:\n",
+            SolcCompiler::FIRST_SUPPORTED_VERSION,
+            SolcCompiler::LAST_SUPPORTED_VERSION,
+        );
+        for name in Self::INSTRUCTIONS.iter() {
+            report.push_str(format!("{:<24}{}\n", name.to_string(), Self::of(name)).as_str());
+        }
+        report
+    }
+}
+
+impl std::fmt::Display for Support {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Supported => write!(f, "supported"),
+            Self::Since {
+                version,
+                description,
+            } => write!(f, "supported; since `solc` v{version}: {description}"),
+            Self::Rejected { reason } => write!(f, "rejected: {reason}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::Name;
+    use super::Support;
+
+    #[test]
+    fn unique() {
+        let unique: HashSet<&Name> = Support::INSTRUCTIONS.iter().collect();
+        assert_eq!(unique.len(), Support::INSTRUCTIONS.len());
+    }
+
+    #[test]
+    fn exhaustive() {
+        assert!(Support::INSTRUCTIONS.iter().all(Support::is_listed));
+        assert!(!Support::is_listed(&Name::RecursiveReturn {
+            input_size: 0
+        }));
+    }
+
+    #[test]
+    fn post_london() {
+        for (name, version) in [
+            (Name::BASEFEE, semver::Version::new(0, 8, 7)),
+            (Name::PREVRANDAO, semver::Version::new(0, 8, 18)),
+            (Name::MCOPY, semver::Version::new(0, 8, 24)),
+            (Name::TLOAD, semver::Version::new(0, 8, 24)),
+            (Name::TSTORE, semver::Version::new(0, 8, 24)),
+            (Name::BLOBHASH, semver::Version::new(0, 8, 24)),
+        ] {
+            assert!(
+                matches!(Support::of(&name), Support::Since { version: since, .. } if since == version),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejected() {
+        let rejected: Vec<String> = Support::INSTRUCTIONS
+            .iter()
+            .filter(|name| matches!(Support::of(name), Support::Rejected { .. }))
+            .map(|name| name.to_string())
+            .collect();
+        assert_eq!(
+            rejected,
+            vec![
+                "BLOBBASEFEE",
+                "CALLCODE",
+                "PC",
+                "EXTCODECOPY",
+                "SELFDESTRUCT"
+            ]
+        );
+    }
+}
@@ -6,12 +6,14 @@ pub mod data;
 pub mod instruction;
 
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashSet;
 
 use serde::Deserialize;
 use serde::Serialize;
 
 use crate::evmla::ethereal_ir::entry_link::EntryLink;
+use crate::evmla::ethereal_ir::trace::Trace;
 use crate::evmla::ethereal_ir::EtherealIR;
 use crate::solc::standard_json::output::contract::evm::extra_metadata::ExtraMetadata;
 
@@ -43,6 +45,15 @@ pub struct Assembly {
     /// The EVMLA extra metadata.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extra_metadata: Option<ExtraMetadata>,
+    /// The function stack size, above which the deepest slots are spilled to memory.
+    #[serde(skip)]
+    pub stack_spill_threshold: Option<usize>,
+    /// The block tag to trace the translation of.
+    #[serde(skip)]
+    pub trace_tag: Option<usize>,
+    /// Whether to attach the instruction indexes as the LLVM debug locations.
+    #[serde(skip)]
+    pub debug_locations: bool,
 }
 
 impl Assembly {
@@ -54,6 +65,44 @@ impl Assembly {
         compiler_llvm_context::keccak256(json.as_slice())
     }
 
+    ///
+    /// Counts the instructions of the deploy and runtime code by their names.
+    ///
+    pub fn instruction_histogram(&self, histogram: &mut BTreeMap<String, usize>) {
+        for instruction in self.code.iter().flatten() {
+            *histogram.entry(instruction.name.to_string()).or_insert(0) += 1;
+        }
+
+        for data in self.data.iter().flat_map(|data| data.values()) {
+            if let Data::Assembly(assembly) = data {
+                assembly.instruction_histogram(histogram);
+            }
+        }
+    }
+
+    ///
+    /// Collects the keys of the immutables pushed or assigned by the assembly and its
+    /// nested assemblies.
+    ///
+    pub fn immutable_keys(&self, keys: &mut BTreeSet<String>) {
+        for instruction in self.code.iter().flatten() {
+            if matches!(
+                instruction.name,
+                InstructionName::PUSHIMMUTABLE | InstructionName::ASSIGNIMMUTABLE
+            ) {
+                if let Some(key) = instruction.value.as_ref() {
+                    keys.insert(key.to_owned());
+                }
+            }
+        }
+
+        for data in self.data.iter().flat_map(|data| data.values()) {
+            if let Data::Assembly(assembly) = data {
+                assembly.immutable_keys(keys);
+            }
+        }
+    }
+
     ///
     /// Sets the full contract path.
     ///
@@ -73,6 +122,50 @@ impl Assembly {
             .unwrap_or_else(|| panic!("The full path of some contracts is unset"))
     }
 
+    ///
+    /// Builds the Ethereal IR of the deploy and runtime code, which is lowered to LLVM IR or
+    /// inspected with a visitor.
+    ///
+    pub fn ethereal_ir(&self, solc_version: &semver::Version) -> anyhow::Result<EtherealIR> {
+        let deploy_code_instructions = self
+            .code
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Deploy code instructions not found"))?;
+        let runtime_code_instructions = match self.data.as_ref().and_then(|data| data.get("0")) {
+            Some(Data::Assembly(assembly)) => assembly
+                .code
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("Runtime code instructions not found"))?,
+            Some(Data::Hash(hash)) => {
+                anyhow::bail!("Expected runtime code instructions, found hash `{}`", hash)
+            }
+            Some(Data::Path(path)) => {
+                anyhow::bail!("Expected runtime code instructions, found path `{}`", path)
+            }
+            None => anyhow::bail!("Runtime code data not found"),
+        };
+
+        let mut blocks = EtherealIR::get_blocks(
+            solc_version.to_owned(),
+            compiler_llvm_context::CodeType::Deploy,
+            deploy_code_instructions,
+        )?;
+        blocks.extend(EtherealIR::get_blocks(
+            solc_version.to_owned(),
+            compiler_llvm_context::CodeType::Runtime,
+            runtime_code_instructions,
+        )?);
+        let mut ethereal_ir = EtherealIR::new(
+            solc_version.to_owned(),
+            self.extra_metadata.clone().unwrap_or_default(),
+            blocks,
+        )?;
+        if let Some(threshold) = self.stack_spill_threshold {
+            ethereal_ir.set_stack_spill_threshold(threshold);
+        }
+        Ok(ethereal_ir)
+    }
+
     ///
     /// Replaces the deploy code dependencies with full contract path and returns the list.
     ///
@@ -206,53 +299,44 @@ where
         Ok(())
     }
 
-    fn into_llvm(mut self, context: &mut compiler_llvm_context::Context<D>) -> anyhow::Result<()> {
+    fn into_llvm(self, context: &mut compiler_llvm_context::Context<D>) -> anyhow::Result<()> {
         let full_path = self.full_path().to_owned();
 
         if let Some(debug_config) = context.debug_config() {
             debug_config.dump_evmla(full_path.as_str(), self.to_string().as_str())?;
         }
-        let deploy_code_blocks = EtherealIR::get_blocks(
-            context.evmla().version.to_owned(),
-            compiler_llvm_context::CodeType::Deploy,
-            self.code
-                .as_deref()
-                .ok_or_else(|| anyhow::anyhow!("Deploy code instructions not found"))?,
-        )?;
-
-        let data = self
-            .data
-            .ok_or_else(|| anyhow::anyhow!("Runtime code data not found"))?
-            .remove("0")
-            .expect("Always exists");
-        if let Some(debug_config) = context.debug_config() {
+        if let (Some(debug_config), Some(data)) = (
+            context.debug_config(),
+            self.data.as_ref().and_then(|data| data.get("0")),
+        ) {
             debug_config.dump_evmla(full_path.as_str(), data.to_string().as_str())?;
         }
-        let runtime_code_instructions = match data {
-            Data::Assembly(assembly) => assembly
-                .code
-                .ok_or_else(|| anyhow::anyhow!("Runtime code instructions not found"))?,
-            Data::Hash(hash) => {
-                anyhow::bail!("Expected runtime code instructions, found hash `{}`", hash)
-            }
-            Data::Path(path) => {
-                anyhow::bail!("Expected runtime code instructions, found path `{}`", path)
-            }
-        };
-        let runtime_code_blocks = EtherealIR::get_blocks(
-            context.evmla().version.to_owned(),
-            compiler_llvm_context::CodeType::Runtime,
-            runtime_code_instructions.as_slice(),
-        )?;
-
-        let extra_metadata = self.extra_metadata.take().unwrap_or_default();
-        let mut blocks = deploy_code_blocks;
-        blocks.extend(runtime_code_blocks);
-        let mut ethereal_ir =
-            EtherealIR::new(context.evmla().version.to_owned(), extra_metadata, blocks)?;
+        let solc_version = context.evmla().version.to_owned();
+        let mut ethereal_ir = self.ethereal_ir(&solc_version)?;
+        #[cfg(feature = "plugins")]
+        crate::plugin::visit_ethereal_ir(full_path.as_str(), &mut ethereal_ir)?;
+        if let (Some(tag), Some(debug_config)) = (self.trace_tag, context.debug_config()) {
+            let mut path = debug_config.output_directory.to_owned();
+            path.push(format!(
+                "{}.{}",
+                crate::debug::contract_directory(full_path.as_str()),
+                crate::r#const::EXTENSION_EVMLA_TRACE
+            ));
+            ethereal_ir.set_trace(Trace::try_new(tag, path)?);
+        }
+        if self.debug_locations {
+            ethereal_ir.set_debug_locations(full_path.as_str());
+        }
         if let Some(debug_config) = context.debug_config() {
             debug_config.dump_ethir(full_path.as_str(), ethereal_ir.to_string().as_str())?;
         }
+        if let Some(debug_config) = context.debug_config() {
+            crate::debug::dump_llvm_block_map(
+                debug_config,
+                full_path.as_str(),
+                ethereal_ir.block_map().as_slice(),
+            )?;
+        }
         ethereal_ir.declare(context)?;
         ethereal_ir.into_llvm(context)?;
 
@@ -0,0 +1,87 @@
+//!
+//! The EVM legacy assembly block tag mapping.
+//!
+
+use serde::Serialize;
+
+///
+/// The EVM legacy assembly block tag mapping.
+///
+/// Correlates a tag of the `solc` EVM legacy assembly with the Ethereal IR function it has
+/// been placed into and the LLVM basic blocks generated for it, one per initial stack state.
+/// The block names are preserved in the LLVM IR dumps, and the recursive functions keep their
+/// names in the zkEVM assembly unless inlined.
+///
+/// The zkEVM assembly labels are not mapped, since LLVM merges, splits, and renumbers the basic
+/// blocks during the code generation without keeping their names.
+///
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockMapping {
+    /// The code type, either `deploy` or `runtime`.
+    pub code_type: String,
+    /// The decimal block tag.
+    pub tag: String,
+    /// The Ethereal IR function name.
+    pub function: String,
+    /// The LLVM basic block names.
+    pub llvm_blocks: Vec<String>,
+}
+
+impl BlockMapping {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        key: &compiler_llvm_context::FunctionBlockKey,
+        function: &str,
+        block_count: usize,
+    ) -> Self {
+        let code_type = match key.code_type {
+            compiler_llvm_context::CodeType::Deploy => "deploy",
+            compiler_llvm_context::CodeType::Runtime => "runtime",
+        };
+
+        Self {
+            code_type: code_type.to_owned(),
+            tag: key.tag.to_string(),
+            function: function.to_owned(),
+            llvm_blocks: (0..block_count)
+                .map(|index| Self::llvm_block_name(key, index))
+                .collect(),
+        }
+    }
+
+    ///
+    /// Returns the LLVM basic block name of the `index`-th copy of the block.
+    ///
+    pub fn llvm_block_name(key: &compiler_llvm_context::FunctionBlockKey, index: usize) -> String {
+        format!("block_{key}/{index}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlockMapping;
+
+    #[test]
+    fn serialize() {
+        let key = compiler_llvm_context::FunctionBlockKey::new(
+            compiler_llvm_context::CodeType::Runtime,
+            num::BigUint::from(42u32),
+        );
+        let mapping = BlockMapping::new(&key, "main", 2);
+
+        let json = serde_json::to_value(&mapping).expect("Always valid");
+        assert_eq!(json["codeType"], "runtime");
+        assert_eq!(json["tag"], "42");
+        assert_eq!(json["function"], "main");
+        assert_eq!(
+            json["llvmBlocks"],
+            serde_json::json!([
+                BlockMapping::llvm_block_name(&key, 0),
+                BlockMapping::llvm_block_name(&key, 1)
+            ])
+        );
+    }
+}
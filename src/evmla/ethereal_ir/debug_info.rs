@@ -0,0 +1,194 @@
+//!
+//! The Ethereal IR debug locations.
+//!
+
+use std::cell::Cell;
+
+use inkwell::debug_info::AsDIScope;
+use inkwell::debug_info::DIFlagsConstants;
+
+///
+/// The Ethereal IR debug locations.
+///
+/// Each element is attached the index of its originating instruction as the LLVM debug location,
+/// so the instructions of LLVM IR, the assembly, and the backend errors can be traced back to the
+/// legacy assembly. The line is the instruction index in the deploy or runtime code, as numbered
+/// in the EVMLA dump, plus one, since the line zero means no location in LLVM. The deploy and
+/// runtime code are distinguished by the `<path>:deploy` and `<path>:runtime` file names.
+///
+/// The module has a single compile unit, and each function gets its own subprogram with a
+/// lexical block per file.
+///
+#[derive(Debug)]
+pub struct DebugInfo<'ctx> {
+    /// The debug info builder.
+    builder: inkwell::debug_info::DebugInfoBuilder<'ctx>,
+    /// The module compile unit.
+    compile_unit: inkwell::debug_info::DICompileUnit<'ctx>,
+    /// The deploy code file.
+    deploy_file: inkwell::debug_info::DIFile<'ctx>,
+    /// The runtime code file.
+    runtime_file: inkwell::debug_info::DIFile<'ctx>,
+    /// The deploy and runtime code scopes of the current function.
+    scopes: Cell<
+        Option<(
+            inkwell::debug_info::DILexicalBlock<'ctx>,
+            inkwell::debug_info::DILexicalBlock<'ctx>,
+        )>,
+    >,
+}
+
+impl<'ctx> DebugInfo<'ctx> {
+    /// The module flag without which LLVM drops the debug info.
+    pub const FLAG_DEBUG_INFO_VERSION: &'static str = "Debug Info Version";
+
+    /// The producer name.
+    pub const PRODUCER: &'static str = "zksolc";
+
+    ///
+    /// Creates the debug info of the module of the contract at `full_path`.
+    ///
+    pub fn new<D>(context: &compiler_llvm_context::Context<'ctx, D>, full_path: &str) -> Self
+    where
+        D: compiler_llvm_context::Dependency + Clone,
+    {
+        let module = context.module();
+        module.add_basic_value_flag(
+            Self::FLAG_DEBUG_INFO_VERSION,
+            inkwell::module::FlagBehavior::Warning,
+            context.integer_const(
+                compiler_common::BIT_LENGTH_X32,
+                inkwell::debug_info::debug_metadata_version() as u64,
+            ),
+        );
+
+        let (builder, compile_unit) = module.create_debug_info_builder(
+            true,
+            inkwell::debug_info::DWARFSourceLanguage::C,
+            full_path,
+            "",
+            Self::PRODUCER,
+            false,
+            "",
+            0,
+            "",
+            inkwell::debug_info::DWARFEmissionKind::LineTablesOnly,
+            0,
+            false,
+            false,
+            "",
+            "",
+        );
+        let deploy_file = builder.create_file(format!("{full_path}:deploy").as_str(), "");
+        let runtime_file = builder.create_file(format!("{full_path}:runtime").as_str(), "");
+
+        Self {
+            builder,
+            compile_unit,
+            deploy_file,
+            runtime_file,
+            scopes: Cell::new(None),
+        }
+    }
+
+    ///
+    /// Creates the subprogram of the current function, to which the debug locations set next
+    /// belong.
+    ///
+    pub fn set_function<D>(&self, context: &compiler_llvm_context::Context<'ctx, D>)
+    where
+        D: compiler_llvm_context::Dependency + Clone,
+    {
+        let function = context.current_function();
+        let function = function.borrow();
+        let file = self.compile_unit.get_file();
+        let subroutine_type = self.builder.create_subroutine_type(
+            file,
+            None,
+            &[],
+            inkwell::debug_info::DIFlags::ZERO,
+        );
+        let subprogram = self.builder.create_function(
+            self.compile_unit.as_debug_info_scope(),
+            function.name(),
+            None,
+            file,
+            0,
+            subroutine_type,
+            true,
+            true,
+            0,
+            inkwell::debug_info::DIFlags::ZERO,
+            false,
+        );
+        function.declaration().value.set_subprogram(subprogram);
+
+        let deploy_scope = self.builder.create_lexical_block(
+            subprogram.as_debug_info_scope(),
+            self.deploy_file,
+            0,
+            0,
+        );
+        let runtime_scope = self.builder.create_lexical_block(
+            subprogram.as_debug_info_scope(),
+            self.runtime_file,
+            0,
+            0,
+        );
+        self.scopes.set(Some((deploy_scope, runtime_scope)));
+    }
+
+    ///
+    /// Sets the debug location of the instructions built next to the instruction `index` in the
+    /// `code_type` code.
+    ///
+    /// Does nothing if the current function has not been set.
+    ///
+    pub fn set_location<D>(
+        &self,
+        context: &compiler_llvm_context::Context<'ctx, D>,
+        code_type: compiler_llvm_context::CodeType,
+        index: usize,
+    ) where
+        D: compiler_llvm_context::Dependency + Clone,
+    {
+        let (deploy_scope, runtime_scope) = match self.scopes.get() {
+            Some(scopes) => scopes,
+            None => return,
+        };
+        let scope = match code_type {
+            compiler_llvm_context::CodeType::Deploy => deploy_scope,
+            compiler_llvm_context::CodeType::Runtime => runtime_scope,
+        };
+        let location = self.builder.create_debug_location(
+            context.llvm(),
+            (index + 1) as u32,
+            0,
+            scope.as_debug_info_scope(),
+            None,
+        );
+        context.builder().set_current_debug_location(location);
+    }
+
+    ///
+    /// Stops attaching the debug location to the instructions built next.
+    ///
+    /// Must be called before building anything outside of the current function, since a debug
+    /// location must belong to the function it is attached in.
+    ///
+    pub fn unset_location<D>(&self, context: &compiler_llvm_context::Context<'ctx, D>)
+    where
+        D: compiler_llvm_context::Dependency + Clone,
+    {
+        context.builder().unset_current_debug_location();
+    }
+
+    ///
+    /// Finalizes the debug info, which must be done after all functions are translated and
+    /// before the module is verified.
+    ///
+    pub fn finalize(&self) {
+        self.scopes.set(None);
+        self.builder.finalize();
+    }
+}
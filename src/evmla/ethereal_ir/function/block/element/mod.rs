@@ -7,8 +7,11 @@ pub mod stack;
 use inkwell::values::BasicValue;
 
 use crate::evmla::assembly::instruction::codecopy;
+use crate::evmla::assembly::instruction::mcopy;
 use crate::evmla::assembly::instruction::name::Name as InstructionName;
 use crate::evmla::assembly::instruction::Instruction;
+use crate::prevrandao::Prevrandao;
+use crate::solc::Compiler as SolcCompiler;
 
 use self::stack::element::Element as StackElement;
 use self::stack::Stack;
@@ -22,6 +25,8 @@ pub struct Element {
     pub solc_version: semver::Version,
     /// The instruction.
     pub instruction: Instruction,
+    /// The instruction index in the deploy or runtime code.
+    pub index: usize,
     /// The stack data.
     pub stack: Stack,
     /// The stack input.
@@ -34,13 +39,14 @@ impl Element {
     ///
     /// A shortcut constructor.
     ///
-    pub fn new(solc_version: semver::Version, instruction: Instruction) -> Self {
+    pub fn new(solc_version: semver::Version, instruction: Instruction, index: usize) -> Self {
         let input_size = instruction.input_size(&solc_version);
         let output_size = instruction.output_size();
 
         Self {
             solc_version,
             instruction,
+            index,
             stack: Stack::new(),
             stack_input: Stack::with_capacity(input_size),
             stack_output: Stack::with_capacity(output_size),
@@ -73,6 +79,22 @@ impl Element {
         }
         arguments
     }
+
+    ///
+    /// Checks whether the instruction may be emitted by the `solc` version, which is only
+    /// possible since `first_version`.
+    ///
+    fn check_version(&self, first_version: &semver::Version) -> anyhow::Result<()> {
+        if self.solc_version < *first_version {
+            anyhow::bail!(
+                "The `{}` instruction is not emitted by `solc` before v{}, found v{}",
+                self.instruction.name,
+                first_version,
+                self.solc_version,
+            );
+        }
+        Ok(())
+    }
 }
 
 impl<D> compiler_llvm_context::WriteLLVM<D> for Element
@@ -719,6 +741,17 @@ where
                 )
                 .map(|_| None)
             }
+            InstructionName::MCOPY => {
+                self.check_version(&SolcCompiler::FIRST_MCOPY_VERSION)?;
+                let arguments = self.pop_arguments_llvm(context);
+                mcopy::copy(
+                    context,
+                    arguments[0].into_int_value(),
+                    arguments[1].into_int_value(),
+                    arguments[2].into_int_value(),
+                )
+                .map(|_| None)
+            }
 
             InstructionName::SLOAD => {
                 let arguments = self.pop_arguments_llvm(context);
@@ -734,6 +767,15 @@ where
                 )
                 .map(|_| None)
             }
+            InstructionName::TLOAD | InstructionName::TSTORE => {
+                self.check_version(&SolcCompiler::FIRST_TRANSIENT_STORAGE_VERSION)?;
+                anyhow::bail!(
+                    "{}Error {}: The `{}` instruction is not supported, since the transient storage (EIP-1153) is not available on EraVM yet",
+                    self.instruction.location_prefix(),
+                    crate::r#const::ERROR_CODE_TRANSIENT_STORAGE,
+                    self.instruction.name,
+                );
+            }
             InstructionName::PUSHIMMUTABLE => {
                 let key = self
                     .instruction
@@ -1155,14 +1197,28 @@ where
                 compiler_llvm_context::contract_context::block_hash(context, index).map(Some)
             }
             InstructionName::DIFFICULTY | InstructionName::PREVRANDAO => {
-                compiler_llvm_context::contract_context::difficulty(context).map(Some)
+                Prevrandao::check_evmla(&self.instruction.name, &self.solc_version)?;
+                Prevrandao::translate(context)
             }
             InstructionName::COINBASE => {
                 compiler_llvm_context::contract_context::coinbase(context).map(Some)
             }
             InstructionName::BASEFEE => {
+                self.check_version(&SolcCompiler::FIRST_BASEFEE_VERSION)?;
                 compiler_llvm_context::contract_context::basefee(context).map(Some)
             }
+            InstructionName::BLOBHASH => {
+                self.check_version(&SolcCompiler::FIRST_BLOBHASH_VERSION)?;
+                let _arguments = self.pop_arguments_llvm(context);
+                Ok(Some(context.field_const(0).as_basic_value_enum()))
+            }
+            InstructionName::BLOBBASEFEE => {
+                anyhow::bail!(
+                    "{}Error {}: The `BLOBBASEFEE` instruction is not supported, since EraVM transactions do not carry blobs (EIP-4844), so there is no blob gas market",
+                    self.instruction.location_prefix(),
+                    crate::r#const::ERROR_CODE_BLOB_BASE_FEE,
+                );
+            }
             InstructionName::MSIZE => {
                 compiler_llvm_context::contract_context::msize(context).map(Some)
             }
@@ -6,10 +6,13 @@ pub mod element;
 
 use std::collections::HashSet;
 
+use compiler_llvm_context::WriteLLVM;
+use inkwell::values::AnyValue;
 use num::Zero;
 
 use crate::evmla::assembly::instruction::name::Name as InstructionName;
 use crate::evmla::assembly::instruction::Instruction;
+use crate::evmla::ethereal_ir::debug_info::DebugInfo;
 
 use self::element::stack::Stack as ElementStack;
 use self::element::Element;
@@ -17,6 +20,11 @@ use self::element::Element;
 ///
 /// The Ethereal IR block.
 ///
+/// The instructions are translated one by one. In particular, the word-by-word `MLOAD`/`MSTORE`
+/// copies emitted by `solc` for structs and fixed-size arrays are not lowered to `memcpy`, since
+/// a forward word copy differs from it if the destination overlaps the source from above, which
+/// cannot be ruled out from the assembly. LLVM may still merge them where the overlap is known.
+///
 #[derive(Debug, Clone)]
 pub struct Block {
     /// The Solidity compiler version.
@@ -46,10 +54,13 @@ impl Block {
     ///
     /// Assembles a block from the sequence of instructions.
     ///
+    /// `offset` is the index of the first instruction of the `slice` in the code.
+    ///
     pub fn try_from_instructions(
         solc_version: semver::Version,
         code_type: compiler_llvm_context::CodeType,
         slice: &[Instruction],
+        offset: usize,
     ) -> anyhow::Result<(Self, usize)> {
         let mut cursor = 0;
 
@@ -79,7 +90,11 @@ impl Block {
         };
 
         while cursor < slice.len() {
-            let element: Element = Element::new(solc_version.clone(), slice[cursor].to_owned());
+            let element: Element = Element::new(
+                solc_version.clone(),
+                slice[cursor].to_owned(),
+                offset + cursor,
+            );
             block.elements.push(element);
 
             match slice[cursor].name {
@@ -103,6 +118,98 @@ impl Block {
         Ok((block, cursor))
     }
 
+    ///
+    /// Translates the block into LLVM IR, attaching the debug location of each element.
+    ///
+    pub fn into_llvm_located<'ctx, D>(
+        self,
+        context: &mut compiler_llvm_context::Context<'ctx, D>,
+        debug_info: &DebugInfo<'ctx>,
+    ) -> anyhow::Result<()>
+    where
+        D: compiler_llvm_context::Dependency + Clone,
+    {
+        context.set_code_type(self.key.code_type);
+
+        for element in self.elements.into_iter() {
+            debug_info.set_location(context, self.key.code_type, element.index);
+            element.into_llvm(context)?;
+            debug_info.unset_location(context);
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Translates the block into LLVM IR, writing the translation trace of each element to `trace`.
+    ///
+    /// The debug locations are attached if `debug_info` is set.
+    ///
+    pub fn into_llvm_traced<'ctx, D>(
+        self,
+        context: &mut compiler_llvm_context::Context<'ctx, D>,
+        debug_info: Option<&DebugInfo<'ctx>>,
+        trace: &mut String,
+    ) -> anyhow::Result<()>
+    where
+        D: compiler_llvm_context::Dependency + Clone,
+    {
+        context.set_code_type(self.key.code_type);
+
+        trace.push_str(
+            format!(
+                "block_{}/{}:\n",
+                self.key,
+                self.instance.unwrap_or_default()
+            )
+            .as_str(),
+        );
+        for element in self.elements.into_iter() {
+            let mut stack_before = element.stack.to_owned();
+            for _ in 0..element.stack_output.len() {
+                let _ = stack_before.pop();
+            }
+            stack_before
+                .elements
+                .extend(element.stack_input.elements.iter().cloned());
+
+            trace.push_str(format!("    {}\n", element.instruction).as_str());
+            trace.push_str(format!("        before: {stack_before}\n").as_str());
+            trace.push_str(format!("        after:  {}\n", element.stack).as_str());
+
+            let basic_block = context.basic_block();
+            let last_instruction = basic_block.get_last_instruction();
+
+            if let Some(debug_info) = debug_info {
+                debug_info.set_location(context, self.key.code_type, element.index);
+            }
+            element.into_llvm(context)?;
+            if let Some(debug_info) = debug_info {
+                debug_info.unset_location(context);
+            }
+
+            let mut instruction = match last_instruction {
+                Some(last_instruction) if context.basic_block() == basic_block => {
+                    last_instruction.get_next_instruction()
+                }
+                _ => context.basic_block().get_first_instruction(),
+            };
+            while let Some(current) = instruction {
+                trace.push_str(
+                    format!(
+                        "        llvm:  {}\n",
+                        current.print_to_string().to_string().trim()
+                    )
+                    .as_str(),
+                );
+                instruction = current.get_next_instruction();
+            }
+        }
+        trace.push('\n');
+
+        Ok(())
+    }
+
     ///
     /// Inserts a predecessor tag.
     ///
@@ -11,6 +11,7 @@ use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::collections::HashMap;
 
+use compiler_llvm_context::WriteLLVM;
 use inkwell::types::BasicType;
 use inkwell::values::BasicValue;
 use num::CheckedAdd;
@@ -24,8 +25,11 @@ use num::Zero;
 
 use crate::evmla::assembly::instruction::name::Name as InstructionName;
 use crate::evmla::assembly::instruction::Instruction;
+use crate::evmla::ethereal_ir::block_map::BlockMapping;
+use crate::evmla::ethereal_ir::debug_info::DebugInfo;
 use crate::evmla::ethereal_ir::function::block::element::stack::element::Element;
 use crate::evmla::ethereal_ir::function::block::element::stack::Stack;
+use crate::evmla::ethereal_ir::trace::Trace;
 use crate::evmla::ethereal_ir::EtherealIR;
 use crate::solc::standard_json::output::contract::evm::extra_metadata::recursive_function::RecursiveFunction;
 use crate::solc::standard_json::output::contract::evm::extra_metadata::ExtraMetadata;
@@ -52,6 +56,10 @@ pub struct Function {
     pub r#type: Type,
     /// The function stack size.
     pub stack_size: usize,
+    /// The stack size, above which the deepest slots are spilled to memory.
+    pub stack_spill_threshold: Option<usize>,
+    /// The translation trace.
+    pub trace: Option<Trace>,
 }
 
 impl Function {
@@ -70,6 +78,8 @@ impl Function {
             blocks: BTreeMap::new(),
             r#type,
             stack_size: 0,
+            stack_spill_threshold: None,
+            trace: None,
         }
     }
 
@@ -1077,6 +1087,64 @@ impl Function {
             .expect("Always exists")
     }
 
+    ///
+    /// Returns the number of the deepest stack slots to be spilled to memory.
+    ///
+    /// Functions with deep stack juggling produce many simultaneously live stack slots, which
+    /// results in pathological register pressure for the backend. The deepest slots are usually
+    /// long-lived and rarely accessed, so keeping them in memory is cheaper than spilling
+    /// them in the register allocator.
+    ///
+    pub fn spilled_slots(&self) -> usize {
+        match self.stack_spill_threshold {
+            Some(threshold) if self.stack_size > threshold => self.stack_size - threshold,
+            _ => 0,
+        }
+    }
+
+    ///
+    /// Makes all loads and stores of the `pointers` in the current function volatile,
+    /// preventing their promotion to registers.
+    ///
+    fn spill_to_memory<'ctx, D>(
+        context: &mut compiler_llvm_context::Context<'ctx, D>,
+        pointers: &[inkwell::values::BasicValueEnum<'ctx>],
+    ) -> anyhow::Result<()>
+    where
+        D: compiler_llvm_context::Dependency + Clone,
+    {
+        if pointers.is_empty() {
+            return Ok(());
+        }
+
+        let function = context.current_function().borrow().declaration().value;
+        for block in function.get_basic_blocks().into_iter() {
+            let mut instruction = block.get_first_instruction();
+            while let Some(current) = instruction {
+                let pointer_operand_index = match current.get_opcode() {
+                    inkwell::values::InstructionOpcode::Load => Some(0),
+                    inkwell::values::InstructionOpcode::Store => Some(1),
+                    _ => None,
+                };
+                if let Some(index) = pointer_operand_index {
+                    let is_spilled = current
+                        .get_operand(index)
+                        .and_then(|operand| operand.left())
+                        .map(|operand| pointers.contains(&operand))
+                        .unwrap_or_default();
+                    if is_spilled {
+                        current.set_volatile(true).map_err(|error| {
+                            anyhow::anyhow!("Stack slot spilling error: {}", error)
+                        })?;
+                    }
+                }
+                instruction = current.get_next_instruction();
+            }
+        }
+
+        Ok(())
+    }
+
     ///
     /// Finalizes the function data.
     ///
@@ -1094,64 +1162,27 @@ impl Function {
             }
         }
     }
-}
-
-impl<D> compiler_llvm_context::WriteLLVM<D> for Function
-where
-    D: compiler_llvm_context::Dependency + Clone,
-{
-    fn declare(&mut self, context: &mut compiler_llvm_context::Context<D>) -> anyhow::Result<()> {
-        let (function_type, output_size) = match self.r#type {
-            Type::Initial => {
-                let output_size = 0;
-                let r#type = context.function_type(
-                    vec![context
-                        .integer_type(compiler_common::BIT_LENGTH_BOOLEAN)
-                        .as_basic_type_enum()],
-                    output_size,
-                    false,
-                );
-                (r#type, output_size)
-            }
-            Type::Recursive {
-                input_size,
-                output_size,
-                ..
-            } => {
-                let r#type = context.function_type(
-                    vec![
-                        context
-                            .integer_type(compiler_common::BIT_LENGTH_FIELD)
-                            .as_basic_type_enum();
-                        input_size
-                    ],
-                    output_size,
-                    false,
-                );
-                (r#type, output_size)
-            }
-        };
-        let function = context.add_function(
-            self.name.as_str(),
-            function_type,
-            output_size,
-            Some(inkwell::module::Linkage::Private),
-        )?;
-        function
-            .borrow_mut()
-            .set_evmla_data(compiler_llvm_context::FunctionEVMLAData::new(
-                self.stack_size,
-            ));
-
-        Ok(())
-    }
 
-    fn into_llvm(self, context: &mut compiler_llvm_context::Context<D>) -> anyhow::Result<()> {
+    ///
+    /// Translates the function into LLVM IR, attaching the debug locations if `debug_info` is set.
+    ///
+    pub fn into_llvm_with_debug_info<'ctx, D>(
+        self,
+        context: &mut compiler_llvm_context::Context<'ctx, D>,
+        debug_info: Option<&DebugInfo<'ctx>>,
+    ) -> anyhow::Result<()>
+    where
+        D: compiler_llvm_context::Dependency + Clone,
+    {
         context.set_current_function(self.name.as_str())?;
+        if let Some(debug_info) = debug_info {
+            debug_info.set_function(context);
+        }
 
         for (key, blocks) in self.blocks.iter() {
             for (index, block) in blocks.iter().enumerate() {
-                let inner = context.append_basic_block(format!("block_{key}/{index}").as_str());
+                let inner =
+                    context.append_basic_block(BlockMapping::llvm_block_name(key, index).as_str());
                 let mut stack_hashes = vec![block.initial_stack.hash()];
                 stack_hashes.extend_from_slice(block.extra_hashes.as_slice());
                 let evmla_data = compiler_llvm_context::FunctionBlockEVMLAData::new(stack_hashes);
@@ -1166,12 +1197,17 @@ where
         }
 
         context.set_basic_block(context.current_function().borrow().entry_block());
+        let spilled_slots = self.spilled_slots();
+        let mut spilled_pointers = Vec::with_capacity(spilled_slots);
         let mut stack_variables = Vec::with_capacity(self.stack_size);
         for stack_index in 0..self.stack_size {
             let pointer = context.build_alloca(
                 context.field_type(),
                 format!("stack_var_{stack_index:03}").as_str(),
             );
+            if stack_index < spilled_slots {
+                spilled_pointers.push(pointer.value.as_basic_value_enum());
+            }
             let value = match self.r#type {
                 Type::Recursive { input_size, .. }
                     if stack_index >= 1 && stack_index <= input_size =>
@@ -1249,7 +1285,15 @@ where
                 .zip(blocks)
             {
                 context.set_basic_block(llvm_block);
-                ir_block.into_llvm(context)?;
+                match (self.trace.as_ref(), debug_info) {
+                    (Some(trace), debug_info) if trace.is_traced(&key) => {
+                        let mut text = String::with_capacity(4096);
+                        ir_block.into_llvm_traced(context, debug_info, &mut text)?;
+                        trace.append(text.as_str())?;
+                    }
+                    (_, Some(debug_info)) => ir_block.into_llvm_located(context, debug_info)?,
+                    _ => ir_block.into_llvm(context)?,
+                }
             }
         }
 
@@ -1268,8 +1312,65 @@ where
             }
         }
 
+        Self::spill_to_memory(context, spilled_pointers.as_slice())?;
+
+        Ok(())
+    }
+}
+
+impl<D> compiler_llvm_context::WriteLLVM<D> for Function
+where
+    D: compiler_llvm_context::Dependency + Clone,
+{
+    fn declare(&mut self, context: &mut compiler_llvm_context::Context<D>) -> anyhow::Result<()> {
+        let (function_type, output_size) = match self.r#type {
+            Type::Initial => {
+                let output_size = 0;
+                let r#type = context.function_type(
+                    vec![context
+                        .integer_type(compiler_common::BIT_LENGTH_BOOLEAN)
+                        .as_basic_type_enum()],
+                    output_size,
+                    false,
+                );
+                (r#type, output_size)
+            }
+            Type::Recursive {
+                input_size,
+                output_size,
+                ..
+            } => {
+                let r#type = context.function_type(
+                    vec![
+                        context
+                            .integer_type(compiler_common::BIT_LENGTH_FIELD)
+                            .as_basic_type_enum();
+                        input_size
+                    ],
+                    output_size,
+                    false,
+                );
+                (r#type, output_size)
+            }
+        };
+        let function = context.add_function(
+            self.name.as_str(),
+            function_type,
+            output_size,
+            Some(inkwell::module::Linkage::Private),
+        )?;
+        function
+            .borrow_mut()
+            .set_evmla_data(compiler_llvm_context::FunctionEVMLAData::new(
+                self.stack_size,
+            ));
+
         Ok(())
     }
+
+    fn into_llvm(self, context: &mut compiler_llvm_context::Context<D>) -> anyhow::Result<()> {
+        self.into_llvm_with_debug_info(context, None)
+    }
 }
 
 impl std::fmt::Display for Function {
@@ -1287,6 +1388,9 @@ impl std::fmt::Display for Function {
             ),
         }?;
         writeln!(f, "    stack_usage: {}", self.stack_size)?;
+        if self.spilled_slots() > 0 {
+            writeln!(f, "    stack_spilled: {}", self.spilled_slots())?;
+        }
         for (_key, blocks) in self.blocks.iter() {
             for block in blocks.iter() {
                 write!(f, "{block}")?;
@@ -1296,3 +1400,34 @@ impl std::fmt::Display for Function {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::r#type::Type;
+    use super::Function;
+
+    fn function(stack_size: usize, stack_spill_threshold: Option<usize>) -> Function {
+        let mut function = Function::new(semver::Version::new(0, 8, 20), Type::new_initial());
+        function.stack_size = stack_size;
+        function.stack_spill_threshold = stack_spill_threshold;
+        function
+    }
+
+    #[test]
+    fn spilled_slots() {
+        assert_eq!(function(10, Some(4)).spilled_slots(), 6);
+        assert_eq!(function(10, Some(10)).spilled_slots(), 0);
+        assert_eq!(function(10, Some(16)).spilled_slots(), 0);
+        assert_eq!(function(10, None).spilled_slots(), 0);
+    }
+
+    #[test]
+    fn display() {
+        let spilled = function(10, Some(4)).to_string();
+        assert!(spilled.contains("stack_usage: 10"));
+        assert!(spilled.contains("stack_spilled: 6"));
+
+        let unspilled = function(10, None).to_string();
+        assert!(!unspilled.contains("stack_spilled"));
+    }
+}
@@ -2,8 +2,12 @@
 //! The Ethereal IR of the EVM bytecode.
 //!
 
+pub mod block_map;
+pub mod debug_info;
 pub mod entry_link;
 pub mod function;
+pub mod trace;
+pub mod visitor;
 
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
@@ -12,9 +16,12 @@ use std::collections::HashMap;
 use crate::evmla::assembly::instruction::Instruction;
 use crate::solc::standard_json::output::contract::evm::extra_metadata::ExtraMetadata;
 
+use self::block_map::BlockMapping;
+use self::debug_info::DebugInfo;
 use self::function::block::Block;
 use self::function::r#type::Type as FunctionType;
 use self::function::Function;
+use self::trace::Trace;
 
 ///
 /// The Ethereal IR of the EVM bytecode.
@@ -38,6 +45,8 @@ pub struct EtherealIR {
     pub entry_function: Function,
     /// The recursive functions.
     pub recursive_functions: BTreeMap<compiler_llvm_context::FunctionBlockKey, Function>,
+    /// The contract path used as the file name of the debug locations, if they are attached.
+    pub debug_locations: Option<String>,
 }
 
 impl EtherealIR {
@@ -70,9 +79,63 @@ impl EtherealIR {
             extra_metadata,
             entry_function,
             recursive_functions,
+            debug_locations: None,
         })
     }
 
+    ///
+    /// Enables spilling the deepest stack slots to memory in functions with stack size above `threshold`.
+    ///
+    pub fn set_stack_spill_threshold(&mut self, threshold: usize) {
+        self.entry_function.stack_spill_threshold = Some(threshold);
+        for (_key, function) in self.recursive_functions.iter_mut() {
+            function.stack_spill_threshold = Some(threshold);
+        }
+    }
+
+    ///
+    /// Enables attaching the instruction indexes as the LLVM debug locations, using the contract
+    /// `full_path` as the file name.
+    ///
+    pub fn set_debug_locations(&mut self, full_path: &str) {
+        self.debug_locations = Some(full_path.to_owned());
+    }
+
+    ///
+    /// Enables the translation trace of the blocks selected by `trace`.
+    ///
+    pub fn set_trace(&mut self, trace: Trace) {
+        for (_key, function) in self.recursive_functions.iter_mut() {
+            function.trace = Some(trace.clone());
+        }
+        self.entry_function.trace = Some(trace);
+    }
+
+    ///
+    /// Returns the mapping of the block tags to the functions and LLVM basic blocks, ordered
+    /// by the code type and tag.
+    ///
+    pub fn block_map(&self) -> Vec<BlockMapping> {
+        let mut keys = Vec::new();
+        for function in
+            std::iter::once(&self.entry_function).chain(self.recursive_functions.values())
+        {
+            for (key, blocks) in function.blocks.iter() {
+                keys.push((
+                    key,
+                    BlockMapping::new(key, function.name.as_str(), blocks.len()),
+                ));
+            }
+        }
+        keys.sort_by_key(|(key, _mapping)| {
+            (
+                matches!(key.code_type, compiler_llvm_context::CodeType::Runtime),
+                key.tag.clone(),
+            )
+        });
+        keys.into_iter().map(|(_key, mapping)| mapping).collect()
+    }
+
     ///
     /// Gets blocks for the specified type of the contract code.
     ///
@@ -89,6 +152,7 @@ impl EtherealIR {
                 solc_version.clone(),
                 code_type,
                 &instructions[offset..],
+                offset,
             )?;
             blocks.insert(
                 compiler_llvm_context::FunctionBlockKey::new(code_type, block.key.tag.clone()),
@@ -118,10 +182,20 @@ where
     fn into_llvm(self, context: &mut compiler_llvm_context::Context<D>) -> anyhow::Result<()> {
         context.evmla_mut().stack = vec![];
 
-        self.entry_function.into_llvm(context)?;
+        let debug_info = self
+            .debug_locations
+            .as_deref()
+            .map(|full_path| DebugInfo::new(context, full_path));
+
+        self.entry_function
+            .into_llvm_with_debug_info(context, debug_info.as_ref())?;
 
         for (_key, function) in self.recursive_functions.into_iter() {
-            function.into_llvm(context)?;
+            function.into_llvm_with_debug_info(context, debug_info.as_ref())?;
+        }
+
+        if let Some(debug_info) = debug_info {
+            debug_info.finalize();
         }
 
         Ok(())
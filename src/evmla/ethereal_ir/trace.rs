@@ -0,0 +1,57 @@
+//!
+//! The Ethereal IR translation trace.
+//!
+
+use std::io::Write;
+use std::path::PathBuf;
+
+///
+/// The Ethereal IR translation trace.
+///
+/// Logs each instruction of the selected blocks with the simulated stack before and after it,
+/// and the LLVM instructions it has been translated into.
+///
+#[derive(Debug, Clone)]
+pub struct Trace {
+    /// The traced block tag.
+    pub tag: num::BigUint,
+    /// The trace file path.
+    pub path: PathBuf,
+}
+
+impl Trace {
+    ///
+    /// A shortcut constructor.
+    ///
+    /// Creates the trace file, truncating it if it exists.
+    ///
+    pub fn try_new(tag: usize, path: PathBuf) -> anyhow::Result<Self> {
+        std::fs::File::create(path.as_path())
+            .map_err(|error| anyhow::anyhow!("File {:?} creating error: {}", path, error))?;
+
+        Ok(Self {
+            tag: num::BigUint::from(tag),
+            path,
+        })
+    }
+
+    ///
+    /// Whether the block must be traced.
+    ///
+    /// The blocks with the tag are traced in both deploy and runtime code.
+    ///
+    pub fn is_traced(&self, key: &compiler_llvm_context::FunctionBlockKey) -> bool {
+        key.tag == self.tag
+    }
+
+    ///
+    /// Appends the block trace to the file.
+    ///
+    pub fn append(&self, text: &str) -> anyhow::Result<()> {
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(self.path.as_path())
+            .and_then(|mut file| file.write_all(text.as_bytes()))
+            .map_err(|error| anyhow::anyhow!("File {:?} writing error: {}", self.path, error))
+    }
+}
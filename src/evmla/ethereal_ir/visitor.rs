@@ -0,0 +1,154 @@
+//!
+//! The Ethereal IR visitor.
+//!
+
+use crate::evmla::ethereal_ir::function::block::element::Element;
+use crate::evmla::ethereal_ir::function::block::Block;
+use crate::evmla::ethereal_ir::function::Function;
+use crate::evmla::ethereal_ir::EtherealIR;
+
+///
+/// The Ethereal IR visitor.
+///
+/// Lets the analysis tools traverse the Ethereal IR, e.g. from the `Plugin::visit_ethereal_ir`
+/// hook. The entry function is visited first, followed by the recursive ones, and the blocks
+/// of each function are visited in the order of their keys, with all the instances of a block
+/// duplicated for different initial stack states. Each method visits the children of its node
+/// by default, calling the corresponding `walk_*` function.
+///
+pub trait Visitor {
+    ///
+    /// Visits the functions of the `ethereal_ir`.
+    ///
+    fn visit_ethereal_ir(&mut self, ethereal_ir: &EtherealIR) {
+        walk_ethereal_ir(self, ethereal_ir);
+    }
+
+    ///
+    /// Visits the blocks of the `function`.
+    ///
+    fn visit_function(&mut self, function: &Function) {
+        walk_function(self, function);
+    }
+
+    ///
+    /// Visits the elements of the `block`.
+    ///
+    fn visit_block(&mut self, block: &Block) {
+        walk_block(self, block);
+    }
+
+    ///
+    /// Visits the `element`, which is a leaf.
+    ///
+    fn visit_element(&mut self, _element: &Element) {}
+}
+
+///
+/// Visits the entry and recursive functions of the `ethereal_ir`.
+///
+pub fn walk_ethereal_ir<V>(visitor: &mut V, ethereal_ir: &EtherealIR)
+where
+    V: Visitor + ?Sized,
+{
+    visitor.visit_function(&ethereal_ir.entry_function);
+    for function in ethereal_ir.recursive_functions.values() {
+        visitor.visit_function(function);
+    }
+}
+
+///
+/// Visits all the block instances of the `function`.
+///
+pub fn walk_function<V>(visitor: &mut V, function: &Function)
+where
+    V: Visitor + ?Sized,
+{
+    for block in function.blocks.values().flatten() {
+        visitor.visit_block(block);
+    }
+}
+
+///
+/// Visits the elements of the `block`.
+///
+pub fn walk_block<V>(visitor: &mut V, block: &Block)
+where
+    V: Visitor + ?Sized,
+{
+    for element in block.elements.iter() {
+        visitor.visit_element(element);
+    }
+}
+
+///
+/// The Ethereal IR mutable visitor.
+///
+/// Lets the transformations rewrite the Ethereal IR in place, e.g. from the
+/// `Plugin::visit_ethereal_ir` hook, in the same order as `Visitor`. Each method visits the
+/// children of its node by default, calling the corresponding `walk_*_mut` function.
+///
+pub trait VisitorMut {
+    ///
+    /// Visits the functions of the `ethereal_ir`.
+    ///
+    fn visit_ethereal_ir(&mut self, ethereal_ir: &mut EtherealIR) {
+        walk_ethereal_ir_mut(self, ethereal_ir);
+    }
+
+    ///
+    /// Visits the blocks of the `function`.
+    ///
+    fn visit_function(&mut self, function: &mut Function) {
+        walk_function_mut(self, function);
+    }
+
+    ///
+    /// Visits the elements of the `block`.
+    ///
+    fn visit_block(&mut self, block: &mut Block) {
+        walk_block_mut(self, block);
+    }
+
+    ///
+    /// Visits the `element`, which is a leaf.
+    ///
+    fn visit_element(&mut self, _element: &mut Element) {}
+}
+
+///
+/// Visits the entry and recursive functions of the `ethereal_ir`.
+///
+pub fn walk_ethereal_ir_mut<V>(visitor: &mut V, ethereal_ir: &mut EtherealIR)
+where
+    V: VisitorMut + ?Sized,
+{
+    visitor.visit_function(&mut ethereal_ir.entry_function);
+    for function in ethereal_ir.recursive_functions.values_mut() {
+        visitor.visit_function(function);
+    }
+}
+
+///
+/// Visits all the block instances of the `function`.
+///
+pub fn walk_function_mut<V>(visitor: &mut V, function: &mut Function)
+where
+    V: VisitorMut + ?Sized,
+{
+    for block in function.blocks.values_mut().flatten() {
+        visitor.visit_block(block);
+    }
+}
+
+///
+/// Visits the elements of the `block`.
+///
+pub fn walk_block_mut<V>(visitor: &mut V, block: &mut Block)
+where
+    V: VisitorMut + ?Sized,
+{
+    for element in block.elements.iter_mut() {
+        visitor.visit_element(element);
+    }
+}
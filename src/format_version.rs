@@ -0,0 +1,104 @@
+//!
+//! The output artifact format version.
+//!
+
+use std::str::FromStr;
+
+use serde::Serialize;
+
+///
+/// The output artifact format version.
+///
+/// The JSON artifacts are stamped with the version, so their parsers can migrate to newer
+/// formats on their own schedule, using the previous version in the meantime. The unversioned
+/// format stays the default until the parsers opt in to the versioned one.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FormatVersion {
+    /// The unversioned format emitted by older releases.
+    #[default]
+    V1,
+    /// The format with the `formatVersion` field.
+    V2,
+}
+
+impl FormatVersion {
+    /// The artifact field name.
+    pub const FIELD_NAME: &'static str = "formatVersion";
+
+    ///
+    /// Serializes the `artifact` to JSON in the format of this version.
+    ///
+    /// The artifact must be serialized to a JSON object.
+    ///
+    pub fn to_value<T: Serialize>(&self, artifact: &T) -> serde_json::Value {
+        let mut value = serde_json::to_value(artifact).expect("Always valid");
+        match self {
+            Self::V1 => {}
+            Self::V2 => {
+                if let Some(object) = value.as_object_mut() {
+                    object.insert(
+                        Self::FIELD_NAME.to_owned(),
+                        serde_json::Value::from(self.number()),
+                    );
+                }
+            }
+        }
+        value
+    }
+
+    ///
+    /// Returns the version number.
+    ///
+    pub fn number(&self) -> usize {
+        match self {
+            Self::V1 => 1,
+            Self::V2 => 2,
+        }
+    }
+}
+
+impl FromStr for FormatVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "1" => Ok(Self::V1),
+            "2" => Ok(Self::V2),
+            string => anyhow::bail!(
+                "Unknown format version `{}`. Supported versions: `1`, `2`",
+                string
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for FormatVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FormatVersion;
+
+    #[test]
+    fn unversioned_default() {
+        let artifact = serde_json::json!({ "contracts": {} });
+
+        let value = FormatVersion::default().to_value(&artifact);
+        assert_eq!(value, artifact);
+    }
+
+    #[test]
+    fn versioned() {
+        let artifact = serde_json::json!({ "contracts": {} });
+
+        let value = FormatVersion::V2.to_value(&artifact);
+        assert_eq!(
+            value.get(FormatVersion::FIELD_NAME),
+            Some(&serde_json::json!(2))
+        );
+    }
+}
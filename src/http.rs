@@ -0,0 +1,302 @@
+//!
+//! The minimal HTTP client.
+//!
+
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpStream;
+use std::net::ToSocketAddrs;
+use std::time::Duration;
+use std::time::Instant;
+
+///
+/// The HTTP endpoint.
+///
+/// Resources are addressed relatively to the endpoint URL path prefix.
+/// Only plain `http://` endpoints are supported, so TLS must be terminated by a local proxy.
+///
+#[derive(Debug, Clone)]
+pub struct Endpoint {
+    /// The host name, used for the `Host` header.
+    pub host: String,
+    /// The TCP port.
+    pub port: u16,
+    /// The path prefix, without the trailing slash.
+    pub prefix: String,
+    /// The connection timeout, and the deadline for the whole request.
+    pub timeout: Duration,
+}
+
+impl Endpoint {
+    /// The default HTTP port.
+    pub const DEFAULT_PORT: u16 = 80;
+
+    /// The default connection and request timeout.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    ///
+    /// Parses the endpoint from a URL like `http://host[:port][/prefix]`.
+    ///
+    pub fn try_from_url(url: &str) -> anyhow::Result<Self> {
+        let rest = url.strip_prefix("http://").ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid URL `{}`: only `http://` endpoints are supported",
+                url
+            )
+        })?;
+
+        let (authority, prefix) = match rest.find('/') {
+            Some(index) => (&rest[..index], rest[index..].trim_end_matches('/')),
+            None => (rest, ""),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse::<u16>()
+                    .map_err(|error| anyhow::anyhow!("Invalid URL `{}` port: {}", url, error))?,
+            ),
+            None => (authority, Self::DEFAULT_PORT),
+        };
+        if host.is_empty() {
+            anyhow::bail!("Invalid URL `{}`: the host is empty", url);
+        }
+
+        Ok(Self {
+            host: host.to_owned(),
+            port,
+            prefix: prefix.to_owned(),
+            timeout: Self::DEFAULT_TIMEOUT,
+        })
+    }
+
+    ///
+    /// Uploads the resource at the `path` relative to the endpoint.
+    ///
+    pub fn put(&self, path: &str, body: &[u8]) -> anyhow::Result<()> {
+        let (status, _body) = self.request("PUT", path, body)?;
+        if !(200..300).contains(&status) {
+            anyhow::bail!(
+                "HTTP endpoint `{}` resource `{}` uploading error: HTTP status {}",
+                self,
+                path,
+                status
+            );
+        }
+        Ok(())
+    }
+
+    ///
+    /// Downloads the resource at the `path` relative to the endpoint, returning `None` if it is not there.
+    ///
+    /// The empty `path` addresses the endpoint URL itself.
+    ///
+    pub fn get(&self, path: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let (status, body) = self.request("GET", path, &[])?;
+        match status {
+            200 => Ok(Some(body)),
+            404 => Ok(None),
+            status => anyhow::bail!(
+                "HTTP endpoint `{}` resource `{}` downloading error: HTTP status {}",
+                self,
+                path,
+                status
+            ),
+        }
+    }
+
+    ///
+    /// Sends the `body` to the resource at the `path` relative to the endpoint, returning the
+    /// response status code and body.
+    ///
+    pub fn post(&self, path: &str, body: &[u8]) -> anyhow::Result<(u16, Vec<u8>)> {
+        self.request("POST", path, body)
+    }
+
+    ///
+    /// Sends an HTTP/1.1 request and returns the response status code and body.
+    ///
+    fn request(&self, method: &str, path: &str, body: &[u8]) -> anyhow::Result<(u16, Vec<u8>)> {
+        let deadline = Instant::now() + self.timeout;
+        let mut stream = self.connect()?;
+
+        let target = match (self.prefix.as_str(), path) {
+            ("", "") => "/".to_owned(),
+            (prefix, "") => prefix.to_owned(),
+            (prefix, path) => format!("{prefix}/{path}"),
+        };
+        let head = format!(
+            "{method} {target} HTTP/1.1\r\nHost: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.host,
+            body.len(),
+        );
+        stream
+            .write_all(head.as_bytes())
+            .and_then(|()| stream.write_all(body))
+            .map_err(|error| {
+                anyhow::anyhow!("HTTP endpoint `{}` writing error: {}", self, error)
+            })?;
+
+        let mut response = Vec::with_capacity(body.len().max(4096));
+        let mut buffer = [0u8; 4096];
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                anyhow::bail!(
+                    "HTTP endpoint `{}` reading error: timed out after {:?}",
+                    self,
+                    self.timeout
+                );
+            }
+            stream.set_read_timeout(Some(remaining)).map_err(|error| {
+                anyhow::anyhow!("HTTP endpoint `{}` reading error: {}", self, error)
+            })?;
+            match stream.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(size) => response.extend_from_slice(&buffer[..size]),
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    anyhow::bail!("HTTP endpoint `{}` reading error: {}", self, error)
+                }
+            }
+        }
+
+        let head_end = response
+            .windows(4)
+            .position(|window| window == b"\r\n\r\n")
+            .ok_or_else(|| anyhow::anyhow!("HTTP endpoint `{}` response is malformed", self))?;
+        let head = String::from_utf8_lossy(&response[..head_end]);
+        if head
+            .lines()
+            .any(|line| line.eq_ignore_ascii_case("transfer-encoding: chunked"))
+        {
+            anyhow::bail!(
+                "HTTP endpoint `{}` chunked responses are not supported",
+                self
+            );
+        }
+        let status = head
+            .lines()
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|status| status.parse::<u16>().ok())
+            .ok_or_else(|| {
+                anyhow::anyhow!("HTTP endpoint `{}` response status is malformed", self)
+            })?;
+
+        Ok((status, response[head_end + 4..].to_vec()))
+    }
+
+    ///
+    /// Connects to the first reachable address of the endpoint host within the timeout.
+    ///
+    fn connect(&self) -> anyhow::Result<TcpStream> {
+        let addresses = (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .map_err(|error| {
+                anyhow::anyhow!("HTTP endpoint `{}` resolution error: {}", self, error)
+            })?;
+
+        let mut last_error = None;
+        for address in addresses {
+            match TcpStream::connect_timeout(&address, self.timeout) {
+                Ok(stream) => {
+                    stream
+                        .set_write_timeout(Some(self.timeout))
+                        .map_err(|error| {
+                            anyhow::anyhow!("HTTP endpoint `{}` connection error: {}", self, error)
+                        })?;
+                    return Ok(stream);
+                }
+                Err(error) => last_error = Some(error),
+            }
+        }
+        match last_error {
+            Some(error) => anyhow::bail!("HTTP endpoint `{}` connection error: {}", self, error),
+            None => anyhow::bail!("HTTP endpoint `{}` host has no addresses", self),
+        }
+    }
+}
+
+impl std::fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "http://{}:{}{}", self.host, self.port, self.prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    use super::Endpoint;
+
+    ///
+    /// Serves a single connection with the `response` after an optional `delay`, returning the
+    /// endpoint pointing to the mock server.
+    ///
+    fn serve(response: &'static [u8], delay: Option<Duration>) -> Endpoint {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Always valid");
+        let port = listener.local_addr().expect("Always valid").port();
+        std::thread::spawn(move || {
+            let (mut stream, _address) = listener.accept().expect("Always valid");
+            let mut request = Vec::new();
+            let mut buffer = [0u8; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                match stream.read(&mut buffer) {
+                    Ok(0) | Err(_) => return,
+                    Ok(size) => request.extend_from_slice(&buffer[..size]),
+                }
+            }
+            if let Some(delay) = delay {
+                std::thread::sleep(delay);
+            }
+            let _ = stream.write_all(response);
+        });
+
+        let mut endpoint =
+            Endpoint::try_from_url(format!("http://127.0.0.1:{port}/prefix").as_str())
+                .expect("Always valid");
+        endpoint.timeout = Duration::from_secs(5);
+        endpoint
+    }
+
+    #[test]
+    fn url() {
+        let endpoint =
+            Endpoint::try_from_url("http://cache.local:8081/zksolc/").expect("Always valid");
+        assert_eq!(endpoint.host, "cache.local");
+        assert_eq!(endpoint.port, 8081);
+        assert_eq!(endpoint.prefix, "/zksolc");
+
+        assert!(Endpoint::try_from_url("https://cache.local").is_err());
+        assert!(Endpoint::try_from_url("http://:80").is_err());
+    }
+
+    #[test]
+    fn get() {
+        let endpoint = serve(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello", None);
+        assert_eq!(
+            endpoint.get("resource").expect("Always valid"),
+            Some(b"hello".to_vec())
+        );
+    }
+
+    #[test]
+    fn get_not_found() {
+        let endpoint = serve(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n", None);
+        assert_eq!(endpoint.get("resource").expect("Always valid"), None);
+    }
+
+    #[test]
+    fn timeout() {
+        let mut endpoint = serve(
+            b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n",
+            Some(Duration::from_secs(2)),
+        );
+        endpoint.timeout = Duration::from_millis(200);
+        let error = endpoint.get("resource").expect_err("Must time out");
+        assert!(error.to_string().contains("reading error"), "{error}");
+    }
+}
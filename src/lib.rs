@@ -2,56 +2,162 @@
 //! Solidity to zkEVM compiler library.
 //!
 
+pub(crate) mod address;
 pub(crate) mod build;
+pub(crate) mod cache;
+pub(crate) mod capabilities;
 pub(crate) mod r#const;
+pub(crate) mod daemon;
+pub(crate) mod debug;
 pub(crate) mod evmla;
+pub(crate) mod format_version;
+pub(crate) mod http;
+pub(crate) mod lockfile;
+pub(crate) mod output_layout;
+pub(crate) mod plugin;
+pub(crate) mod prevrandao;
 pub(crate) mod process;
 pub(crate) mod project;
+pub(crate) mod scaffold;
+pub(crate) mod server;
 pub(crate) mod solc;
+pub(crate) mod target;
+pub(crate) mod workspace;
 pub(crate) mod yul;
 
+pub use self::build::archive::Archive as BuildArchive;
+pub use self::build::calldata::Calldata as BuildCalldata;
 pub use self::build::contract::Contract as ContractBuild;
+pub use self::build::manifest::Manifest as BuildManifest;
+pub use self::build::metadata_hash_placement::MetadataHashPlacement;
+pub use self::build::recommendation::Recommendation as OptimizationRecommendation;
+pub use self::build::source_bundle::SourceBundle as BuildSourceBundle;
+pub use self::build::store::Store as BuildStore;
+pub use self::build::verify_bundle::VerifyBundle as BuildVerifyBundle;
 pub use self::build::Build;
+pub use self::cache::Cache as OutputCache;
+pub use self::capabilities::Capabilities;
+pub use self::daemon::request::Request as DaemonRequest;
+pub use self::daemon::Daemon;
+pub use self::debug::filter::Filter as DebugFilter;
+pub use self::evmla::assembly::instruction::name::Name as EVMLAInstructionName;
+pub use self::evmla::assembly::instruction::support::Support as EVMLAInstructionSupport;
+pub use self::evmla::assembly::instruction::Instruction as EVMLAInstruction;
+pub use self::evmla::ethereal_ir::function::block::element::Element as EtherealIRElement;
+pub use self::evmla::ethereal_ir::function::block::Block as EtherealIRBlock;
+pub use self::evmla::ethereal_ir::function::Function as EtherealIRFunction;
+pub use self::evmla::ethereal_ir::visitor::walk_block as walk_ethereal_ir_block;
+pub use self::evmla::ethereal_ir::visitor::walk_block_mut as walk_ethereal_ir_block_mut;
+pub use self::evmla::ethereal_ir::visitor::walk_ethereal_ir;
+pub use self::evmla::ethereal_ir::visitor::walk_ethereal_ir_mut;
+pub use self::evmla::ethereal_ir::visitor::walk_function as walk_ethereal_ir_function;
+pub use self::evmla::ethereal_ir::visitor::walk_function_mut as walk_ethereal_ir_function_mut;
+pub use self::evmla::ethereal_ir::visitor::Visitor as EtherealIRVisitor;
+pub use self::evmla::ethereal_ir::visitor::VisitorMut as EtherealIRVisitorMut;
+pub use self::evmla::ethereal_ir::EtherealIR;
+pub use self::format_version::FormatVersion;
+pub use self::lockfile::Lockfile;
+pub use self::output_layout::OutputLayout;
+pub use self::plugin::reentrancy_guard::ReentrancyGuard;
+#[cfg(feature = "plugins")]
+pub use self::plugin::register as register_plugin;
+pub use self::plugin::Plugin;
 pub use self::process::input::Input as ProcessInput;
 pub use self::process::output::Output as ProcessOutput;
 pub use self::process::run as run_process;
+pub use self::process::run_persistent as run_process_persistent;
 pub use self::process::EXECUTABLE;
+pub use self::project::contract::ir::IR as ProjectContractIR;
 pub use self::project::contract::Contract as ProjectContract;
+pub use self::project::mutation::Specification as MutationSpecification;
 pub use self::project::Project;
 pub use self::r#const::*;
+pub use self::scaffold::Scaffold;
+pub use self::server::limits::Limits as ServerLimits;
+pub use self::server::Server;
 pub use self::solc::combined_json::contract::Contract as SolcCombinedJsonContract;
 pub use self::solc::combined_json::CombinedJson as SolcCombinedJson;
 pub use self::solc::pipeline::Pipeline as SolcPipeline;
+pub use self::solc::standard_json::input::builder::Builder as SolcStandardJsonInputBuilder;
 pub use self::solc::standard_json::input::language::Language as SolcStandardJsonInputLanguage;
+pub use self::solc::standard_json::input::resolver::Resolver as SolcStandardJsonInputResolver;
+pub use self::solc::standard_json::input::resolver::Resolvers as SolcStandardJsonInputResolvers;
+pub use self::solc::standard_json::input::settings::debug::revert_strings::RevertStrings as SolcStandardJsonInputSettingsDebugRevertStrings;
+pub use self::solc::standard_json::input::settings::debug::Debug as SolcStandardJsonInputSettingsDebug;
 pub use self::solc::standard_json::input::settings::metadata::Metadata as SolcStandardJsonInputSettingsMetadata;
 pub use self::solc::standard_json::input::settings::optimizer::Optimizer as SolcStandardJsonInputSettingsOptimizer;
+pub use self::solc::standard_json::input::settings::sandbox::Sandbox as SolcStandardJsonInputSettingsSandbox;
 pub use self::solc::standard_json::input::settings::selection::file::flag::Flag as SolcStandardJsonInputSettingsSelectionFileFlag;
 pub use self::solc::standard_json::input::settings::selection::file::File as SolcStandardJsonInputSettingsSelectionFile;
 pub use self::solc::standard_json::input::settings::selection::Selection as SolcStandardJsonInputSettingsSelection;
+pub use self::solc::standard_json::input::settings::severity_override::SeverityOverride as SolcStandardJsonInputSettingsSeverityOverride;
+pub use self::solc::standard_json::input::settings::warnings_as_errors::WarningsAsErrors as SolcStandardJsonInputSettingsWarningsAsErrors;
 pub use self::solc::standard_json::input::settings::Settings as SolcStandardJsonInputSettings;
 pub use self::solc::standard_json::input::source::Source as SolcStandardJsonInputSource;
 pub use self::solc::standard_json::input::Input as SolcStandardJsonInput;
 pub use self::solc::standard_json::output::contract::evm::bytecode::Bytecode as SolcStandardJsonOutputContractEVMBytecode;
 pub use self::solc::standard_json::output::contract::evm::EVM as SolcStandardJsonOutputContractEVM;
 pub use self::solc::standard_json::output::contract::Contract as SolcStandardJsonOutputContract;
+pub use self::solc::standard_json::output::error::format::Format as ErrorFormat;
+pub use self::solc::standard_json::output::error::render::Renderer as ErrorRenderer;
+pub use self::solc::standard_json::output::error::source_location::SourceLocation as SolcStandardJsonOutputErrorSourceLocation;
+pub use self::solc::standard_json::output::error::Error as SolcStandardJsonOutputError;
+pub use self::solc::standard_json::output::listing::Listing as SolcStandardJsonOutputListing;
 pub use self::solc::standard_json::output::Output as SolcStandardJsonOutput;
 pub use self::solc::version::Version as SolcVersion;
 pub use self::solc::Compiler as SolcCompiler;
+pub use self::target::Target;
+pub use self::workspace::Workspace;
+pub use self::yul::entry_objects::EntryObjects as YulEntryObjects;
+pub use self::yul::parser::statement::block::Block as YulBlock;
+pub use self::yul::parser::statement::expression::function_call::name::Name as YulFunctionName;
+pub use self::yul::parser::statement::expression::function_call::FunctionCall as YulFunctionCall;
+pub use self::yul::parser::statement::expression::Expression as YulExpression;
+pub use self::yul::parser::statement::function_definition::FunctionDefinition as YulFunctionDefinition;
+pub use self::yul::parser::statement::object::Object as YulObject;
+pub use self::yul::parser::statement::Statement as YulStatement;
+pub use self::yul::visitor::walk_block as walk_yul_block;
+pub use self::yul::visitor::walk_block_mut as walk_yul_block_mut;
+pub use self::yul::visitor::walk_expression as walk_yul_expression;
+pub use self::yul::visitor::walk_expression_mut as walk_yul_expression_mut;
+pub use self::yul::visitor::walk_function_call as walk_yul_function_call;
+pub use self::yul::visitor::walk_function_call_mut as walk_yul_function_call_mut;
+pub use self::yul::visitor::walk_function_definition as walk_yul_function_definition;
+pub use self::yul::visitor::walk_function_definition_mut as walk_yul_function_definition_mut;
+pub use self::yul::visitor::walk_object as walk_yul_object;
+pub use self::yul::visitor::walk_object_mut as walk_yul_object_mut;
+pub use self::yul::visitor::walk_statement as walk_yul_statement;
+pub use self::yul::visitor::walk_statement_mut as walk_yul_statement_mut;
+pub use self::yul::visitor::Visitor as YulVisitor;
+pub use self::yul::visitor::VisitorMut as YulVisitorMut;
 
 mod tests;
 
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
 ///
 /// Runs the Yul mode.
 ///
+#[allow(clippy::too_many_arguments)]
 pub fn yul(
     input_files: &[PathBuf],
     solc: &mut SolcCompiler,
     optimizer_settings: compiler_llvm_context::OptimizerSettings,
+    target: Target,
     is_system_mode: bool,
     include_metadata_hash: bool,
+    determinism_check: bool,
+    output_histogram: bool,
+    entry_objects: YulEntryObjects,
     debug_config: Option<compiler_llvm_context::DebugConfig>,
+    debug_filter: DebugFilter,
 ) -> anyhow::Result<Build> {
     let path = match input_files.len() {
         1 => input_files.first().expect("Always exists"),
@@ -75,13 +181,18 @@ pub fn yul(
         Some(&*solc)
     };
 
-    let project = Project::try_from_yul_path(path, solc_validator)?;
+    let mut project = Project::try_from_yul_path(path, &entry_objects, solc_validator)?;
+    project.output_histogram = output_histogram;
+    project.debug_filter = debug_filter;
 
     let build = project.compile(
         optimizer_settings,
+        target,
         is_system_mode,
         include_metadata_hash,
+        determinism_check,
         zkevm_assembly::RunningVmEncodingMode::Production,
+        None,
         debug_config,
     )?;
 
@@ -91,12 +202,17 @@ pub fn yul(
 ///
 /// Runs the LLVM IR mode.
 ///
+#[allow(clippy::too_many_arguments)]
 pub fn llvm_ir(
     input_files: &[PathBuf],
     optimizer_settings: compiler_llvm_context::OptimizerSettings,
+    target: Target,
     is_system_mode: bool,
     include_metadata_hash: bool,
+    determinism_check: bool,
+    output_histogram: bool,
     debug_config: Option<compiler_llvm_context::DebugConfig>,
+    debug_filter: DebugFilter,
 ) -> anyhow::Result<Build> {
     let path = match input_files.len() {
         1 => input_files.first().expect("Always exists"),
@@ -107,13 +223,18 @@ pub fn llvm_ir(
         ),
     };
 
-    let project = Project::try_from_llvm_ir_path(path)?;
+    let mut project = Project::try_from_llvm_ir_path(path)?;
+    project.output_histogram = output_histogram;
+    project.debug_filter = debug_filter;
 
     let build = project.compile(
         optimizer_settings,
+        target,
         is_system_mode,
         include_metadata_hash,
+        determinism_check,
         zkevm_assembly::RunningVmEncodingMode::Production,
+        None,
         debug_config,
     )?;
 
@@ -125,8 +246,12 @@ pub fn llvm_ir(
 ///
 pub fn zkasm(
     input_files: &[PathBuf],
+    target: Target,
     include_metadata_hash: bool,
+    determinism_check: bool,
+    output_histogram: bool,
     debug_config: Option<compiler_llvm_context::DebugConfig>,
+    debug_filter: DebugFilter,
 ) -> anyhow::Result<Build> {
     let path = match input_files.len() {
         1 => input_files.first().expect("Always exists"),
@@ -137,14 +262,19 @@ pub fn zkasm(
         ),
     };
 
-    let project = Project::try_from_zkasm_path(path)?;
+    let mut project = Project::try_from_zkasm_path(path)?;
+    project.output_histogram = output_histogram;
+    project.debug_filter = debug_filter;
 
     let optimizer_settings = compiler_llvm_context::OptimizerSettings::none();
     let build = project.compile(
         optimizer_settings,
+        target,
         false,
         include_metadata_hash,
+        determinism_check,
         zkevm_assembly::RunningVmEncodingMode::Production,
+        None,
         debug_config,
     )?;
 
@@ -161,32 +291,73 @@ pub fn standard_output(
     solc: &mut SolcCompiler,
     solc_optimizer_enabled: bool,
     optimizer_settings: compiler_llvm_context::OptimizerSettings,
+    target: Target,
     force_evmla: bool,
     is_system_mode: bool,
     include_metadata_hash: bool,
+    metadata_hash_placement: MetadataHashPlacement,
+    determinism_check: bool,
+    output_evm_bytecode: bool,
+    output_yul: bool,
+    output_histogram: bool,
+    normalize_sources: bool,
+    evmla_stack_spill_threshold: Option<usize>,
+    evmla_trace_tag: Option<usize>,
+    evmla_debug_locations: bool,
+    reentrancy_guard: Vec<String>,
+    order_by_selector: bool,
+    contract_time_budget: Option<Duration>,
+    strict: bool,
+    erc_checks: bool,
+    mutation_specification: Option<MutationSpecification>,
+    warnings_as_errors: Option<SolcStandardJsonInputSettingsWarningsAsErrors>,
+    error_format: ErrorFormat,
+    recommend_optimization: bool,
     base_path: Option<String>,
     include_paths: Vec<String>,
     allow_paths: Option<String>,
+    artifact_store: Option<&BuildStore>,
     debug_config: Option<compiler_llvm_context::DebugConfig>,
+    debug_filter: DebugFilter,
 ) -> anyhow::Result<Build> {
     let solc_version = solc.version()?;
     let solc_pipeline = SolcPipeline::new(&solc_version, force_evmla);
+    if output_yul && solc_pipeline == SolcPipeline::EVMLA {
+        anyhow::bail!("Yul output is only available in the Yul pipeline, which requires solc >=0.8.13 and no `--force-evmla`.");
+    }
+    if mutation_specification.is_some() && solc_pipeline == SolcPipeline::Yul {
+        anyhow::bail!("Mutation testing is only supported in the EVM legacy assembly pipeline. Use `--force-evmla`.");
+    }
 
-    let solc_input = SolcStandardJsonInput::try_from_paths(
+    let mut output_selection = SolcStandardJsonInputSettingsSelection::new_required(solc_pipeline);
+    if output_evm_bytecode {
+        output_selection.extend_with_evm_bytecode();
+    }
+
+    let mut solc_input = SolcStandardJsonInput::try_from_paths(
         SolcStandardJsonInputLanguage::Solidity,
         input_files,
         libraries,
-        SolcStandardJsonInputSettingsSelection::new_required(solc_pipeline),
+        output_selection,
         SolcStandardJsonInputSettingsOptimizer::new(solc_optimizer_enabled, None),
         None,
         solc_pipeline == SolcPipeline::Yul,
     )?;
+    let unnormalized_sources = solc_input.normalize_sources(normalize_sources);
 
     let source_code_files = solc_input
         .sources
         .iter()
-        .map(|(path, source)| (path.to_owned(), source.content.to_owned()))
+        .map(|(path, source)| {
+            (
+                path.to_owned(),
+                source.content.clone().unwrap_or_else(|| Arc::from("")),
+            )
+        })
         .collect();
+    let source_hashes = solc_input.source_hashes();
+    let error_sources = source_code_files.clone();
+    let error_renderer = ErrorRenderer::new(&error_sources, error_format);
 
     let libraries = solc_input.settings.libraries.clone().unwrap_or_default();
     let mut solc_output = solc.standard_json(
@@ -197,7 +368,30 @@ pub fn standard_output(
         allow_paths,
     )?;
 
-    if let Some(errors) = solc_output.errors.as_deref() {
+    if strict {
+        solc_output.check_strict();
+    }
+    if erc_checks {
+        solc_output.check_erc();
+    }
+    for path in unnormalized_sources.into_iter() {
+        solc_output.errors.get_or_insert_with(Vec::new).push(
+            SolcStandardJsonOutputError::message_unnormalized_source(path.as_str()),
+        );
+    }
+    if SolcCompiler::is_unsupported(&solc_version.default) {
+        solc_output.errors.get_or_insert_with(Vec::new).push(
+            SolcStandardJsonOutputError::message_unsupported_solc_version(
+                &solc_version.default,
+                &SolcCompiler::LAST_SUPPORTED_VERSION,
+            ),
+        );
+    }
+    if let Some(errors) = solc_output.errors.as_deref_mut() {
+        if let Some(warnings_as_errors) = warnings_as_errors.as_ref() {
+            warnings_as_errors.apply(errors);
+        }
+
         let mut has_errors = false;
 
         for error in errors.iter() {
@@ -205,7 +399,7 @@ pub fn standard_output(
                 has_errors = true;
             }
 
-            eprintln!("{error}");
+            eprintln!("{}", error_renderer.render(error));
         }
 
         if has_errors {
@@ -213,47 +407,477 @@ pub fn standard_output(
         }
     }
 
-    let project = solc_output.try_to_project(
+    let mut project = solc_output.try_to_project(
         source_code_files,
         libraries,
         solc_pipeline,
         &solc_version.default,
         debug_config.as_ref(),
+        &debug_filter,
     )?;
+    project.evmla_stack_spill_threshold = evmla_stack_spill_threshold;
+    project.evmla_trace_tag = evmla_trace_tag;
+    project.evmla_debug_locations = evmla_debug_locations;
+    project.reentrancy_guard = reentrancy_guard;
+    project.order_by_selector = order_by_selector;
+    project.output_histogram = output_histogram;
+    project.debug_filter = debug_filter;
+    project.metadata_hash_placement = metadata_hash_placement;
+    project.contract_time_budget = contract_time_budget;
 
-    let build = project.compile(
-        optimizer_settings,
+    let evm_bytecode = if output_evm_bytecode {
+        solc_output.get_evm_bytecode()
+    } else {
+        BTreeMap::new()
+    };
+    let yul = if output_yul {
+        project.get_yul()
+    } else {
+        BTreeMap::new()
+    };
+    let mutation_project = mutation_specification.as_ref().map(|_| project.clone());
+    let recommendation_project = if recommend_optimization {
+        Some(project.clone())
+    } else {
+        None
+    };
+
+    let mut build = project.compile(
+        optimizer_settings.clone(),
+        target,
         is_system_mode,
         include_metadata_hash,
+        determinism_check,
         zkevm_assembly::RunningVmEncodingMode::Production,
+        artifact_store,
         debug_config,
     )?;
 
+    for (path, evm_bytecode) in evm_bytecode.into_iter() {
+        if let Some(contract) = build.contracts.get_mut(path.as_str()) {
+            contract.evm_bytecode = Some(evm_bytecode);
+        }
+    }
+    for (path, yul) in yul.into_iter() {
+        if let Some(contract) = build.contracts.get_mut(path.as_str()) {
+            contract.yul = Some(yul);
+        }
+    }
+    build.compiled_sources = source_hashes
+        .iter()
+        .map(|(path, hash)| (path.to_owned(), hash.keccak256.to_owned()))
+        .collect();
+    build
+        .compiled_sources
+        .extend(solc_output.get_source_keccak256_hashes());
+    build.source_hashes = source_hashes;
+    build.source_names = solc_output
+        .sources
+        .as_ref()
+        .map(|sources| sources.keys().cloned().collect())
+        .unwrap_or_default();
+    build.source_code = error_sources.clone();
+
+    let mut messages: Vec<SolcStandardJsonOutputError> = build
+        .invalidated
+        .iter()
+        .map(|(path, changed_sources)| {
+            SolcStandardJsonOutputError::message_recompiled(
+                path.as_str(),
+                changed_sources.as_slice(),
+            )
+        })
+        .collect();
+    if let (Some(specification), Some(mutation_project)) =
+        (mutation_specification, mutation_project)
+    {
+        let source_ids: BTreeMap<String, usize> = solc_output
+            .sources
+            .as_ref()
+            .map(|sources| {
+                sources
+                    .iter()
+                    .map(|(path, source)| (path.to_owned(), source.id))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        for mutant in specification.mutants.into_iter() {
+            let mut project = mutation_project.clone();
+            let mutated = mutant.apply(&mut project, &source_ids)?;
+            if mutated.is_empty() {
+                messages.push(SolcStandardJsonOutputError::message_mutant_not_applied(
+                    mutant.name.as_str(),
+                ));
+                continue;
+            }
+
+            let mutant_build = project.compile_mutant(
+                &build,
+                &mutated.into_keys().collect(),
+                optimizer_settings.clone(),
+                target,
+                is_system_mode,
+                include_metadata_hash,
+            )?;
+            build.mutants.insert(mutant.name, mutant_build);
+        }
+    }
+
+    if let Some(recommendation_project) = recommendation_project {
+        let mut sizes: BTreeMap<String, BTreeMap<char, usize>> = BTreeMap::new();
+        for mode in OptimizationRecommendation::MODES.into_iter() {
+            let mode_optimizer_settings =
+                compiler_llvm_context::OptimizerSettings::try_from_cli(mode)?;
+            if mode_optimizer_settings.to_string() == optimizer_settings.to_string() {
+                for (path, contract) in build.contracts.iter() {
+                    sizes
+                        .entry(path.to_owned())
+                        .or_default()
+                        .insert(mode, contract.build.bytecode.len());
+                }
+                continue;
+            }
+
+            let mode_build = recommendation_project.clone().compile(
+                mode_optimizer_settings,
+                target,
+                is_system_mode,
+                include_metadata_hash,
+                false,
+                zkevm_assembly::RunningVmEncodingMode::Production,
+                None,
+                None,
+            )?;
+            for (path, contract) in mode_build.contracts.into_iter() {
+                sizes
+                    .entry(path)
+                    .or_default()
+                    .insert(mode, contract.build.bytecode.len());
+            }
+        }
+        for (path, sizes) in sizes.into_iter() {
+            eprintln!("{}", OptimizationRecommendation::new(path, sizes));
+        }
+    }
+
+    for (hash, paths) in build.duplicates().into_iter() {
+        let contracts: Vec<(String, Option<SolcStandardJsonOutputErrorSourceLocation>)> = paths
+            .into_iter()
+            .map(|path| {
+                let location = solc_output.contract_location(path.as_str());
+                (path, location)
+            })
+            .collect();
+        messages.push(SolcStandardJsonOutputError::message_duplicate_bytecode(
+            hash.as_str(),
+            contracts.as_slice(),
+        ));
+    }
+    let is_promoted = warnings_as_errors
+        .map(|warnings_as_errors| warnings_as_errors.apply(messages.as_mut_slice()))
+        .unwrap_or_default();
+    for message in messages.iter() {
+        eprintln!("{}", error_renderer.render(message));
+    }
+    if is_promoted {
+        anyhow::bail!("Error(s) found. Compilation aborted");
+    }
+
     Ok(build)
 }
 
 ///
 /// Runs the standard JSON mode.
 ///
+/// If `use_daemon` is set, the inputs with all the sources inline are delegated to the running
+/// compile daemon, if any.
+///
+/// If `verify_bundle_directory` is set, the verification bundle of each compiled contract is
+/// written into it, so the build can be reproduced with `zksolc replay`.
+///
 #[allow(clippy::too_many_arguments)]
 pub fn standard_json(
     solc: &mut SolcCompiler,
     force_evmla: bool,
     is_system_mode: bool,
+    determinism_check: bool,
+    allow_network: bool,
+    ipfs_gateway: Option<String>,
     base_path: Option<String>,
     include_paths: Vec<String>,
     allow_paths: Option<String>,
+    hermetic: bool,
+    use_daemon: bool,
     debug_config: Option<compiler_llvm_context::DebugConfig>,
+    debug_filter: DebugFilter,
+    verify_bundle_directory: Option<PathBuf>,
+    format_version: FormatVersion,
+) -> anyhow::Result<()> {
+    let mut input = Vec::new();
+    std::io::stdin()
+        .read_to_end(&mut input)
+        .map_err(|error| anyhow::anyhow!("Standard input reading error: {}", error))?;
+
+    if use_daemon && !hermetic && debug_config.is_none() {
+        let input: serde_json::Value = serde_json::from_slice(input.as_slice())?;
+        if DaemonRequest::is_delegable(&input) {
+            let request = DaemonRequest {
+                solc: solc.executable.clone(),
+                force_evmla,
+                is_system_mode,
+                determinism_check,
+                input,
+            };
+            if let Some(solc_output) = Daemon::delegate(&request)? {
+                if let Some(directory) = verify_bundle_directory {
+                    write_verify_bundles(
+                        &request.input,
+                        &solc_output,
+                        solc,
+                        force_evmla,
+                        is_system_mode,
+                        directory.as_path(),
+                    )?;
+                }
+                serde_json::to_writer(std::io::stdout(), &format_version.to_value(&solc_output))?;
+                std::process::exit(0);
+            }
+        }
+    }
+
+    let solc_output = standard_json_output(
+        input.as_slice(),
+        solc,
+        force_evmla,
+        is_system_mode,
+        determinism_check,
+        allow_network,
+        ipfs_gateway,
+        base_path,
+        include_paths,
+        allow_paths,
+        hermetic,
+        debug_config,
+        debug_filter,
+    )?;
+    if let Some(directory) = verify_bundle_directory {
+        write_verify_bundles(
+            &serde_json::from_slice(input.as_slice())?,
+            &serde_json::to_value(&solc_output).expect("Always valid"),
+            solc,
+            force_evmla,
+            is_system_mode,
+            directory.as_path(),
+        )?;
+    }
+    serde_json::to_writer(std::io::stdout(), &format_version.to_value(&solc_output))?;
+    std::process::exit(0);
+}
+
+///
+/// Writes the verification bundles of the contracts compiled from the standard JSON `input`
+/// into `directory`.
+///
+fn write_verify_bundles(
+    input: &serde_json::Value,
+    output: &serde_json::Value,
+    solc: &mut SolcCompiler,
+    force_evmla: bool,
+    is_system_mode: bool,
+    directory: &Path,
 ) -> anyhow::Result<()> {
+    let solc_version = solc.version()?.default;
+    for bundle in
+        BuildVerifyBundle::new_all(input, output, &solc_version, force_evmla, is_system_mode)?
+            .into_iter()
+    {
+        bundle.write(directory)?;
+    }
+    Ok(())
+}
+
+///
+/// Runs the standard JSON batch mode.
+///
+/// Reads the standard JSON inputs from `stdin` line by line, and writes an output line for
+/// each of them to `stdout`, until `stdin` is closed.
+///
+#[allow(clippy::too_many_arguments)]
+pub fn standard_json_batch(
+    solc: &mut SolcCompiler,
+    force_evmla: bool,
+    is_system_mode: bool,
+    determinism_check: bool,
+    allow_network: bool,
+    ipfs_gateway: Option<String>,
+    base_path: Option<String>,
+    include_paths: Vec<String>,
+    allow_paths: Option<String>,
+    hermetic: bool,
+    format_version: FormatVersion,
+) -> anyhow::Result<()> {
+    standard_json_batch_output(
+        std::io::stdin().lock(),
+        std::io::stdout(),
+        solc,
+        force_evmla,
+        is_system_mode,
+        determinism_check,
+        allow_network,
+        ipfs_gateway,
+        base_path,
+        include_paths,
+        allow_paths,
+        hermetic,
+        format_version,
+    )
+}
+
+///
+/// Compiles the standard JSON inputs read from `reader` line by line, and writes an output line
+/// for each of them to `writer`, until `reader` is exhausted.
+///
+/// The `solc` version, the worker processes with the initialized LLVM target, and the outputs
+/// of the self-contained inputs are reused across the inputs. `solc` itself has no persistent
+/// mode, so it is still run once per input. The errors of the compiler itself are reported in
+/// the output of the failed input, so the others are still compiled.
+///
+#[allow(clippy::too_many_arguments)]
+pub fn standard_json_batch_output<R, W>(
+    mut reader: R,
+    mut writer: W,
+    solc: &mut SolcCompiler,
+    force_evmla: bool,
+    is_system_mode: bool,
+    determinism_check: bool,
+    allow_network: bool,
+    ipfs_gateway: Option<String>,
+    base_path: Option<String>,
+    include_paths: Vec<String>,
+    allow_paths: Option<String>,
+    hermetic: bool,
+    format_version: FormatVersion,
+) -> anyhow::Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    let mut cache = OutputCache::default();
+
+    let mut line = String::with_capacity(16384);
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let input = line.trim();
+        if input.is_empty() {
+            continue;
+        }
+
+        let key = OutputCache::key(input.as_bytes());
+        let mut output = match cache.get(key.as_str()) {
+            Some(output) => output.to_owned(),
+            None => {
+                let output = match standard_json_output(
+                    input.as_bytes(),
+                    solc,
+                    force_evmla,
+                    is_system_mode,
+                    determinism_check,
+                    allow_network,
+                    ipfs_gateway.clone(),
+                    base_path.clone(),
+                    include_paths.clone(),
+                    allow_paths.clone(),
+                    hermetic,
+                    None,
+                    DebugFilter::default(),
+                ) {
+                    Ok(output) => format_version.to_value(&output),
+                    Err(error) => serde_json::json!({
+                        "errors": [SolcStandardJsonOutputError::message_compiler_failure(
+                            error.to_string().as_str()
+                        )],
+                    }),
+                };
+                let output = serde_json::to_vec(&output).expect("Always valid");
+                if serde_json::from_str(input)
+                    .map(|input: serde_json::Value| OutputCache::is_cacheable(&input))
+                    .unwrap_or_default()
+                {
+                    cache.insert(key, output.clone());
+                }
+                output
+            }
+        };
+        output.push(b'\n');
+        writer.write_all(output.as_slice())?;
+        writer.flush()?;
+    }
+}
+
+///
+/// Compiles the standard JSON input read from `reader`, returning the standard JSON output.
+///
+/// The `solc` errors are returned in the output, whereas the errors of the compiler itself are
+/// returned as `Err`.
+///
+/// The local sources are only read from the base path or the current directory, and the include
+/// and allowed paths. In `hermetic` mode, the network sources are not resolved.
+///
+#[allow(clippy::too_many_arguments)]
+pub fn standard_json_output<R: std::io::Read>(
+    reader: R,
+    solc: &mut SolcCompiler,
+    force_evmla: bool,
+    is_system_mode: bool,
+    determinism_check: bool,
+    allow_network: bool,
+    ipfs_gateway: Option<String>,
+    base_path: Option<String>,
+    include_paths: Vec<String>,
+    allow_paths: Option<String>,
+    hermetic: bool,
+    debug_config: Option<compiler_llvm_context::DebugConfig>,
+    debug_filter: DebugFilter,
+) -> anyhow::Result<SolcStandardJsonOutput> {
     let solc_version = solc.version()?;
     let solc_pipeline = SolcPipeline::new(&solc_version, force_evmla);
     let zksolc_version = semver::Version::parse(env!("CARGO_PKG_VERSION")).expect("Always valid");
 
-    let solc_input = SolcStandardJsonInput::try_from_stdin(solc_pipeline)?;
+    let mut solc_input = SolcStandardJsonInput::try_from_reader(reader, solc_pipeline)?;
+    solc.sandbox = solc_input.settings.sandbox.clone();
+    if hermetic && solc.sandbox.is_some() {
+        anyhow::bail!(
+            "The sandbox is not supported in hermetic mode, as it creates a temporary directory."
+        );
+    }
+    let roots = SolcStandardJsonInputResolvers::roots(
+        base_path.as_deref(),
+        include_paths.as_slice(),
+        allow_paths.as_deref(),
+    );
+    let resolvers = if solc.sandbox.is_some() {
+        SolcStandardJsonInputResolvers::new_sandboxed()
+    } else if hermetic {
+        SolcStandardJsonInputResolvers::new_hermetic(roots)
+    } else {
+        SolcStandardJsonInputResolvers::new(allow_network, ipfs_gateway, roots)
+    };
+    solc_input.resolve_sources(&resolvers)?;
+    let unnormalized_sources = solc_input.normalize_sources(solc_input.settings.normalize_sources);
+    let input_warnings = std::mem::take(&mut solc_input.input_warnings);
     let source_code_files = solc_input
         .sources
         .iter()
-        .map(|(path, source)| (path.to_owned(), source.content.to_owned()))
+        .map(|(path, source)| {
+            (
+                path.to_owned(),
+                source.content.clone().unwrap_or_else(|| Arc::from("")),
+            )
+        })
         .collect();
 
     let optimizer_settings =
@@ -265,6 +889,62 @@ pub fn standard_json(
         }
         None => true,
     };
+    let metadata_hash_placement = solc_input
+        .settings
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.hash_placement)
+        .unwrap_or_default();
+
+    let target = solc_input.settings.target.unwrap_or_default();
+    let empty_contracts = solc_input.settings.empty_contracts.unwrap_or_default();
+    let warnings_as_errors = solc_input.settings.warnings_as_errors.clone();
+    let severity_overrides = solc_input
+        .settings
+        .severity_overrides
+        .clone()
+        .unwrap_or_default();
+    let output_evm_bytecode = solc_input.settings.output_evm_bytecode;
+    let output_statistics = solc_input.settings.output_statistics;
+    let output_histogram = solc_input.settings.output_histogram;
+    let output_code_sections = solc_input.settings.output_code_sections;
+    let output_immutables = solc_input.settings.output_immutables;
+    let output_gas_usages = solc_input.settings.output_gas_usages;
+    let evmla_stack_spill_threshold = solc_input.settings.evmla_stack_spill_threshold;
+    let reentrancy_guard = solc_input
+        .settings
+        .reentrancy_guard
+        .clone()
+        .unwrap_or_default();
+    let order_by_selector = solc_input.settings.order_by_selector;
+    let loop_unroll_threshold = solc_input.settings.loop_unroll_threshold;
+    let output_constant_loops = solc_input.settings.output_constant_loops;
+    if (loop_unroll_threshold.is_some() || output_constant_loops)
+        && solc_pipeline == SolcPipeline::EVMLA
+    {
+        anyhow::bail!("The constant-sized loop unrolling is only supported in the Yul pipeline");
+    }
+    let contract_time_budget = solc_input.settings.contract_time_budget;
+    if contract_time_budget == Some(0) {
+        anyhow::bail!("The contract time budget must be positive.");
+    }
+    let strict = solc_input.settings.strict;
+    let strict_gas = solc_input.settings.strict_gas;
+    let erc_checks = solc_input.settings.erc_checks;
+    let revert_string_ids = solc_input
+        .settings
+        .debug
+        .as_mut()
+        .map(|debug| debug.take_revert_string_ids())
+        .unwrap_or_default();
+    if revert_string_ids && solc_pipeline == SolcPipeline::EVMLA {
+        anyhow::bail!("The revert string identifiers are only supported in the Yul pipeline");
+    }
+    let source_hashes = if solc_input.settings.output_source_hashes {
+        solc_input.source_hashes()
+    } else {
+        BTreeMap::new()
+    };
 
     let libraries = solc_input.settings.libraries.clone().unwrap_or_default();
     let mut solc_output = solc.standard_json(
@@ -274,35 +954,214 @@ pub fn standard_json(
         include_paths,
         allow_paths,
     )?;
+    if strict {
+        solc_output.check_strict();
+    }
+    if erc_checks {
+        solc_output.check_erc();
+    }
+    let gas_usages = if output_gas_usages || strict_gas {
+        solc_output.get_gas_usages()
+    } else {
+        BTreeMap::new()
+    };
+    if strict_gas {
+        solc_output.check_strict_gas(&gas_usages);
+    }
+
+    if let Some(errors) = solc_output.errors.as_mut() {
+        SolcStandardJsonInputSettingsSeverityOverride::apply_all(
+            severity_overrides.as_slice(),
+            errors,
+        )?;
+        if let Some(warnings_as_errors) = warnings_as_errors.as_ref() {
+            warnings_as_errors.apply(errors);
+        }
 
-    if let Some(errors) = solc_output.errors.as_deref() {
         for error in errors.iter() {
             if error.severity.as_str() == "error" {
-                serde_json::to_writer(std::io::stdout(), &solc_output)?;
-                std::process::exit(0);
+                return Ok(solc_output);
             }
         }
     }
 
-    let project = solc_output.try_to_project(
+    let mut project = solc_output.try_to_project(
         source_code_files,
         libraries,
         solc_pipeline,
         &solc_version.default,
         debug_config.as_ref(),
+        &debug_filter,
     )?;
+    project.evmla_stack_spill_threshold = evmla_stack_spill_threshold;
+    project.reentrancy_guard = reentrancy_guard;
+    project.revert_string_ids = revert_string_ids;
+    project.order_by_selector = order_by_selector;
+    project.loop_unroll_threshold = loop_unroll_threshold;
+    project.output_constant_loops = output_constant_loops;
+    project.output_histogram = output_histogram;
+    project.debug_filter = debug_filter;
+    project.metadata_hash_placement = metadata_hash_placement;
+    project.contract_time_budget = contract_time_budget.map(Duration::from_secs);
+    if output_immutables {
+        for (path, immutables) in solc_output.get_immutables().into_iter() {
+            if let Some(contract) = project.contracts.get_mut(path.as_str()) {
+                contract.immutables = immutables;
+            }
+        }
+    }
 
-    let build = project.compile(
+    let mut build = project.compile(
         optimizer_settings,
+        target,
         is_system_mode,
         include_metadata_hash,
+        determinism_check,
         zkevm_assembly::RunningVmEncodingMode::Production,
+        None,
         debug_config,
     )?;
+    for contract in build.contracts.values_mut() {
+        if !output_statistics {
+            contract.statistics = None;
+        }
+        if !output_code_sections {
+            contract.code_sections = None;
+        }
+    }
+    if output_gas_usages {
+        for (path, usages) in gas_usages.into_iter() {
+            if let Some(contract) = build.contracts.get_mut(path.as_str()) {
+                contract.gas_usages = Some(usages);
+            }
+        }
+    }
 
+    for description in input_warnings.into_iter() {
+        solc_output.errors.get_or_insert_with(Vec::new).push(
+            SolcStandardJsonOutputError::message_input_field(description.as_str()),
+        );
+    }
+    for path in unnormalized_sources.into_iter() {
+        solc_output.errors.get_or_insert_with(Vec::new).push(
+            SolcStandardJsonOutputError::message_unnormalized_source(path.as_str()),
+        );
+    }
+    if SolcCompiler::is_unsupported(&solc_version.default) {
+        solc_output.errors.get_or_insert_with(Vec::new).push(
+            SolcStandardJsonOutputError::message_unsupported_solc_version(
+                &solc_version.default,
+                &SolcCompiler::LAST_SUPPORTED_VERSION,
+            ),
+        );
+    }
+    for (path, skipped) in build.skipped.iter() {
+        let location = solc_output.contract_location(path.as_str());
+        solc_output.errors.get_or_insert_with(Vec::new).push(
+            SolcStandardJsonOutputError::message_skipped(
+                path.as_str(),
+                skipped.to_string().as_str(),
+                location,
+            ),
+        );
+    }
+    for (hash, paths) in build.duplicates().into_iter() {
+        let contracts: Vec<(String, Option<SolcStandardJsonOutputErrorSourceLocation>)> = paths
+            .into_iter()
+            .map(|path| {
+                let location = solc_output.contract_location(path.as_str());
+                (path, location)
+            })
+            .collect();
+        solc_output.errors.get_or_insert_with(Vec::new).push(
+            SolcStandardJsonOutputError::message_duplicate_bytecode(
+                hash.as_str(),
+                contracts.as_slice(),
+            ),
+        );
+    }
+
+    if let Some(errors) = solc_output.errors.as_mut() {
+        SolcStandardJsonInputSettingsSeverityOverride::apply_all(
+            severity_overrides.as_slice(),
+            errors,
+        )?;
+    }
+    if let (Some(warnings_as_errors), Some(errors)) =
+        (warnings_as_errors, solc_output.errors.as_deref_mut())
+    {
+        if warnings_as_errors.apply(errors) {
+            return Ok(solc_output);
+        }
+    }
+
+    if output_evm_bytecode {
+        solc_output.preserve_evm_bytecode();
+    }
+    solc_output.set_source_hashes(source_hashes);
     build.write_to_standard_json(&mut solc_output, &solc_version, &zksolc_version)?;
-    serde_json::to_writer(std::io::stdout(), &solc_output)?;
-    std::process::exit(0);
+    solc_output.process_empty_contracts(empty_contracts);
+    Ok(solc_output)
+}
+
+///
+/// Runs the contract discovery mode.
+///
+/// Only the `solc` analysis stage is run, so the contracts can be listed before a full build.
+///
+pub fn list_contracts(
+    input_files: &[PathBuf],
+    solc: &mut SolcCompiler,
+    error_format: ErrorFormat,
+    base_path: Option<String>,
+    include_paths: Vec<String>,
+    allow_paths: Option<String>,
+) -> anyhow::Result<Vec<SolcStandardJsonOutputListing>> {
+    let solc_version = solc.version()?;
+    let solc_pipeline = SolcPipeline::new(&solc_version, false);
+
+    let solc_input = SolcStandardJsonInput::try_from_paths(
+        SolcStandardJsonInputLanguage::Solidity,
+        input_files,
+        vec![],
+        SolcStandardJsonInputSettingsSelection::new_analysis(),
+        SolcStandardJsonInputSettingsOptimizer::new(false, None),
+        None,
+        false,
+    )?;
+    let source_code_files: BTreeMap<String, Arc<str>> = solc_input
+        .sources
+        .iter()
+        .map(|(path, source)| {
+            (
+                path.to_owned(),
+                source.content.clone().unwrap_or_else(|| Arc::from("")),
+            )
+        })
+        .collect();
+    let error_renderer = ErrorRenderer::new(&source_code_files, error_format);
+
+    let solc_output = solc.standard_json(
+        solc_input,
+        solc_pipeline,
+        base_path,
+        include_paths,
+        allow_paths,
+    )?;
+    if let Some(errors) = solc_output.errors.as_deref() {
+        let mut has_errors = false;
+        for error in errors.iter() {
+            if error.severity.as_str() == "error" {
+                has_errors = true;
+                eprintln!("{}", error_renderer.render(error));
+            }
+        }
+        if has_errors {
+            anyhow::bail!("Error(s) found. Contract discovery aborted");
+        }
+    }
+
+    Ok(solc_output.list_contracts())
 }
 
 ///
@@ -316,14 +1175,29 @@ pub fn combined_json(
     solc: &mut SolcCompiler,
     solc_optimizer_enabled: bool,
     optimizer_settings: compiler_llvm_context::OptimizerSettings,
+    target: Target,
     force_evmla: bool,
     is_system_mode: bool,
     include_metadata_hash: bool,
+    metadata_hash_placement: MetadataHashPlacement,
+    determinism_check: bool,
+    normalize_sources: bool,
+    evmla_stack_spill_threshold: Option<usize>,
+    evmla_trace_tag: Option<usize>,
+    evmla_debug_locations: bool,
+    reentrancy_guard: Vec<String>,
+    order_by_selector: bool,
+    strict: bool,
+    erc_checks: bool,
+    warnings_as_errors: Option<SolcStandardJsonInputSettingsWarningsAsErrors>,
+    error_format: ErrorFormat,
     base_path: Option<String>,
     include_paths: Vec<String>,
     allow_paths: Option<String>,
     debug_config: Option<compiler_llvm_context::DebugConfig>,
+    debug_filter: DebugFilter,
     output_directory: Option<PathBuf>,
+    format_version: FormatVersion,
     overwrite: bool,
 ) -> anyhow::Result<()> {
     let zksolc_version = semver::Version::parse(env!("CARGO_PKG_VERSION")).expect("Always valid");
@@ -334,13 +1208,34 @@ pub fn combined_json(
         solc,
         solc_optimizer_enabled,
         optimizer_settings,
+        target,
         force_evmla,
         is_system_mode,
         include_metadata_hash,
+        metadata_hash_placement,
+        determinism_check,
+        false,
+        false,
+        false,
+        normalize_sources,
+        evmla_stack_spill_threshold,
+        evmla_trace_tag,
+        evmla_debug_locations,
+        reentrancy_guard,
+        order_by_selector,
+        None,
+        strict,
+        erc_checks,
+        None,
+        warnings_as_errors,
+        error_format,
+        false,
         base_path,
         include_paths,
         allow_paths,
+        None,
         debug_config,
+        debug_filter,
     )?;
 
     let mut combined_json = solc.combined_json(input_files, format.as_str())?;
@@ -350,12 +1245,17 @@ pub fn combined_json(
         Some(output_directory) => {
             std::fs::create_dir_all(output_directory.as_path())?;
 
-            combined_json.write_to_directory(output_directory.as_path(), overwrite)?;
+            combined_json.write_to_directory(
+                output_directory.as_path(),
+                format_version,
+                overwrite,
+            )?;
         }
         None => {
             println!(
                 "{}",
-                serde_json::to_string(&combined_json).expect("Always valid")
+                serde_json::to_string(&format_version.to_value(&combined_json))
+                    .expect("Always valid")
             );
         }
     }
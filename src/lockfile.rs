@@ -0,0 +1,215 @@
+//!
+//! The source lockfile.
+//!
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+use sha3::Digest;
+
+///
+/// The source lockfile.
+///
+/// Pins the keccak256 hashes of the input files and of every import resolved by `solc`,
+/// including those from `node_modules` and `lib` directories, so a build can be checked for
+/// using exactly the same sources. The hashes are taken from the compiled sources: the input
+/// files read into memory and the imports as recorded by `solc` in the contract metadata.
+///
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Lockfile {
+    /// The lockfile format version.
+    pub version: usize,
+    /// The `0x`-prefixed keccak256 hashes of the sources, keyed by the source unit names.
+    pub sources: BTreeMap<String, String>,
+}
+
+impl Lockfile {
+    /// The current lockfile format version.
+    pub const VERSION: usize = 1;
+
+    ///
+    /// A shortcut constructor.
+    ///
+    /// `sources` are the `0x`-prefixed keccak256 hashes of the compiled sources, keyed by the
+    /// source unit names.
+    ///
+    pub fn new(sources: BTreeMap<String, String>) -> Self {
+        Self {
+            version: Self::VERSION,
+            sources,
+        }
+    }
+
+    ///
+    /// Reads the lockfile.
+    ///
+    pub fn try_from_path(path: &Path) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(path)
+            .map_err(|error| anyhow::anyhow!("Lockfile {:?} opening error: {}", path, error))?;
+        let lockfile: Self = serde_json::from_reader(std::io::BufReader::new(file))
+            .map_err(|error| anyhow::anyhow!("Lockfile {:?} parsing error: {}", path, error))?;
+        if lockfile.version != Self::VERSION {
+            anyhow::bail!(
+                "Lockfile {:?} version {} is not supported, expected {}",
+                path,
+                lockfile.version,
+                Self::VERSION
+            );
+        }
+        Ok(lockfile)
+    }
+
+    ///
+    /// Writes the lockfile.
+    ///
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        let mut json = serde_json::to_vec_pretty(self).expect("Always valid");
+        json.push(b'\n');
+        std::fs::write(path, json)
+            .map_err(|error| anyhow::anyhow!("Lockfile {:?} writing error: {}", path, error))
+    }
+
+    ///
+    /// Checks whether the `actual` sources are the same as the locked ones.
+    ///
+    /// All the changed, added, and removed sources are reported.
+    ///
+    pub fn check(&self, actual: &Self) -> anyhow::Result<()> {
+        let mut differences = Vec::new();
+        for (name, hash) in self.sources.iter() {
+            match actual.sources.get(name) {
+                Some(actual_hash) if actual_hash == hash => {}
+                Some(actual_hash) => differences.push(format!(
+                    "changed: `{name}` (locked {hash}, found {actual_hash})"
+                )),
+                None => differences.push(format!("removed: `{name}`")),
+            }
+        }
+        for name in actual.sources.keys() {
+            if !self.sources.contains_key(name) {
+                differences.push(format!("added: `{name}`"));
+            }
+        }
+
+        if !differences.is_empty() {
+            anyhow::bail!(
+                "The sources differ from the lockfile:\n{}",
+                differences.join("\n")
+            );
+        }
+        Ok(())
+    }
+
+    ///
+    /// Checks whether the locked sources on the file system are unchanged, before they are
+    /// compiled.
+    ///
+    /// The source unit names are resolved the way `solc` does: as is, then relative to the base
+    /// path, then relative to each include path. The sources that are not found are reported
+    /// as removed.
+    ///
+    pub fn check_files(
+        &self,
+        base_path: Option<&str>,
+        include_paths: &[String],
+    ) -> anyhow::Result<()> {
+        let mut sources = BTreeMap::new();
+        for name in self.sources.keys() {
+            let path = match Self::resolve(name.as_str(), base_path, include_paths) {
+                Some(path) => path,
+                None => continue,
+            };
+            let content = std::fs::read(path.as_path())
+                .map_err(|error| anyhow::anyhow!("Source {:?} reading error: {}", path, error))?;
+            let hash = sha3::Keccak256::digest(content.as_slice());
+            sources.insert(name.to_owned(), format!("0x{}", hex::encode(hash)));
+        }
+        self.check(&Self::new(sources))
+    }
+
+    ///
+    /// Resolves the source unit name to a file system path.
+    ///
+    pub fn resolve(
+        name: &str,
+        base_path: Option<&str>,
+        include_paths: &[String],
+    ) -> Option<PathBuf> {
+        std::iter::once(PathBuf::from(name))
+            .chain(base_path.map(|base_path| Path::new(base_path).join(name)))
+            .chain(
+                include_paths
+                    .iter()
+                    .map(|include_path| Path::new(include_path).join(name)),
+            )
+            .find(|path| path.is_file())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::Lockfile;
+
+    fn lockfile(sources: &[(&str, &str)]) -> Lockfile {
+        Lockfile::new(
+            sources
+                .iter()
+                .map(|(name, hash)| (name.to_string(), hash.to_string()))
+                .collect::<BTreeMap<String, String>>(),
+        )
+    }
+
+    #[test]
+    fn unchanged() {
+        let locked = lockfile(&[("a.sol", "0x01"), ("lib/b.sol", "0x02")]);
+        let actual = lockfile(&[("a.sol", "0x01"), ("lib/b.sol", "0x02")]);
+        assert!(locked.check(&actual).is_ok());
+    }
+
+    #[test]
+    fn changed() {
+        let locked = lockfile(&[("a.sol", "0x01"), ("lib/b.sol", "0x02")]);
+        let actual = lockfile(&[("a.sol", "0x01"), ("lib/b.sol", "0x03"), ("c.sol", "0x04")]);
+        let error = locked.check(&actual).expect_err("Always fails").to_string();
+        assert!(error.contains("changed: `lib/b.sol`"));
+        assert!(error.contains("added: `c.sol`"));
+    }
+
+    #[test]
+    fn files() {
+        let directory = std::env::temp_dir().join(format!(
+            "zksolc-lockfile-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        std::fs::create_dir_all(directory.join("lib")).expect("Always valid");
+        std::fs::write(directory.join("lib").join("b.sol"), "").expect("Always valid");
+        let base_path = directory.to_string_lossy().to_string();
+
+        let empty_hash = "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+        let locked = lockfile(&[("lib/b.sol", empty_hash)]);
+        assert!(locked.check_files(Some(base_path.as_str()), &[]).is_ok());
+
+        std::fs::write(directory.join("lib").join("b.sol"), "contract B {}").expect("Always valid");
+        let error = locked
+            .check_files(Some(base_path.as_str()), &[])
+            .expect_err("Always fails")
+            .to_string();
+        assert!(error.contains("changed: `lib/b.sol`"));
+
+        let locked = lockfile(&[("lib/c.sol", empty_hash)]);
+        let error = locked
+            .check_files(Some(base_path.as_str()), &[])
+            .expect_err("Always fails")
+            .to_string();
+        assert!(error.contains("removed: `lib/c.sol`"));
+
+        std::fs::remove_dir_all(directory).expect("Always valid");
+    }
+}
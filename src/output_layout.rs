@@ -0,0 +1,75 @@
+//!
+//! The output directory layout.
+//!
+
+use std::str::FromStr;
+
+use crate::build::contract::Contract as ContractBuild;
+
+///
+/// The output directory layout.
+///
+/// The `solc` layout mimics the file names of `solc --bin --abi --metadata -o`, so the scripts
+/// postprocessing the `solc` output directories can be reused as is.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutputLayout {
+    /// The artifacts are named after the source file and contract names, e.g. `Test.sol:Test.zbin`,
+    /// and the bytecode is binary.
+    #[default]
+    Zksolc,
+    /// The artifacts are named after the contract names, e.g. `Test.bin` and `Test.abi`, and the
+    /// bytecode is hexadecimal.
+    Solc,
+}
+
+impl OutputLayout {
+    /// The ABI file extension of the `solc` layout.
+    pub const EXTENSION_ABI: &'static str = "abi";
+
+    /// The metadata file name suffix of the `solc` layout.
+    pub const SOLC_METADATA_FILE_NAME_SUFFIX: &'static str = "_meta";
+
+    /// The EVM bytecode file name suffix of the `solc` layout.
+    pub const SOLC_EVM_BYTECODE_FILE_NAME_SUFFIX: &'static str = "_evm";
+
+    ///
+    /// Returns the artifact file name stem of the contract with the full `path`.
+    ///
+    pub fn file_name<'a>(&self, path: &'a str) -> &'a str {
+        match self {
+            Self::Zksolc => ContractBuild::short_path(path),
+            Self::Solc => path
+                .rfind(':')
+                .map(|last_colon| &path[last_colon + 1..])
+                .unwrap_or(path),
+        }
+    }
+}
+
+impl FromStr for OutputLayout {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "zksolc" => Ok(Self::Zksolc),
+            "solc" => Ok(Self::Solc),
+            string => anyhow::bail!(
+                "Unknown output layout `{}`. Supported layouts: `zksolc`, `solc`",
+                string
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OutputLayout;
+
+    #[test]
+    fn file_name() {
+        let path = "contracts/token/Token.sol:Token";
+        assert_eq!(OutputLayout::Zksolc.file_name(path), "Token.sol:Token");
+        assert_eq!(OutputLayout::Solc.file_name(path), "Token");
+    }
+}
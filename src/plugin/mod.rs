@@ -0,0 +1,105 @@
+//!
+//! The compiler plugin hooks.
+//!
+//! Plugins are passes that observe or rewrite the IR before it is lowered to LLVM IR.
+//! With the `plugins` feature, they can be registered with `register` before compiling,
+//! including in the recursive process, as each contract is compiled in a separate one.
+//! A tool built on the compiler must therefore register the plugins in its `main` before
+//! calling `run_process`.
+//!
+//! The IRs passed to the hooks can be traversed with the `YulVisitor` and `EtherealIRVisitor`
+//! traits, or rewritten with the `YulVisitorMut` and `EtherealIRVisitorMut` ones, overriding only
+//! the methods for the nodes of interest.
+//!
+//! The built-in plugins, e.g. the reentrancy guard, are enabled by the project settings instead,
+//! so they are available without the `plugins` feature and run before the registered ones.
+//!
+
+pub mod reentrancy_guard;
+
+#[cfg(feature = "plugins")]
+use std::sync::RwLock;
+
+#[cfg(feature = "plugins")]
+use once_cell::sync::Lazy;
+
+use crate::evmla::ethereal_ir::EtherealIR;
+use crate::yul::parser::statement::object::Object;
+
+/// The registered plugins.
+#[cfg(feature = "plugins")]
+static PLUGINS: Lazy<RwLock<Vec<Box<dyn Plugin>>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+///
+/// The compiler plugin.
+///
+/// The hooks are called for each contract in the order of the plugin registration.
+/// All hooks do nothing by default.
+///
+pub trait Plugin: Send + Sync {
+    ///
+    /// Returns the plugin name used in error messages.
+    ///
+    fn name(&self) -> &str;
+
+    ///
+    /// Observes or rewrites the Yul AST of the contract at `path`.
+    ///
+    fn visit_yul(&self, _path: &str, _object: &mut Object) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    ///
+    /// Observes or rewrites the Ethereal IR of the contract at `path`.
+    ///
+    fn visit_ethereal_ir(&self, _path: &str, _ethereal_ir: &mut EtherealIR) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+///
+/// Registers the plugin.
+///
+#[cfg(feature = "plugins")]
+pub fn register(plugin: Box<dyn Plugin>) {
+    PLUGINS.write().expect("Sync").push(plugin);
+}
+
+///
+/// Runs the Yul hooks of all registered plugins.
+///
+#[cfg(feature = "plugins")]
+pub fn visit_yul(path: &str, object: &mut Object) -> anyhow::Result<()> {
+    for plugin in PLUGINS.read().expect("Sync").iter() {
+        visit_yul_with(plugin.as_ref(), path, object)?;
+    }
+    Ok(())
+}
+
+///
+/// Runs the Yul hook of the `plugin`, e.g. a built-in one enabled by the project settings.
+///
+pub fn visit_yul_with(plugin: &dyn Plugin, path: &str, object: &mut Object) -> anyhow::Result<()> {
+    plugin
+        .visit_yul(path, object)
+        .map_err(|error| anyhow::anyhow!("Plugin `{}` Yul pass error: {}", plugin.name(), error))
+}
+
+///
+/// Runs the Ethereal IR hooks of all registered plugins.
+///
+#[cfg(feature = "plugins")]
+pub fn visit_ethereal_ir(path: &str, ethereal_ir: &mut EtherealIR) -> anyhow::Result<()> {
+    for plugin in PLUGINS.read().expect("Sync").iter() {
+        plugin
+            .visit_ethereal_ir(path, ethereal_ir)
+            .map_err(|error| {
+                anyhow::anyhow!(
+                    "Plugin `{}` Ethereal IR pass error: {}",
+                    plugin.name(),
+                    error
+                )
+            })?;
+    }
+    Ok(())
+}
@@ -0,0 +1,580 @@
+//!
+//! The reentrancy guard injection plugin.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+use num::Num;
+use num::One;
+use sha3::Digest;
+
+use crate::solc::standard_json::output::contract::erc::Erc;
+use crate::yul::lexer::token::lexeme::literal::integer::Integer as IntegerLiteral;
+use crate::yul::lexer::token::lexeme::literal::Literal as LexicalLiteral;
+use crate::yul::lexer::Lexer;
+use crate::yul::parser::statement::block::Block;
+use crate::yul::parser::statement::expression::function_call::name::Name;
+use crate::yul::parser::statement::expression::function_call::FunctionCall;
+use crate::yul::parser::statement::expression::Expression;
+use crate::yul::parser::statement::object::Object;
+use crate::yul::parser::statement::Statement;
+use crate::yul::visitor::Visitor;
+
+use super::Plugin;
+
+///
+/// The reentrancy guard injection plugin.
+///
+/// Locks the selected external functions on entry and unlocks them before returning, so that
+/// a reentrant call to any of them reverts.
+///
+/// The functions are found by their selectors in the Yul dispatcher `switch`. The lock is
+/// released before each `return` and `stop` in the dispatcher case and in the `external_fun_*`
+/// wrapper it calls, where `solc` emits the ABI encoding and return of external functions.
+///
+/// The lock is kept in a reserved storage slot, since the transient storage is not available on
+/// EraVM yet. The slot is derived from a hash like in EIP-1967, so it does not collide with the
+/// slots allocated by `solc`. As the storage writes revert in static calls, the `view` and `pure`
+/// functions cannot be guarded.
+///
+/// The guard is a built-in plugin enabled by the project settings, so it is run for each
+/// contract with `crate::plugin::visit_yul_with` instead of being registered.
+///
+#[derive(Debug)]
+pub struct ReentrancyGuard {
+    /// The function signatures, keyed by their selectors.
+    selectors: BTreeMap<num::BigUint, String>,
+    /// The signatures of the functions instrumented by the last run.
+    instrumented: Mutex<Vec<String>>,
+}
+
+impl ReentrancyGuard {
+    /// The lock slot key preimage, whose hash minus one is the slot key.
+    pub const LOCK_SLOT_PREIMAGE: &'static str = "zksolc.reentrancy_guard.lock";
+
+    /// The prefix of the `solc` external function wrappers called by the dispatcher.
+    pub const EXTERNAL_FUNCTION_PREFIX: &'static str = "external_fun_";
+
+    ///
+    /// A shortcut constructor.
+    ///
+    /// The `signatures` are canonical function signatures, e.g. `transfer(address,uint256)`.
+    /// The signatures of the `view` and `pure` functions in the ABI of the contract `solc`
+    /// metadata are rejected, as they may be called via `STATICCALL`, where the lock write reverts.
+    ///
+    pub fn try_new(
+        signatures: &[String],
+        metadata_json: &serde_json::Value,
+    ) -> anyhow::Result<Self> {
+        let metadata: serde_json::Value = match metadata_json {
+            serde_json::Value::String(metadata) => {
+                serde_json::from_str(metadata.as_str()).unwrap_or_default()
+            }
+            metadata => metadata.to_owned(),
+        };
+        let abi = metadata
+            .get("output")
+            .and_then(|output| output.get("abi"))
+            .and_then(|abi| abi.as_array())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for entry in abi.iter() {
+            if entry.get("type").and_then(|r#type| r#type.as_str()) != Some("function") {
+                continue;
+            }
+            let mutability = match entry
+                .get("stateMutability")
+                .and_then(|mutability| mutability.as_str())
+            {
+                Some(mutability @ ("view" | "pure")) => mutability,
+                _ => continue,
+            };
+            let signature = Erc::signature(entry, "inputs", false);
+            if signatures.contains(&signature) {
+                anyhow::bail!(
+                    "The reentrancy guard cannot be applied to the {} function `{}`, as its lock is written to the storage, which reverts in static calls.",
+                    mutability,
+                    signature
+                );
+            }
+        }
+
+        let selectors = signatures
+            .iter()
+            .map(|signature| {
+                let hash = sha3::Keccak256::digest(signature.as_bytes());
+                (
+                    num::BigUint::from_bytes_be(&hash[..4]),
+                    signature.to_owned(),
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            selectors,
+            instrumented: Mutex::new(vec![]),
+        })
+    }
+
+    ///
+    /// Returns the signatures of the functions instrumented by the last run of the plugin.
+    ///
+    pub fn instrumented(&self) -> Vec<String> {
+        self.instrumented.lock().expect("Sync").clone()
+    }
+
+    ///
+    /// Returns the lock slot key, which is `keccak256(LOCK_SLOT_PREIMAGE) - 1`.
+    ///
+    pub fn lock_slot() -> String {
+        let hash = sha3::Keccak256::digest(Self::LOCK_SLOT_PREIMAGE.as_bytes());
+        let slot = num::BigUint::from_bytes_be(hash.as_slice()) - num::BigUint::one();
+        format!("{slot:#066x}")
+    }
+
+    ///
+    /// Injects the guard into the runtime code of the `object`, returning the signatures of the
+    /// instrumented functions.
+    ///
+    pub fn instrument(&self, object: &mut Object) -> anyhow::Result<Vec<String>> {
+        let runtime = match object.inner_object.as_mut() {
+            Some(runtime) => runtime,
+            None => return Ok(vec![]),
+        };
+
+        let slot = Self::lock_slot();
+        let lock = Self::parse_statements(
+            format!("{{ if sload({slot}) {{ revert(0, 0) }} sstore({slot}, 1) }}").as_str(),
+        )?;
+        let unlock = Self::parse_statements(format!("{{ sstore({slot}, 0) }}").as_str())?;
+
+        let mut instrumented = BTreeSet::new();
+        let mut functions = BTreeSet::new();
+        self.instrument_dispatcher(
+            &mut runtime.code.block,
+            lock.as_slice(),
+            unlock.as_slice(),
+            &mut instrumented,
+            &mut functions,
+        );
+
+        for statement in runtime.code.block.statements.iter_mut() {
+            if let Statement::FunctionDefinition(function) = statement {
+                if functions.contains(function.identifier.as_str()) {
+                    Self::unlock_before_exits(&mut function.body, unlock.as_slice());
+                }
+            }
+        }
+
+        Ok(instrumented.into_iter().collect())
+    }
+
+    ///
+    /// Finds the dispatcher cases of the selected functions, locking them on entry.
+    ///
+    /// The external function wrappers called by the cases are collected to the `functions`.
+    ///
+    fn instrument_dispatcher(
+        &self,
+        block: &mut Block,
+        lock: &[Statement],
+        unlock: &[Statement],
+        instrumented: &mut BTreeSet<String>,
+        functions: &mut BTreeSet<String>,
+    ) {
+        for statement in block.statements.iter_mut() {
+            match statement {
+                Statement::Block(block) => {
+                    self.instrument_dispatcher(block, lock, unlock, instrumented, functions)
+                }
+                Statement::IfConditional(conditional) => self.instrument_dispatcher(
+                    &mut conditional.block,
+                    lock,
+                    unlock,
+                    instrumented,
+                    functions,
+                ),
+                Statement::Switch(switch) => {
+                    for case in switch.cases.iter_mut() {
+                        let selector = match Self::literal_value(&case.literal.inner) {
+                            Some(selector) => selector,
+                            None => continue,
+                        };
+                        let signature = match self.selectors.get(&selector) {
+                            Some(signature) => signature,
+                            None => continue,
+                        };
+
+                        Self::collect_wrappers(&case.block, functions);
+                        Self::unlock_before_exits(&mut case.block, unlock);
+                        let mut statements = lock.to_vec();
+                        statements.append(&mut case.block.statements);
+                        case.block.statements = statements;
+                        instrumented.insert(signature.to_owned());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    ///
+    /// Inserts the `unlock` statements before each `return` and `stop` in the `block`.
+    ///
+    fn unlock_before_exits(block: &mut Block, unlock: &[Statement]) {
+        let mut statements = Vec::with_capacity(block.statements.len());
+        for mut statement in block.statements.drain(..) {
+            match statement {
+                Statement::Expression(Expression::FunctionCall(ref call))
+                    if matches!(call.name, Name::Return | Name::Stop) =>
+                {
+                    statements.extend_from_slice(unlock);
+                }
+                Statement::Block(ref mut block) => Self::unlock_before_exits(block, unlock),
+                Statement::IfConditional(ref mut conditional) => {
+                    Self::unlock_before_exits(&mut conditional.block, unlock)
+                }
+                Statement::Switch(ref mut switch) => {
+                    for case in switch.cases.iter_mut() {
+                        Self::unlock_before_exits(&mut case.block, unlock);
+                    }
+                    if let Some(default) = switch.default.as_mut() {
+                        Self::unlock_before_exits(default, unlock);
+                    }
+                }
+                Statement::ForLoop(ref mut for_loop) => {
+                    Self::unlock_before_exits(&mut for_loop.body, unlock)
+                }
+                _ => {}
+            }
+            statements.push(statement);
+        }
+        block.statements = statements;
+    }
+
+    ///
+    /// Collects the names of the external function wrappers called in the `block`.
+    ///
+    /// The other functions are not collected, as they may be shared with the unguarded functions,
+    /// including the `view` ones, where the unlock would revert in static calls.
+    ///
+    fn collect_wrappers(block: &Block, functions: &mut BTreeSet<String>) {
+        let mut calls = Calls::default();
+        calls.visit_block(block);
+        functions.extend(
+            calls
+                .0
+                .into_iter()
+                .filter(|name| name.starts_with(Self::EXTERNAL_FUNCTION_PREFIX)),
+        );
+    }
+
+    ///
+    /// Returns the value of the integer literal.
+    ///
+    fn literal_value(literal: &LexicalLiteral) -> Option<num::BigUint> {
+        match literal {
+            LexicalLiteral::Integer(IntegerLiteral::Decimal { inner }) => {
+                num::BigUint::from_str_radix(inner.as_str(), compiler_common::BASE_DECIMAL).ok()
+            }
+            LexicalLiteral::Integer(IntegerLiteral::Hexadecimal { inner }) => {
+                num::BigUint::from_str_radix(
+                    &inner["0x".len()..],
+                    compiler_common::BASE_HEXADECIMAL,
+                )
+                .ok()
+            }
+            _ => None,
+        }
+    }
+
+    ///
+    /// Parses the Yul block into its statements.
+    ///
+    fn parse_statements(code: &str) -> anyhow::Result<Vec<Statement>> {
+        let mut lexer = Lexer::new(code.to_owned());
+        let block = Block::parse(&mut lexer, None)
+            .map_err(|error| anyhow::anyhow!("Reentrancy guard parsing error: {:?}", error))?;
+        Ok(block.statements)
+    }
+}
+
+impl Plugin for ReentrancyGuard {
+    fn name(&self) -> &str {
+        "reentrancy-guard"
+    }
+
+    fn visit_yul(&self, _path: &str, object: &mut Object) -> anyhow::Result<()> {
+        let instrumented = self.instrument(object)?;
+        *self.instrumented.lock().expect("Sync") = instrumented;
+        Ok(())
+    }
+}
+
+///
+/// Collects the names of the called user-defined functions.
+///
+#[derive(Default)]
+struct Calls(BTreeSet<String>);
+
+impl Visitor for Calls {
+    fn visit_function_call(&mut self, call: &FunctionCall) {
+        if let Name::UserDefined(ref name) = call.name {
+            self.0.insert(name.to_owned());
+        }
+        crate::yul::visitor::walk_function_call(self, call);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::Num;
+    use num::One;
+    use sha3::Digest;
+
+    use crate::yul::lexer::Lexer;
+    use crate::yul::parser::statement::expression::function_call::name::Name;
+    use crate::yul::parser::statement::expression::function_call::FunctionCall;
+    use crate::yul::parser::statement::object::Object;
+    use crate::yul::parser::statement::Statement;
+    use crate::yul::visitor::Visitor;
+
+    use super::ReentrancyGuard;
+
+    ///
+    /// Collects the names of all the called functions.
+    ///
+    #[derive(Default)]
+    struct Names(Vec<Name>);
+
+    impl Visitor for Names {
+        fn visit_function_call(&mut self, call: &FunctionCall) {
+            self.0.push(call.name.clone());
+            crate::yul::visitor::walk_function_call(self, call);
+        }
+    }
+
+    #[test]
+    fn instrument() {
+        let input = r#"
+object "Test" {
+    code {
+        {
+            return(0, 0)
+        }
+    }
+    object "Test_deployed" {
+        code {
+            {
+                switch shr(224, calldataload(0))
+                case 0xa9059cbb { external_fun_transfer() }
+                case 0x70a08231 { external_fun_balanceOf() }
+                default { revert(0, 0) }
+            }
+            function external_fun_transfer() {
+                return(0, 0)
+            }
+            function external_fun_balanceOf() {
+                return(0, 0)
+            }
+        }
+    }
+}
+    "#;
+
+        let mut lexer = Lexer::new(input.to_owned());
+        let mut object = Object::parse(&mut lexer, None).expect("Always valid");
+
+        let instrumented = ReentrancyGuard::try_new(
+            &["transfer(address,uint256)".to_owned()],
+            &serde_json::Value::Null,
+        )
+        .expect("Always valid")
+        .instrument(&mut object)
+        .expect("Always valid");
+        assert_eq!(instrumented, vec!["transfer(address,uint256)".to_owned()]);
+
+        let runtime = object.inner_object.expect("Always exists");
+        let function_sizes: Vec<(String, usize)> = runtime
+            .code
+            .block
+            .statements
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::FunctionDefinition(function) => Some((
+                    function.identifier.to_owned(),
+                    function.body.statements.len(),
+                )),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            function_sizes,
+            vec![
+                ("external_fun_transfer".to_owned(), 2),
+                ("external_fun_balanceOf".to_owned(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn wrapper_only() {
+        let input = r#"
+object "Test" {
+    code {
+        {
+            return(0, 0)
+        }
+    }
+    object "Test_deployed" {
+        code {
+            {
+                switch shr(224, calldataload(0))
+                case 0xa9059cbb {
+                    if callvalue() { revert(0, 0) }
+                    for { } 1 { } { external_fun_transfer() }
+                }
+                default { revert(0, 0) }
+            }
+            function external_fun_transfer() {
+                abi_encode()
+                return(0, 0)
+            }
+            function abi_encode() {
+                if iszero(calldatasize()) { return(0, 0) }
+            }
+            function unrelated() {
+                return(0, 0)
+            }
+        }
+    }
+}
+    "#;
+
+        let mut lexer = Lexer::new(input.to_owned());
+        let mut object = Object::parse(&mut lexer, None).expect("Always valid");
+
+        let guard = ReentrancyGuard::try_new(
+            &["transfer(address,uint256)".to_owned()],
+            &serde_json::Value::Null,
+        )
+        .expect("Always valid");
+        crate::plugin::visit_yul_with(&guard, "Test.sol:Test", &mut object).expect("Always valid");
+        assert_eq!(
+            guard.instrumented(),
+            vec!["transfer(address,uint256)".to_owned()]
+        );
+
+        let runtime = object.inner_object.expect("Always exists");
+        let function_sizes: Vec<(String, usize)> = runtime
+            .code
+            .block
+            .statements
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::FunctionDefinition(function) => Some((
+                    function.identifier.to_owned(),
+                    function.body.statements.len(),
+                )),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            function_sizes,
+            vec![
+                ("external_fun_transfer".to_owned(), 3),
+                ("abi_encode".to_owned(), 1),
+                ("unrelated".to_owned(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn lock_in_storage() {
+        let input = r#"
+object "Test" {
+    code {
+        {
+            return(0, 0)
+        }
+    }
+    object "Test_deployed" {
+        code {
+            {
+                switch shr(224, calldataload(0))
+                case 0xa9059cbb { return(0, 0) }
+                default { revert(0, 0) }
+            }
+        }
+    }
+}
+    "#;
+
+        let mut lexer = Lexer::new(input.to_owned());
+        let mut object = Object::parse(&mut lexer, None).expect("Always valid");
+        ReentrancyGuard::try_new(
+            &["transfer(address,uint256)".to_owned()],
+            &serde_json::Value::Null,
+        )
+        .expect("Always valid")
+        .instrument(&mut object)
+        .expect("Always valid");
+
+        let mut names = Names::default();
+        names.visit_object(&object);
+        assert!(names.0.contains(&Name::SLoad));
+        assert!(names.0.contains(&Name::SStore));
+        assert!(!names.0.contains(&Name::TLoad));
+        assert!(!names.0.contains(&Name::TStore));
+    }
+
+    #[test]
+    fn lock_slot() {
+        let slot = ReentrancyGuard::lock_slot();
+        assert_eq!(slot.len(), 66);
+
+        let slot = num::BigUint::from_str_radix(&slot["0x".len()..], 16).expect("Always valid");
+        let hash = sha3::Keccak256::digest(ReentrancyGuard::LOCK_SLOT_PREIMAGE.as_bytes());
+        assert_eq!(
+            slot + num::BigUint::one(),
+            num::BigUint::from_bytes_be(hash.as_slice())
+        );
+    }
+
+    #[test]
+    fn view_rejected() {
+        let metadata = serde_json::json!({
+            "output": {
+                "abi": [
+                    {
+                        "type": "function",
+                        "name": "transfer",
+                        "inputs": [{ "type": "address" }, { "type": "uint256" }],
+                        "stateMutability": "nonpayable",
+                    },
+                    {
+                        "type": "function",
+                        "name": "balanceOf",
+                        "inputs": [{ "type": "address" }],
+                        "stateMutability": "view",
+                    },
+                ],
+            },
+        });
+        let metadata = serde_json::Value::String(metadata.to_string());
+
+        assert!(
+            ReentrancyGuard::try_new(&["transfer(address,uint256)".to_owned()], &metadata).is_ok()
+        );
+        let error = ReentrancyGuard::try_new(&["balanceOf(address)".to_owned()], &metadata)
+            .expect_err("Must be rejected");
+        assert!(
+            error
+                .to_string()
+                .contains("view function `balanceOf(address)`"),
+            "{error}"
+        );
+    }
+}
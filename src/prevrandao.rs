@@ -0,0 +1,200 @@
+//!
+//! The `difficulty` and `prevrandao` builtin reconciliation.
+//!
+
+use std::borrow::Cow;
+
+use crate::evmla::assembly::instruction::name::Name as EVMLAInstructionName;
+use crate::solc::Compiler as SolcCompiler;
+use crate::yul::lexer::token::lexeme::keyword::Keyword;
+use crate::yul::lexer::token::lexeme::symbol::Symbol;
+use crate::yul::lexer::token::lexeme::Lexeme;
+use crate::yul::lexer::Lexer;
+
+///
+/// The `difficulty` and `prevrandao` builtin reconciliation.
+///
+/// Since v0.8.18, `solc` exposes the `DIFFICULTY` opcode as `prevrandao` (EIP-4399) and no
+/// longer accepts `difficulty` in Yul for the default EVM version, while the older versions
+/// only know `difficulty`. Both builtins return the same value on EraVM, so the policy below is
+/// the only place deciding which name is accepted where:
+///
+/// - the translators of both pipelines accept either builtin and translate them identically;
+/// - the EVM legacy assembly `PREVRANDAO` is rejected before v0.8.18, since `solc` could not
+///   have emitted it, which indicates a corrupted or mismatched input;
+/// - the Yul sources are validated by `solc` with the builtin renamed to the one its version
+///   accepts, so a source written for either version range passes the validation.
+///
+#[derive(Debug)]
+pub struct Prevrandao;
+
+impl Prevrandao {
+    /// The builtin name before `solc` v0.8.18.
+    pub const DIFFICULTY: &'static str = "difficulty";
+
+    /// The builtin name since `solc` v0.8.18.
+    pub const PREVRANDAO: &'static str = "prevrandao";
+
+    ///
+    /// Returns the builtin name accepted by the `solc` of `version`.
+    ///
+    pub fn builtin_name(version: &semver::Version) -> &'static str {
+        if version >= &SolcCompiler::FIRST_PREVRANDAO_VERSION {
+            Self::PREVRANDAO
+        } else {
+            Self::DIFFICULTY
+        }
+    }
+
+    ///
+    /// Checks whether the EVM legacy assembly instruction could be emitted by the `solc` of
+    /// `version`.
+    ///
+    pub fn check_evmla(
+        name: &EVMLAInstructionName,
+        version: &semver::Version,
+    ) -> anyhow::Result<()> {
+        if let EVMLAInstructionName::PREVRANDAO = name {
+            if version < &SolcCompiler::FIRST_PREVRANDAO_VERSION {
+                anyhow::bail!(
+                    "The `{}` instruction is not emitted by `solc` before v{}, found v{}",
+                    name,
+                    SolcCompiler::FIRST_PREVRANDAO_VERSION,
+                    version,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Renames the builtin calls in the Yul `source_code` to the builtin accepted by the `solc`
+    /// of `version`.
+    ///
+    /// Only the identifier tokens followed by an opening parenthesis are renamed, so the string
+    /// literals, comments, and longer identifiers are left intact. The source is returned as is
+    /// if it only uses the accepted name, defines a function with the other name, or cannot be
+    /// tokenized, which is then reported by `solc`.
+    ///
+    pub fn reconcile_yul<'a>(source_code: &'a str, version: &semver::Version) -> Cow<'a, str> {
+        let (from, to) = match Self::builtin_name(version) {
+            Self::PREVRANDAO => (Self::DIFFICULTY, Self::PREVRANDAO),
+            _ => (Self::PREVRANDAO, Self::DIFFICULTY),
+        };
+
+        let mut lexer = Lexer::new(source_code);
+        let mut tokens = Vec::new();
+        loop {
+            let token = match lexer.next() {
+                Ok(token) => token,
+                Err(_) => return Cow::Borrowed(source_code),
+            };
+            if let Lexeme::EndOfFile = token.lexeme {
+                break;
+            }
+            tokens.push((lexer.offset() - token.length, token.lexeme));
+        }
+
+        let mut offsets = Vec::new();
+        for (index, (offset, lexeme)) in tokens.iter().enumerate() {
+            match lexeme {
+                Lexeme::Identifier(identifier) if identifier.inner == from => {}
+                _ => continue,
+            }
+            if index > 0 && tokens[index - 1].1 == Lexeme::Keyword(Keyword::Function) {
+                return Cow::Borrowed(source_code);
+            }
+            if let Some((_, Lexeme::Symbol(Symbol::ParenthesisLeft))) = tokens.get(index + 1) {
+                offsets.push(*offset);
+            }
+        }
+        if offsets.is_empty() {
+            return Cow::Borrowed(source_code);
+        }
+
+        let mut result = String::with_capacity(source_code.len() + offsets.len());
+        let mut last = 0;
+        for offset in offsets.into_iter() {
+            result.push_str(&source_code[last..offset]);
+            result.push_str(to);
+            last = offset + from.len();
+        }
+        result.push_str(&source_code[last..]);
+        Cow::Owned(result)
+    }
+
+    ///
+    /// Translates either builtin.
+    ///
+    pub fn translate<'ctx, D>(
+        context: &mut compiler_llvm_context::Context<'ctx, D>,
+    ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>>
+    where
+        D: compiler_llvm_context::Dependency + Clone,
+    {
+        compiler_llvm_context::contract_context::difficulty(context).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Prevrandao;
+    use crate::evmla::assembly::instruction::name::Name as EVMLAInstructionName;
+
+    #[test]
+    fn builtin_name() {
+        assert_eq!(
+            Prevrandao::builtin_name(&semver::Version::new(0, 8, 17)),
+            "difficulty"
+        );
+        assert_eq!(
+            Prevrandao::builtin_name(&semver::Version::new(0, 8, 18)),
+            "prevrandao"
+        );
+    }
+
+    #[test]
+    fn check_evmla() {
+        let old = semver::Version::new(0, 8, 17);
+        let new = semver::Version::new(0, 8, 20);
+
+        assert!(Prevrandao::check_evmla(&EVMLAInstructionName::DIFFICULTY, &old).is_ok());
+        assert!(Prevrandao::check_evmla(&EVMLAInstructionName::DIFFICULTY, &new).is_ok());
+        assert!(Prevrandao::check_evmla(&EVMLAInstructionName::PREVRANDAO, &old).is_err());
+        assert!(Prevrandao::check_evmla(&EVMLAInstructionName::PREVRANDAO, &new).is_ok());
+    }
+
+    #[test]
+    fn reconcile_yul() {
+        let source_code = r#"let difficulty.old := difficulty()
+// difficulty() in a comment
+let message := "prevrandao()"
+let x := prevrandao()"#;
+
+        assert_eq!(
+            Prevrandao::reconcile_yul(source_code, &semver::Version::new(0, 8, 20)),
+            r#"let difficulty.old := prevrandao()
+// difficulty() in a comment
+let message := "prevrandao()"
+let x := prevrandao()"#
+        );
+        assert_eq!(
+            Prevrandao::reconcile_yul(source_code, &semver::Version::new(0, 8, 17)),
+            r#"let difficulty.old := difficulty()
+// difficulty() in a comment
+let message := "prevrandao()"
+let x := difficulty()"#
+        );
+    }
+
+    #[test]
+    fn reconcile_yul_user_function() {
+        let source_code = "function prevrandao() -> x { x := 1 }
+let y := prevrandao()";
+
+        assert_eq!(
+            Prevrandao::reconcile_yul(source_code, &semver::Version::new(0, 8, 17)),
+            source_code
+        );
+    }
+}
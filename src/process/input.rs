@@ -9,6 +9,7 @@ use serde::Serialize;
 
 use crate::project::contract::Contract;
 use crate::project::Project;
+use crate::target::Target;
 
 ///
 /// The input data.
@@ -19,10 +20,14 @@ pub struct Input {
     pub contract: Contract,
     /// The project representation.
     pub project: Project,
+    /// The compilation target.
+    pub target: Target,
     /// The system mode flag.
     pub is_system_mode: bool,
     /// Whether to append the metadata hash.
     pub include_metadata_hash: bool,
+    /// Whether to compile the contract twice and check the results for equality.
+    pub determinism_check: bool,
     /// Enables the test bytecode encoding.
     pub enable_test_encoding: bool,
     /// The optimizer settings.
@@ -35,11 +40,14 @@ impl Input {
     ///
     /// A shortcut constructor.
     ///
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         contract: Contract,
         project: Project,
+        target: Target,
         is_system_mode: bool,
         include_metadata_hash: bool,
+        determinism_check: bool,
         enable_test_encoding: bool,
         optimizer_settings: compiler_llvm_context::OptimizerSettings,
         debug_config: Option<compiler_llvm_context::DebugConfig>,
@@ -47,8 +55,10 @@ impl Input {
         Self {
             contract,
             project,
+            target,
             is_system_mode,
             include_metadata_hash,
+            determinism_check,
             enable_test_encoding,
             optimizer_settings,
             debug_config,
@@ -4,17 +4,26 @@
 
 pub mod input;
 pub mod output;
+pub mod pool;
+pub mod progress;
+pub mod timeout;
 
+use std::io::BufRead;
 use std::io::Read;
 use std::io::Write;
 use std::path::PathBuf;
-use std::process::Command;
+use std::time::Instant;
 
 use once_cell::sync::OnceCell;
 use serde::Deserialize;
 
+use crate::build::code_sections::CodeSections;
+use crate::build::histogram::Histogram;
+use crate::build::statistics::Statistics;
+
 use self::input::Input;
 use self::output::Output;
+use self::progress::Message as ProgressMessage;
 
 /// The overriden executable name used when the compiler is run as a library.
 pub static EXECUTABLE: OnceCell<PathBuf> = OnceCell::new();
@@ -30,25 +39,9 @@ pub fn run() -> anyhow::Result<()> {
     let mut buffer = Vec::with_capacity(16384);
     stdin.read_to_end(&mut buffer).expect("Stdin reading error");
 
-    let mut deserializer = serde_json::Deserializer::from_slice(buffer.as_slice());
-    deserializer.disable_recursion_limit();
-    let deserializer = serde_stacker::Deserializer::new(&mut deserializer);
-    let input = Input::deserialize(deserializer)?;
-
-    if input.enable_test_encoding {
-        zkevm_assembly::set_encoding_mode(zkevm_assembly::RunningVmEncodingMode::Testing);
-    }
-    let result = input.contract.compile(
-        input.project,
-        input.optimizer_settings,
-        input.is_system_mode,
-        input.include_metadata_hash,
-        input.debug_config,
-    );
-
-    match result {
-        Ok(build) => {
-            let output = Output::new(build);
+    let input = parse_input(buffer.as_slice())?;
+    match compile(input) {
+        Ok(output) => {
             let json = serde_json::to_vec(&output).expect("Always valid");
             stdout
                 .write_all(json.as_slice())
@@ -66,47 +59,108 @@ pub fn run() -> anyhow::Result<()> {
 }
 
 ///
-/// Runs this process recursively to compile a single contract.
+/// Read inputs from `stdin` line by line, compile the contracts, and write a result line
+/// to `stdout` for each of them, until `stdin` is closed.
+///
+/// The LLVM target is initialized only once for all the contracts compiled by the process.
+/// The finished compilation stages are reported before each result line.
 ///
-pub fn call(input: Input) -> anyhow::Result<Output> {
-    let input_json = serde_json::to_vec(&input).expect("Always valid");
+pub fn run_persistent() -> anyhow::Result<()> {
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
 
-    let executable = match EXECUTABLE.get() {
-        Some(executable) => executable.to_owned(),
-        None => std::env::current_exe()?,
-    };
+    progress::enable();
 
-    let mut command = Command::new(executable.as_path());
-    command.stdin(std::process::Stdio::piped());
-    command.stdout(std::process::Stdio::piped());
-    command.stderr(std::process::Stdio::piped());
-    command.arg("--recursive-process");
-    let process = command.spawn().map_err(|error| {
-        anyhow::anyhow!("{:?} subprocess spawning error: {:?}", executable, error)
-    })?;
-
-    process
-        .stdin
-        .as_ref()
-        .ok_or_else(|| anyhow::anyhow!("{:?} stdin getting error", executable))?
-        .write_all(input_json.as_slice())
-        .map_err(|error| anyhow::anyhow!("{:?} stdin writing error: {:?}", executable, error))?;
-    let output = process.wait_with_output().map_err(|error| {
-        anyhow::anyhow!("{:?} subprocess output error: {:?}", executable, error)
-    })?;
-    if !output.status.success() {
-        anyhow::bail!(
-            "{}",
-            String::from_utf8_lossy(output.stderr.as_slice()).to_string(),
-        );
+    let mut line = String::with_capacity(16384);
+    loop {
+        line.clear();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+
+        let result = parse_input(line.trim_end().as_bytes())
+            .and_then(compile)
+            .map_err(|error| error.to_string());
+        let mut json = serde_json::to_vec(&ProgressMessage::Result(result)).expect("Always valid");
+        json.push(b'\n');
+        stdout
+            .write_all(json.as_slice())
+            .expect("Stdout writing error");
+        stdout.flush().expect("Stdout flushing error");
     }
+}
 
-    let output: Output = serde_json::from_slice(output.stdout.as_slice()).map_err(|error| {
-        anyhow::anyhow!(
-            "{:?} subprocess output parsing error: {}",
-            executable,
-            error,
+///
+/// Parses the process input without the recursion limit.
+///
+fn parse_input(buffer: &[u8]) -> anyhow::Result<Input> {
+    let mut deserializer = serde_json::Deserializer::from_slice(buffer);
+    deserializer.disable_recursion_limit();
+    let deserializer = serde_stacker::Deserializer::new(&mut deserializer);
+    let input = Input::deserialize(deserializer)?;
+    Ok(input)
+}
+
+///
+/// Compiles a contract and collects its statistics.
+///
+fn compile(input: Input) -> anyhow::Result<Output> {
+    if input.enable_test_encoding {
+        zkevm_assembly::set_encoding_mode(zkevm_assembly::RunningVmEncodingMode::Testing);
+    }
+    let ir_size = input.contract.ir.size();
+    let source_histogram = if input.project.output_histogram {
+        Some(input.contract.ir.instruction_histogram())
+    } else {
+        None
+    };
+    let optimizer_settings = input.optimizer_settings.clone();
+    let start_time = Instant::now();
+    let mut build = if input.determinism_check {
+        progress::stop();
+        let reference = input.contract.clone().compile(
+            input.project.clone(),
+            input.target,
+            input.optimizer_settings.clone(),
+            input.is_system_mode,
+            input.include_metadata_hash,
+            None,
+        );
+        progress::start();
+        let result = input.contract.compile(
+            input.project,
+            input.target,
+            input.optimizer_settings,
+            input.is_system_mode,
+            input.include_metadata_hash,
+            input.debug_config,
+        );
+        match (reference, result) {
+            (Ok(reference), Ok(build)) => build.check_determinism(&reference).map(|()| build),
+            (Err(error), _) | (_, Err(error)) => Err(error),
+        }
+    } else {
+        progress::start();
+        input.contract.compile(
+            input.project,
+            input.target,
+            input.optimizer_settings,
+            input.is_system_mode,
+            input.include_metadata_hash,
+            input.debug_config,
         )
-    })?;
-    Ok(output)
+    }?;
+
+    build.statistics = Some(Statistics::new(
+        start_time.elapsed(),
+        ir_size,
+        &build.build,
+        &optimizer_settings,
+    ));
+    build.histogram = source_histogram.map(|source_histogram| {
+        Histogram::new(source_histogram, build.build.assembly_text.as_str())
+    });
+    build.code_sections = Some(CodeSections::new(build.build.assembly_text.as_str()));
+
+    Ok(Output::new(build))
 }
@@ -0,0 +1,300 @@
+//!
+//! The pool of persistent worker processes.
+//!
+
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Child;
+use std::process::ChildStderr;
+use std::process::ChildStdin;
+use std::process::ChildStdout;
+use std::process::Command;
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::time::Instant;
+
+use once_cell::sync::OnceCell;
+
+use super::input::Input;
+use super::output::Output;
+use super::progress::Message as ProgressMessage;
+use super::timeout::Timeout;
+use super::EXECUTABLE;
+
+///
+/// The pool of persistent worker processes.
+///
+/// Each worker initializes the LLVM target once and then compiles as many contracts as it
+/// is given, so the per-contract process startup and LLVM initialization overhead is paid
+/// only once per worker instead of once per contract.
+///
+/// The pool is shared by all projects compiled by the process. It is never dropped, so the idle
+/// workers are not terminated explicitly, but exit as soon as their input is closed along with
+/// the compiler process.
+///
+#[derive(Debug)]
+pub struct Pool {
+    /// The compiler executable path.
+    executable: PathBuf,
+    /// The idle workers.
+    idle: Mutex<Vec<Worker>>,
+}
+
+/// The pool shared by all projects compiled by the process.
+static GLOBAL: OnceCell<Pool> = OnceCell::new();
+
+///
+/// The persistent worker process.
+///
+#[derive(Debug)]
+struct Worker {
+    /// The child process.
+    child: Child,
+    /// The child process input.
+    stdin: ChildStdin,
+    /// The child process output lines, read by a separate thread to enforce the time budget.
+    lines: mpsc::Receiver<std::io::Result<String>>,
+    /// The thread forwarding the child process error output and returning its last lines.
+    stderr: Option<JoinHandle<VecDeque<String>>>,
+    /// Whether the worker has enabled the test bytecode encoding, which cannot be reverted.
+    enable_test_encoding: bool,
+}
+
+impl Pool {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn try_new() -> anyhow::Result<Self> {
+        let executable = match EXECUTABLE.get() {
+            Some(executable) => executable.to_owned(),
+            None => std::env::current_exe()?,
+        };
+
+        Ok(Self {
+            executable,
+            idle: Mutex::new(Vec::new()),
+        })
+    }
+
+    ///
+    /// Returns the pool shared by all projects compiled by the process.
+    ///
+    pub fn global() -> anyhow::Result<&'static Self> {
+        GLOBAL.get_or_try_init(Self::try_new)
+    }
+
+    ///
+    /// Compiles a contract with an idle worker, spawning a new one if there is none.
+    ///
+    /// The worker is returned to the pool only if it has handled the request successfully,
+    /// so a crashed or desynchronized worker is never reused.
+    ///
+    /// If the `budget` is exceeded, the worker is killed and the [`Timeout`] error is returned.
+    ///
+    pub fn call(&self, input: Input, budget: Option<Duration>) -> anyhow::Result<Output> {
+        let worker = {
+            let mut idle = self.idle.lock().expect("Sync");
+            idle.iter()
+                .position(|worker| worker.enable_test_encoding == input.enable_test_encoding)
+                .map(|index| idle.swap_remove(index))
+        };
+        let mut worker = match worker {
+            Some(worker) => worker,
+            None => Worker::spawn(self.executable.as_path(), input.enable_test_encoding)?,
+        };
+
+        let result = match worker.call(&input, budget) {
+            Ok(result) => result,
+            Err(error) if error.is::<Timeout>() => {
+                worker.kill();
+                return Err(error);
+            }
+            Err(error) => anyhow::bail!("{:?} subprocess error: {}", self.executable, error),
+        };
+        self.idle.lock().expect("Sync").push(worker);
+
+        result.map_err(|error| anyhow::anyhow!("{}", error))
+    }
+}
+
+impl Worker {
+    /// The number of the last error output lines attached to the unexpected termination error.
+    const STDERR_TAIL_LINES: usize = 32;
+
+    ///
+    /// Spawns a new worker process.
+    ///
+    pub fn spawn(executable: &std::path::Path, enable_test_encoding: bool) -> anyhow::Result<Self> {
+        let mut command = Command::new(executable);
+        command.stdin(std::process::Stdio::piped());
+        command.stdout(std::process::Stdio::piped());
+        command.stderr(std::process::Stdio::piped());
+        command.arg("--recursive-process-persistent");
+        let mut child = command.spawn().map_err(|error| {
+            anyhow::anyhow!("{:?} subprocess spawning error: {:?}", executable, error)
+        })?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("{:?} stdin getting error", executable))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("{:?} stdout getting error", executable))?;
+        let stderr = child
+            .stderr
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("{:?} stderr getting error", executable))?;
+        let lines = Self::read_lines(stdout);
+        let stderr = Self::read_stderr(stderr);
+
+        Ok(Self {
+            child,
+            stdin,
+            lines,
+            stderr: Some(stderr),
+            enable_test_encoding,
+        })
+    }
+
+    ///
+    /// Sends the input to the worker and reads its result.
+    ///
+    /// The outer error means the worker itself has failed or exceeded the `budget`, and the
+    /// inner one is the contract compilation error.
+    ///
+    pub fn call(
+        &mut self,
+        input: &Input,
+        budget: Option<Duration>,
+    ) -> anyhow::Result<Result<Output, String>> {
+        let mut input_json = serde_json::to_vec(input).expect("Always valid");
+        input_json.push(b'\n');
+        self.stdin
+            .write_all(input_json.as_slice())
+            .map_err(|error| anyhow::anyhow!("stdin writing error: {:?}", error))?;
+        self.stdin
+            .flush()
+            .map_err(|error| anyhow::anyhow!("stdin flushing error: {:?}", error))?;
+
+        let deadline = budget.map(|budget| (budget, Instant::now() + budget));
+        let mut stages = Vec::new();
+        loop {
+            let line = match deadline {
+                Some((budget, deadline)) => {
+                    match self
+                        .lines
+                        .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                    {
+                        Ok(line) => Some(line),
+                        Err(mpsc::RecvTimeoutError::Timeout) => {
+                            return Err(
+                                Timeout::new(budget, stages, &input.optimizer_settings).into()
+                            );
+                        }
+                        Err(mpsc::RecvTimeoutError::Disconnected) => None,
+                    }
+                }
+                None => self.lines.recv().ok(),
+            };
+            let line = match line {
+                Some(line) => {
+                    line.map_err(|error| anyhow::anyhow!("stdout reading error: {:?}", error))?
+                }
+                None => {
+                    let status = self.child.wait()?;
+                    let stderr = self
+                        .stderr
+                        .take()
+                        .and_then(|stderr| stderr.join().ok())
+                        .unwrap_or_default();
+                    if stderr.is_empty() {
+                        anyhow::bail!("the worker has terminated unexpectedly: {}", status);
+                    }
+                    anyhow::bail!(
+                        "the worker has terminated unexpectedly: {}\n{}",
+                        status,
+                        Vec::from(stderr).join("\n")
+                    );
+                }
+            };
+
+            let mut deserializer = serde_json::Deserializer::from_str(line.trim_end());
+            deserializer.disable_recursion_limit();
+            let deserializer = serde_stacker::Deserializer::new(&mut deserializer);
+            let message: ProgressMessage = serde::Deserialize::deserialize(deserializer)
+                .map_err(|error| anyhow::anyhow!("output parsing error: {}", error))?;
+            match message {
+                ProgressMessage::Stage(stage) => stages.push(stage),
+                ProgressMessage::Result(result) => return Ok(result),
+            }
+        }
+    }
+
+    ///
+    /// Kills the worker, e.g. if it has exceeded the time budget.
+    ///
+    pub fn kill(mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+
+    ///
+    /// Spawns the thread forwarding the worker output lines to the returned channel.
+    ///
+    fn read_lines(stdout: ChildStdout) -> mpsc::Receiver<std::io::Result<String>> {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut stdout = BufReader::new(stdout);
+            loop {
+                let mut line = String::new();
+                match stdout.read_line(&mut line) {
+                    Ok(0) => break,
+                    Ok(_) => {
+                        if sender.send(Ok(line)).is_err() {
+                            break;
+                        }
+                    }
+                    Err(error) => {
+                        let _ = sender.send(Err(error));
+                        break;
+                    }
+                }
+            }
+        });
+        receiver
+    }
+
+    ///
+    /// Spawns the thread forwarding the worker error output to the compiler one.
+    ///
+    /// The thread returns the last lines once the worker has exited, so they can be attached
+    /// to the unexpected termination error.
+    ///
+    fn read_stderr(stderr: ChildStderr) -> JoinHandle<VecDeque<String>> {
+        std::thread::spawn(move || {
+            let mut tail = VecDeque::with_capacity(Self::STDERR_TAIL_LINES);
+            let mut stderr = BufReader::new(stderr);
+            loop {
+                let mut line = String::new();
+                match stderr.read_line(&mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {
+                        eprint!("{line}");
+                        if tail.len() == Self::STDERR_TAIL_LINES {
+                            tail.pop_front();
+                        }
+                        tail.push_back(line.trim_end().to_owned());
+                    }
+                }
+            }
+            tail
+        })
+    }
+}
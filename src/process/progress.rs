@@ -0,0 +1,126 @@
+//!
+//! The compilation progress.
+//!
+
+use std::io::Write;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::output::Output;
+
+/// Whether the progress is reported, which is only done by the persistent workers.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The start time of the contract being compiled.
+static START: Mutex<Option<Instant>> = Mutex::new(None);
+
+///
+/// The contract compilation stage.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Stage {
+    /// The IR checks and transformations before the translation.
+    Preprocessing,
+    /// The translation to LLVM IR.
+    Translation,
+    /// The LLVM optimization, code generation, and assembling.
+    Build,
+}
+
+impl Stage {
+    ///
+    /// Returns the stage following this one, if any.
+    ///
+    pub fn next(&self) -> Option<Self> {
+        match self {
+            Self::Preprocessing => Some(Self::Translation),
+            Self::Translation => Some(Self::Build),
+            Self::Build => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Preprocessing => write!(f, "preprocessing"),
+            Self::Translation => write!(f, "LLVM IR translation"),
+            Self::Build => write!(f, "LLVM optimization and code generation"),
+        }
+    }
+}
+
+///
+/// The finished stage with its completion time.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct StageTime {
+    /// The finished stage.
+    pub stage: Stage,
+    /// The time since the contract compilation start in milliseconds.
+    pub time: u64,
+}
+
+///
+/// The persistent worker output line.
+///
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Message {
+    /// A stage of the contract being compiled has finished.
+    Stage(StageTime),
+    /// The contract compilation result.
+    Result(Result<Output, String>),
+}
+
+///
+/// Enables the progress reporting to `stdout`.
+///
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+///
+/// Marks the start of a contract compilation.
+///
+pub fn start() {
+    *START.lock().expect("Sync") = Some(Instant::now());
+}
+
+///
+/// Stops reporting the stages until the next compilation start, e.g. during the reference
+/// compilation of the determinism check.
+///
+pub fn stop() {
+    *START.lock().expect("Sync") = None;
+}
+
+///
+/// Reports the `stage` of the contract being compiled as finished.
+///
+/// Does nothing unless the progress reporting is enabled and a compilation is started. The
+/// `stdout` errors are ignored, as they are reported by the result writing.
+///
+pub fn finish(stage: Stage) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let time = match *START.lock().expect("Sync") {
+        Some(start) => start.elapsed().as_millis() as u64,
+        None => return,
+    };
+
+    let mut json =
+        serde_json::to_vec(&Message::Stage(StageTime { stage, time })).expect("Always valid");
+    json.push(b'\n');
+    let mut stdout = std::io::stdout().lock();
+    let _ = stdout
+        .write_all(json.as_slice())
+        .and_then(|()| stdout.flush());
+}
@@ -0,0 +1,150 @@
+//!
+//! The compile time budget overrun.
+//!
+
+use std::time::Duration;
+
+use super::progress::Stage;
+use super::progress::StageTime;
+
+///
+/// The compile time budget overrun.
+///
+/// The worker compiling the contract is killed as soon as the budget is exceeded, so only the
+/// stages finished by then are known.
+///
+#[derive(Debug, Clone)]
+pub struct Timeout {
+    /// The compile time budget.
+    pub budget: Duration,
+    /// The stages finished within the budget.
+    pub stages: Vec<StageTime>,
+    /// The interrupted stage.
+    pub stage: Stage,
+    /// The suggested settings.
+    pub suggestions: Vec<String>,
+}
+
+impl Timeout {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        budget: Duration,
+        stages: Vec<StageTime>,
+        optimizer_settings: &compiler_llvm_context::OptimizerSettings,
+    ) -> Self {
+        let stage = match stages.last() {
+            Some(last) => last.stage.next().unwrap_or(last.stage),
+            None => Stage::Preprocessing,
+        };
+
+        let mut suggestions = Vec::new();
+        if stage == Stage::Build {
+            if optimizer_settings.to_string()
+                != compiler_llvm_context::OptimizerSettings::none().to_string()
+            {
+                suggestions.push(
+                    "lower the optimization mode, e.g. `--optimization 1`, to shorten the LLVM optimization".to_owned(),
+                );
+            }
+            if optimizer_settings.is_verify_each_enabled {
+                suggestions.push(
+                    "drop `--llvm-verify-each`, which verifies the IR after every LLVM pass"
+                        .to_owned(),
+                );
+            }
+        }
+        suggestions.push(format!(
+            "raise the budget, e.g. `--contract-time-budget {}`",
+            budget.as_secs().max(1) * 2
+        ));
+
+        Self {
+            budget,
+            stages,
+            stage,
+            suggestions,
+        }
+    }
+}
+
+impl std::fmt::Display for Timeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the compile time budget of {} s has been exceeded at the {} stage",
+            self.budget.as_secs(),
+            self.stage
+        )?;
+        if !self.stages.is_empty() {
+            let mut previous = 0;
+            let stages = self
+                .stages
+                .iter()
+                .map(|stage| {
+                    let time = stage.time.saturating_sub(previous);
+                    previous = stage.time;
+                    format!("{}: {} ms", stage.stage, time)
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            write!(f, " (finished {stages})")?;
+        }
+        write!(f, ". Suggestions: {}", self.suggestions.join("; "))
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Timeout;
+    use crate::process::progress::Stage;
+    use crate::process::progress::StageTime;
+
+    #[test]
+    fn interrupted_build() {
+        let timeout = Timeout::new(
+            Duration::from_secs(30),
+            vec![
+                StageTime {
+                    stage: Stage::Preprocessing,
+                    time: 120,
+                },
+                StageTime {
+                    stage: Stage::Translation,
+                    time: 2120,
+                },
+            ],
+            &compiler_llvm_context::OptimizerSettings::cycles(),
+        );
+
+        assert_eq!(timeout.stage, Stage::Build);
+        assert_eq!(timeout.suggestions.len(), 2);
+        assert_eq!(
+            timeout.to_string(),
+            "the compile time budget of 30 s has been exceeded at the LLVM optimization and code generation stage \
+(finished preprocessing: 120 ms, LLVM IR translation: 2000 ms). \
+Suggestions: lower the optimization mode, e.g. `--optimization 1`, to shorten the LLVM optimization; \
+raise the budget, e.g. `--contract-time-budget 60`"
+        );
+    }
+
+    #[test]
+    fn interrupted_preprocessing() {
+        let timeout = Timeout::new(
+            Duration::from_secs(10),
+            vec![],
+            &compiler_llvm_context::OptimizerSettings::none(),
+        );
+
+        assert_eq!(timeout.stage, Stage::Preprocessing);
+        assert_eq!(
+            timeout.suggestions,
+            vec!["raise the budget, e.g. `--contract-time-budget 20`".to_owned()]
+        );
+    }
+}
@@ -7,12 +7,22 @@ pub mod llvm_ir;
 pub mod yul;
 pub mod zkasm;
 
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
 use serde::Deserialize;
 use serde::Serialize;
 
 use crate::evmla::assembly::Assembly;
+use crate::evmla::ethereal_ir::visitor::Visitor as EtherealIRVisitor;
 use crate::solc::standard_json::output::contract::evm::extra_metadata::ExtraMetadata;
+use crate::yul::immutable_keys::ImmutableKeys as YulImmutableKeys;
+use crate::yul::lexer::token::lexeme::Lexeme;
+use crate::yul::lexer::Lexer;
+use crate::yul::parser::statement::expression::function_call::name::Name as YulFunctionName;
 use crate::yul::parser::statement::object::Object;
+use crate::yul::visitor::Visitor as YulVisitor;
 
 use self::evmla::EVMLA;
 use self::llvm_ir::LLVMIR;
@@ -40,8 +50,8 @@ impl IR {
     ///
     /// A shortcut constructor.
     ///
-    pub fn new_yul(source_code: String, object: Object) -> Self {
-        Self::Yul(Yul::new(source_code, object))
+    pub fn new_yul(source_code: Arc<str>, object: Object, solc_output: Option<String>) -> Self {
+        Self::Yul(Yul::new(source_code, object, solc_output))
     }
 
     ///
@@ -64,6 +74,105 @@ impl IR {
     pub fn new_zkasm(path: String, source: String) -> Self {
         Self::ZKASM(ZKASM::new(path, source))
     }
+
+    ///
+    /// Returns the IR size in bytes.
+    ///
+    /// The EVM legacy assembly size is measured in its JSON representation.
+    ///
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Yul(inner) => inner.source_code.len(),
+            Self::EVMLA(inner) => serde_json::to_vec(&inner.assembly)
+                .map(|json| json.len())
+                .unwrap_or_default(),
+            Self::LLVMIR(inner) => inner.source.len(),
+            Self::ZKASM(inner) => inner.source.len(),
+        }
+    }
+
+    ///
+    /// Returns the counts of the source EVM instructions or Yul built-in function calls.
+    ///
+    /// The LLVM IR and zkEVM assembly are not counted, as they are not translated from EVM.
+    ///
+    pub fn instruction_histogram(&self) -> BTreeMap<String, usize> {
+        let mut histogram = BTreeMap::new();
+        match self {
+            Self::Yul(inner) => {
+                let mut lexer = Lexer::new(inner.source_code.clone());
+                while let Ok(token) = lexer.next() {
+                    match token.lexeme {
+                        Lexeme::Identifier(identifier) => {
+                            if let YulFunctionName::UserDefined(_) =
+                                YulFunctionName::from(identifier.inner.as_str())
+                            {
+                                continue;
+                            }
+                            *histogram.entry(identifier.inner).or_insert(0) += 1;
+                        }
+                        Lexeme::EndOfFile => break,
+                        _ => {}
+                    }
+                }
+            }
+            Self::EVMLA(inner) => inner.assembly.instruction_histogram(&mut histogram),
+            Self::LLVMIR(_inner) => {}
+            Self::ZKASM(_inner) => {}
+        }
+        histogram
+    }
+
+    ///
+    /// Returns the keys of the immutables loaded or assigned by the IR, which the code generator
+    /// allocates the slots for.
+    ///
+    pub fn immutable_keys(&self) -> BTreeSet<String> {
+        match self {
+            Self::Yul(inner) => {
+                let mut collector = YulImmutableKeys::default();
+                collector.visit_object(&inner.object);
+                collector.keys
+            }
+            Self::EVMLA(inner) => {
+                let mut keys = BTreeSet::new();
+                inner.assembly.immutable_keys(&mut keys);
+                keys
+            }
+            Self::LLVMIR(_inner) => BTreeSet::new(),
+            Self::ZKASM(_inner) => BTreeSet::new(),
+        }
+    }
+
+    ///
+    /// Traverses the Yul AST with the `visitor`. Does nothing if the IR is not Yul.
+    ///
+    pub fn visit_yul<V>(&self, visitor: &mut V)
+    where
+        V: YulVisitor,
+    {
+        if let Self::Yul(inner) = self {
+            visitor.visit_object(&inner.object);
+        }
+    }
+
+    ///
+    /// Builds the Ethereal IR with the `solc_version` and traverses it with the `visitor`.
+    /// Does nothing if the IR is not EVM legacy assembly.
+    ///
+    pub fn visit_ethereal_ir<V>(
+        &self,
+        solc_version: &semver::Version,
+        visitor: &mut V,
+    ) -> anyhow::Result<()>
+    where
+        V: EtherealIRVisitor,
+    {
+        if let Self::EVMLA(inner) = self {
+            visitor.visit_ethereal_ir(&inner.assembly.ethereal_ir(solc_version)?);
+        }
+        Ok(())
+    }
 }
 
 impl<D> compiler_llvm_context::WriteLLVM<D> for IR
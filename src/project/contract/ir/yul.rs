@@ -2,6 +2,8 @@
 //! The contract Yul source code.
 //!
 
+use std::sync::Arc;
+
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -13,19 +15,23 @@ use crate::yul::parser::statement::object::Object;
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Yul {
     /// The Yul source code.
-    pub source_code: String,
+    pub source_code: Arc<str>,
     /// The Yul AST object.
     pub object: Object,
+    /// The `solc` output the source code is taken from, e.g. `irOptimized`, if it is produced by
+    /// `solc` in the Yul pipeline.
+    pub solc_output: Option<String>,
 }
 
 impl Yul {
     ///
     /// A shortcut constructor.
     ///
-    pub fn new(source_code: String, object: Object) -> Self {
+    pub fn new(source_code: Arc<str>, object: Object, solc_output: Option<String>) -> Self {
         Self {
             source_code,
             object,
+            solc_output,
         }
     }
 }
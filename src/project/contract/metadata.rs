@@ -3,6 +3,9 @@
 //!
 
 use serde::Serialize;
+use sha3::Digest;
+
+use crate::solc::standard_json::input::settings::debug::revert_strings::RevertStrings;
 
 ///
 /// The Solidity contract metadata.
@@ -17,6 +20,27 @@ pub struct Metadata {
     pub zk_version: semver::Version,
     /// The zkEVM compiler stringified optimizer settings.
     pub optimizer_settings: String,
+    /// The signatures of the functions instrumented with the reentrancy guard.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reentrancy_guarded: Option<Vec<String>>,
+    /// The `solc` output lowered in the Yul pipeline, which is `irOptimized`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yul_source: Option<String>,
+    /// The `solc` version newer than the last supported one, forced with `--force`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unsupported_solc_version: Option<semver::Version>,
+    /// The revert strings mode applied by zksolc, which is not seen by `solc`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_strings: Option<RevertStrings>,
+    /// Whether the function dispatch and code are ordered by selector.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_by_selector: Option<bool>,
+    /// The threshold of the constant-sized loop unrolling applied by zksolc.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loop_unroll_threshold: Option<usize>,
+    /// The EVM legacy assembly stack spilling threshold applied by zksolc.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evmla_stack_spill_threshold: Option<usize>,
 }
 
 impl Metadata {
@@ -32,6 +56,119 @@ impl Metadata {
             solc_metadata,
             zk_version,
             optimizer_settings: optimizer_settings.to_string(),
+            reentrancy_guarded: None,
+            yul_source: None,
+            unsupported_solc_version: None,
+            revert_strings: None,
+            order_by_selector: None,
+            loop_unroll_threshold: None,
+            evmla_stack_spill_threshold: None,
+        }
+    }
+
+    ///
+    /// Hashes the metadata JSON without allocating its intermediate string representation.
+    ///
+    pub fn keccak256(&self) -> [u8; compiler_common::BYTE_LENGTH_FIELD] {
+        let mut hasher = sha3::Keccak256::new();
+        serde_json::to_writer(&mut hasher, self).expect("Always valid");
+        hasher.finalize().into()
+    }
+
+    ///
+    /// Converts the metadata into a JSON value, moving the `solc` metadata instead of cloning it.
+    ///
+    pub fn into_json(self) -> serde_json::Value {
+        let mut object = serde_json::Map::with_capacity(8);
+        object.insert("solc_metadata".to_owned(), self.solc_metadata);
+        object.insert(
+            "zk_version".to_owned(),
+            serde_json::Value::String(self.zk_version.to_string()),
+        );
+        object.insert(
+            "optimizer_settings".to_owned(),
+            serde_json::Value::String(self.optimizer_settings),
+        );
+        if let Some(reentrancy_guarded) = self.reentrancy_guarded {
+            object.insert(
+                "reentrancy_guarded".to_owned(),
+                serde_json::Value::Array(
+                    reentrancy_guarded
+                        .into_iter()
+                        .map(serde_json::Value::String)
+                        .collect(),
+                ),
+            );
+        }
+        if let Some(yul_source) = self.yul_source {
+            object.insert(
+                "yul_source".to_owned(),
+                serde_json::Value::String(yul_source),
+            );
+        }
+        if let Some(unsupported_solc_version) = self.unsupported_solc_version {
+            object.insert(
+                "unsupported_solc_version".to_owned(),
+                serde_json::Value::String(unsupported_solc_version.to_string()),
+            );
         }
+        if let Some(revert_strings) = self.revert_strings {
+            object.insert(
+                "revert_strings".to_owned(),
+                serde_json::to_value(revert_strings).expect("Always valid"),
+            );
+        }
+        if let Some(order_by_selector) = self.order_by_selector {
+            object.insert(
+                "order_by_selector".to_owned(),
+                serde_json::Value::Bool(order_by_selector),
+            );
+        }
+        if let Some(loop_unroll_threshold) = self.loop_unroll_threshold {
+            object.insert(
+                "loop_unroll_threshold".to_owned(),
+                serde_json::Value::from(loop_unroll_threshold),
+            );
+        }
+        if let Some(evmla_stack_spill_threshold) = self.evmla_stack_spill_threshold {
+            object.insert(
+                "evmla_stack_spill_threshold".to_owned(),
+                serde_json::Value::from(evmla_stack_spill_threshold),
+            );
+        }
+        serde_json::Value::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sha3::Digest;
+
+    use crate::solc::standard_json::input::settings::debug::revert_strings::RevertStrings;
+
+    use super::Metadata;
+
+    #[test]
+    fn same_as_serialized() {
+        let mut metadata = Metadata::new(
+            serde_json::Value::String("{\"compiler\":{}}".to_owned()),
+            semver::Version::new(1, 3, 13),
+            compiler_llvm_context::OptimizerSettings::cycles(),
+        );
+        metadata.reentrancy_guarded = Some(vec!["withdraw(uint256)".to_owned()]);
+        metadata.yul_source = Some("irOptimized".to_owned());
+        metadata.unsupported_solc_version = Some(semver::Version::new(0, 8, 21));
+        metadata.revert_strings = Some(RevertStrings::Id);
+        metadata.order_by_selector = Some(true);
+        metadata.loop_unroll_threshold = Some(16);
+        metadata.evmla_stack_spill_threshold = Some(8);
+
+        let string = serde_json::to_string(&metadata).expect("Always valid");
+        let expected: [u8; compiler_common::BYTE_LENGTH_FIELD] =
+            sha3::Keccak256::digest(string.as_bytes()).into();
+        let value = serde_json::to_value(&metadata).expect("Always valid");
+
+        assert_eq!(metadata.keccak256(), expected);
+        assert_eq!(metadata.into_json(), value);
     }
 }
@@ -5,16 +5,28 @@
 pub mod ir;
 pub mod metadata;
 
+use std::collections::BTreeSet;
 use std::collections::HashSet;
 
 use serde::Deserialize;
 use serde::Serialize;
-use sha3::Digest;
 
 use compiler_llvm_context::WriteLLVM;
 
+use crate::build::assembly_check::AssemblyCheck;
 use crate::build::contract::Contract as ContractBuild;
+use crate::build::immutable::Immutable;
+use crate::build::metadata_hash_placement::MetadataHashPlacement;
+use crate::plugin::reentrancy_guard::ReentrancyGuard;
+use crate::process::progress::Stage as ProcessStage;
 use crate::project::Project;
+use crate::solc::standard_json::input::settings::debug::revert_strings::RevertStrings as SolcStandardJsonInputSettingsDebugRevertStrings;
+use crate::solc::Compiler as SolcCompiler;
+use crate::target::Target;
+use crate::yul::data_references::DataReferences;
+use crate::yul::function_order::FunctionOrder;
+use crate::yul::loop_unrolling::LoopUnrolling;
+use crate::yul::revert_strings::RevertStringIds;
 
 use self::ir::IR;
 use self::metadata::Metadata;
@@ -30,6 +42,8 @@ pub struct Contract {
     pub ir: IR,
     /// The metadata JSON.
     pub metadata_json: serde_json::Value,
+    /// The immutables to report the slots of.
+    pub immutables: Vec<Immutable>,
 }
 
 impl Contract {
@@ -52,6 +66,7 @@ impl Contract {
                     "source_version": source_version.to_string(),
                 })
             }),
+            immutables: vec![],
         }
     }
 
@@ -88,6 +103,7 @@ impl Contract {
     pub fn compile(
         mut self,
         project: Project,
+        target: Target,
         optimizer_settings: compiler_llvm_context::OptimizerSettings,
         is_system_mode: bool,
         include_metadata_hash: bool,
@@ -95,23 +111,99 @@ impl Contract {
     ) -> anyhow::Result<ContractBuild> {
         let llvm = inkwell::context::Context::create();
         let optimizer = compiler_llvm_context::Optimizer::new(optimizer_settings);
+        let metadata_hash_placement = project.metadata_hash_placement;
 
-        let metadata = Metadata::new(
-            self.metadata_json.take(),
+        if let IR::Yul(ref yul) = self.ir {
+            DataReferences::check(&yul.object, &project.identifier_paths)?;
+        }
+
+        let reentrancy_guarded = if project.reentrancy_guard.is_empty() {
+            None
+        } else {
+            match self.ir {
+                IR::Yul(ref mut yul) => {
+                    let guard = ReentrancyGuard::try_new(
+                        project.reentrancy_guard.as_slice(),
+                        &self.metadata_json,
+                    )?;
+                    crate::plugin::visit_yul_with(&guard, self.path.as_str(), &mut yul.object)?;
+                    Some(guard.instrumented())
+                }
+                IR::EVMLA(_) => {
+                    anyhow::bail!("The reentrancy guard is only supported in the Yul pipeline")
+                }
+                IR::LLVMIR(_) | IR::ZKASM(_) => None,
+            }
+        };
+
+        if project.order_by_selector {
+            match self.ir {
+                IR::Yul(ref mut yul) => FunctionOrder::by_selector(&mut yul.object),
+                IR::EVMLA(_) => {
+                    anyhow::bail!("The selector-based function ordering is only supported in the Yul pipeline")
+                }
+                IR::LLVMIR(_) | IR::ZKASM(_) => {}
+            }
+        }
+
+        let revert_string_ids = match self.ir {
+            IR::Yul(ref mut yul) if project.revert_string_ids => {
+                Some(RevertStringIds::substitute(&mut yul.object)?)
+            }
+            _ => None,
+        };
+
+        let constant_loops = match self.ir {
+            IR::Yul(ref mut yul)
+                if project.loop_unroll_threshold.is_some() || project.output_constant_loops =>
+            {
+                let constant_loops =
+                    LoopUnrolling::new(project.loop_unroll_threshold).apply(&mut yul.object);
+                Some(constant_loops).filter(|_| project.output_constant_loops)
+            }
+            _ => None,
+        };
+
+        let mut metadata = Metadata::new(
+            self.metadata_json.clone(),
             semver::Version::parse(env!("CARGO_PKG_VERSION")).expect("Always valid"),
             optimizer.settings().to_owned(),
         );
-        let metadata_json = serde_json::to_value(&metadata).expect("Always valid");
+        metadata.reentrancy_guarded = reentrancy_guarded;
+        if SolcCompiler::is_unsupported(&project.version) {
+            metadata.unsupported_solc_version = Some(project.version.to_owned());
+        }
+        if project.order_by_selector {
+            metadata.order_by_selector = Some(true);
+        }
+        match self.ir {
+            IR::Yul(ref yul) => {
+                metadata.yul_source = yul.solc_output.to_owned();
+                metadata.loop_unroll_threshold = project.loop_unroll_threshold;
+            }
+            IR::EVMLA(_) => {
+                metadata.evmla_stack_spill_threshold = project.evmla_stack_spill_threshold
+            }
+            IR::LLVMIR(_) | IR::ZKASM(_) => {}
+        }
+        if revert_string_ids.is_some() {
+            metadata.revert_strings = Some(SolcStandardJsonInputSettingsDebugRevertStrings::Id);
+        }
         let metadata_hash: Option<[u8; compiler_common::BYTE_LENGTH_FIELD]> =
             if include_metadata_hash {
-                let metadata_string = serde_json::to_string(&metadata).expect("Always valid");
-                Some(sha3::Keccak256::digest(metadata_string.as_bytes()).into())
+                Some(metadata.keccak256())
             } else {
                 None
             };
+        let metadata_json = metadata.into_json();
 
         let version = project.version.clone();
         let identifier = self.identifier().to_owned();
+        if let IR::EVMLA(ref mut evmla) = self.ir {
+            evmla.assembly.stack_spill_threshold = project.evmla_stack_spill_threshold;
+            evmla.assembly.trace_tag = project.evmla_trace_tag;
+            evmla.assembly.debug_locations = project.evmla_debug_locations;
+        }
 
         let module = match self.ir {
             IR::LLVMIR(ref llvm_ir) => {
@@ -124,15 +216,28 @@ impl Contract {
                     .map_err(|error| anyhow::anyhow!(error.to_string()))?
             }
             IR::ZKASM(ref zkasm) => {
+                if cfg!(debug_assertions) {
+                    AssemblyCheck::check(self.path.as_str(), zkasm.source.as_str())?;
+                }
+                let (assembly_text, assembler_metadata_hash) =
+                    metadata_hash_placement.place(zkasm.source.as_str(), metadata_hash);
                 let build = compiler_llvm_context::build_assembly_text(
                     self.path.as_str(),
-                    zkasm.source.as_str(),
-                    metadata_hash,
+                    assembly_text.as_str(),
+                    assembler_metadata_hash,
                     debug_config.as_ref(),
                 )?;
+                metadata_hash_placement
+                    .validate(
+                        build.bytecode.as_slice(),
+                        metadata_hash.as_ref(),
+                        assembly_text.as_str(),
+                    )
+                    .map_err(|error| anyhow::anyhow!("The contract `{}` {}", self.path, error))?;
                 return Ok(ContractBuild::new(
                     self.path,
                     identifier,
+                    target,
                     build,
                     metadata_json,
                     HashSet::new(),
@@ -146,7 +251,7 @@ impl Contract {
             optimizer,
             Some(project),
             include_metadata_hash,
-            debug_config,
+            debug_config.clone(),
         );
         context.set_solidity_data(compiler_llvm_context::ContextSolidityData::default());
         match self.ir {
@@ -163,6 +268,20 @@ impl Contract {
         }
 
         let factory_dependencies = self.drain_factory_dependencies();
+        let mut immutables = std::mem::take(&mut self.immutables);
+
+        #[cfg(feature = "plugins")]
+        if let IR::Yul(ref mut yul) = self.ir {
+            crate::plugin::visit_yul(self.path.as_str(), &mut yul.object)?;
+        }
+
+        let immutable_keys = if immutables.is_empty() {
+            BTreeSet::new()
+        } else {
+            self.ir.immutable_keys()
+        };
+
+        crate::process::progress::finish(ProcessStage::Preprocessing);
 
         self.ir.declare(&mut context).map_err(|error| {
             anyhow::anyhow!(
@@ -178,16 +297,60 @@ impl Contract {
                 error
             )
         })?;
+        crate::process::progress::finish(ProcessStage::Translation);
 
-        let build = context.build(self.path.as_str(), metadata_hash)?;
+        // Only the immutables used in the IR have slots, which the code generator has allocated
+        // by now, so the lookup below never allocates new ones.
+        for immutable in immutables.iter_mut() {
+            if immutable_keys.contains(immutable.key.as_str()) {
+                immutable.offset = Some(
+                    context
+                        .solidity_mut()
+                        .get_or_allocate_immutable(immutable.key.as_str()),
+                );
+            }
+        }
 
-        Ok(ContractBuild::new(
+        let build = match (metadata_hash_placement, metadata_hash) {
+            (MetadataHashPlacement::ConstantSection, Some(_)) => {
+                let build = context.build(self.path.as_str(), None)?;
+                let (assembly_text, assembler_metadata_hash) =
+                    metadata_hash_placement.place(build.assembly_text.as_str(), metadata_hash);
+                compiler_llvm_context::build_assembly_text(
+                    self.path.as_str(),
+                    assembly_text.as_str(),
+                    assembler_metadata_hash,
+                    debug_config.as_ref(),
+                )?
+            }
+            _ => context.build(self.path.as_str(), metadata_hash)?,
+        };
+        if cfg!(debug_assertions) {
+            AssemblyCheck::check(self.path.as_str(), build.assembly_text.as_str())?;
+        }
+        metadata_hash_placement
+            .validate(
+                build.bytecode.as_slice(),
+                metadata_hash.as_ref(),
+                build.assembly_text.as_str(),
+            )
+            .map_err(|error| anyhow::anyhow!("The contract `{}` {}", self.path, error))?;
+        crate::process::progress::finish(ProcessStage::Build);
+
+        let mut contract_build = ContractBuild::new(
             self.path,
             identifier,
+            target,
             build,
             metadata_json,
             factory_dependencies,
-        ))
+        );
+        contract_build.revert_string_ids = revert_string_ids;
+        contract_build.constant_loops = constant_loops;
+        if !immutables.is_empty() {
+            contract_build.immutables = Some(immutables);
+        }
+        Ok(contract_build)
     }
 }
 
@@ -3,10 +3,14 @@
 //!
 
 pub mod contract;
+pub mod mutation;
 
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
@@ -14,13 +18,22 @@ use serde::Deserialize;
 use serde::Serialize;
 use sha3::Digest;
 
+use crate::address::Address;
 use crate::build::contract::Contract as ContractBuild;
+use crate::build::metadata_hash_placement::MetadataHashPlacement;
+use crate::build::skipped::Skipped as BuildSkipped;
+use crate::build::store::Store as BuildStore;
 use crate::build::Build;
+use crate::debug::filter::Filter as DebugFilter;
 use crate::process::input::Input as ProcessInput;
+use crate::process::pool::Pool as ProcessPool;
+use crate::process::timeout::Timeout as ProcessTimeout;
 use crate::project::contract::ir::IR;
 use crate::solc::Compiler as SolcCompiler;
-use crate::yul::lexer::Lexer;
-use crate::yul::parser::statement::object::Object;
+use crate::target::Target;
+use crate::yul::builtin_versions::BuiltinVersions as YulBuiltinVersions;
+use crate::yul::entry_objects::EntryObjects;
+use crate::yul::extraction::Extraction as YulExtraction;
 
 use self::contract::Contract;
 
@@ -37,6 +50,34 @@ pub struct Project {
     pub identifier_paths: BTreeMap<String, String>,
     /// The library addresses.
     pub libraries: BTreeMap<String, BTreeMap<String, String>>,
+    /// The EVM legacy assembly function stack size, above which the deepest slots are spilled to memory.
+    pub evmla_stack_spill_threshold: Option<usize>,
+    /// The EVM legacy assembly block tag to trace the translation of.
+    pub evmla_trace_tag: Option<usize>,
+    /// Whether to attach the EVM legacy assembly instruction indexes as the LLVM debug locations.
+    pub evmla_debug_locations: bool,
+    /// The signatures of the functions to instrument with the reentrancy guard.
+    pub reentrancy_guard: Vec<String>,
+    /// Whether to replace the revert strings with their identifiers.
+    pub revert_string_ids: bool,
+    /// Whether to order the functions by selector instead of the source order.
+    pub order_by_selector: bool,
+    /// The maximal trip count multiplied by the body size of the constant-sized loops to unroll.
+    pub loop_unroll_threshold: Option<usize>,
+    /// Whether to report the constant-sized loops.
+    pub output_constant_loops: bool,
+    /// Whether to output the source and zkEVM instruction histograms.
+    pub output_histogram: bool,
+    /// The metadata hash placement.
+    pub metadata_hash_placement: MetadataHashPlacement,
+    /// The compilation target, which is set by `compile` for the factory dependencies.
+    pub target: Target,
+    /// The per-contract compile time budget, which does not affect the build artifacts.
+    #[serde(skip)]
+    pub contract_time_budget: Option<Duration>,
+    /// The debug dump filter, which is only used by the parent process.
+    #[serde(skip)]
+    pub debug_filter: DebugFilter,
 }
 
 impl Project {
@@ -58,40 +99,323 @@ impl Project {
             contracts,
             identifier_paths,
             libraries,
+            evmla_stack_spill_threshold: None,
+            evmla_trace_tag: None,
+            evmla_debug_locations: false,
+            reentrancy_guard: vec![],
+            revert_string_ids: false,
+            order_by_selector: false,
+            loop_unroll_threshold: None,
+            output_constant_loops: false,
+            output_histogram: false,
+            metadata_hash_placement: MetadataHashPlacement::default(),
+            target: Target::default(),
+            contract_time_budget: None,
+            debug_filter: DebugFilter::default(),
         }
     }
 
+    ///
+    /// Returns the Yul source code of the contracts, keyed by the full contract path.
+    ///
+    /// Only the contracts compiled via the Yul pipeline are returned.
+    ///
+    pub fn get_yul(&self) -> BTreeMap<String, String> {
+        self.contracts
+            .iter()
+            .filter_map(|(path, contract)| match contract.ir {
+                IR::Yul(ref yul) => Some((path.to_owned(), yul.source_code.to_string())),
+                _ => None,
+            })
+            .collect()
+    }
+
     ///
     /// Compiles all contracts, returning their build artifacts.
     ///
+    /// If `determinism_check` is set, each contract is compiled twice and the results are compared.
+    ///
+    /// If `store` is set, the contracts whose transitive sources and settings are unchanged are
+    /// taken from its build cache, and the recompiled ones are reported in the build. The cache
+    /// is bypassed if `debug_config` is set, as the IRs of the cached contracts are not dumped.
+    /// The transitive sources are only recorded for the contracts built successfully, so the
+    /// changes are reported again after a failed build.
+    ///
+    /// The contracts exceeding the compile time budget, if set, are skipped and reported in the
+    /// build along with their dependents, and the rest of the build is completed.
+    ///
+    #[allow(clippy::too_many_arguments)]
     pub fn compile(
         self,
         optimizer_settings: compiler_llvm_context::OptimizerSettings,
+        target: Target,
         is_system_mode: bool,
         include_metadata_hash: bool,
+        determinism_check: bool,
         bytecode_encoding: zkevm_assembly::RunningVmEncodingMode,
+        store: Option<&BuildStore>,
         debug_config: Option<compiler_llvm_context::DebugConfig>,
     ) -> anyhow::Result<Build> {
-        let project = self.clone();
-        let results: BTreeMap<String, anyhow::Result<ContractBuild>> = self
+        self.compile_reusing(
+            optimizer_settings,
+            target,
+            is_system_mode,
+            include_metadata_hash,
+            determinism_check,
+            bytecode_encoding,
+            store,
+            debug_config,
+            BTreeMap::new(),
+        )
+    }
+
+    ///
+    /// Compiles the mutant of the project, whose `mutated` contracts have their instructions
+    /// replaced, returning its build artifacts.
+    ///
+    /// Only the mutated contracts and the contracts depending on them via factory dependencies,
+    /// directly or transitively, are recompiled. The builds of the rest are reused from the
+    /// `base` build of the project.
+    ///
+    pub fn compile_mutant(
+        self,
+        base: &Build,
+        mutated: &BTreeSet<String>,
+        optimizer_settings: compiler_llvm_context::OptimizerSettings,
+        target: Target,
+        is_system_mode: bool,
+        include_metadata_hash: bool,
+    ) -> anyhow::Result<Build> {
+        let mut recompiled = mutated.to_owned();
+        loop {
+            let dependents: Vec<String> = base
+                .contracts
+                .iter()
+                .filter(|(path, contract)| {
+                    !recompiled.contains(path.as_str())
+                        && contract
+                            .build
+                            .factory_dependencies
+                            .values()
+                            .any(|dependency_path| recompiled.contains(dependency_path.as_str()))
+                })
+                .map(|(path, _contract)| path.to_owned())
+                .collect();
+            if dependents.is_empty() {
+                break;
+            }
+            recompiled.extend(dependents);
+        }
+
+        let mut reused = BTreeMap::new();
+        for (path, contract) in base.contracts.iter() {
+            if recompiled.contains(path.as_str()) {
+                continue;
+            }
+            let mut contract: ContractBuild = serde_json::to_value(contract)
+                .and_then(serde_json::from_value)
+                .map_err(|error| {
+                    anyhow::anyhow!("Contract `{}` build copying error: {}", path, error)
+                })?;
+            // The `solc` outputs attached to the base build are not a part of the mutant one.
+            contract.evm_bytecode = None;
+            contract.yul = None;
+            reused.insert(path.to_owned(), contract);
+        }
+
+        self.compile_reusing(
+            optimizer_settings,
+            target,
+            is_system_mode,
+            include_metadata_hash,
+            false,
+            zkevm_assembly::RunningVmEncodingMode::Production,
+            None,
+            None,
+            reused,
+        )
+    }
+
+    ///
+    /// Compiles the contracts, except for the `cached` ones, whose builds are reused.
+    ///
+    /// See `Project::compile` for the rest of the arguments.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    fn compile_reusing(
+        self,
+        optimizer_settings: compiler_llvm_context::OptimizerSettings,
+        target: Target,
+        is_system_mode: bool,
+        include_metadata_hash: bool,
+        determinism_check: bool,
+        bytecode_encoding: zkevm_assembly::RunningVmEncodingMode,
+        store: Option<&BuildStore>,
+        debug_config: Option<compiler_llvm_context::DebugConfig>,
+        mut cached: BTreeMap<String, ContractBuild>,
+    ) -> anyhow::Result<Build> {
+        target.validate()?;
+
+        let store = store.filter(|_| debug_config.is_none());
+        let settings = serde_json::json!({
+            "zksolc": env!("CARGO_PKG_VERSION"),
+            "optimizer": optimizer_settings.to_string(),
+            "target": target.to_string(),
+            "systemMode": is_system_mode,
+            "metadataHash": include_metadata_hash,
+            "testing": bytecode_encoding == zkevm_assembly::RunningVmEncodingMode::Testing,
+            "libraries": self.libraries,
+            "evmlaStackSpillThreshold": self.evmla_stack_spill_threshold,
+            "evmlaTraceTag": self.evmla_trace_tag,
+            "evmlaDebugLocations": self.evmla_debug_locations,
+            "reentrancyGuard": self.reentrancy_guard,
+            "revertStringIds": self.revert_string_ids,
+            "orderBySelector": self.order_by_selector,
+            "loopUnrollThreshold": self.loop_unroll_threshold,
+            "outputConstantLoops": self.output_constant_loops,
+            "outputHistogram": self.output_histogram,
+            "metadataHashPlacement": self.metadata_hash_placement,
+        });
+        let mut keys = BTreeMap::new();
+        if let Some(store) = store {
+            for (path, contract) in self.contracts.iter() {
+                if cached.contains_key(path.as_str()) {
+                    continue;
+                }
+                let key = match BuildStore::build_key(contract, &settings) {
+                    Some(key) => key,
+                    None => continue,
+                };
+                match store.get_build(key.as_str())? {
+                    Some(mut build) => {
+                        if let Some(statistics) = build.statistics.as_mut() {
+                            statistics.cache_hit = true;
+                        }
+                        cached.insert(path.to_owned(), build);
+                    }
+                    None => {
+                        keys.insert(path.to_owned(), key);
+                    }
+                }
+            }
+        }
+
+        let mut invalidated = BTreeMap::new();
+        let mut current_sources = BTreeMap::new();
+        if let Some(store) = store {
+            let previous_sources = store.read_sources();
+            for path in keys.keys() {
+                let sources = BuildStore::contract_sources(&self.contracts[path.as_str()])
+                    .unwrap_or_default();
+                let changed = match previous_sources.get(path.as_str()) {
+                    Some(previous) => BuildStore::changed_sources(previous, &sources),
+                    None => vec![],
+                };
+                invalidated.insert(path.to_owned(), changed);
+                current_sources.insert(path.to_owned(), sources);
+            }
+        }
+
+        let mut project = self.clone();
+        project.target = target;
+        let contract_time_budget = self.contract_time_budget;
+        let pool = ProcessPool::global()?;
+        let mut results: BTreeMap<String, anyhow::Result<ContractBuild>> = self
             .contracts
+            .into_iter()
+            .filter(|(path, _contract)| !cached.contains_key(path.as_str()))
+            .collect::<Vec<(String, Contract)>>()
             .into_par_iter()
             .map(|(full_path, contract)| {
-                let process_output = crate::process::call(ProcessInput::new(
-                    contract,
-                    project.clone(),
-                    is_system_mode,
-                    include_metadata_hash,
-                    bytecode_encoding == zkevm_assembly::RunningVmEncodingMode::Testing,
-                    optimizer_settings.clone(),
-                    debug_config.clone(),
-                ));
+                let debug_config = match debug_config.as_ref() {
+                    Some(debug_config) => {
+                        match crate::debug::contract_config(
+                            debug_config,
+                            &project.debug_filter,
+                            full_path.as_str(),
+                        ) {
+                            Ok(debug_config) => debug_config,
+                            Err(error) => return (full_path, Err(error)),
+                        }
+                    }
+                    None => None,
+                };
+
+                let process_output = pool.call(
+                    ProcessInput::new(
+                        contract,
+                        project.clone(),
+                        target,
+                        is_system_mode,
+                        include_metadata_hash,
+                        determinism_check,
+                        bytecode_encoding == zkevm_assembly::RunningVmEncodingMode::Testing,
+                        optimizer_settings.clone(),
+                        debug_config,
+                    ),
+                    contract_time_budget,
+                );
 
                 (full_path, process_output.map(|output| output.build))
             })
             .collect();
 
-        let mut build = Build::default();
+        if let Some(debug_config) = debug_config.as_ref() {
+            crate::debug::write_index(debug_config, &project.debug_filter, results.keys())?;
+        }
+
+        if let Some(store) = store {
+            for (path, result) in results.iter() {
+                if let (Some(key), Ok(contract)) = (keys.get(path.as_str()), result) {
+                    store.put_build(key.as_str(), contract)?;
+                }
+            }
+            current_sources.retain(|path, _sources| matches!(results.get(path), Some(Ok(_))));
+            store.write_sources(current_sources)?;
+        }
+        results.extend(cached.into_iter().map(|(path, build)| (path, Ok(build))));
+
+        let mut skipped = BTreeMap::new();
+        results.retain(|path, result| {
+            match result
+                .as_ref()
+                .err()
+                .and_then(|error| error.downcast_ref::<ProcessTimeout>())
+            {
+                Some(timeout) => {
+                    skipped.insert(path.to_owned(), BuildSkipped::Timeout(timeout.to_owned()));
+                    false
+                }
+                None => true,
+            }
+        });
+        loop {
+            let dependents: Vec<(String, String)> = results
+                .iter()
+                .filter_map(|(path, result)| {
+                    let contract = result.as_ref().ok()?;
+                    contract
+                        .factory_dependencies
+                        .iter()
+                        .filter_map(|dependency| project.identifier_paths.get(dependency.as_str()))
+                        .find(|dependency_path| skipped.contains_key(dependency_path.as_str()))
+                        .map(|dependency_path| (path.to_owned(), dependency_path.to_owned()))
+                })
+                .collect();
+            if dependents.is_empty() {
+                break;
+            }
+            for (path, dependency_path) in dependents.into_iter() {
+                results.remove(path.as_str());
+                skipped.insert(path, BuildSkipped::Dependency(dependency_path));
+            }
+        }
+
+        let mut build = Build {
+            invalidated,
+            skipped,
+            ..Build::default()
+        };
         let mut hashes = HashMap::with_capacity(results.len());
         for (path, result) in results.iter() {
             match result {
@@ -143,11 +467,12 @@ impl Project {
     ///
     pub fn try_from_yul_path(
         path: &Path,
+        entry_objects: &EntryObjects,
         solc_validator: Option<&SolcCompiler>,
     ) -> anyhow::Result<Self> {
         let source_code = std::fs::read_to_string(path)
             .map_err(|error| anyhow::anyhow!("Yul file {:?} reading error: {}", path, error))?;
-        Self::try_from_yul_string(path, source_code.as_str(), solc_validator)
+        Self::try_from_yul_string(path, source_code.as_str(), entry_objects, solc_validator)
     }
 
     ///
@@ -158,18 +483,32 @@ impl Project {
     pub fn try_from_yul_string(
         path: &Path,
         source_code: &str,
+        entry_objects: &EntryObjects,
         solc_validator: Option<&SolcCompiler>,
     ) -> anyhow::Result<Self> {
+        let version = solc_validator
+            .and_then(|solc| solc.version.as_ref())
+            .map(|version| version.default.to_owned())
+            .unwrap_or(SolcCompiler::LAST_SUPPORTED_VERSION);
+        let source_code = YulExtraction::extract_single(source_code)
+            .map_err(|error| anyhow::anyhow!("Yul file {:?} extraction error: {}", path, error))?;
+        let source_code = source_code.as_str();
+
         if let Some(solc) = solc_validator {
-            solc.validate_yul(path)?;
+            solc.validate_yul(path, source_code)?;
         }
 
         let path = path.to_string_lossy().to_string();
         let source_hash = sha3::Keccak256::digest(source_code.as_bytes()).into();
 
-        let mut lexer = Lexer::new(source_code.to_owned());
-        let object = Object::parse(&mut lexer, None)
+        let object = entry_objects
+            .parse(source_code)
             .map_err(|error| anyhow::anyhow!("Yul object `{}` parsing error: {}", path, error))?;
+        if solc_validator.is_some() {
+            YulBuiltinVersions::check(&object, &version).map_err(|error| {
+                anyhow::anyhow!("Yul object `{}` builtin error: {}", path, error)
+            })?;
+        }
 
         let mut project_contracts = BTreeMap::new();
         project_contracts.insert(
@@ -178,7 +517,7 @@ impl Project {
                 path,
                 source_hash,
                 SolcCompiler::LAST_SUPPORTED_VERSION,
-                IR::new_yul(source_code.to_owned(), object),
+                IR::new_yul(Arc::from(source_code), object, None),
                 None,
             ),
         );
@@ -271,9 +610,11 @@ impl compiler_llvm_context::Dependency for Project {
                 )
             })?;
 
+        let target = project.target;
         contract
             .compile(
                 project,
+                target,
                 optimizer_settings,
                 is_system_mode,
                 include_metadata_hash,
@@ -306,7 +647,16 @@ impl compiler_llvm_context::Dependency for Project {
             for (contract_name, address) in contracts.iter() {
                 let key = format!("{file_path}:{contract_name}");
                 if key.as_str() == path {
-                    return Ok(address["0x".len()..].to_owned());
+                    return Address::validate(address)
+                        .map(|digits| digits.to_owned())
+                        .map_err(|error| {
+                            anyhow::anyhow!(
+                                "The library `{}` address `{}` is invalid: {}",
+                                path,
+                                address,
+                                error
+                            )
+                        });
                 }
             }
         }
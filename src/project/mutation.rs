@@ -0,0 +1,322 @@
+//!
+//! The mutation testing specification.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::evmla::assembly::data::Data;
+use crate::evmla::assembly::instruction::name::Name as InstructionName;
+use crate::evmla::assembly::instruction::Instruction;
+use crate::evmla::assembly::Assembly;
+use crate::project::contract::ir::IR;
+use crate::project::Project;
+
+///
+/// The mutation testing specification.
+///
+#[derive(Debug, Deserialize)]
+pub struct Specification {
+    /// The mutants to build.
+    pub mutants: Vec<Mutant>,
+}
+
+///
+/// The mutant, produced by replacing the EVM instructions at the Solidity source code location.
+///
+#[derive(Debug, Deserialize)]
+pub struct Mutant {
+    /// The unique mutant name, used as its output subdirectory name.
+    pub name: String,
+    /// The Solidity source code file path, as it appears in the `solc` output.
+    pub path: String,
+    /// The source code location start offset.
+    pub begin: isize,
+    /// The source code location end offset.
+    pub end: isize,
+    /// The original instruction, e.g. `ADD`.
+    pub from: InstructionName,
+    /// The replacement instruction, e.g. `SUB`.
+    pub to: InstructionName,
+}
+
+impl Specification {
+    ///
+    /// Reads the specification from the JSON file.
+    ///
+    pub fn try_from_path(path: &Path) -> anyhow::Result<Self> {
+        let json = std::fs::read(path)
+            .map_err(|error| anyhow::anyhow!("File {:?} reading error: {}", path, error))?;
+        let specification: Self = serde_json::from_slice(json.as_slice())
+            .map_err(|error| anyhow::anyhow!("File {:?} parsing error: {}", path, error))?;
+
+        let mut names = BTreeSet::new();
+        for mutant in specification.mutants.iter() {
+            if !names.insert(mutant.name.as_str()) {
+                anyhow::bail!("Mutant `{}` is specified more than once", mutant.name);
+            }
+            if mutant.name.is_empty()
+                || mutant.name.contains(|character: char| {
+                    !character.is_ascii_alphanumeric() && character != '_' && character != '-'
+                })
+            {
+                anyhow::bail!(
+                    "Mutant name `{}` must only contain ASCII alphanumerics, `_`, and `-`",
+                    mutant.name
+                );
+            }
+        }
+
+        Ok(specification)
+    }
+}
+
+impl Mutant {
+    ///
+    /// Replaces the matching instructions in all contracts of the `project`, returning the
+    /// numbers of replaced instructions keyed by the paths of the mutated contracts.
+    ///
+    /// The `source_ids` map the source code file paths to their indexes in the `solc` source list.
+    ///
+    pub fn apply(
+        &self,
+        project: &mut Project,
+        source_ids: &BTreeMap<String, usize>,
+    ) -> anyhow::Result<BTreeMap<String, usize>> {
+        let version = project.version.to_owned();
+        let from = Instruction::from(self.from.clone());
+        let to = Instruction::from(self.to.clone());
+        if from.input_size(&version) != to.input_size(&version)
+            || from.output_size() != to.output_size()
+        {
+            anyhow::bail!(
+                "Mutant `{}` replaces `{}` with `{}`, which have different stack arities",
+                self.name,
+                self.from,
+                self.to
+            );
+        }
+
+        let source_id = *source_ids.get(self.path.as_str()).ok_or_else(|| {
+            anyhow::anyhow!("Mutant `{}` source `{}` not found", self.name, self.path)
+        })? as isize;
+
+        let mut counts = BTreeMap::new();
+        for (path, contract) in project.contracts.iter_mut() {
+            let count = match contract.ir {
+                IR::EVMLA(ref mut evmla) => self.apply_assembly(&mut evmla.assembly, source_id),
+                IR::Yul(_) => anyhow::bail!(
+                    "Mutation testing is only supported in the EVM legacy assembly pipeline"
+                ),
+                IR::LLVMIR(_) | IR::ZKASM(_) => 0,
+            };
+            if count > 0 {
+                counts.insert(path.to_owned(), count);
+            }
+        }
+        Ok(counts)
+    }
+
+    ///
+    /// Replaces the matching instructions in the assembly and its runtime code.
+    ///
+    fn apply_assembly(&self, assembly: &mut Assembly, source_id: isize) -> usize {
+        let mut count = 0;
+        for instruction in assembly.code.iter_mut().flatten() {
+            if instruction.name == self.from
+                && instruction.source == Some(source_id)
+                && instruction.begin == Some(self.begin)
+                && instruction.end == Some(self.end)
+            {
+                instruction.name = self.to.clone();
+                count += 1;
+            }
+        }
+
+        for data in assembly.data.iter_mut().flat_map(|data| data.values_mut()) {
+            if let Data::Assembly(assembly) = data {
+                count += self.apply_assembly(assembly, source_id);
+            }
+        }
+
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::evmla::assembly::data::Data;
+    use crate::evmla::assembly::instruction::name::Name as InstructionName;
+    use crate::evmla::assembly::Assembly;
+    use crate::project::contract::ir::IR;
+    use crate::project::contract::Contract;
+    use crate::project::Project;
+    use crate::solc::standard_json::output::contract::evm::extra_metadata::ExtraMetadata;
+    use crate::yul::lexer::Lexer;
+    use crate::yul::parser::statement::object::Object;
+
+    use super::Mutant;
+    use super::Specification;
+
+    fn project(ir: IR) -> Project {
+        let version = semver::Version::new(0, 8, 20);
+        let contract = Contract::new(
+            "Test.sol:Test".to_owned(),
+            [0; compiler_common::BYTE_LENGTH_FIELD],
+            version.clone(),
+            ir,
+            None,
+        );
+        Project::new(
+            version,
+            BTreeMap::from([("Test.sol:Test".to_owned(), contract)]),
+            BTreeMap::new(),
+        )
+    }
+
+    fn evmla_project() -> Project {
+        let code = serde_json::json!([
+            { "name": "PUSH", "value": "1", "source": 0, "begin": 10, "end": 15 },
+            { "name": "ADD", "source": 0, "begin": 10, "end": 15 },
+            { "name": "ADD", "source": 0, "begin": 20, "end": 25 },
+            { "name": "ADD", "source": 1, "begin": 10, "end": 15 },
+        ]);
+        let assembly: Assembly = serde_json::from_value(serde_json::json!({
+            ".code": code,
+            ".data": { "0": { ".code": code } },
+        }))
+        .expect("Always valid");
+        project(IR::new_evmla(assembly, ExtraMetadata::default()))
+    }
+
+    fn mutant(from: InstructionName, to: InstructionName) -> Mutant {
+        Mutant {
+            name: "add-to-sub".to_owned(),
+            path: "Test.sol".to_owned(),
+            begin: 10,
+            end: 15,
+            from,
+            to,
+        }
+    }
+
+    fn source_ids() -> BTreeMap<String, usize> {
+        BTreeMap::from([("Test.sol".to_owned(), 0), ("Other.sol".to_owned(), 1)])
+    }
+
+    #[test]
+    fn applied_to_deploy_and_runtime_code() {
+        let mut project = evmla_project();
+        let counts = mutant(InstructionName::ADD, InstructionName::SUB)
+            .apply(&mut project, &source_ids())
+            .expect("Always valid");
+        assert_eq!(counts, BTreeMap::from([("Test.sol:Test".to_owned(), 2)]));
+
+        let assembly = match project.contracts["Test.sol:Test"].ir {
+            IR::EVMLA(ref evmla) => &evmla.assembly,
+            _ => panic!("Always EVMLA"),
+        };
+        let names = |assembly: &Assembly| -> Vec<InstructionName> {
+            assembly
+                .code
+                .iter()
+                .flatten()
+                .map(|instruction| instruction.name.clone())
+                .collect()
+        };
+        let expected = vec![
+            InstructionName::PUSH,
+            InstructionName::SUB,
+            InstructionName::ADD,
+            InstructionName::ADD,
+        ];
+        assert_eq!(names(assembly), expected);
+        match assembly.data.as_ref().and_then(|data| data.get("0")) {
+            Some(Data::Assembly(runtime)) => assert_eq!(names(runtime), expected),
+            _ => panic!("Always exists"),
+        }
+    }
+
+    #[test]
+    fn different_arity() {
+        let error = mutant(InstructionName::ADD, InstructionName::ISZERO)
+            .apply(&mut evmla_project(), &source_ids())
+            .expect_err("Always invalid");
+        assert!(error.to_string().contains("different stack arities"));
+    }
+
+    #[test]
+    fn unknown_source() {
+        let mut mutant = mutant(InstructionName::ADD, InstructionName::SUB);
+        mutant.path = "Unknown.sol".to_owned();
+        let error = mutant
+            .apply(&mut evmla_project(), &source_ids())
+            .expect_err("Always invalid");
+        assert!(error.to_string().contains("source `Unknown.sol` not found"));
+    }
+
+    #[test]
+    fn yul_unsupported() {
+        let source_code = r#"object "Test" { code { { return(0, 0) } } }"#;
+        let mut lexer = Lexer::new(source_code.to_owned());
+        let object = Object::parse(&mut lexer, None).expect("Always valid");
+        let mut project = project(IR::new_yul(source_code.into(), object, None));
+
+        let error = mutant(InstructionName::ADD, InstructionName::SUB)
+            .apply(&mut project, &source_ids())
+            .expect_err("Always invalid");
+        assert!(error
+            .to_string()
+            .contains("only supported in the EVM legacy assembly"));
+    }
+
+    #[test]
+    fn specification_validated() {
+        let path = std::env::temp_dir().join(format!(
+            "zksolc-mutation-{}-{}.json",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        let mutant = serde_json::json!({
+            "name": "add-to-sub",
+            "path": "Test.sol",
+            "begin": 10,
+            "end": 15,
+            "from": "ADD",
+            "to": "SUB",
+        });
+
+        std::fs::write(
+            path.as_path(),
+            serde_json::json!({ "mutants": [mutant] }).to_string(),
+        )
+        .expect("Always valid");
+        let specification = Specification::try_from_path(path.as_path()).expect("Always valid");
+        assert_eq!(specification.mutants.len(), 1);
+
+        std::fs::write(
+            path.as_path(),
+            serde_json::json!({ "mutants": [mutant, mutant] }).to_string(),
+        )
+        .expect("Always valid");
+        let error = Specification::try_from_path(path.as_path()).expect_err("Always invalid");
+        assert!(error.to_string().contains("specified more than once"));
+
+        let mut invalid = mutant.clone();
+        invalid["name"] = serde_json::Value::String("../escape".to_owned());
+        std::fs::write(
+            path.as_path(),
+            serde_json::json!({ "mutants": [invalid] }).to_string(),
+        )
+        .expect("Always valid");
+        let error = Specification::try_from_path(path.as_path()).expect_err("Always invalid");
+        assert!(error.to_string().contains("must only contain"));
+
+        std::fs::remove_file(path).expect("Always valid");
+    }
+}
@@ -0,0 +1,199 @@
+//!
+//! The project scaffolding.
+//!
+
+use std::path::Path;
+use std::path::PathBuf;
+
+///
+/// The project scaffolding.
+///
+/// Generates a minimal project built with `zksolc workspace zksolc-workspace.json` from the
+/// project directory, without going through Hardhat or Foundry.
+///
+#[derive(Debug)]
+pub struct Scaffold {
+    /// The project name.
+    pub name: String,
+    /// Whether the project contains system contracts.
+    pub is_system: bool,
+}
+
+impl Scaffold {
+    /// The workspace config file name.
+    pub const WORKSPACE_FILE_NAME: &'static str = "zksolc-workspace.json";
+
+    /// The standard JSON input file name.
+    pub const INPUT_FILE_NAME: &'static str = "zksolc-input.json";
+
+    /// The sample contract path.
+    pub const CONTRACT_PATH: &'static str = "contracts/Greeter.sol";
+
+    /// The output directory name.
+    pub const OUTPUT_DIRECTORY_NAME: &'static str = "artifacts-zk";
+
+    /// The sample contract.
+    const CONTRACT: &'static str = r#"// SPDX-License-Identifier: MIT
+
+pragma solidity ^0.8.0;
+
+contract Greeter {
+    string private greeting;
+
+    constructor(string memory _greeting) {
+        greeting = _greeting;
+    }
+
+    function greet() public view returns (string memory) {
+        return greeting;
+    }
+
+    function setGreeting(string memory _greeting) public {
+        greeting = _greeting;
+    }
+}
+"#;
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(name: String, is_system: bool) -> Self {
+        Self { name, is_system }
+    }
+
+    ///
+    /// Writes the project files to `directory`, returning their paths.
+    ///
+    /// Nothing is written if any of the files exists, unless `overwrite` is set.
+    ///
+    pub fn write(&self, directory: &Path, overwrite: bool) -> anyhow::Result<Vec<PathBuf>> {
+        let files = self.files();
+
+        if !overwrite {
+            let existing: Vec<String> = files
+                .iter()
+                .map(|(path, _content)| directory.join(path))
+                .filter(|path| path.exists())
+                .map(|path| format!("{path:?}"))
+                .collect();
+            if !existing.is_empty() {
+                anyhow::bail!(
+                    "Refusing to overwrite the existing files (use --overwrite to force): {}",
+                    existing.join(", ")
+                );
+            }
+        }
+
+        let mut paths = Vec::with_capacity(files.len());
+        for (path, content) in files.into_iter() {
+            let path = directory.join(path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|error| {
+                    anyhow::anyhow!("Directory {:?} creating error: {}", parent, error)
+                })?;
+            }
+            std::fs::write(path.as_path(), content)
+                .map_err(|error| anyhow::anyhow!("File {:?} writing error: {}", path, error))?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    ///
+    /// Returns the project files with their contents.
+    ///
+    pub fn files(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (Self::WORKSPACE_FILE_NAME, self.workspace()),
+            (Self::INPUT_FILE_NAME, self.input()),
+            (Self::CONTRACT_PATH, Self::CONTRACT.to_owned()),
+            (".gitignore", format!("/{}/\n", Self::OUTPUT_DIRECTORY_NAME)),
+        ]
+    }
+
+    ///
+    /// Returns the workspace config.
+    ///
+    fn workspace(&self) -> String {
+        let config = serde_json::json!({
+            "projects": [{
+                "name": self.name,
+                "input": Self::INPUT_FILE_NAME,
+                "outputDirectory": Self::OUTPUT_DIRECTORY_NAME,
+                "systemMode": self.is_system,
+            }]
+        });
+        Self::to_pretty_json(&config)
+    }
+
+    ///
+    /// Returns the standard JSON input with the recommended settings.
+    ///
+    /// The system contracts are deployed to fixed addresses and verified by their bytecode
+    /// hashes, so the metadata hash is not appended to them.
+    ///
+    fn input(&self) -> String {
+        let mut settings = serde_json::json!({
+            "optimizer": {
+                "enabled": true,
+                "mode": "3",
+            },
+            "outputSelection": {
+                "*": {
+                    "*": ["abi", "metadata"],
+                },
+            },
+        });
+        if self.is_system {
+            settings["metadata"] = serde_json::json!({ "bytecodeHash": "none" });
+        }
+
+        let input = serde_json::json!({
+            "language": "Solidity",
+            "sources": {
+                Self::CONTRACT_PATH: {
+                    "urls": [Self::CONTRACT_PATH],
+                },
+            },
+            "settings": settings,
+        });
+        Self::to_pretty_json(&input)
+    }
+
+    ///
+    /// Pretty-prints the JSON with a trailing newline.
+    ///
+    fn to_pretty_json(value: &serde_json::Value) -> String {
+        let mut json = serde_json::to_string_pretty(value).expect("Always valid");
+        json.push('\n');
+        json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scaffold;
+    use crate::solc::pipeline::Pipeline as SolcPipeline;
+    use crate::solc::standard_json::input::Input as SolcStandardJsonInput;
+
+    #[test]
+    fn input_is_valid() {
+        for is_system in [false, true] {
+            let scaffold = Scaffold::new("test".to_owned(), is_system);
+            let input = SolcStandardJsonInput::try_from_reader(
+                scaffold.input().as_bytes(),
+                SolcPipeline::Yul,
+            )
+            .expect("Always valid");
+            assert!(input.sources.contains_key(Scaffold::CONTRACT_PATH));
+        }
+    }
+
+    #[test]
+    fn workspace_is_valid() {
+        let scaffold = Scaffold::new("test".to_owned(), true);
+        let workspace: crate::workspace::Workspace =
+            serde_json::from_str(scaffold.workspace().as_str()).expect("Always valid");
+        assert!(workspace.projects[0].system_mode);
+    }
+}
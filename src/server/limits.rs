@@ -0,0 +1,79 @@
+//!
+//! The compile server resource limits.
+//!
+
+use std::time::Duration;
+
+///
+/// The compile server resource limits.
+///
+#[derive(Debug, Clone)]
+pub struct Limits {
+    /// The maximal request body size in bytes.
+    pub max_request_size: usize,
+    /// The maximal request handling time, including the compilation.
+    pub timeout: Duration,
+    /// The maximal number of requests handled concurrently.
+    pub max_concurrent_requests: usize,
+    /// The number of queued jobs compiled concurrently, including the synchronous ones.
+    pub max_concurrent_jobs: usize,
+    /// The maximal number of pending jobs in the queue.
+    pub max_queued_jobs: usize,
+    /// The maximal total size of the queued inputs and the kept outputs in bytes.
+    pub max_queue_size: usize,
+    /// The maximal number of requests per client address per minute.
+    pub rate_limit: Option<usize>,
+}
+
+impl Limits {
+    /// The default maximal request body size in bytes.
+    pub const DEFAULT_MAX_REQUEST_SIZE: usize = 64 * 1024 * 1024;
+
+    /// The default maximal request handling time in seconds.
+    pub const DEFAULT_TIMEOUT_SECONDS: u64 = 300;
+
+    /// The default maximal number of requests handled concurrently.
+    pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+    /// The default number of queued jobs compiled concurrently.
+    pub const DEFAULT_MAX_CONCURRENT_JOBS: usize = 2;
+
+    /// The default maximal number of pending jobs in the queue.
+    pub const DEFAULT_MAX_QUEUED_JOBS: usize = 64;
+
+    /// The default maximal total size of the queued inputs and the kept outputs in bytes.
+    pub const DEFAULT_MAX_QUEUE_SIZE: usize = 512 * 1024 * 1024;
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(
+        max_request_size: Option<usize>,
+        timeout_seconds: Option<u64>,
+        max_concurrent_requests: Option<usize>,
+        max_concurrent_jobs: Option<usize>,
+        max_queued_jobs: Option<usize>,
+        max_queue_size: Option<usize>,
+        rate_limit: Option<usize>,
+    ) -> Self {
+        Self {
+            max_request_size: max_request_size.unwrap_or(Self::DEFAULT_MAX_REQUEST_SIZE),
+            timeout: Duration::from_secs(timeout_seconds.unwrap_or(Self::DEFAULT_TIMEOUT_SECONDS)),
+            max_concurrent_requests: max_concurrent_requests
+                .unwrap_or(Self::DEFAULT_MAX_CONCURRENT_REQUESTS)
+                .max(1),
+            max_concurrent_jobs: max_concurrent_jobs
+                .unwrap_or(Self::DEFAULT_MAX_CONCURRENT_JOBS)
+                .max(1),
+            max_queued_jobs: max_queued_jobs.unwrap_or(Self::DEFAULT_MAX_QUEUED_JOBS),
+            max_queue_size: max_queue_size.unwrap_or(Self::DEFAULT_MAX_QUEUE_SIZE),
+            rate_limit,
+        }
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::new(None, None, None, None, None, None, None)
+    }
+}
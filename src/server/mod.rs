@@ -0,0 +1,503 @@
+//!
+//! The compile server.
+//!
+
+pub mod limits;
+pub mod queue;
+pub mod rate_limiter;
+pub mod request;
+pub mod response;
+
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::process::Command;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::solc::standard_json::input::settings::sandbox::Sandbox;
+use crate::solc::Compiler as SolcCompiler;
+
+use self::limits::Limits;
+use self::queue::JobStatus;
+use self::queue::Queue;
+use self::rate_limiter::RateLimiter;
+use self::request::DeadlineReader;
+use self::request::Request;
+use self::response::Response;
+
+///
+/// The compile server.
+///
+/// Serves the standard JSON compilation over HTTP, both as plain endpoints and as JSON-RPC 2.0.
+/// Each compilation runs in a separate `zksolc --standard-json` process, so a crash or a timeout
+/// does not affect the other requests.
+///
+/// The request bodies are untrusted, so each of them is compiled in the sandbox, which only
+/// accepts the inline sources, and is never delegated to the compile daemon.
+///
+#[derive(Debug)]
+pub struct Server {
+    /// The listening address.
+    pub host: String,
+    /// The listening TCP port.
+    pub port: u16,
+    /// The `solc` executable path.
+    pub solc: String,
+    /// The `solc` long version, reported by the version endpoint.
+    pub solc_version: String,
+    /// The resource limits.
+    pub limits: Limits,
+    /// The sandbox enforced for all requests, overriding the one in their settings.
+    pub sandbox: Sandbox,
+    /// The job queue.
+    queue: Queue,
+    /// The client request rate limiter.
+    rate_limiter: RateLimiter,
+}
+
+impl Server {
+    /// The JSON-RPC parse error code.
+    const JSON_RPC_PARSE_ERROR: i64 = -32700;
+
+    /// The JSON-RPC invalid request error code.
+    const JSON_RPC_INVALID_REQUEST: i64 = -32600;
+
+    /// The JSON-RPC method not found error code.
+    const JSON_RPC_METHOD_NOT_FOUND: i64 = -32601;
+
+    /// The JSON-RPC invalid parameters error code.
+    const JSON_RPC_INVALID_PARAMS: i64 = -32602;
+
+    /// The JSON-RPC compilation error code.
+    const JSON_RPC_COMPILATION_ERROR: i64 = -32000;
+
+    /// The compilation process polling interval.
+    const POLLING_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// The compilation time limit error message.
+    const TIMEOUT_MESSAGE: &'static str = "The compilation time limit exceeded";
+
+    /// The rejection response writing time limit.
+    const REJECTION_TIMEOUT: Duration = Duration::from_secs(1);
+
+    ///
+    /// A shortcut constructor.
+    ///
+    /// Checks the `solc` executable, so the misconfiguration is reported before serving.
+    ///
+    pub fn try_new(host: String, port: u16, solc: String, limits: Limits) -> anyhow::Result<Self> {
+        let solc_version = SolcCompiler::new(solc.clone()).version()?.long;
+        Ok(Self {
+            host,
+            port,
+            solc,
+            solc_version,
+            limits,
+            sandbox: Sandbox::default(),
+            queue: Queue::new(limits.max_queued_jobs, limits.max_queue_size),
+            rate_limiter: RateLimiter::default(),
+        })
+    }
+
+    ///
+    /// Accepts the connections until the process is terminated.
+    ///
+    pub fn run(self) -> anyhow::Result<()> {
+        let listener = TcpListener::bind((self.host.as_str(), self.port)).map_err(|error| {
+            anyhow::anyhow!("Binding to {}:{} error: {}", self.host, self.port, error)
+        })?;
+        eprintln!("Listening on http://{}:{}", self.host, self.port);
+
+        let server = Arc::new(self);
+        for _ in 0..server.limits.max_concurrent_jobs {
+            let server = server.clone();
+            std::thread::spawn(move || server.work());
+        }
+
+        let active_requests = Arc::new(AtomicUsize::new(0));
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(error) => {
+                    eprintln!("Connection accepting error: {error}");
+                    continue;
+                }
+            };
+
+            if let (Some(limit), Ok(address)) = (server.limits.rate_limit, stream.peer_addr()) {
+                if !server.rate_limiter.check(address.ip(), limit) {
+                    Self::reject(stream, Response::error(429, "Too many requests"));
+                    continue;
+                }
+            }
+            if active_requests.fetch_add(1, Ordering::SeqCst)
+                >= server.limits.max_concurrent_requests
+            {
+                active_requests.fetch_sub(1, Ordering::SeqCst);
+                Self::reject(stream, Response::error(503, "Too many concurrent requests"));
+                continue;
+            }
+
+            let server = server.clone();
+            let active_requests = active_requests.clone();
+            std::thread::spawn(move || {
+                if let Err(error) = server.handle(stream) {
+                    eprintln!("Request handling error: {error}");
+                }
+                active_requests.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Writes the rejection `response` in a separate thread, so a slow client cannot block
+    /// the accepting loop.
+    ///
+    fn reject(mut stream: TcpStream, response: Response) {
+        std::thread::spawn(move || {
+            let _ = stream.set_write_timeout(Some(Self::REJECTION_TIMEOUT));
+            let _ = response.write(&mut stream);
+        });
+    }
+
+    ///
+    /// Reads the request from the `stream` and writes the response.
+    ///
+    /// The whole request must be received within the time limit, so a client sending it slowly
+    /// cannot hold the connection indefinitely.
+    ///
+    fn handle(&self, mut stream: TcpStream) -> anyhow::Result<()> {
+        stream.set_write_timeout(Some(self.limits.timeout))?;
+
+        let mut reader = DeadlineReader::new(&stream, Instant::now() + self.limits.timeout);
+        let request = match Request::read(&mut reader, self.limits.max_request_size) {
+            Ok(request) => request,
+            Err(error) => {
+                Response::error(400, error.to_string().as_str()).write(&mut stream)?;
+                return Ok(());
+            }
+        };
+
+        let response = match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/health") => Response::json(200, &serde_json::json!({ "status": "ok" })),
+            ("GET", "/version") => Response::json(200, &self.version()),
+            ("POST", "/compile") => match self.queue.submit_and_wait(request.body) {
+                Some(JobStatus::Done(output)) => Response::json(200, &output),
+                Some(JobStatus::Failed(error)) if error == Self::TIMEOUT_MESSAGE => {
+                    Response::error(504, error.as_str())
+                }
+                Some(JobStatus::Failed(error)) => Response::error(500, error.as_str()),
+                Some(JobStatus::Queued | JobStatus::Running) => {
+                    Response::error(500, "The job has not finished")
+                }
+                None => Response::error(503, "The job queue is full"),
+            },
+            ("POST", "/jobs") => match self.queue.submit(request.body) {
+                Some(id) => Response::json(202, &serde_json::json!({ "id": id })),
+                None => Response::error(503, "The job queue is full"),
+            },
+            ("GET", path) if path.starts_with("/jobs/") => {
+                match self.queue.status(&path["/jobs/".len()..]) {
+                    Some(status) => Response::json(200, &status.to_json()),
+                    None => Response::error(404, "Unknown job"),
+                }
+            }
+            ("POST", "/") => self.json_rpc(request.body.as_slice()),
+            (_, "/health" | "/version" | "/compile" | "/jobs" | "/") => {
+                Response::error(405, "Method not allowed")
+            }
+            _ => Response::error(404, "Not found"),
+        };
+        response.write(&mut stream)?;
+
+        Ok(())
+    }
+
+    ///
+    /// Handles the JSON-RPC 2.0 request.
+    ///
+    fn json_rpc(&self, body: &[u8]) -> Response {
+        let request: serde_json::Value = match serde_json::from_slice(body) {
+            Ok(request) => request,
+            Err(error) => {
+                return Self::json_rpc_error(
+                    serde_json::Value::Null,
+                    Self::JSON_RPC_PARSE_ERROR,
+                    error.to_string().as_str(),
+                )
+            }
+        };
+        let id = request.get("id").cloned().unwrap_or_default();
+        let method = match request.get("method").and_then(|method| method.as_str()) {
+            Some(method) => method,
+            None => {
+                return Self::json_rpc_error(
+                    id,
+                    Self::JSON_RPC_INVALID_REQUEST,
+                    "The method is missing",
+                )
+            }
+        };
+
+        let result = match method {
+            "health" => serde_json::json!("ok"),
+            "version" => self.version(),
+            "submit" => {
+                let input = serde_json::to_vec(&request.get("params").cloned().unwrap_or_default())
+                    .expect("Always valid");
+                match self.queue.submit(input) {
+                    Some(id) => serde_json::json!({ "id": id }),
+                    None => {
+                        return Self::json_rpc_error(
+                            id,
+                            Self::JSON_RPC_COMPILATION_ERROR,
+                            "The job queue is full",
+                        )
+                    }
+                }
+            }
+            "status" => {
+                let status = request
+                    .get("params")
+                    .and_then(|params| params.get("id"))
+                    .and_then(|job_id| job_id.as_str())
+                    .and_then(|job_id| self.queue.status(job_id));
+                match status {
+                    Some(status) => status.to_json(),
+                    None => {
+                        return Self::json_rpc_error(
+                            id,
+                            Self::JSON_RPC_INVALID_PARAMS,
+                            "Unknown job",
+                        )
+                    }
+                }
+            }
+            "compile" => {
+                let input = serde_json::to_vec(&request.get("params").cloned().unwrap_or_default())
+                    .expect("Always valid");
+                match self.queue.submit_and_wait(input) {
+                    Some(JobStatus::Done(output)) => output,
+                    Some(JobStatus::Failed(error)) => {
+                        return Self::json_rpc_error(
+                            id,
+                            Self::JSON_RPC_COMPILATION_ERROR,
+                            error.as_str(),
+                        )
+                    }
+                    Some(JobStatus::Queued | JobStatus::Running) => {
+                        return Self::json_rpc_error(
+                            id,
+                            Self::JSON_RPC_COMPILATION_ERROR,
+                            "The job has not finished",
+                        )
+                    }
+                    None => {
+                        return Self::json_rpc_error(
+                            id,
+                            Self::JSON_RPC_COMPILATION_ERROR,
+                            "The job queue is full",
+                        )
+                    }
+                }
+            }
+            method => {
+                return Self::json_rpc_error(
+                    id,
+                    Self::JSON_RPC_METHOD_NOT_FOUND,
+                    format!("Unknown method `{method}`").as_str(),
+                )
+            }
+        };
+
+        Response::json(
+            200,
+            &serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        )
+    }
+
+    ///
+    /// Creates the JSON-RPC 2.0 error response.
+    ///
+    fn json_rpc_error(id: serde_json::Value, code: i64, message: &str) -> Response {
+        Response::json(
+            200,
+            &serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": message },
+            }),
+        )
+    }
+
+    ///
+    /// Compiles the queued jobs until the process is terminated.
+    ///
+    fn work(&self) {
+        loop {
+            let (id, input) = self.queue.take();
+            let status = match self.compile(input.as_slice()) {
+                Ok(Some(output)) => match serde_json::from_slice(output.as_slice()) {
+                    Ok(output) => JobStatus::Done(output),
+                    Err(error) => JobStatus::Failed(error.to_string()),
+                },
+                Ok(None) => JobStatus::Failed(Self::TIMEOUT_MESSAGE.to_owned()),
+                Err(error) => JobStatus::Failed(error.to_string()),
+            };
+            self.queue.finish(id, status);
+        }
+    }
+
+    ///
+    /// Returns the compiler versions.
+    ///
+    fn version(&self) -> serde_json::Value {
+        serde_json::json!({
+            "zksolc": env!("CARGO_PKG_VERSION"),
+            "solc": self.solc_version,
+        })
+    }
+
+    ///
+    /// Compiles the standard JSON `input` in a separate process.
+    ///
+    /// Returns `None` if the process has been killed after exceeding the time limit.
+    ///
+    fn compile(&self, input: &[u8]) -> anyhow::Result<Option<Vec<u8>>> {
+        let executable = match crate::process::EXECUTABLE.get() {
+            Some(executable) => executable.to_owned(),
+            None => std::env::current_exe()?,
+        };
+
+        let mut command = Command::new(executable.as_path());
+        command.stdin(std::process::Stdio::piped());
+        command.stdout(std::process::Stdio::piped());
+        command.stderr(std::process::Stdio::piped());
+        command.arg("--standard-json");
+        command.arg("--solc");
+        command.arg(self.solc.as_str());
+        let mut process = command.spawn().map_err(|error| {
+            anyhow::anyhow!("{:?} subprocess spawning error: {:?}", executable, error)
+        })?;
+
+        let mut stdin = process
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("{:?} stdin getting error", executable))?;
+        let input = Self::enforce_sandbox(&self.sandbox, input)?;
+        let writer = std::thread::spawn(move || stdin.write_all(input.as_slice()));
+        let stdout = Self::read_in_background(process.stdout.take());
+        let stderr = Self::read_in_background(process.stderr.take());
+
+        let deadline = Instant::now() + self.limits.timeout;
+        let status = loop {
+            if let Some(status) = process.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                process.kill()?;
+                process.wait()?;
+                return Ok(None);
+            }
+            std::thread::sleep(Self::POLLING_INTERVAL);
+        };
+        writer
+            .join()
+            .expect("Stdin writer thread panicked")
+            .map_err(|error| {
+                anyhow::anyhow!("{:?} stdin writing error: {:?}", executable, error)
+            })?;
+        let stdout = stdout.join().expect("Stdout reader thread panicked")?;
+        let stderr = stderr.join().expect("Stderr reader thread panicked")?;
+
+        if !status.success() {
+            anyhow::bail!("{}", String::from_utf8_lossy(stderr.as_slice()));
+        }
+        Ok(Some(stdout))
+    }
+
+    ///
+    /// Sets the `sandbox` in the standard JSON `input` settings, replacing the requested one.
+    ///
+    /// The sandboxed inputs are resolved without the file and network access, which is stricter
+    /// than the hermetic mode, and are never delegated to the compile daemon.
+    ///
+    fn enforce_sandbox(sandbox: &Sandbox, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut input: serde_json::Value = serde_json::from_slice(input)?;
+        let settings = input
+            .as_object_mut()
+            .ok_or_else(|| anyhow::anyhow!("The standard JSON input must be an object"))?
+            .entry("settings")
+            .or_insert_with(|| serde_json::json!({}));
+        settings
+            .as_object_mut()
+            .ok_or_else(|| anyhow::anyhow!("The standard JSON input settings must be an object"))?
+            .insert(
+                "sandbox".to_owned(),
+                serde_json::to_value(sandbox).expect("Always valid"),
+            );
+        Ok(serde_json::to_vec(&input).expect("Always valid"))
+    }
+
+    ///
+    /// Reads the subprocess `stream` to the end in a separate thread.
+    ///
+    fn read_in_background<R: Read + Send + 'static>(
+        stream: Option<R>,
+    ) -> std::thread::JoinHandle<std::io::Result<Vec<u8>>> {
+        std::thread::spawn(move || {
+            let mut buffer = Vec::new();
+            if let Some(mut stream) = stream {
+                stream.read_to_end(&mut buffer)?;
+            }
+            Ok(buffer)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solc::standard_json::input::settings::sandbox::Sandbox;
+
+    use super::Server;
+
+    #[test]
+    fn sandbox_enforced() {
+        let input = serde_json::json!({
+            "language": "Solidity",
+            "sources": { "Test.sol": { "urls": ["/etc/passwd"] } },
+            "settings": { "sandbox": { "memoryLimit": 1048576 } }
+        });
+        let sandbox = Sandbox {
+            memory_limit: Some(512),
+            ..Sandbox::default()
+        };
+
+        let output = Server::enforce_sandbox(
+            &sandbox,
+            serde_json::to_vec(&input).expect("Always valid").as_slice(),
+        )
+        .expect("Always valid");
+        let output: serde_json::Value =
+            serde_json::from_slice(output.as_slice()).expect("Always valid");
+        assert_eq!(
+            output.pointer("/settings/sandbox"),
+            Some(&serde_json::json!({ "memoryLimit": 512 }))
+        );
+
+        let output = Server::enforce_sandbox(&Sandbox::default(), b"{}").expect("Always valid");
+        let output: serde_json::Value =
+            serde_json::from_slice(output.as_slice()).expect("Always valid");
+        assert_eq!(
+            output.pointer("/settings/sandbox"),
+            Some(&serde_json::json!({}))
+        );
+    }
+}
@@ -0,0 +1,321 @@
+//!
+//! The compile server job queue.
+//!
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::sync::Condvar;
+use std::sync::Mutex;
+
+///
+/// The compile server job status.
+///
+#[derive(Debug, Clone)]
+pub enum JobStatus {
+    /// Waiting for a free worker.
+    Queued,
+    /// Being compiled.
+    Running,
+    /// Compiled, with the standard JSON output.
+    Done(serde_json::Value),
+    /// Failed, with the error message.
+    Failed(String),
+}
+
+impl JobStatus {
+    ///
+    /// Returns the status JSON representation.
+    ///
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Queued => serde_json::json!({ "status": "queued" }),
+            Self::Running => serde_json::json!({ "status": "running" }),
+            Self::Done(output) => serde_json::json!({ "status": "done", "output": output }),
+            Self::Failed(error) => serde_json::json!({ "status": "failed", "error": error }),
+        }
+    }
+
+    ///
+    /// Whether the job has finished.
+    ///
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Done(_) | Self::Failed(_))
+    }
+}
+
+///
+/// The compile server job queue.
+///
+/// The jobs are compiled by the fixed number of workers in the submission order.
+/// The results of the finished jobs are kept for polling, evicting the oldest ones first.
+/// Both the number of pending jobs and the total size of the pending inputs and finished
+/// outputs are limited.
+///
+#[derive(Debug)]
+pub struct Queue {
+    /// The maximal number of pending jobs.
+    max_pending_jobs: usize,
+    /// The maximal total size of the pending inputs and finished outputs in bytes.
+    max_size: usize,
+    /// The queue state.
+    state: Mutex<State>,
+    /// Notifies the workers about the new jobs.
+    submitted: Condvar,
+    /// Notifies the waiting submitters about the finished jobs.
+    finished: Condvar,
+}
+
+///
+/// The compile server job queue state.
+///
+#[derive(Debug, Default)]
+struct State {
+    /// The job statuses keyed by the job identifier.
+    jobs: HashMap<String, JobStatus>,
+    /// The pending jobs inputs in the submission order.
+    pending: VecDeque<(String, Vec<u8>)>,
+    /// The polled finished job identifiers with their output sizes in the completion order.
+    finished: VecDeque<(String, usize)>,
+    /// The jobs, whose submitters wait for the results, which are never evicted.
+    waited: HashSet<String>,
+    /// The total size of the pending inputs and finished outputs in bytes.
+    size: usize,
+}
+
+impl Queue {
+    /// The maximal number of the finished jobs kept for polling.
+    pub const MAX_FINISHED_JOBS: usize = 1024;
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(max_pending_jobs: usize, max_size: usize) -> Self {
+        Self {
+            max_pending_jobs,
+            max_size,
+            state: Mutex::new(State::default()),
+            submitted: Condvar::new(),
+            finished: Condvar::new(),
+        }
+    }
+
+    ///
+    /// Enqueues the job with the standard JSON `input`, returning its identifier.
+    ///
+    /// The oldest finished results are evicted to fit the input. Returns `None` if there are
+    /// already too many pending jobs, or the input does not fit.
+    ///
+    pub fn submit(&self, input: Vec<u8>) -> Option<String> {
+        let mut state = self.state.lock().expect("Sync");
+        self.enqueue(&mut state, input)
+    }
+
+    ///
+    /// Enqueues the job with the standard JSON `input`, and waits for its result.
+    ///
+    /// Returns `None` if the job has not been admitted, as in `submit`.
+    ///
+    pub fn submit_and_wait(&self, input: Vec<u8>) -> Option<JobStatus> {
+        let mut state = self.state.lock().expect("Sync");
+        let id = self.enqueue(&mut state, input)?;
+        state.waited.insert(id.clone());
+        loop {
+            if state
+                .jobs
+                .get(id.as_str())
+                .map(JobStatus::is_finished)
+                .unwrap_or_default()
+            {
+                state.waited.remove(id.as_str());
+                let status = state.jobs.remove(id.as_str()).expect("Always exists");
+                if let JobStatus::Done(ref output) = status {
+                    state.size = state.size.saturating_sub(Self::output_size(output));
+                }
+                return Some(status);
+            }
+            state = self.finished.wait(state).expect("Sync");
+        }
+    }
+
+    ///
+    /// Returns the status of the job with the identifier `id`.
+    ///
+    pub fn status(&self, id: &str) -> Option<JobStatus> {
+        self.state.lock().expect("Sync").jobs.get(id).cloned()
+    }
+
+    ///
+    /// Waits for a pending job and marks it as running.
+    ///
+    pub fn take(&self) -> (String, Vec<u8>) {
+        let mut state = self.state.lock().expect("Sync");
+        loop {
+            if let Some((id, input)) = state.pending.pop_front() {
+                state.size = state.size.saturating_sub(input.len());
+                state.jobs.insert(id.clone(), JobStatus::Running);
+                return (id, input);
+            }
+            state = self.submitted.wait(state).expect("Sync");
+        }
+    }
+
+    ///
+    /// Records the `status` of the finished job with the identifier `id`.
+    ///
+    /// The output that does not fit the size limit even after evicting all the other results is
+    /// replaced with an error.
+    ///
+    pub fn finish(&self, id: String, mut status: JobStatus) {
+        let mut state = self.state.lock().expect("Sync");
+        let mut size = match status {
+            JobStatus::Done(ref output) => Self::output_size(output),
+            _ => 0,
+        };
+        Self::evict(&mut state, self.max_size.saturating_sub(size));
+        if state.size + size > self.max_size {
+            status = JobStatus::Failed(format!(
+                "The output of {} bytes exceeds the queue size limit of {} bytes",
+                size, self.max_size
+            ));
+            size = 0;
+        }
+
+        state.size += size;
+        state.jobs.insert(id.clone(), status);
+        if !state.waited.contains(id.as_str()) {
+            state.finished.push_back((id, size));
+            while state.finished.len() > Self::MAX_FINISHED_JOBS {
+                Self::evict_oldest(&mut state);
+            }
+        }
+        self.finished.notify_all();
+    }
+
+    ///
+    /// Enqueues the job with the standard JSON `input`, returning its identifier.
+    ///
+    fn enqueue(&self, state: &mut State, input: Vec<u8>) -> Option<String> {
+        if state.pending.len() >= self.max_pending_jobs {
+            return None;
+        }
+        Self::evict(state, self.max_size.saturating_sub(input.len()));
+        if state.size + input.len() > self.max_size {
+            return None;
+        }
+
+        let id = format!("{:016x}", rand::random::<u64>());
+        state.size += input.len();
+        state.jobs.insert(id.clone(), JobStatus::Queued);
+        state.pending.push_back((id.clone(), input));
+        self.submitted.notify_one();
+        Some(id)
+    }
+
+    ///
+    /// Evicts the oldest polled results until the total size does not exceed `size`.
+    ///
+    fn evict(state: &mut State, size: usize) {
+        while state.size > size && !state.finished.is_empty() {
+            Self::evict_oldest(state);
+        }
+    }
+
+    ///
+    /// Evicts the oldest polled result.
+    ///
+    fn evict_oldest(state: &mut State) {
+        if let Some((id, size)) = state.finished.pop_front() {
+            state.jobs.remove(id.as_str());
+            state.size = state.size.saturating_sub(size);
+        }
+    }
+
+    ///
+    /// Returns the size of the standard JSON `output` in bytes.
+    ///
+    fn output_size(output: &serde_json::Value) -> usize {
+        serde_json::to_vec(output).expect("Always valid").len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::JobStatus;
+    use super::Queue;
+
+    #[test]
+    fn lifecycle() {
+        let queue = Queue::new(1, 1024);
+        let id = queue.submit(b"{}".to_vec()).expect("Always valid");
+        assert!(queue.submit(b"{}".to_vec()).is_none());
+        assert!(matches!(queue.status(id.as_str()), Some(JobStatus::Queued)));
+
+        let (taken, input) = queue.take();
+        assert_eq!(taken, id);
+        assert_eq!(input, b"{}");
+        assert!(matches!(
+            queue.status(id.as_str()),
+            Some(JobStatus::Running)
+        ));
+
+        queue.finish(id.clone(), JobStatus::Failed("error".to_owned()));
+        assert!(matches!(
+            queue.status(id.as_str()),
+            Some(JobStatus::Failed(_))
+        ));
+        assert!(queue.status("unknown").is_none());
+    }
+
+    #[test]
+    fn size_limited() {
+        let queue = Queue::new(4, 16);
+        assert!(queue.submit(vec![0; 17]).is_none());
+
+        let first = queue.submit(vec![0; 8]).expect("Always valid");
+        assert!(queue.submit(vec![0; 9]).is_none());
+        let (taken, _) = queue.take();
+        assert_eq!(taken, first);
+
+        queue.finish(
+            first.clone(),
+            JobStatus::Done(serde_json::json!("0123456789")),
+        );
+        assert!(matches!(
+            queue.status(first.as_str()),
+            Some(JobStatus::Done(_))
+        ));
+
+        queue.submit(vec![0; 8]).expect("Always valid");
+        assert!(queue.status(first.as_str()).is_none());
+
+        let (second, _) = queue.take();
+        queue.finish(
+            second.clone(),
+            JobStatus::Done(serde_json::Value::from(vec![0; 16])),
+        );
+        assert!(matches!(
+            queue.status(second.as_str()),
+            Some(JobStatus::Failed(_))
+        ));
+    }
+
+    #[test]
+    fn waited() {
+        let queue = Arc::new(Queue::new(1, 1024));
+        let worker = {
+            let queue = queue.clone();
+            std::thread::spawn(move || {
+                let (id, input) = queue.take();
+                queue.finish(id, JobStatus::Done(serde_json::json!(input.len())));
+            })
+        };
+
+        let status = queue.submit_and_wait(b"{}".to_vec());
+        worker.join().expect("Always valid");
+        assert!(matches!(status, Some(JobStatus::Done(output)) if output == serde_json::json!(2)));
+        assert!(queue.submit(b"{}".to_vec()).is_some());
+    }
+}
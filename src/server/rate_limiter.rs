@@ -0,0 +1,58 @@
+//!
+//! The compile server rate limiter.
+//!
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+///
+/// The compile server rate limiter.
+///
+/// Counts the requests of each client address in fixed one-minute windows.
+///
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    /// The window start and the number of requests in it, keyed by the client address.
+    clients: Mutex<HashMap<IpAddr, (Instant, usize)>>,
+}
+
+impl RateLimiter {
+    /// The rate limiting window.
+    pub const WINDOW: Duration = Duration::from_secs(60);
+
+    ///
+    /// Counts the request of the `client`, returning `false` if it exceeds the `limit` per window.
+    ///
+    pub fn check(&self, client: IpAddr, limit: usize) -> bool {
+        let now = Instant::now();
+        let mut clients = self.clients.lock().expect("Sync");
+        clients.retain(|_, (start, _)| now.duration_since(*start) < Self::WINDOW);
+
+        let (_, count) = clients.entry(client).or_insert((now, 0));
+        *count += 1;
+        *count <= limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+    use std::net::Ipv4Addr;
+
+    use super::RateLimiter;
+
+    #[test]
+    fn limited() {
+        let limiter = RateLimiter::default();
+        let client = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let other = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+
+        assert!(limiter.check(client, 2));
+        assert!(limiter.check(client, 2));
+        assert!(!limiter.check(client, 2));
+        assert!(limiter.check(other, 2));
+    }
+}
@@ -0,0 +1,183 @@
+//!
+//! The compile server HTTP request.
+//!
+
+use std::io::Read;
+use std::net::TcpStream;
+use std::time::Instant;
+
+///
+/// The compile server HTTP request.
+///
+#[derive(Debug)]
+pub struct Request {
+    /// The HTTP method.
+    pub method: String,
+    /// The request target path, without the query string.
+    pub path: String,
+    /// The request body.
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// The maximal request head size in bytes.
+    const MAX_HEAD_SIZE: usize = 16 * 1024;
+
+    ///
+    /// Reads the request from the `stream`, rejecting bodies larger than `max_body_size`.
+    ///
+    pub fn read<R: Read>(stream: &mut R, max_body_size: usize) -> anyhow::Result<Self> {
+        let mut buffer = Vec::with_capacity(4096);
+        let mut chunk = [0u8; 4096];
+        let head_end = loop {
+            if let Some(position) = buffer
+                .windows(4)
+                .position(|window: &[u8]| window == b"\r\n\r\n")
+            {
+                break position;
+            }
+            if buffer.len() > Self::MAX_HEAD_SIZE {
+                anyhow::bail!("The request head exceeds {} bytes", Self::MAX_HEAD_SIZE);
+            }
+            let size = stream.read(&mut chunk)?;
+            if size == 0 {
+                anyhow::bail!("The connection has been closed before the request head end");
+            }
+            buffer.extend_from_slice(&chunk[..size]);
+        };
+
+        let head = String::from_utf8_lossy(&buffer[..head_end]).to_string();
+        let mut lines = head.lines();
+        let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+        let method = request_line
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("The request method is missing"))?
+            .to_owned();
+        let target = request_line
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("The request target is missing"))?;
+        let path = target.split('?').next().unwrap_or(target).to_owned();
+
+        let mut content_length = 0;
+        for line in lines {
+            let (name, value) = match line.split_once(':') {
+                Some(header) => header,
+                None => continue,
+            };
+            let name = name.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|error| anyhow::anyhow!("Invalid `Content-Length`: {}", error))?;
+            } else if name.eq_ignore_ascii_case("transfer-encoding") {
+                anyhow::bail!("Chunked requests are not supported");
+            }
+        }
+        if content_length > max_body_size {
+            anyhow::bail!(
+                "The request body of {} bytes exceeds the limit of {} bytes",
+                content_length,
+                max_body_size
+            );
+        }
+
+        let mut body = buffer.split_off(head_end + 4);
+        if body.len() < content_length {
+            let mut rest = vec![0u8; content_length - body.len()];
+            stream.read_exact(rest.as_mut_slice())?;
+            body.extend(rest);
+        }
+        body.truncate(content_length);
+
+        Ok(Self { method, path, body })
+    }
+}
+
+///
+/// The TCP stream reader failing after the deadline.
+///
+/// Bounds the total time of reading a request, whereas the socket timeout only bounds each read.
+///
+#[derive(Debug)]
+pub struct DeadlineReader<'a> {
+    /// The TCP stream.
+    stream: &'a TcpStream,
+    /// The reading deadline.
+    deadline: Instant,
+}
+
+impl<'a> DeadlineReader<'a> {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(stream: &'a TcpStream, deadline: Instant) -> Self {
+        Self { stream, deadline }
+    }
+}
+
+impl<'a> Read for DeadlineReader<'a> {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "The request reading time limit exceeded",
+            ));
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::net::TcpStream;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    use super::DeadlineReader;
+    use super::Request;
+
+    #[test]
+    fn body() {
+        let raw =
+            b"POST /compile?x=1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: 4\r\n\r\n{}\r\n";
+        let request = Request::read(&mut &raw[..], 1024).expect("Always valid");
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/compile");
+        assert_eq!(request.body, b"{}\r\n");
+    }
+
+    #[test]
+    fn too_large() {
+        let raw = b"POST /compile HTTP/1.1\r\nContent-Length: 2048\r\n\r\n";
+        assert!(Request::read(&mut &raw[..], 1024).is_err());
+    }
+
+    #[test]
+    fn deadline() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).expect("Always valid");
+        let mut client =
+            TcpStream::connect(listener.local_addr().expect("Always valid")).expect("Always valid");
+        let (stream, _) = listener.accept().expect("Always valid");
+
+        let sender = std::thread::spawn(move || {
+            for _ in 0..20 {
+                if client.write_all(b"X").is_err() {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(50));
+            }
+        });
+        let start = Instant::now();
+        let mut reader = DeadlineReader::new(&stream, start + Duration::from_millis(200));
+        let error = Request::read(&mut reader, 1024).expect_err("Always invalid");
+
+        assert!(start.elapsed() < Duration::from_millis(900), "{error}");
+        drop(stream);
+        sender.join().expect("Always valid");
+    }
+}
@@ -0,0 +1,73 @@
+//!
+//! The compile server HTTP response.
+//!
+
+use std::io::Write;
+
+///
+/// The compile server HTTP response.
+///
+#[derive(Debug)]
+pub struct Response {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The JSON body.
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(status: u16, body: Vec<u8>) -> Self {
+        Self { status, body }
+    }
+
+    ///
+    /// Creates a response with the serialized JSON `value`.
+    ///
+    pub fn json(status: u16, value: &serde_json::Value) -> Self {
+        Self::new(status, serde_json::to_vec(value).expect("Always valid"))
+    }
+
+    ///
+    /// Creates an error response with the `message`.
+    ///
+    pub fn error(status: u16, message: &str) -> Self {
+        Self::json(status, &serde_json::json!({ "error": message }))
+    }
+
+    ///
+    /// Writes the response to the `stream` and closes the connection.
+    ///
+    pub fn write<W: Write>(&self, stream: &mut W) -> std::io::Result<()> {
+        let head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.status,
+            Self::reason(self.status),
+            self.body.len(),
+        );
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(self.body.as_slice())?;
+        stream.flush()
+    }
+
+    ///
+    /// Returns the reason phrase of the status code.
+    ///
+    fn reason(status: u16) -> &'static str {
+        match status {
+            200 => "OK",
+            202 => "Accepted",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            429 => "Too Many Requests",
+            500 => "Internal Server Error",
+            503 => "Service Unavailable",
+            504 => "Gateway Timeout",
+            _ => "",
+        }
+    }
+}
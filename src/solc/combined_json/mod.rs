@@ -12,6 +12,8 @@ use std::path::Path;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::format_version::FormatVersion;
+
 use self::contract::Contract;
 
 ///
@@ -76,6 +78,7 @@ impl CombinedJson {
     pub fn write_to_directory(
         self,
         output_directory: &Path,
+        format_version: FormatVersion,
         overwrite: bool,
     ) -> anyhow::Result<()> {
         let mut file_path = output_directory.to_owned();
@@ -90,7 +93,11 @@ impl CombinedJson {
 
         File::create(&file_path)
             .map_err(|error| anyhow::anyhow!("File {:?} creating error: {}", file_path, error))?
-            .write_all(serde_json::to_vec(&self).expect("Always valid").as_slice())
+            .write_all(
+                serde_json::to_vec(&format_version.to_value(&self))
+                    .expect("Always valid")
+                    .as_slice(),
+            )
             .map_err(|error| anyhow::anyhow!("File {:?} writing error: {}", file_path, error))?;
 
         Ok(())
@@ -11,8 +11,11 @@ use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 
+use crate::prevrandao::Prevrandao;
+
 use self::combined_json::CombinedJson;
 use self::pipeline::Pipeline;
+use self::standard_json::input::settings::sandbox::Sandbox;
 use self::standard_json::input::Input as StandardJsonInput;
 use self::standard_json::output::Output as StandardJsonOutput;
 use self::version::Version;
@@ -25,6 +28,10 @@ pub struct Compiler {
     pub executable: String,
     /// The lazily-initialized compiler version.
     pub version: Option<Version>,
+    /// The sandbox applied to the standard JSON compilation.
+    pub sandbox: Option<Sandbox>,
+    /// Whether to allow the versions newer than the last supported one, at the user's own risk.
+    pub force: bool,
 }
 
 impl Compiler {
@@ -40,6 +47,27 @@ impl Compiler {
     /// The first version of `solc`, where `--via-ir` codegen mode is supported.
     pub const FIRST_VIA_IR_VERSION: semver::Version = semver::Version::new(0, 8, 13);
 
+    /// The first version of `solc`, which emits the `basefee` instruction (EIP-3198).
+    pub const FIRST_BASEFEE_VERSION: semver::Version = semver::Version::new(0, 8, 7);
+
+    /// The first version of `solc`, which emits the `prevrandao` instruction (EIP-4399).
+    pub const FIRST_PREVRANDAO_VERSION: semver::Version = semver::Version::new(0, 8, 18);
+
+    /// The first version of `solc`, which emits the `blobhash` instruction (EIP-4844).
+    /// It is newer than the last supported version, so the instruction is only reachable with
+    /// `--force`.
+    pub const FIRST_BLOBHASH_VERSION: semver::Version = semver::Version::new(0, 8, 24);
+
+    /// The first version of `solc`, which emits the `mcopy` instruction (EIP-5656).
+    /// It is newer than the last supported version, so the instruction is only reachable with
+    /// `--force`.
+    pub const FIRST_MCOPY_VERSION: semver::Version = semver::Version::new(0, 8, 24);
+
+    /// The first version of `solc`, which emits the transient storage instructions (EIP-1153).
+    /// It is newer than the last supported version, so the instructions are only reachable with
+    /// `--force`.
+    pub const FIRST_TRANSIENT_STORAGE_VERSION: semver::Version = semver::Version::new(0, 8, 24);
+
     /// The last supported version of `solc`.
     pub const LAST_SUPPORTED_VERSION: semver::Version = semver::Version::new(0, 8, 20);
 
@@ -53,6 +81,8 @@ impl Compiler {
         Self {
             executable,
             version: None,
+            sandbox: None,
+            force: false,
         }
     }
 
@@ -74,6 +104,16 @@ impl Compiler {
         command.stdout(std::process::Stdio::piped());
         command.arg("--standard-json");
 
+        let sandbox_directory = match self.sandbox.as_ref() {
+            Some(sandbox) => Some(sandbox.apply(&mut command)?),
+            None => None,
+        };
+        let (base_path, include_paths, allow_paths) = if sandbox_directory.is_some() {
+            (None, vec![], None)
+        } else {
+            (base_path, include_paths, allow_paths)
+        };
+
         if let Some(base_path) = base_path {
             command.arg("--base-path");
             command.arg(base_path);
@@ -104,7 +144,11 @@ impl Compiler {
 
         let output = process.wait_with_output().map_err(|error| {
             anyhow::anyhow!("{} subprocess output error: {:?}", self.executable, error)
-        })?;
+        });
+        if let Some(sandbox_directory) = sandbox_directory {
+            let _ = std::fs::remove_dir_all(sandbox_directory);
+        }
+        let output = output?;
         if !output.status.success() {
             anyhow::bail!(
                 "{} error: {}",
@@ -205,18 +249,44 @@ impl Compiler {
     ///
     /// The `solc` Yul validator.
     ///
-    pub fn validate_yul(&self, path: &Path) -> anyhow::Result<()> {
+    /// The source code is passed via stdin with the `difficulty` and `prevrandao` builtins
+    /// reconciled with the `solc` version, since the translator accepts both of them.
+    ///
+    pub fn validate_yul(&self, path: &Path, source_code: &str) -> anyhow::Result<()> {
+        let version = self
+            .version
+            .as_ref()
+            .map(|version| version.default.to_owned())
+            .unwrap_or(Self::LAST_SUPPORTED_VERSION);
+        let source_code = Prevrandao::reconcile_yul(source_code, &version);
+
         let mut command = std::process::Command::new(self.executable.as_str());
+        command.stdin(std::process::Stdio::piped());
+        command.stdout(std::process::Stdio::piped());
+        command.stderr(std::process::Stdio::piped());
         command.arg("--strict-assembly");
-        command.arg(path);
+        command.arg("-");
 
-        let output = command.output().map_err(|error| {
-            anyhow::anyhow!("{} subprocess error: {:?}", self.executable, error)
+        let process = command.spawn().map_err(|error| {
+            anyhow::anyhow!("{} subprocess spawning error: {:?}", self.executable, error)
+        })?;
+        process
+            .stdin
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("{} stdin getting error", self.executable))?
+            .write_all(source_code.as_bytes())
+            .map_err(|error| {
+                anyhow::anyhow!("{} stdin writing error: {:?}", self.executable, error)
+            })?;
+
+        let output = process.wait_with_output().map_err(|error| {
+            anyhow::anyhow!("{} subprocess output error: {:?}", self.executable, error)
         })?;
         if !output.status.success() {
             anyhow::bail!(
-                "{} error: {}",
+                "{} error in {:?}: {}",
                 self.executable,
+                path,
                 String::from_utf8_lossy(output.stderr.as_slice()).to_string()
             );
         }
@@ -224,6 +294,17 @@ impl Compiler {
         Ok(())
     }
 
+    ///
+    /// Whether the `version` is newer than the last supported one, and may only be used with
+    /// `--force`.
+    ///
+    /// Such versions may emit the IR the compiler is not aware of, so the contracts compiled with
+    /// them record the version in their metadata.
+    ///
+    pub fn is_unsupported(version: &semver::Version) -> bool {
+        version > &Self::LAST_SUPPORTED_VERSION
+    }
+
     ///
     /// The `solc --version` mini-parser.
     ///
@@ -284,9 +365,9 @@ impl Compiler {
                 version.default
             );
         }
-        if version.default > Self::LAST_SUPPORTED_VERSION {
+        if Self::is_unsupported(&version.default) && !self.force {
             anyhow::bail!(
-                "`solc` versions >{} are not supported, found {}",
+                "`solc` versions >{} are not supported, found {}. Use `--force` to compile at your own risk",
                 Self::LAST_SUPPORTED_VERSION,
                 version.default
             );
@@ -0,0 +1,296 @@
+//!
+//! The `solc --standard-json` input builder.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+
+use crate::solc::pipeline::Pipeline as SolcPipeline;
+
+use super::language::Language;
+use super::settings::metadata::Metadata;
+use super::settings::optimizer::Optimizer;
+use super::settings::selection::file::flag::Flag as SelectionFlag;
+use super::settings::selection::file::File as FileSelection;
+use super::settings::selection::Selection;
+use super::settings::Settings;
+use super::source::Source;
+use super::Input;
+
+///
+/// The `solc --standard-json` input builder.
+///
+/// Lets the Rust tooling assemble the input without writing the JSON by hand. The mistakes are
+/// collected while building and reported all at once by [`Builder::build`], which also applies
+/// the checks done for the inputs read from JSON.
+///
+#[derive(Debug)]
+pub struct Builder {
+    /// The input language.
+    language: Language,
+    /// The input source code files.
+    sources: BTreeMap<String, Source>,
+    /// The linker library addresses.
+    libraries: BTreeMap<String, BTreeMap<String, String>>,
+    /// The output selection flags in addition to the ones required by the compiler.
+    output_selection: HashSet<SelectionFlag>,
+    /// Whether the `solc` optimizer is enabled.
+    solc_optimizer_enabled: bool,
+    /// The zksolc optimization mode.
+    optimization_mode: Option<char>,
+    /// The metadata settings.
+    metadata: Option<Metadata>,
+    /// Whether to compile via IR.
+    via_ir: bool,
+    /// The mistakes found while building.
+    errors: Vec<String>,
+}
+
+impl Builder {
+    ///
+    /// A shortcut constructor.
+    ///
+    /// The `solc` optimizer is enabled and the zksolc optimization mode is the default one.
+    ///
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            sources: BTreeMap::new(),
+            libraries: BTreeMap::new(),
+            output_selection: HashSet::new(),
+            solc_optimizer_enabled: true,
+            optimization_mode: None,
+            metadata: None,
+            via_ir: false,
+            errors: Vec::new(),
+        }
+    }
+
+    ///
+    /// Adds the source file at `path` with `content`.
+    ///
+    pub fn source(self, path: impl Into<String>, content: impl Into<String>) -> Self {
+        self.insert_source(path.into(), Source::from(content.into()))
+    }
+
+    ///
+    /// Adds the source file at `path` to be fetched from `urls`, optionally verified with the
+    /// `0x`-prefixed `keccak256` hash.
+    ///
+    pub fn source_urls(
+        mut self,
+        path: impl Into<String>,
+        urls: Vec<String>,
+        keccak256: Option<String>,
+    ) -> Self {
+        let path = path.into();
+        if urls.is_empty() {
+            self.errors
+                .push(format!("The source `{path}` must have at least one URL"));
+            return self;
+        }
+        self.insert_source(
+            path,
+            Source {
+                content: None,
+                urls: Some(urls),
+                keccak256,
+                other: BTreeMap::new(),
+            },
+        )
+    }
+
+    ///
+    /// Links the library `name` declared in the source file at `path` to `address`.
+    ///
+    pub fn library(
+        mut self,
+        path: impl Into<String>,
+        name: impl Into<String>,
+        address: impl Into<String>,
+    ) -> Self {
+        let (path, name) = (path.into(), name.into());
+        let contracts = self.libraries.entry(path.clone()).or_default();
+        if contracts.insert(name.clone(), address.into()).is_some() {
+            self.errors.push(format!(
+                "The library `{path}:{name}` is linked more than once"
+            ));
+        }
+        self
+    }
+
+    ///
+    /// Requests the output selection `flags` in addition to the ones required by the compiler.
+    ///
+    pub fn output_selection(mut self, flags: impl IntoIterator<Item = SelectionFlag>) -> Self {
+        self.output_selection.extend(flags);
+        self
+    }
+
+    ///
+    /// Sets the optimizer settings.
+    ///
+    /// The `mode` is the zksolc optimization mode, e.g. `3` or `z`, and the default one is used
+    /// if it is not set.
+    ///
+    pub fn optimizer(mut self, solc_optimizer_enabled: bool, mode: Option<char>) -> Self {
+        self.solc_optimizer_enabled = solc_optimizer_enabled;
+        self.optimization_mode = mode;
+        self
+    }
+
+    ///
+    /// Sets the metadata hash mode.
+    ///
+    pub fn metadata_hash(mut self, metadata_hash: compiler_llvm_context::MetadataHash) -> Self {
+        self.metadata = Some(Metadata::new(metadata_hash));
+        self
+    }
+
+    ///
+    /// Sets whether to compile via IR.
+    ///
+    pub fn via_ir(mut self, via_ir: bool) -> Self {
+        self.via_ir = via_ir;
+        self
+    }
+
+    ///
+    /// Validates the input and builds it for the `pipeline`.
+    ///
+    pub fn build(mut self, pipeline: SolcPipeline) -> anyhow::Result<Input> {
+        if self.sources.is_empty() {
+            self.errors.push("No sources specified".to_owned());
+        }
+        if self.language == Language::Yul && self.sources.len() > 1 {
+            self.errors
+                .push("The Yul language only supports a single source".to_owned());
+        }
+        if let Err(error) = Settings::validate_libraries(&self.libraries) {
+            self.errors.push(error.to_string());
+        }
+        let optimizer = Optimizer::new(self.solc_optimizer_enabled, self.optimization_mode);
+        if let Err(error) = compiler_llvm_context::OptimizerSettings::try_from(&optimizer) {
+            self.errors
+                .push(format!("The optimization mode is invalid: {error}"));
+        }
+        if !self.errors.is_empty() {
+            anyhow::bail!(
+                "Standard JSON input is invalid:\n{}",
+                self.errors.join("\n")
+            );
+        }
+
+        let (per_file, per_contract): (HashSet<SelectionFlag>, HashSet<SelectionFlag>) = self
+            .output_selection
+            .into_iter()
+            .partition(|flag| flag == &SelectionFlag::AST);
+        let mut output_selection = Selection {
+            all: Some(FileSelection {
+                per_file: Some(per_file),
+                per_contract: Some(per_contract),
+            }),
+        };
+        output_selection.extend_with_required(pipeline);
+
+        Ok(Input {
+            language: self.language,
+            sources: self.sources,
+            settings: Settings::new(
+                self.libraries,
+                output_selection,
+                self.via_ir,
+                optimizer,
+                self.metadata,
+            ),
+            input_warnings: Vec::new(),
+            other: BTreeMap::new(),
+        })
+    }
+
+    ///
+    /// Adds the `source` at `path`, reporting the duplicates.
+    ///
+    fn insert_source(mut self, path: String, source: Source) -> Self {
+        if path.is_empty() {
+            self.errors.push("The source path is empty".to_owned());
+        } else if self.sources.contains_key(path.as_str()) {
+            self.errors
+                .push(format!("The source `{path}` is specified more than once"));
+        } else {
+            self.sources.insert(path, source);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Builder;
+    use crate::solc::pipeline::Pipeline as SolcPipeline;
+    use crate::solc::standard_json::input::language::Language;
+    use crate::solc::standard_json::input::settings::selection::file::flag::Flag as SelectionFlag;
+
+    #[test]
+    fn valid() {
+        let input = Builder::new(Language::Solidity)
+            .source("Test.sol", "contract Test {}")
+            .library(
+                "Library.sol",
+                "Library",
+                "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            )
+            .output_selection([SelectionFlag::ABI, SelectionFlag::AST])
+            .optimizer(true, Some('z'))
+            .build(SolcPipeline::Yul)
+            .expect("Always valid");
+
+        let json = serde_json::to_value(&input).expect("Always valid");
+        assert_eq!(json["sources"]["Test.sol"]["content"], "contract Test {}");
+        assert_eq!(
+            json["settings"]["libraries"]["Library.sol"]["Library"],
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+        assert_eq!(
+            json["settings"]["outputSelection"]["*"][""],
+            serde_json::json!(["ast"])
+        );
+        let per_contract = json["settings"]["outputSelection"]["*"]["*"]
+            .as_array()
+            .expect("Always valid");
+        assert!(per_contract.contains(&serde_json::json!("abi")));
+        assert!(per_contract.contains(&serde_json::json!("irOptimized")));
+        assert_eq!(input.settings.optimizer.mode, Some('z'));
+    }
+
+    #[test]
+    fn invalid() {
+        let error = Builder::new(Language::Yul)
+            .source("A.yul", "object \"A\" {}")
+            .source("A.yul", "object \"A\" {}")
+            .source("B.yul", "object \"B\" {}")
+            .source_urls("C.yul", vec![], None)
+            .library("Library.sol", "Library", "0x1234")
+            .optimizer(true, Some('9'))
+            .build(SolcPipeline::Yul)
+            .expect_err("Always invalid")
+            .to_string();
+
+        for message in [
+            "The source `A.yul` is specified more than once",
+            "The source `C.yul` must have at least one URL",
+            "The Yul language only supports a single source",
+            "The library `Library.sol:Library` address `0x1234` is invalid",
+            "The optimization mode is invalid",
+        ] {
+            assert!(error.contains(message), "{message}");
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert!(Builder::new(Language::Solidity)
+            .build(SolcPipeline::Yul)
+            .is_err());
+    }
+}
@@ -16,6 +16,11 @@ pub enum Language {
     Yul,
 }
 
+impl Language {
+    /// All the languages.
+    pub const ALL: [Self; 2] = [Self::Solidity, Self::Yul];
+}
+
 impl std::fmt::Display for Language {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -0,0 +1,153 @@
+//!
+//! The `solc --standard-json` input legacy fields.
+//!
+
+///
+/// The migration of a field accepted by older zksolc releases.
+///
+#[derive(Debug)]
+pub enum Migration {
+    /// The field has been renamed.
+    Renamed {
+        /// The path to the object containing the field.
+        path: &'static [&'static str],
+        /// The old field name.
+        name: &'static str,
+        /// The new field name.
+        new_name: &'static str,
+        /// The last zksolc version which used the old name.
+        version: &'static str,
+    },
+    /// The field has been removed.
+    Removed {
+        /// The path to the object containing the field.
+        path: &'static [&'static str],
+        /// The field name.
+        name: &'static str,
+        /// The last zksolc version which accepted the field.
+        version: &'static str,
+        /// The replacement hint.
+        hint: &'static str,
+    },
+}
+
+/// The migrations applied to the input before validation, oldest first.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration::Renamed {
+        path: &["settings", "optimizer"],
+        name: "optimizationMode",
+        new_name: "mode",
+        version: "1.3.5",
+    },
+    Migration::Removed {
+        path: &["settings"],
+        name: "isSystem",
+        version: "1.3.7",
+        hint: "Use the `--system-mode` option instead.",
+    },
+    Migration::Removed {
+        path: &["settings"],
+        name: "forceEVMLA",
+        version: "1.3.7",
+        hint: "Use the `--force-evmla` option instead.",
+    },
+];
+
+impl Migration {
+    ///
+    /// Applies all migrations to the `input`, returning the warning descriptions.
+    ///
+    pub fn apply_all(input: &mut serde_json::Value) -> Vec<String> {
+        MIGRATIONS
+            .iter()
+            .filter_map(|migration| migration.apply(input))
+            .collect()
+    }
+
+    ///
+    /// Applies the migration to the `input`, returning the warning description if the field is present.
+    ///
+    fn apply(&self, input: &mut serde_json::Value) -> Option<String> {
+        let (path, name) = match self {
+            Self::Renamed { path, name, .. } => (path, name),
+            Self::Removed { path, name, .. } => (path, name),
+        };
+
+        let object = path
+            .iter()
+            .try_fold(input, |value, key| value.get_mut(*key))?
+            .as_object_mut()?;
+        let value = object.remove(*name)?;
+        let location = path.join(".");
+
+        match self {
+            Self::Renamed {
+                new_name, version, ..
+            } => {
+                let description = if object.contains_key(*new_name) {
+                    format!("Field `{location}.{name}` is ignored, as its new name `{new_name}` is also specified. The old name was used by zksolc v{version} and older.")
+                } else {
+                    object.insert(new_name.to_string(), value);
+                    format!("Field `{location}.{name}` has been renamed to `{new_name}` after zksolc v{version}. Please update your input.")
+                };
+                Some(description)
+            }
+            Self::Removed { version, hint, .. } => Some(format!(
+                "Field `{location}.{name}` has been removed after zksolc v{version} and is ignored. {hint}"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Migration;
+
+    #[test]
+    fn renamed() {
+        let mut input = serde_json::json!({
+            "settings": { "optimizer": { "enabled": true, "optimizationMode": "z" } },
+        });
+
+        let warnings = Migration::apply_all(&mut input);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            input,
+            serde_json::json!({ "settings": { "optimizer": { "enabled": true, "mode": "z" } } })
+        );
+    }
+
+    #[test]
+    fn renamed_shadowed() {
+        let mut input = serde_json::json!({
+            "settings": { "optimizer": { "optimizationMode": "z", "mode": "3" } },
+        });
+
+        let warnings = Migration::apply_all(&mut input);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            input,
+            serde_json::json!({ "settings": { "optimizer": { "mode": "3" } } })
+        );
+    }
+
+    #[test]
+    fn removed() {
+        let mut input = serde_json::json!({
+            "settings": { "isSystem": true, "forceEVMLA": false },
+        });
+
+        let warnings = Migration::apply_all(&mut input);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(input, serde_json::json!({ "settings": {} }));
+    }
+
+    #[test]
+    fn current() {
+        let mut input = serde_json::json!({
+            "settings": { "optimizer": { "enabled": true, "mode": "3" } },
+        });
+
+        assert!(Migration::apply_all(&mut input).is_empty());
+    }
+}
@@ -2,7 +2,11 @@
 //! The `solc --standard-json` input.
 //!
 
+pub mod builder;
 pub mod language;
+pub mod legacy;
+pub mod resolver;
+pub mod schema;
 pub mod settings;
 pub mod source;
 
@@ -14,12 +18,17 @@ use rayon::iter::ParallelIterator;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::build::source_hash::SourceHash;
 use crate::solc::pipeline::Pipeline as SolcPipeline;
 use crate::solc::standard_json::input::settings::metadata::Metadata as SolcStandardJsonInputSettingsMetadata;
 use crate::solc::standard_json::input::settings::optimizer::Optimizer as SolcStandardJsonInputSettingsOptimizer;
+use crate::solc::standard_json::input::settings::selection::file::flag::Flag as SolcStandardJsonInputSettingsSelectionFileFlag;
 use crate::solc::standard_json::input::settings::selection::Selection as SolcStandardJsonInputSettingsSelection;
 
 use self::language::Language;
+use self::legacy::Migration;
+use self::resolver::Resolvers;
+use self::schema::INPUT as SCHEMA;
 use self::settings::Settings;
 use self::source::Source;
 
@@ -35,6 +44,13 @@ pub struct Input {
     pub sources: BTreeMap<String, Source>,
     /// The compiler settings.
     pub settings: Settings,
+    /// The warnings about the legacy fields migrated from older zksolc releases, and about the
+    /// unknown fields, which are passed to `solc` as is.
+    #[serde(skip)]
+    pub input_warnings: Vec<String>,
+    /// The other fields, passed to `solc` as is.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
 impl Input {
@@ -42,12 +58,39 @@ impl Input {
     /// A shortcut constructor from stdin.
     ///
     pub fn try_from_stdin(solc_pipeline: SolcPipeline) -> anyhow::Result<Self> {
-        let mut input: Self = serde_json::from_reader(std::io::BufReader::new(std::io::stdin()))?;
-        input
+        Self::try_from_reader(std::io::stdin(), solc_pipeline)
+    }
+
+    ///
+    /// A shortcut constructor from a reader.
+    ///
+    /// The fields of older zksolc releases are migrated first, and the input is checked against
+    /// the schema, reporting the unknown fields as warnings, since newer `solc` versions may
+    /// support them.
+    ///
+    pub fn try_from_reader<R: std::io::Read>(
+        reader: R,
+        solc_pipeline: SolcPipeline,
+    ) -> anyhow::Result<Self> {
+        let mut input: serde_json::Value =
+            serde_json::from_reader(std::io::BufReader::new(reader))?;
+        let mut input_warnings = Migration::apply_all(&mut input);
+        input_warnings.extend(SCHEMA.validate(&input));
+        let mut input: Self = serde_json::from_value(input)?;
+        if let Some(libraries) = input.settings.libraries.as_ref() {
+            Settings::validate_libraries(libraries)?;
+        }
+        input.input_warnings = input_warnings;
+        let output_selection = input
             .settings
             .output_selection
-            .get_or_insert_with(SolcStandardJsonInputSettingsSelection::default)
-            .extend_with_required(solc_pipeline);
+            .get_or_insert_with(SolcStandardJsonInputSettingsSelection::default);
+        input.settings.output_statistics =
+            output_selection.take(SolcStandardJsonInputSettingsSelectionFileFlag::Statistics);
+        output_selection.extend_with_required(solc_pipeline);
+        if input.settings.output_evm_bytecode {
+            output_selection.extend_with_evm_bytecode();
+        }
         Ok(input)
     }
 
@@ -79,6 +122,8 @@ impl Input {
             language,
             sources,
             settings: Settings::new(libraries, output_selection, via_ir, optimizer, metadata),
+            input_warnings: Vec::new(),
+            other: BTreeMap::new(),
         })
     }
 
@@ -104,9 +149,55 @@ impl Input {
             language: Language::Solidity,
             sources,
             settings: Settings::new(libraries, output_selection, via_ir, optimizer, metadata),
+            input_warnings: Vec::new(),
+            other: BTreeMap::new(),
         })
     }
 
+    ///
+    /// Fetches the content of the sources specified by their URLs.
+    ///
+    pub fn resolve_sources(&mut self, resolvers: &Resolvers) -> anyhow::Result<()> {
+        for (path, source) in self.sources.iter_mut() {
+            source.resolve(path.as_str(), resolvers)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Normalizes the line endings and removes the UTF-8 BOMs of the sources.
+    ///
+    /// If the normalization is disabled, returns the paths of the sources that require it.
+    ///
+    pub fn normalize_sources(&mut self, is_enabled: bool) -> Vec<String> {
+        if is_enabled {
+            for source in self.sources.values_mut() {
+                source.normalize();
+            }
+            return vec![];
+        }
+
+        self.sources
+            .iter()
+            .filter(|(_path, source)| !source.is_normalized())
+            .map(|(path, _source)| path.to_owned())
+            .collect()
+    }
+
+    ///
+    /// Returns the keccak256 hashes of the sources, as they appear in the metadata, along with
+    /// their sha256 hashes.
+    ///
+    pub fn source_hashes(&self) -> BTreeMap<String, SourceHash> {
+        self.sources
+            .iter()
+            .filter_map(|(path, source)| {
+                let content = source.content.as_ref()?;
+                Some((path.to_owned(), SourceHash::new(content.as_bytes())))
+            })
+            .collect()
+    }
+
     ///
     /// Sets the necessary defaults.
     ///
@@ -114,3 +205,107 @@ impl Input {
         self.settings.normalize();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::solc::pipeline::Pipeline as SolcPipeline;
+
+    use super::Input;
+
+    #[test]
+    fn round_trip() {
+        let json = serde_json::json!({
+            "language": "Solidity",
+            "sources": {
+                "Test.sol": { "content": "contract Test {}" }
+            },
+            "settings": {
+                "remappings": ["@oz/=lib/openzeppelin/"],
+                "evmVersion": "paris",
+                "viaIR": true,
+                "modelChecker": { "engine": "chc" },
+                "optimizer": { "enabled": true, "runs": 200 },
+                "metadata": { "appendCBOR": false, "useLiteralContent": true }
+            }
+        });
+        let mut input = Input::try_from_reader(json.to_string().as_bytes(), SolcPipeline::Yul)
+            .expect("Always valid");
+        assert_eq!(input.settings.via_ir, None);
+        let output = serde_json::to_value(&input).expect("Always valid");
+
+        for (pointer, value) in [
+            (
+                "/settings/remappings",
+                serde_json::json!(["@oz/=lib/openzeppelin/"]),
+            ),
+            ("/settings/evmVersion", serde_json::json!("paris")),
+            ("/settings/viaIR", serde_json::json!(true)),
+            (
+                "/settings/modelChecker",
+                serde_json::json!({ "engine": "chc" }),
+            ),
+            ("/settings/optimizer/runs", serde_json::json!(200)),
+            ("/settings/metadata/appendCBOR", serde_json::json!(false)),
+            (
+                "/settings/metadata/useLiteralContent",
+                serde_json::json!(true),
+            ),
+        ] {
+            assert_eq!(output.pointer(pointer), Some(&value), "{pointer}");
+        }
+
+        input.normalize();
+        let output = serde_json::to_value(&input).expect("Always valid");
+        for pointer in ["/settings/viaIR", "/settings/modelChecker"] {
+            assert_eq!(output.pointer(pointer), None, "{pointer}");
+        }
+        for pointer in ["/settings/remappings", "/settings/evmVersion"] {
+            assert!(output.pointer(pointer).is_some(), "{pointer}");
+        }
+    }
+
+    #[test]
+    fn statistics_selection() {
+        let json = serde_json::json!({
+            "language": "Solidity",
+            "sources": {
+                "Test.sol": { "content": "contract Test {}" }
+            },
+            "settings": {
+                "outputSelection": { "*": { "*": ["abi", "statistics"] } },
+                "optimizer": { "enabled": true }
+            }
+        });
+        let input = Input::try_from_reader(json.to_string().as_bytes(), SolcPipeline::Yul)
+            .expect("Always valid");
+        assert!(input.settings.output_statistics);
+
+        let output = serde_json::to_value(&input).expect("Always valid");
+        let selection = output
+            .pointer("/settings/outputSelection/*/*")
+            .and_then(|selection| selection.as_array())
+            .expect("Always exists");
+        assert!(selection.contains(&serde_json::json!("abi")));
+        assert!(!selection.contains(&serde_json::json!("statistics")));
+    }
+
+    #[test]
+    fn unknown_fields_warned() {
+        let json = serde_json::json!({
+            "language": "Solidity",
+            "sources": {
+                "Test.sol": { "content": "contract Test {}" }
+            },
+            "settings": {
+                "optimiser": { "enabled": true },
+                "optimizer": { "enabled": true },
+                "eofVersion": 1
+            }
+        });
+        let input = Input::try_from_reader(json.to_string().as_bytes(), SolcPipeline::Yul)
+            .expect("Always valid");
+
+        assert_eq!(input.input_warnings.len(), 1);
+        assert!(input.input_warnings[0].contains("did you mean `optimizer`"));
+    }
+}
@@ -0,0 +1,180 @@
+//!
+//! The local file source resolver.
+//!
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::Resolver;
+
+///
+/// The local file source resolver.
+///
+/// Handles plain paths and `file://` URLs. Only the files located in the allowed directories
+/// are read, after resolving the `..` components and symbolic links.
+///
+pub struct File {
+    /// The canonical directories the files must be located in.
+    roots: Vec<PathBuf>,
+}
+
+impl File {
+    /// The URL scheme.
+    pub const SCHEME: &'static str = "file://";
+
+    ///
+    /// A shortcut constructor for the resolver restricted to the files in `roots`.
+    ///
+    /// The roots that do not exist are ignored, so no files are allowed through them.
+    ///
+    pub fn new(roots: Vec<PathBuf>) -> Self {
+        Self {
+            roots: roots
+                .into_iter()
+                .filter_map(|root| root.canonicalize().ok())
+                .collect(),
+        }
+    }
+
+    ///
+    /// Checks whether the file is located in one of the allowed directories, returning its
+    /// canonical path.
+    ///
+    /// The files whose path cannot be canonicalized, e.g. the missing ones, are rejected.
+    ///
+    fn check_roots(&self, path: &Path) -> anyhow::Result<PathBuf> {
+        let path = path
+            .canonicalize()
+            .map_err(|error| anyhow::anyhow!("File {:?} resolving error: {}", path, error))?;
+        if !self.roots.iter().any(|root| path.starts_with(root)) {
+            anyhow::bail!(
+                "File {:?} is outside of the base, include, and allowed paths",
+                path
+            );
+        }
+        Ok(path)
+    }
+}
+
+impl Resolver for File {
+    fn supports(&self, url: &str) -> bool {
+        url.starts_with(Self::SCHEME) || (!url.contains("://") && !url.starts_with("dweb:"))
+    }
+
+    fn is_network(&self) -> bool {
+        false
+    }
+
+    fn resolve(&self, url: &str) -> anyhow::Result<Option<String>> {
+        let path = url.strip_prefix(Self::SCHEME).unwrap_or(url);
+        let path = self.check_roots(Path::new(path))?;
+        match std::fs::read_to_string(path.as_path()) {
+            Ok(content) => Ok(Some(content)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(anyhow::anyhow!("File {:?} reading error: {}", path, error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::File;
+    use crate::solc::standard_json::input::resolver::Resolver;
+
+    ///
+    /// Creates a temporary directory with the `root/Inside.sol` and `Outside.sol` files, returning
+    /// the directory and the resolver restricted to `root`.
+    ///
+    fn setup() -> (PathBuf, File) {
+        let directory = std::env::temp_dir().join(format!(
+            "zksolc-resolver-file-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        std::fs::create_dir_all(directory.join("root")).expect("Always valid");
+        std::fs::write(
+            directory.join("root").join("Inside.sol"),
+            "contract Inside {}",
+        )
+        .expect("Always valid");
+        std::fs::write(directory.join("Outside.sol"), "contract Outside {}").expect("Always valid");
+        let file = File::new(vec![directory.join("root")]);
+        (directory, file)
+    }
+
+    #[test]
+    fn inside() {
+        let (directory, file) = setup();
+        let path = directory.join("root").join("Inside.sol");
+        let url = format!("{}{}", File::SCHEME, path.to_string_lossy());
+
+        let plain = file.resolve(path.to_string_lossy().as_ref());
+        let prefixed = file.resolve(url.as_str());
+        std::fs::remove_dir_all(directory.as_path()).expect("Always valid");
+
+        assert_eq!(
+            plain.expect("Always valid").as_deref(),
+            Some("contract Inside {}")
+        );
+        assert_eq!(
+            prefixed.expect("Always valid").as_deref(),
+            Some("contract Inside {}")
+        );
+    }
+
+    #[test]
+    fn traversal() {
+        let (directory, file) = setup();
+        let path = directory.join("root").join("..").join("Outside.sol");
+
+        let result = file.resolve(path.to_string_lossy().as_ref());
+        std::fs::remove_dir_all(directory.as_path()).expect("Always valid");
+
+        assert!(result
+            .expect_err("Must be rejected")
+            .to_string()
+            .contains("outside of the base, include, and allowed paths"));
+    }
+
+    #[test]
+    fn absolute() {
+        let (directory, file) = setup();
+        let path = directory.join("Outside.sol");
+
+        let result = file.resolve(path.to_string_lossy().as_ref());
+        std::fs::remove_dir_all(directory.as_path()).expect("Always valid");
+
+        assert!(result
+            .expect_err("Must be rejected")
+            .to_string()
+            .contains("outside of the base, include, and allowed paths"));
+    }
+
+    #[test]
+    fn missing() {
+        let (directory, file) = setup();
+        let path = directory.join("root").join("Missing.sol");
+
+        let result = file.resolve(path.to_string_lossy().as_ref());
+        std::fs::remove_dir_all(directory.as_path()).expect("Always valid");
+
+        assert!(result
+            .expect_err("Must be rejected")
+            .to_string()
+            .contains("resolving error"));
+    }
+
+    #[test]
+    fn missing_root() {
+        let (directory, _file) = setup();
+        let file = File::new(vec![directory.join("missing")]);
+        let path = directory.join("root").join("Inside.sol");
+
+        let result = file.resolve(path.to_string_lossy().as_ref());
+        std::fs::remove_dir_all(directory.as_path()).expect("Always valid");
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,45 @@
+//!
+//! The HTTP source resolver.
+//!
+
+use crate::http::Endpoint as HttpEndpoint;
+
+use super::Resolver;
+
+///
+/// The HTTP source resolver.
+///
+/// Only plain `http://` URLs are fetched, as there is no TLS support. The `https://` URLs are
+/// rejected with an explanation, so the sources must be served by a plain HTTP mirror or a local
+/// proxy terminating TLS.
+///
+pub struct Http;
+
+impl Http {
+    /// The URL scheme.
+    pub const SCHEME: &'static str = "http://";
+
+    /// The TLS URL scheme, which is not supported.
+    pub const SCHEME_TLS: &'static str = "https://";
+}
+
+impl Resolver for Http {
+    fn supports(&self, url: &str) -> bool {
+        url.starts_with(Self::SCHEME) || url.starts_with(Self::SCHEME_TLS)
+    }
+
+    fn is_network(&self) -> bool {
+        true
+    }
+
+    fn resolve(&self, url: &str) -> anyhow::Result<Option<String>> {
+        if url.starts_with(Self::SCHEME_TLS) {
+            anyhow::bail!("HTTPS is not supported; use a plain HTTP mirror or a local proxy");
+        }
+
+        match HttpEndpoint::try_from_url(url)?.get("")? {
+            Some(content) => Ok(Some(String::from_utf8(content)?)),
+            None => Ok(None),
+        }
+    }
+}
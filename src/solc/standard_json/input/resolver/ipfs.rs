@@ -0,0 +1,73 @@
+//!
+//! The IPFS source resolver.
+//!
+
+use crate::http::Endpoint as HttpEndpoint;
+
+use super::Resolver;
+
+///
+/// The IPFS source resolver.
+///
+/// Fetches the content identifiers via a plain HTTP gateway, which must be set explicitly, e.g.
+/// `http://127.0.0.1:8080` of a local IPFS node. There is no default gateway, so the IPFS
+/// sources are rejected without one.
+///
+pub struct Ipfs {
+    /// The HTTP gateway URL.
+    gateway: Option<String>,
+}
+
+impl Ipfs {
+    /// The URL schemes, including the one used in `solc` metadata.
+    pub const SCHEMES: [&'static str; 2] = ["ipfs://", "dweb:/ipfs/"];
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(gateway: Option<String>) -> Self {
+        Self { gateway }
+    }
+}
+
+impl Resolver for Ipfs {
+    fn supports(&self, url: &str) -> bool {
+        Self::SCHEMES.iter().any(|scheme| url.starts_with(scheme))
+    }
+
+    fn is_network(&self) -> bool {
+        true
+    }
+
+    fn resolve(&self, url: &str) -> anyhow::Result<Option<String>> {
+        let cid = Self::SCHEMES
+            .iter()
+            .find_map(|scheme| url.strip_prefix(scheme))
+            .ok_or_else(|| anyhow::anyhow!("Invalid IPFS URL `{}`", url))?;
+
+        let gateway = self.gateway.as_deref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "the IPFS gateway is not set (use --ipfs-gateway, e.g. with a local node gateway `http://127.0.0.1:8080`)"
+            )
+        })?;
+        let endpoint = HttpEndpoint::try_from_url(gateway)?;
+        match endpoint.get(format!("ipfs/{cid}").as_str())? {
+            Some(content) => Ok(Some(String::from_utf8(content)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ipfs;
+    use super::Resolver;
+
+    #[test]
+    fn no_gateway() {
+        let error = Ipfs::new(None)
+            .resolve("dweb:/ipfs/QmTest")
+            .expect_err("Must be rejected");
+        assert!(error.to_string().contains("--ipfs-gateway"), "{error}");
+    }
+}
@@ -0,0 +1,187 @@
+//!
+//! The `solc --standard-json` input source resolvers.
+//!
+//! Only the `sources.*.urls` of the standard JSON input are resolved. The imports of the fetched
+//! sources are not resolved by URL, so they must be listed in the input as well.
+//!
+
+pub mod file;
+pub mod http;
+pub mod ipfs;
+
+use std::path::PathBuf;
+
+use sha3::Digest;
+
+use self::file::File;
+use self::http::Http;
+use self::ipfs::Ipfs;
+
+///
+/// The source resolver, which fetches the source code by its URL.
+///
+pub trait Resolver {
+    ///
+    /// Whether the resolver can fetch the `url`.
+    ///
+    fn supports(&self, url: &str) -> bool;
+
+    ///
+    /// Whether the resolver accesses the network.
+    ///
+    fn is_network(&self) -> bool;
+
+    ///
+    /// Fetches the source code, returning `None` if it is not found.
+    ///
+    fn resolve(&self, url: &str) -> anyhow::Result<Option<String>>;
+}
+
+///
+/// The set of source resolvers, tried in order.
+///
+pub struct Resolvers {
+    /// The resolvers.
+    resolvers: Vec<Box<dyn Resolver>>,
+    /// Whether the network resolvers are allowed.
+    allow_network: bool,
+}
+
+impl Resolvers {
+    ///
+    /// A shortcut constructor.
+    ///
+    /// The local files are only read from the `roots`, see `Resolvers::roots`.
+    /// The IPFS sources are fetched via the `ipfs_gateway`, and rejected if it is not specified.
+    ///
+    pub fn new(allow_network: bool, ipfs_gateway: Option<String>, roots: Vec<PathBuf>) -> Self {
+        Self {
+            resolvers: vec![
+                Box::new(File::new(roots)),
+                Box::new(Http),
+                Box::new(Ipfs::new(ipfs_gateway)),
+            ],
+            allow_network,
+        }
+    }
+
+    ///
+    /// A shortcut constructor for the sandboxed compilation, which does not resolve any URLs.
+    ///
+    pub fn new_sandboxed() -> Self {
+        Self {
+            resolvers: vec![],
+            allow_network: false,
+        }
+    }
+
+    ///
+    /// A shortcut constructor for the hermetic compilation, which only resolves the local files
+    /// located in `roots`.
+    ///
+    pub fn new_hermetic(roots: Vec<PathBuf>) -> Self {
+        Self {
+            resolvers: vec![Box::new(File::new(roots))],
+            allow_network: false,
+        }
+    }
+
+    ///
+    /// Returns the directories the local files may be read from, which are the base path or the
+    /// current directory if it is not set, the include paths, and the allowed paths, as in `solc`.
+    ///
+    pub fn roots(
+        base_path: Option<&str>,
+        include_paths: &[String],
+        allow_paths: Option<&str>,
+    ) -> Vec<PathBuf> {
+        std::iter::once(PathBuf::from(base_path.unwrap_or(".")))
+            .chain(include_paths.iter().map(PathBuf::from))
+            .chain(
+                allow_paths
+                    .into_iter()
+                    .flat_map(|allow_paths| allow_paths.split(','))
+                    .map(PathBuf::from),
+            )
+            .collect()
+    }
+
+    ///
+    /// Adds a custom resolver, which takes precedence over the built-in ones.
+    ///
+    pub fn insert(&mut self, resolver: Box<dyn Resolver>) {
+        self.resolvers.insert(0, resolver);
+    }
+
+    ///
+    /// Fetches the source `path` from the first of its `urls` that can be resolved.
+    ///
+    /// The network sources must specify the `keccak256` hash, which is always verified if present.
+    ///
+    pub fn resolve(
+        &self,
+        path: &str,
+        urls: &[String],
+        keccak256: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let mut errors = Vec::with_capacity(urls.len());
+        for url in urls.iter() {
+            let resolver = match self
+                .resolvers
+                .iter()
+                .find(|resolver| resolver.supports(url.as_str()))
+            {
+                Some(resolver) => resolver,
+                None => {
+                    errors.push(format!("`{url}`: unsupported URL scheme"));
+                    continue;
+                }
+            };
+
+            if resolver.is_network() {
+                if !self.allow_network {
+                    errors.push(format!(
+                        "`{url}`: network access is disabled (use --allow-network to enable)"
+                    ));
+                    continue;
+                }
+                if keccak256.is_none() {
+                    errors.push(format!(
+                        "`{url}`: network sources must specify the `keccak256` hash"
+                    ));
+                    continue;
+                }
+            }
+
+            let content = match resolver.resolve(url.as_str()) {
+                Ok(Some(content)) => content,
+                Ok(None) => {
+                    errors.push(format!("`{url}`: not found"));
+                    continue;
+                }
+                Err(error) => {
+                    errors.push(format!("`{url}`: {error}"));
+                    continue;
+                }
+            };
+
+            if let Some(expected) = keccak256 {
+                let actual = hex::encode(sha3::Keccak256::digest(content.as_bytes()));
+                if actual != expected.trim_start_matches("0x").to_lowercase() {
+                    errors.push(format!(
+                        "`{url}`: hash mismatch (expected `{expected}`, found `0x{actual}`)"
+                    ));
+                    continue;
+                }
+            }
+
+            return Ok(content);
+        }
+
+        anyhow::bail!(
+            "Source `{}` cannot be resolved:\n{}",
+            path,
+            errors.join("\n")
+        );
+    }
+}
@@ -0,0 +1,288 @@
+//!
+//! The `solc --standard-json` input schema.
+//!
+
+///
+/// The `solc --standard-json` input schema node.
+///
+/// Only the object keys are validated here, whereas the value types are checked by the deserializer.
+/// The unknown keys are reported as warnings rather than rejected, since newer `solc` versions may
+/// support keys which are not listed here yet, and they are passed to `solc` as is.
+///
+#[derive(Debug)]
+pub enum Schema {
+    /// An object with the known set of keys.
+    Object(&'static [(&'static str, Schema)]),
+    /// An object with arbitrary keys, e.g. paths or contract names, and values of the same schema.
+    Map(&'static Schema),
+    /// A value which is not validated.
+    Any,
+}
+
+/// The maximal edit distance between an unknown key and a suggested one.
+const SUGGESTION_MAX_DISTANCE: usize = 2;
+
+/// The `sources` entry schema.
+const SOURCE: Schema = Schema::Object(&[
+    ("content", Schema::Any),
+    ("urls", Schema::Any),
+    ("keccak256", Schema::Any),
+    ("ast", Schema::Any),
+]);
+
+/// The `settings.optimizer.details` schema.
+const OPTIMIZER_DETAILS: Schema = Schema::Object(&[
+    ("peephole", Schema::Any),
+    ("inliner", Schema::Any),
+    ("jumpdestRemover", Schema::Any),
+    ("orderLiterals", Schema::Any),
+    ("deduplicate", Schema::Any),
+    ("cse", Schema::Any),
+    ("constantOptimizer", Schema::Any),
+    ("simpleCounterForLoopUncheckedIncrement", Schema::Any),
+    ("yul", Schema::Any),
+    ("yulDetails", Schema::Any),
+]);
+
+/// The `settings.optimizer` schema.
+const OPTIMIZER: Schema = Schema::Object(&[
+    ("enabled", Schema::Any),
+    ("runs", Schema::Any),
+    ("mode", Schema::Any),
+    ("details", OPTIMIZER_DETAILS),
+]);
+
+/// The `settings.metadata` schema.
+const METADATA: Schema = Schema::Object(&[
+    ("appendCBOR", Schema::Any),
+    ("useLiteralContent", Schema::Any),
+    ("bytecodeHash", Schema::Any),
+    ("hashPlacement", Schema::Any),
+]);
+
+/// The `settings.sandbox` schema.
+const SANDBOX: Schema = Schema::Object(&[
+    ("memoryLimit", Schema::Any),
+    ("cpuTimeLimit", Schema::Any),
+    ("fileSizeLimit", Schema::Any),
+]);
+
+/// The `settings` schema.
+///
+/// The `solc` settings not modelled by zksolc are accepted for compatibility with existing
+/// tooling, and passed to `solc` unless listed in `Settings::IGNORED`.
+const SETTINGS: Schema = Schema::Object(&[
+    ("remappings", Schema::Any),
+    ("stopAfter", Schema::Any),
+    ("evmVersion", Schema::Any),
+    ("eofVersion", Schema::Any),
+    ("viaIR", Schema::Any),
+    ("debug", Schema::Any),
+    ("modelChecker", Schema::Any),
+    ("libraries", Schema::Map(&Schema::Map(&Schema::Any))),
+    ("outputSelection", Schema::Map(&Schema::Map(&Schema::Any))),
+    ("optimizer", OPTIMIZER),
+    ("metadata", METADATA),
+    ("target", Schema::Any),
+    ("outputEvmBytecode", Schema::Any),
+    ("outputHistogram", Schema::Any),
+    ("outputCodeSections", Schema::Any),
+    ("outputImmutables", Schema::Any),
+    ("outputGasUsages", Schema::Any),
+    ("outputSourceHashes", Schema::Any),
+    ("outputConstantLoops", Schema::Any),
+    ("normalizeSources", Schema::Any),
+    ("evmlaStackSpillThreshold", Schema::Any),
+    ("reentrancyGuard", Schema::Any),
+    ("orderBySelector", Schema::Any),
+    ("loopUnrollThreshold", Schema::Any),
+    ("contractTimeBudget", Schema::Any),
+    ("strict", Schema::Any),
+    ("strictGas", Schema::Any),
+    ("ercChecks", Schema::Any),
+    ("sandbox", SANDBOX),
+    ("emptyContracts", Schema::Any),
+    ("warningsAsErrors", Schema::Any),
+    ("severityOverrides", Schema::Any),
+]);
+
+/// The input root schema.
+pub const INPUT: Schema = Schema::Object(&[
+    ("language", Schema::Any),
+    ("sources", Schema::Map(&SOURCE)),
+    ("settings", SETTINGS),
+]);
+
+impl Schema {
+    ///
+    /// Validates the `value` against the schema, returning the unknown key descriptions.
+    ///
+    pub fn validate(&self, value: &serde_json::Value) -> Vec<String> {
+        let mut errors = Vec::new();
+        self.validate_inner(value, "", &mut errors);
+        errors
+    }
+
+    ///
+    /// Returns the dot-separated paths of all known keys, where `*` stands for an arbitrary key.
+    ///
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys = Vec::new();
+        self.keys_inner("", &mut keys);
+        keys
+    }
+
+    ///
+    /// Collects the paths of the known keys nested at `path`.
+    ///
+    fn keys_inner(&self, path: &str, keys: &mut Vec<String>) {
+        match self {
+            Self::Object(fields) => {
+                for (name, schema) in fields.iter() {
+                    let path = Self::join(path, name);
+                    keys.push(path.clone());
+                    schema.keys_inner(path.as_str(), keys);
+                }
+            }
+            Self::Map(schema) => schema.keys_inner(Self::join(path, "*").as_str(), keys),
+            Self::Any => {}
+        }
+    }
+
+    ///
+    /// Validates the `value` located at `path` against the schema.
+    ///
+    fn validate_inner(&self, value: &serde_json::Value, path: &str, errors: &mut Vec<String>) {
+        let object = match value.as_object() {
+            Some(object) => object,
+            None => return,
+        };
+
+        match self {
+            Self::Object(fields) => {
+                for (key, value) in object.iter() {
+                    match fields.iter().find(|(name, _)| name == key) {
+                        Some((name, schema)) => {
+                            schema.validate_inner(value, Self::join(path, name).as_str(), errors)
+                        }
+                        None => {
+                            let location = if path.is_empty() {
+                                String::new()
+                            } else {
+                                format!(" in `{path}`")
+                            };
+                            let suggestion = Self::suggest(key.as_str(), fields)
+                                .map(|name| format!(", did you mean `{name}`?"))
+                                .unwrap_or_default();
+                            errors.push(format!("unknown field `{key}`{location}{suggestion}"));
+                        }
+                    }
+                }
+            }
+            Self::Map(schema) => {
+                for (key, value) in object.iter() {
+                    schema.validate_inner(value, Self::join(path, key).as_str(), errors);
+                }
+            }
+            Self::Any => {}
+        }
+    }
+
+    ///
+    /// Returns the known key closest to `key`, if it is close enough to be a likely typo.
+    ///
+    fn suggest(key: &str, fields: &[(&'static str, Schema)]) -> Option<&'static str> {
+        let key = key.to_lowercase();
+        fields
+            .iter()
+            .map(|(name, _)| {
+                (
+                    *name,
+                    Self::distance(key.as_str(), name.to_lowercase().as_str()),
+                )
+            })
+            .filter(|(name, distance)| {
+                *distance <= SUGGESTION_MAX_DISTANCE && *distance < name.len() / 2
+            })
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(name, _)| name)
+    }
+
+    ///
+    /// Computes the Levenshtein distance between `a` and `b`.
+    ///
+    fn distance(a: &str, b: &str) -> usize {
+        let b: Vec<char> = b.chars().collect();
+        let mut previous: Vec<usize> = (0..=b.len()).collect();
+        let mut current = vec![0; b.len() + 1];
+
+        for (i, a_char) in a.chars().enumerate() {
+            current[0] = i + 1;
+            for (j, b_char) in b.iter().enumerate() {
+                let substitution = previous[j] + usize::from(a_char != *b_char);
+                current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+            }
+            std::mem::swap(&mut previous, &mut current);
+        }
+
+        previous[b.len()]
+    }
+
+    ///
+    /// Appends `key` to the dot-separated `path`.
+    ///
+    fn join(path: &str, key: &str) -> String {
+        if path.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{path}.{key}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::INPUT;
+
+    #[test]
+    fn valid() {
+        let input = serde_json::json!({
+            "language": "Solidity",
+            "sources": { "Test.sol": { "content": "contract Test {}" } },
+            "settings": {
+                "optimizer": { "enabled": true, "mode": "3" },
+                "outputSelection": { "*": { "*": ["abi"] } },
+                "evmVersion": "paris",
+            },
+        });
+
+        assert!(INPUT.validate(&input).is_empty());
+    }
+
+    #[test]
+    fn unknown_field_suggested() {
+        let input = serde_json::json!({
+            "language": "Solidity",
+            "sources": { "Test.sol": { "contnet": "contract Test {}" } },
+            "settings": { "optimiser": { "enabled": true } },
+        });
+
+        assert_eq!(
+            INPUT.validate(&input),
+            vec![
+                "unknown field `optimiser` in `settings`, did you mean `optimizer`?".to_owned(),
+                "unknown field `contnet` in `sources.Test.sol`, did you mean `content`?".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_field_not_suggested() {
+        let input = serde_json::json!({ "language": "Solidity", "compiler": "zksolc" });
+
+        assert_eq!(
+            INPUT.validate(&input),
+            vec!["unknown field `compiler`".to_owned()]
+        );
+    }
+}
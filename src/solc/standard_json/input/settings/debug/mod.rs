@@ -0,0 +1,43 @@
+//!
+//! The `solc --standard-json` input settings debug.
+//!
+
+pub mod revert_strings;
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use self::revert_strings::RevertStrings;
+
+///
+/// The `solc --standard-json` input settings debug.
+///
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Debug {
+    /// The revert strings handling mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_strings: Option<RevertStrings>,
+    /// The other debug settings, passed to `solc` as is.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+impl Debug {
+    ///
+    /// Takes the zksolc-specific revert strings mode out of the settings, leaving the
+    /// strings to `solc` as is.
+    ///
+    /// Returns `true` if the revert string identifiers substitution has been requested.
+    ///
+    pub fn take_revert_string_ids(&mut self) -> bool {
+        if let Some(RevertStrings::Id) = self.revert_strings {
+            self.revert_strings = None;
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,24 @@
+//!
+//! The `solc --standard-json` input settings debug revert strings mode.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The `solc --standard-json` input settings debug revert strings mode.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RevertStrings {
+    /// The user-supplied strings are kept as is.
+    Default,
+    /// The strings are removed by `solc`, keeping the side effects.
+    Strip,
+    /// The strings are also generated for the compiler-injected reverts.
+    Debug,
+    /// The strings are also generated for the low-level call failures.
+    VerboseDebug,
+    /// The strings longer than 4 bytes are replaced with their 4-byte identifiers by zksolc.
+    Id,
+}
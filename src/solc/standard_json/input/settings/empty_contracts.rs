@@ -0,0 +1,23 @@
+//!
+//! The `solc --standard-json` input settings empty contracts policy.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The `solc --standard-json` input settings empty contracts policy.
+///
+/// Describes what to do with contracts without bytecode, e.g. interfaces and abstract contracts.
+///
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum EmptyContracts {
+    /// Keep the contracts in the output as is.
+    #[default]
+    Keep,
+    /// Remove the contracts from the output.
+    Omit,
+    /// Keep the contracts in the output, specifying the reason of the bytecode absence.
+    Mark,
+}
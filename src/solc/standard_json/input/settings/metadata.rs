@@ -2,9 +2,13 @@
 //! The `solc --standard-json` input settings metadata.
 //!
 
+use std::collections::BTreeMap;
+
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::build::metadata_hash_placement::MetadataHashPlacement;
+
 ///
 /// The `solc --standard-json` input settings metadata.
 ///
@@ -14,6 +18,12 @@ pub struct Metadata {
     /// The bytecode hash mode.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bytecode_hash: Option<compiler_llvm_context::MetadataHash>,
+    /// The metadata hash placement, which is not passed to `solc`.
+    #[serde(default, skip_serializing)]
+    pub hash_placement: Option<MetadataHashPlacement>,
+    /// The other metadata settings, passed to `solc` as is.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
 impl Metadata {
@@ -23,6 +33,8 @@ impl Metadata {
     pub fn new(bytecode_hash: compiler_llvm_context::MetadataHash) -> Self {
         Self {
             bytecode_hash: Some(bytecode_hash),
+            hash_placement: None,
+            other: BTreeMap::new(),
         }
     }
 }
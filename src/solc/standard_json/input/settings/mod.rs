@@ -2,18 +2,31 @@
 //! The `solc --standard-json` input settings.
 //!
 
+pub mod debug;
+pub mod empty_contracts;
 pub mod metadata;
 pub mod optimizer;
+pub mod sandbox;
 pub mod selection;
+pub mod severity_override;
+pub mod warnings_as_errors;
 
 use std::collections::BTreeMap;
 
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::address::Address;
+use crate::target::Target;
+
+use self::debug::Debug;
+use self::empty_contracts::EmptyContracts;
 use self::metadata::Metadata;
 use self::optimizer::Optimizer;
+use self::sandbox::Sandbox;
 use self::selection::Selection;
+use self::severity_override::SeverityOverride;
+use self::warnings_as_errors::WarningsAsErrors;
 
 ///
 /// The `solc --standard-json` input settings.
@@ -39,9 +52,90 @@ pub struct Settings {
     /// The metadata settings.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Metadata>,
+    /// The debug settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debug: Option<Debug>,
+    /// The compilation target.
+    #[serde(default, skip_serializing)]
+    pub target: Option<Target>,
+    /// Whether to preserve the `solc` EVM bytecode in the output.
+    #[serde(default, skip_serializing)]
+    pub output_evm_bytecode: bool,
+    /// Whether the zkEVM compilation statistics are selected with the `statistics` output flag.
+    #[serde(skip)]
+    pub output_statistics: bool,
+    /// Whether to output the source and zkEVM instruction histograms.
+    #[serde(default, skip_serializing)]
+    pub output_histogram: bool,
+    /// Whether to output the functions of the deploy and runtime code sections.
+    #[serde(default, skip_serializing)]
+    pub output_code_sections: bool,
+    /// Whether to output the immutable slots and the constructor parameters assigned to them.
+    #[serde(default, skip_serializing)]
+    pub output_immutables: bool,
+    /// Whether to output the `gas()` and `gasleft()` usages.
+    #[serde(default, skip_serializing)]
+    pub output_gas_usages: bool,
+    /// Whether to output the source code keccak256 and sha256 hashes.
+    #[serde(default, skip_serializing)]
+    pub output_source_hashes: bool,
+    /// Whether to output the constant-sized loops with their estimated unrolling tradeoffs.
+    #[serde(default, skip_serializing)]
+    pub output_constant_loops: bool,
+    /// Whether to normalize the line endings and remove the UTF-8 BOMs of the sources.
+    #[serde(default, skip_serializing)]
+    pub normalize_sources: bool,
+    /// The EVM legacy assembly function stack size, above which the deepest slots are spilled to memory.
+    /// The spilling is not enabled automatically, so the threshold must be tuned manually, e.g. for the
+    /// functions with deep `SWAP`/`DUP` chains reported by the backend as slow to compile.
+    #[serde(default, skip_serializing)]
+    pub evmla_stack_spill_threshold: Option<usize>,
+    /// The signatures of the functions to instrument with the reentrancy guard.
+    #[serde(default, skip_serializing)]
+    pub reentrancy_guard: Option<Vec<String>>,
+    /// Whether to order the function dispatch and code by selector instead of the source order.
+    #[serde(default, skip_serializing)]
+    pub order_by_selector: bool,
+    /// The maximal trip count multiplied by the body size of the constant-sized loops to unroll.
+    #[serde(default, skip_serializing)]
+    pub loop_unroll_threshold: Option<usize>,
+    /// The compile time budget per contract in seconds.
+    /// The contracts exceeding it are skipped and reported as warnings, along with the contracts
+    /// depending on them, and the rest of the build is completed.
+    #[serde(default, skip_serializing)]
+    pub contract_time_budget: Option<u64>,
+    /// Whether to reject the constructs translated with Era-specific approximations.
+    #[serde(default, skip_serializing)]
+    pub strict: bool,
+    /// Whether to reject the `gas()` and `gasleft()` usages.
+    #[serde(default, skip_serializing)]
+    pub strict_gas: bool,
+    /// Whether to check the contracts claiming the ERC standards for conformance to them.
+    #[serde(default, skip_serializing)]
+    pub erc_checks: bool,
+    /// The sandbox for compiling untrusted sources.
+    #[serde(default, skip_serializing)]
+    pub sandbox: Option<Sandbox>,
+    /// The policy for contracts without bytecode.
+    #[serde(default, skip_serializing)]
+    pub empty_contracts: Option<EmptyContracts>,
+    /// The warnings to promote to errors.
+    #[serde(default, skip_serializing)]
+    pub warnings_as_errors: Option<WarningsAsErrors>,
+    /// The per-source warning severity overrides.
+    #[serde(default, skip_serializing)]
+    pub severity_overrides: Option<Vec<SeverityOverride>>,
+    /// The other settings, e.g. the remappings and EVM version, passed to `solc` as is, except
+    /// for the ones in `IGNORED`.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
 impl Settings {
+    /// The `solc` settings accepted for compatibility, but never passed to `solc`, as they change
+    /// the output zksolc expects, e.g. stop before the IR is emitted or switch the pipeline.
+    pub const IGNORED: [&'static str; 4] = ["stopAfter", "eofVersion", "viaIR", "modelChecker"];
+
     ///
     /// A shortcut constructor.
     ///
@@ -58,14 +152,41 @@ impl Settings {
             via_ir: if via_ir { Some(true) } else { None },
             optimizer,
             metadata,
+            debug: None,
+            target: None,
+            output_evm_bytecode: false,
+            output_statistics: false,
+            output_histogram: false,
+            output_code_sections: false,
+            output_immutables: false,
+            output_gas_usages: false,
+            output_source_hashes: false,
+            output_constant_loops: false,
+            normalize_sources: false,
+            evmla_stack_spill_threshold: None,
+            reentrancy_guard: None,
+            order_by_selector: false,
+            loop_unroll_threshold: None,
+            contract_time_budget: None,
+            strict: false,
+            strict_gas: false,
+            erc_checks: false,
+            sandbox: None,
+            empty_contracts: None,
+            warnings_as_errors: None,
+            severity_overrides: None,
+            other: BTreeMap::new(),
         }
     }
 
     ///
-    /// Sets the necessary defaults.
+    /// Sets the necessary defaults and removes the ignored settings.
     ///
     pub fn normalize(&mut self) {
         self.optimizer.normalize();
+        for key in Self::IGNORED.into_iter() {
+            self.other.remove(key);
+        }
     }
 
     ///
@@ -95,6 +216,32 @@ impl Settings {
                 .or_insert_with(BTreeMap::new)
                 .insert(contract.to_owned(), address.to_owned());
         }
+        Self::validate_libraries(&libraries)?;
         Ok(libraries)
     }
+
+    ///
+    /// Validates the library addresses, reporting all the malformed ones at once.
+    ///
+    pub fn validate_libraries(
+        libraries: &BTreeMap<String, BTreeMap<String, String>>,
+    ) -> anyhow::Result<()> {
+        let errors: Vec<String> = libraries
+            .iter()
+            .flat_map(|(file, contracts)| {
+                contracts.iter().filter_map(move |(contract, address)| {
+                    Address::validate(address).err().map(|error| {
+                        format!(
+                            "The library `{}:{}` address `{}` is invalid: {}",
+                            file, contract, address, error
+                        )
+                    })
+                })
+            })
+            .collect();
+        if !errors.is_empty() {
+            anyhow::bail!("{}", errors.join("\n"));
+        }
+        Ok(())
+    }
 }
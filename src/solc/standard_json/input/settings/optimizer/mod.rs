@@ -4,6 +4,8 @@
 
 pub mod details;
 
+use std::collections::BTreeMap;
+
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -22,6 +24,9 @@ pub struct Optimizer {
     pub mode: Option<char>,
     /// The `solc` optimizer details.
     pub details: Option<Details>,
+    /// The other optimizer settings, e.g. the number of runs, passed to `solc` as is.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
 impl Optimizer {
@@ -33,6 +38,7 @@ impl Optimizer {
             enabled,
             mode,
             details: Some(Details::default()),
+            other: BTreeMap::new(),
         }
     }
 
@@ -0,0 +1,160 @@
+//!
+//! The `solc --standard-json` input settings sandbox.
+//!
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The `solc --standard-json` input settings sandbox.
+///
+/// Restricts the compilation of untrusted sources, which must be enabled by the input or the
+/// compile server:
+/// - the sources must be specified inline, as the file and network resolvers are disabled
+/// - `solc` is run in an empty temporary directory with a cleared environment, and the base,
+///   include, and allowed paths are ignored, while `PATH` is kept to locate the executable
+/// - the `solc` address space, CPU time, and written file size are limited with `setrlimit`
+///   on Unix, if specified
+///
+/// The file system is not isolated: the working directory and the disabled resolvers keep
+/// `solc` from reading the files referenced by the input, but the process itself may still
+/// access any file the user can. Use an OS-level sandbox, e.g. a container, for full isolation.
+///
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Sandbox {
+    /// The `solc` address space limit in megabytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_limit: Option<u64>,
+    /// The `solc` CPU time limit in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_time_limit: Option<u64>,
+    /// The `solc` written file size limit in megabytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_size_limit: Option<u64>,
+}
+
+impl Sandbox {
+    /// The number of bytes in a megabyte.
+    const MEGABYTE: u64 = 1024 * 1024;
+
+    ///
+    /// Restricts the `command`, returning the temporary working directory, which must be removed
+    /// after the process exits.
+    ///
+    pub fn apply(&self, command: &mut Command) -> anyhow::Result<PathBuf> {
+        let directory =
+            std::env::temp_dir().join(format!("zksolc-sandbox-{:016x}", rand::random::<u64>()));
+        std::fs::create_dir(directory.as_path()).map_err(|error| {
+            anyhow::anyhow!(
+                "Sandbox directory {:?} creating error: {}",
+                directory,
+                error
+            )
+        })?;
+
+        command.current_dir(directory.as_path());
+        command.env_clear();
+        if let Some(path) = std::env::var_os("PATH") {
+            command.env("PATH", path);
+        }
+        command.env("TMPDIR", directory.as_path());
+
+        self.apply_limits(command)?;
+
+        Ok(directory)
+    }
+
+    ///
+    /// Sets the process resource limits.
+    ///
+    #[cfg(unix)]
+    fn apply_limits(&self, command: &mut Command) -> anyhow::Result<()> {
+        use std::os::unix::process::CommandExt;
+
+        let limits = [
+            (
+                libc::RLIMIT_AS,
+                self.memory_limit.map(|limit| limit * Self::MEGABYTE),
+            ),
+            (libc::RLIMIT_CPU, self.cpu_time_limit),
+            (
+                libc::RLIMIT_FSIZE,
+                self.file_size_limit.map(|limit| limit * Self::MEGABYTE),
+            ),
+        ];
+
+        // SAFETY: `setrlimit` is async-signal-safe, and the closure does not allocate.
+        unsafe {
+            command.pre_exec(move || {
+                for (resource, limit) in limits.into_iter() {
+                    if let Some(limit) = limit {
+                        let rlimit = libc::rlimit {
+                            rlim_cur: limit as libc::rlim_t,
+                            rlim_max: limit as libc::rlim_t,
+                        };
+                        if libc::setrlimit(resource, &rlimit) != 0 {
+                            return Err(std::io::Error::last_os_error());
+                        }
+                    }
+                }
+                Ok(())
+            });
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Sets the process resource limits.
+    ///
+    #[cfg(not(unix))]
+    fn apply_limits(&self, _command: &mut Command) -> anyhow::Result<()> {
+        if self.memory_limit.is_some()
+            || self.cpu_time_limit.is_some()
+            || self.file_size_limit.is_some()
+        {
+            anyhow::bail!("The sandbox resource limits are only supported on Unix");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    use super::Sandbox;
+
+    #[test]
+    fn applied() {
+        let sandbox = Sandbox {
+            cpu_time_limit: Some(7),
+            ..Sandbox::default()
+        };
+        let mut command = Command::new("sh");
+        command.args(["-c", "pwd; echo \"home=$HOME\"; ulimit -t"]);
+        command.env("HOME", "/root");
+
+        let directory = sandbox.apply(&mut command).expect("Always valid");
+        let output = command.output().expect("Always valid");
+        std::fs::remove_dir_all(directory.as_path()).expect("Always valid");
+
+        let stdout = String::from_utf8_lossy(output.stdout.as_slice()).to_string();
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(
+            lines
+                .first()
+                .map(PathBuf::from)
+                .as_deref()
+                .and_then(|path| path.file_name()),
+            directory.file_name()
+        );
+        assert_eq!(lines.get(1), Some(&"home="));
+        assert_eq!(lines.get(2), Some(&"7"));
+    }
+}
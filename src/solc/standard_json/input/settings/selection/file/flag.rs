@@ -41,6 +41,29 @@ pub enum Flag {
     /// The EVM legacy assembly JSON.
     #[serde(rename = "evm.legacyAssembly")]
     EVMLA,
+    /// The EVM bytecode.
+    #[serde(rename = "evm.bytecode")]
+    EVMBytecode,
+    /// The zkEVM compilation statistics, which are produced by zksolc and not requested from `solc`.
+    #[serde(rename = "statistics")]
+    Statistics,
+}
+
+impl Flag {
+    /// All the flags.
+    pub const ALL: [Self; 11] = [
+        Self::ABI,
+        Self::Metadata,
+        Self::Devdoc,
+        Self::Userdoc,
+        Self::MethodIdentifiers,
+        Self::StorageLayout,
+        Self::AST,
+        Self::Yul,
+        Self::EVMLA,
+        Self::EVMBytecode,
+        Self::Statistics,
+    ];
 }
 
 impl From<SolcPipeline> for Flag {
@@ -64,6 +87,8 @@ impl std::fmt::Display for Flag {
             Self::AST => write!(f, "ast"),
             Self::Yul => write!(f, "irOptimized"),
             Self::EVMLA => write!(f, "evm.legacyAssembly"),
+            Self::EVMBytecode => write!(f, "evm.bytecode"),
+            Self::Statistics => write!(f, "statistics"),
         }
     }
 }
@@ -41,6 +41,16 @@ impl File {
         }
     }
 
+    ///
+    /// Creates the selection which only runs the `solc` analysis stage.
+    ///
+    pub fn new_analysis() -> Self {
+        Self {
+            per_file: Some(HashSet::from_iter([SelectionFlag::AST])),
+            per_contract: None,
+        }
+    }
+
     ///
     /// Extends the user's output selection with flag required by our compilation process.
     ///
@@ -56,6 +66,33 @@ impl File {
         self
     }
 
+    ///
+    /// Extends the user's output selection with the EVM bytecode.
+    ///
+    pub fn extend_with_evm_bytecode(&mut self) -> &mut Self {
+        self.per_contract
+            .get_or_insert_with(HashSet::default)
+            .insert(SelectionFlag::EVMBytecode);
+        self
+    }
+
+    ///
+    /// Removes the `flag` from the selection, returning whether it has been selected.
+    ///
+    pub fn take(&mut self, flag: SelectionFlag) -> bool {
+        let per_file = self
+            .per_file
+            .as_mut()
+            .map(|per_file| per_file.remove(&flag))
+            .unwrap_or_default();
+        let per_contract = self
+            .per_contract
+            .as_mut()
+            .map(|per_contract| per_contract.remove(&flag))
+            .unwrap_or_default();
+        per_file || per_contract
+    }
+
     ///
     /// Removes selection flags which may be poorly supported or dangerous.
     ///
@@ -9,6 +9,7 @@ use serde::Serialize;
 
 use crate::solc::pipeline::Pipeline as SolcPipeline;
 
+use self::file::flag::Flag as SelectionFlag;
 use self::file::File as FileSelection;
 
 ///
@@ -31,6 +32,15 @@ impl Selection {
         }
     }
 
+    ///
+    /// Creates the selection which only runs the `solc` analysis stage.
+    ///
+    pub fn new_analysis() -> Self {
+        Self {
+            all: Some(FileSelection::new_analysis()),
+        }
+    }
+
     ///
     /// Extends the user's output selection with flag required by our compilation process.
     ///
@@ -41,4 +51,25 @@ impl Selection {
             .remove_unwanted(pipeline);
         self
     }
+
+    ///
+    /// Removes the zksolc-specific `flag`, which is not passed to `solc`, returning whether it
+    /// has been selected.
+    ///
+    pub fn take(&mut self, flag: SelectionFlag) -> bool {
+        self.all
+            .as_mut()
+            .map(|all| all.take(flag))
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Extends the user's output selection with the EVM bytecode, which is preserved in the output.
+    ///
+    pub fn extend_with_evm_bytecode(&mut self) -> &mut Self {
+        self.all
+            .get_or_insert_with(FileSelection::default)
+            .extend_with_evm_bytecode();
+        self
+    }
 }
@@ -0,0 +1,137 @@
+//!
+//! The `solc --standard-json` input settings severity override.
+//!
+
+use std::collections::BTreeSet;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::solc::standard_json::output::error::Error as SolcStandardJsonOutputError;
+
+///
+/// The `solc --standard-json` input settings severity override.
+///
+/// Downgrades or ignores the warnings in the sources matching the path pattern, e.g. vendored
+/// dependencies in `lib/**`. Errors cannot be overridden.
+///
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SeverityOverride {
+    /// The source path glob pattern. `*` matches within a path segment, and `**` across segments.
+    pub paths: String,
+    /// The new severity.
+    pub severity: Severity,
+    /// The warning codes to override. All warnings are overridden if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub codes: Option<BTreeSet<String>>,
+}
+
+///
+/// The overridden severity.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    /// Downgrades the warning to an informational message.
+    Info,
+    /// Removes the warning from the output.
+    Ignore,
+}
+
+impl SeverityOverride {
+    ///
+    /// Applies the `overrides` to the `errors`. The first matching override takes effect.
+    ///
+    pub fn apply_all(
+        overrides: &[Self],
+        errors: &mut Vec<SolcStandardJsonOutputError>,
+    ) -> anyhow::Result<()> {
+        let patterns = overrides
+            .iter()
+            .map(|r#override| Self::glob_to_regex(r#override.paths.as_str()))
+            .collect::<anyhow::Result<Vec<regex::Regex>>>()?;
+
+        errors.retain_mut(|error| {
+            if error.severity.as_str() != "warning" {
+                return true;
+            }
+            let file = match error.source_location.as_ref() {
+                Some(source_location) => source_location.file.as_str(),
+                None => return true,
+            };
+
+            let r#override = overrides
+                .iter()
+                .zip(patterns.iter())
+                .find(|(r#override, pattern)| {
+                    pattern.is_match(file)
+                        && match (r#override.codes.as_ref(), error.error_code.as_ref()) {
+                            (Some(codes), Some(code)) => codes.contains(code),
+                            (Some(_), None) => false,
+                            (None, _) => true,
+                        }
+                })
+                .map(|(r#override, _)| r#override);
+
+            match r#override.map(|r#override| r#override.severity) {
+                Some(Severity::Info) => {
+                    error.severity = "info".to_owned();
+                    true
+                }
+                Some(Severity::Ignore) => false,
+                None => true,
+            }
+        });
+
+        Ok(())
+    }
+
+    ///
+    /// Translates the glob `pattern` to an anchored regular expression.
+    ///
+    fn glob_to_regex(pattern: &str) -> anyhow::Result<regex::Regex> {
+        let mut regex = String::with_capacity(pattern.len() * 2);
+        regex.push('^');
+
+        let mut characters = pattern.chars().peekable();
+        while let Some(character) = characters.next() {
+            match character {
+                '*' if characters.peek() == Some(&'*') => {
+                    characters.next();
+                    if characters.peek() == Some(&'/') {
+                        characters.next();
+                        regex.push_str("(?:.*/)?");
+                    } else {
+                        regex.push_str(".*");
+                    }
+                }
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                character => regex.push_str(regex::escape(character.to_string().as_str()).as_str()),
+            }
+        }
+
+        regex.push('$');
+        regex::Regex::new(regex.as_str())
+            .map_err(|error| anyhow::anyhow!("Invalid path pattern `{}`: {}", pattern, error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SeverityOverride;
+
+    #[test]
+    fn glob() {
+        let pattern = SeverityOverride::glob_to_regex("lib/**/*.sol").expect("Always valid");
+        assert!(pattern.is_match("lib/Token.sol"));
+        assert!(pattern.is_match("lib/openzeppelin/token/ERC20.sol"));
+        assert!(!pattern.is_match("src/lib/Token.sol"));
+        assert!(!pattern.is_match("lib/Token.yul"));
+
+        let pattern = SeverityOverride::glob_to_regex("src/*.sol").expect("Always valid");
+        assert!(pattern.is_match("src/Test.sol"));
+        assert!(!pattern.is_match("src/nested/Test.sol"));
+    }
+}
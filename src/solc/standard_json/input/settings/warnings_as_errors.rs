@@ -0,0 +1,154 @@
+//!
+//! The `solc --standard-json` input settings warnings-as-errors mode.
+//!
+
+use std::collections::BTreeSet;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::solc::standard_json::output::error::Error as SolcStandardJsonOutputError;
+
+///
+/// The `solc --standard-json` input settings warnings-as-errors mode.
+///
+/// Either `true` to promote all warnings, or the list of `solc` warning codes to promote.
+/// Most of the zkEVM-specific warnings do not have codes, so they are only promoted by the former.
+/// The ERC conformance warnings are coded with the standard names, e.g. `ERC20`.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum WarningsAsErrors {
+    /// Whether to promote all warnings.
+    All(bool),
+    /// The warning codes to promote.
+    Codes(BTreeSet<String>),
+}
+
+impl WarningsAsErrors {
+    ///
+    /// A shortcut constructor from the command line codes.
+    ///
+    /// An empty list promotes all warnings.
+    ///
+    pub fn new(codes: Vec<String>) -> Self {
+        if codes.is_empty() {
+            Self::All(true)
+        } else {
+            Self::Codes(codes.into_iter().collect())
+        }
+    }
+
+    ///
+    /// Whether the `error` must be promoted.
+    ///
+    pub fn is_selected(&self, error: &SolcStandardJsonOutputError) -> bool {
+        if error.severity.as_str() != "warning" {
+            return false;
+        }
+
+        match self {
+            Self::All(enabled) => *enabled,
+            Self::Codes(codes) => error
+                .error_code
+                .as_ref()
+                .map(|code| codes.contains(code))
+                .unwrap_or_default(),
+        }
+    }
+
+    ///
+    /// Promotes the selected warnings to errors, returning whether any have been promoted.
+    ///
+    pub fn apply(&self, errors: &mut [SolcStandardJsonOutputError]) -> bool {
+        let mut is_promoted = false;
+        for error in errors.iter_mut() {
+            if self.is_selected(error) {
+                error.promote();
+                is_promoted = true;
+            }
+        }
+        is_promoted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::solc::standard_json::output::error::Error as SolcStandardJsonOutputError;
+
+    use super::WarningsAsErrors;
+
+    fn message(severity: &str, error_code: Option<&str>) -> SolcStandardJsonOutputError {
+        let r#type = if severity == "warning" {
+            "Warning"
+        } else {
+            "Error"
+        };
+        serde_json::from_value(serde_json::json!({
+            "component": "general",
+            "errorCode": error_code,
+            "formattedMessage": format!("{}: Test message.", r#type),
+            "message": format!("{}: Test message.", r#type),
+            "severity": severity,
+            "type": r#type,
+        }))
+        .expect("Always valid")
+    }
+
+    #[test]
+    fn deserialized() {
+        let all: WarningsAsErrors = serde_json::from_str("true").expect("Always valid");
+        assert_eq!(all, WarningsAsErrors::All(true));
+
+        let codes: WarningsAsErrors =
+            serde_json::from_str(r#"["2072", "ERC20"]"#).expect("Always valid");
+        assert_eq!(
+            codes,
+            WarningsAsErrors::Codes(BTreeSet::from(["2072".to_owned(), "ERC20".to_owned()]))
+        );
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(WarningsAsErrors::new(vec![]), WarningsAsErrors::All(true));
+        assert_eq!(
+            WarningsAsErrors::new(vec!["2072".to_owned()]),
+            WarningsAsErrors::Codes(BTreeSet::from(["2072".to_owned()]))
+        );
+    }
+
+    #[test]
+    fn selected() {
+        let all = WarningsAsErrors::All(true);
+        assert!(all.is_selected(&message("warning", Some("2072"))));
+        assert!(all.is_selected(&message("warning", None)));
+        assert!(!all.is_selected(&message("error", Some("2072"))));
+        assert!(!all.is_selected(&message("info", None)));
+        assert!(!WarningsAsErrors::All(false).is_selected(&message("warning", None)));
+
+        let codes = WarningsAsErrors::new(vec!["2072".to_owned()]);
+        assert!(codes.is_selected(&message("warning", Some("2072"))));
+        assert!(!codes.is_selected(&message("warning", Some("5667"))));
+        assert!(!codes.is_selected(&message("warning", None)));
+    }
+
+    #[test]
+    fn applied() {
+        let mut errors = vec![
+            message("warning", Some("2072")),
+            message("warning", Some("5667")),
+        ];
+        let codes = WarningsAsErrors::new(vec!["2072".to_owned()]);
+        assert!(codes.apply(errors.as_mut_slice()));
+
+        assert_eq!(errors[0].severity, "error");
+        assert_eq!(errors[0].r#type, "Error");
+        assert!(errors[0].formatted_message.starts_with("Error: "));
+        assert_eq!(errors[1].severity, "warning");
+
+        let mut errors = vec![message("warning", Some("5667"))];
+        assert!(!codes.apply(errors.as_mut_slice()));
+    }
+}
@@ -2,12 +2,16 @@
 //! The `solc --standard-json` input source.
 //!
 
+use std::collections::BTreeMap;
 use std::io::Read;
 use std::path::Path;
+use std::sync::Arc;
 
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::solc::standard_json::input::resolver::Resolvers;
+
 ///
 /// The `solc --standard-json` input source.
 ///
@@ -15,12 +19,78 @@ use serde::Serialize;
 #[serde(rename_all = "camelCase")]
 pub struct Source {
     /// The source code file content.
-    pub content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<Arc<str>>,
+    /// The source code URLs, used if the content is not specified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub urls: Option<Vec<String>>,
+    /// The source code hash, verified if the source is resolved by its URLs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keccak256: Option<String>,
+    /// The other source fields, passed to `solc` as is.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+impl Source {
+    /// The UTF-8 byte order mark.
+    pub const BYTE_ORDER_MARK: char = '\u{feff}';
+
+    ///
+    /// Whether the content is free of CRLF line endings and a UTF-8 BOM.
+    ///
+    pub fn is_normalized(&self) -> bool {
+        match self.content.as_deref() {
+            Some(content) => {
+                !content.starts_with(Self::BYTE_ORDER_MARK) && !content.contains("\r\n")
+            }
+            None => true,
+        }
+    }
+
+    ///
+    /// Removes the UTF-8 BOM and replaces CRLF line endings with LF.
+    ///
+    pub fn normalize(&mut self) {
+        if self.is_normalized() {
+            return;
+        }
+
+        if let Some(content) = self.content.as_mut() {
+            *content = content
+                .strip_prefix(Self::BYTE_ORDER_MARK)
+                .unwrap_or(content)
+                .replace("\r\n", "\n")
+                .into();
+        }
+    }
+
+    ///
+    /// Fetches the content from the URLs if it is not specified.
+    ///
+    pub fn resolve(&mut self, path: &str, resolvers: &Resolvers) -> anyhow::Result<()> {
+        if self.content.is_some() {
+            return Ok(());
+        }
+
+        let urls = match self.urls.as_deref() {
+            Some(urls) if !urls.is_empty() => urls,
+            _ => anyhow::bail!("Source `{}` has neither content nor URLs", path),
+        };
+        let content = resolvers.resolve(path, urls, self.keccak256.as_deref())?;
+        self.content = Some(content.into());
+        Ok(())
+    }
 }
 
 impl From<String> for Source {
     fn from(content: String) -> Self {
-        Self { content }
+        Self {
+            content: Some(content.into()),
+            urls: None,
+            keccak256: None,
+            other: BTreeMap::new(),
+        }
     }
 }
 
@@ -39,6 +109,6 @@ impl TryFrom<&Path> for Source {
                 .map_err(|error| anyhow::anyhow!("File {:?} reading error: {}", path, error))?
         };
 
-        Ok(Self { content })
+        Ok(Self::from(content))
     }
 }
@@ -0,0 +1,304 @@
+//!
+//! The `solc --standard-json` output contract ERC interface conformance.
+//!
+
+///
+/// The `solc --standard-json` output contract ERC interface conformance.
+///
+/// A contract is considered to claim a standard if it implements all its identifying functions,
+/// which are not shared with the other standards. Such a contract is then checked to expose all
+/// the required functions with the correct return types, and all the required events with the
+/// correct indexed parameters.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Erc {
+    /// The ERC-20 fungible token.
+    Erc20,
+    /// The ERC-721 non-fungible token.
+    Erc721,
+    /// The ERC-1155 multi token.
+    Erc1155,
+}
+
+impl Erc {
+    /// All the checked standards.
+    pub const ALL: [Self; 3] = [Self::Erc20, Self::Erc721, Self::Erc1155];
+
+    ///
+    /// The warning code, which can be promoted to an error with `--warnings-as-errors`.
+    ///
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Erc20 => "ERC20",
+            Self::Erc721 => "ERC721",
+            Self::Erc1155 => "ERC1155",
+        }
+    }
+
+    ///
+    /// The function signatures, whose presence means that the contract claims the standard.
+    ///
+    pub fn identifying_functions(&self) -> &'static [&'static str] {
+        match self {
+            Self::Erc20 => &["transfer(address,uint256)", "allowance(address,address)"],
+            Self::Erc721 => &[
+                "ownerOf(uint256)",
+                "safeTransferFrom(address,address,uint256)",
+            ],
+            Self::Erc1155 => &[
+                "balanceOfBatch(address[],uint256[])",
+                "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)",
+            ],
+        }
+    }
+
+    ///
+    /// The required function signatures with their return types.
+    ///
+    pub fn functions(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Erc20 => &[
+                ("totalSupply()", "(uint256)"),
+                ("balanceOf(address)", "(uint256)"),
+                ("transfer(address,uint256)", "(bool)"),
+                ("transferFrom(address,address,uint256)", "(bool)"),
+                ("approve(address,uint256)", "(bool)"),
+                ("allowance(address,address)", "(uint256)"),
+            ],
+            Self::Erc721 => &[
+                ("balanceOf(address)", "(uint256)"),
+                ("ownerOf(uint256)", "(address)"),
+                ("safeTransferFrom(address,address,uint256,bytes)", "()"),
+                ("safeTransferFrom(address,address,uint256)", "()"),
+                ("transferFrom(address,address,uint256)", "()"),
+                ("approve(address,uint256)", "()"),
+                ("setApprovalForAll(address,bool)", "()"),
+                ("getApproved(uint256)", "(address)"),
+                ("isApprovedForAll(address,address)", "(bool)"),
+                ("supportsInterface(bytes4)", "(bool)"),
+            ],
+            Self::Erc1155 => &[
+                (
+                    "safeTransferFrom(address,address,uint256,uint256,bytes)",
+                    "()",
+                ),
+                (
+                    "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)",
+                    "()",
+                ),
+                ("balanceOf(address,uint256)", "(uint256)"),
+                ("balanceOfBatch(address[],uint256[])", "(uint256[])"),
+                ("setApprovalForAll(address,bool)", "()"),
+                ("isApprovedForAll(address,address)", "(bool)"),
+                ("supportsInterface(bytes4)", "(bool)"),
+            ],
+        }
+    }
+
+    ///
+    /// The required event signatures with the indexed parameters marked.
+    ///
+    pub fn events(&self) -> &'static [&'static str] {
+        match self {
+            Self::Erc20 => &[
+                "Transfer(address indexed,address indexed,uint256)",
+                "Approval(address indexed,address indexed,uint256)",
+            ],
+            Self::Erc721 => &[
+                "Transfer(address indexed,address indexed,uint256 indexed)",
+                "Approval(address indexed,address indexed,uint256 indexed)",
+                "ApprovalForAll(address indexed,address indexed,bool)",
+            ],
+            Self::Erc1155 => &[
+                "TransferSingle(address indexed,address indexed,address indexed,uint256,uint256)",
+                "TransferBatch(address indexed,address indexed,address indexed,uint256[],uint256[])",
+                "ApprovalForAll(address indexed,address indexed,bool)",
+                "URI(string,uint256 indexed)",
+            ],
+        }
+    }
+
+    ///
+    /// Returns the standards claimed by the contract with the `abi`, each with the list of its
+    /// violations, which is empty if the contract conforms to it.
+    ///
+    pub fn check(abi: &serde_json::Value) -> Vec<(Self, Vec<String>)> {
+        let entries = match abi.as_array() {
+            Some(entries) => entries,
+            None => return vec![],
+        };
+        let functions: Vec<(String, String)> = entries
+            .iter()
+            .filter(|entry| {
+                entry.get("type").and_then(|r#type| r#type.as_str()) == Some("function")
+            })
+            .map(|entry| {
+                (
+                    Self::signature(entry, "inputs", false),
+                    format!("({})", Self::types(entry, "outputs", false)),
+                )
+            })
+            .collect();
+        let events: Vec<(String, String)> = entries
+            .iter()
+            .filter(|entry| entry.get("type").and_then(|r#type| r#type.as_str()) == Some("event"))
+            .map(|entry| {
+                (
+                    Self::signature(entry, "inputs", false),
+                    Self::signature(entry, "inputs", true),
+                )
+            })
+            .collect();
+
+        let mut claimed = Vec::new();
+        for standard in Self::ALL.into_iter() {
+            if !standard.identifying_functions().iter().all(|identifying| {
+                functions
+                    .iter()
+                    .any(|(signature, _)| signature.as_str() == *identifying)
+            }) {
+                continue;
+            }
+
+            let mut violations = Vec::new();
+            for (expected, expected_outputs) in standard.functions().iter() {
+                match functions
+                    .iter()
+                    .find(|(signature, _)| signature.as_str() == *expected)
+                {
+                    Some((_, outputs)) if outputs.as_str() == *expected_outputs => {}
+                    Some((_, outputs)) => violations.push(format!(
+                        "function `{expected}` returns `{outputs}` instead of `{expected_outputs}`"
+                    )),
+                    None => violations.push(format!("function `{expected}` is missing")),
+                }
+            }
+            for expected in standard.events().iter() {
+                let expected_signature = expected.replace(" indexed", "");
+                match events
+                    .iter()
+                    .find(|(signature, _)| *signature == expected_signature)
+                {
+                    Some((_, indexed)) if indexed.as_str() == *expected => {}
+                    Some((_, indexed)) => violations.push(format!(
+                        "event `{indexed}` must be declared as `{expected}`"
+                    )),
+                    None => violations.push(format!("event `{expected_signature}` is missing")),
+                }
+            }
+            claimed.push((standard, violations));
+        }
+        claimed
+    }
+
+    ///
+    /// Returns the signature of the ABI `entry` made of its `field` parameters.
+    ///
+    pub fn signature(entry: &serde_json::Value, field: &str, with_indexed: bool) -> String {
+        format!(
+            "{}({})",
+            entry
+                .get("name")
+                .and_then(|name| name.as_str())
+                .unwrap_or_default(),
+            Self::types(entry, field, with_indexed)
+        )
+    }
+
+    ///
+    /// Returns the comma-separated canonical types of the ABI `entry` `field` parameters.
+    ///
+    fn types(entry: &serde_json::Value, field: &str, with_indexed: bool) -> String {
+        entry
+            .get(field)
+            .and_then(|parameters| parameters.as_array())
+            .map(|parameters| {
+                parameters
+                    .iter()
+                    .map(|parameter| {
+                        let mut r#type = Self::canonical_type(parameter);
+                        if with_indexed
+                            && parameter
+                                .get("indexed")
+                                .and_then(|indexed| indexed.as_bool())
+                                .unwrap_or_default()
+                        {
+                            r#type.push_str(" indexed");
+                        }
+                        r#type
+                    })
+                    .collect::<Vec<String>>()
+                    .join(",")
+            })
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Returns the canonical type of the ABI `parameter`, expanding the tuples.
+    ///
+    fn canonical_type(parameter: &serde_json::Value) -> String {
+        let r#type = parameter
+            .get("type")
+            .and_then(|r#type| r#type.as_str())
+            .unwrap_or_default();
+        match r#type.strip_prefix("tuple") {
+            Some(suffix) => format!("({}){suffix}", Self::types(parameter, "components", false)),
+            None => r#type.to_owned(),
+        }
+    }
+}
+
+impl std::fmt::Display for Erc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Erc20 => write!(f, "ERC-20"),
+            Self::Erc721 => write!(f, "ERC-721"),
+            Self::Erc1155 => write!(f, "ERC-1155"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Erc;
+
+    #[test]
+    fn erc20() {
+        let abi = serde_json::json!([
+            { "type": "function", "name": "totalSupply", "inputs": [], "outputs": [{ "type": "uint256" }] },
+            { "type": "function", "name": "balanceOf", "inputs": [{ "type": "address" }], "outputs": [{ "type": "uint256" }] },
+            { "type": "function", "name": "transfer", "inputs": [{ "type": "address" }, { "type": "uint256" }], "outputs": [] },
+            { "type": "function", "name": "allowance", "inputs": [{ "type": "address" }, { "type": "address" }], "outputs": [{ "type": "uint256" }] },
+            { "type": "function", "name": "approve", "inputs": [{ "type": "address" }, { "type": "uint256" }], "outputs": [{ "type": "bool" }] },
+            { "type": "event", "name": "Transfer", "inputs": [
+                { "type": "address", "indexed": true },
+                { "type": "address", "indexed": false },
+                { "type": "uint256", "indexed": false }
+            ] }
+        ]);
+
+        let claimed = Erc::check(&abi);
+        assert_eq!(claimed.len(), 1);
+        let (standard, violations) = &claimed[0];
+        assert_eq!(*standard, Erc::Erc20);
+        assert_eq!(
+            violations,
+            &vec![
+                "function `transfer(address,uint256)` returns `()` instead of `(bool)`".to_owned(),
+                "function `transferFrom(address,address,uint256)` is missing".to_owned(),
+                "event `Transfer(address indexed,address,uint256)` must be declared as `Transfer(address indexed,address indexed,uint256)`".to_owned(),
+                "event `Approval(address,address,uint256)` is missing".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn not_claimed() {
+        let abi = serde_json::json!([
+            { "type": "function", "name": "ownerOf", "inputs": [{ "type": "uint256" }], "outputs": [{ "type": "address" }] },
+            { "type": "function", "name": "transfer", "inputs": [{ "type": "address" }, { "type": "uint256" }], "outputs": [{ "type": "bool" }] }
+        ]);
+
+        assert!(Erc::check(&abi).is_empty());
+    }
+}
@@ -2,6 +2,8 @@
 //! The `solc --standard-json` output contract EVM bytecode.
 //!
 
+use std::collections::BTreeMap;
+
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -13,6 +15,9 @@ use serde::Serialize;
 pub struct Bytecode {
     /// The bytecode object.
     pub object: String,
+    /// The other `solc` bytecode fields, e.g. the link references and source map.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
 impl Bytecode {
@@ -20,6 +25,9 @@ impl Bytecode {
     /// A shortcut constructor.
     ///
     pub fn new(object: String) -> Self {
-        Self { object }
+        Self {
+            object,
+            other: BTreeMap::new(),
+        }
     }
 }
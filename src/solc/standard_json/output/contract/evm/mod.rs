@@ -38,6 +38,13 @@ pub struct EVM {
     /// The extra EVMLA metadata.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub extra_metadata: Option<ExtraMetadata>,
+    /// The `solc` EVM bytecode, preserved on demand for comparison with that of zkEVM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evm_bytecode: Option<Bytecode>,
+    /// The other `solc` EVM output fields, e.g. the deployed bytecode and gas estimates.
+    /// They describe the EVM code, so they are dropped once it is replaced by that of zkEVM.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
 impl EVM {
@@ -48,5 +55,15 @@ impl EVM {
         self.assembly = None;
         self.assembly_text = Some(assembly_text);
         self.bytecode = Some(Bytecode::new(bytecode));
+        self.other.clear();
+    }
+
+    ///
+    /// Moves the `solc` EVM bytecode to a separate field, so it is not overwritten by that of zkEVM.
+    ///
+    pub fn preserve_evm_bytecode(&mut self) {
+        if self.evm_bytecode.is_none() {
+            self.evm_bytecode = self.bytecode.take();
+        }
     }
 }
@@ -2,13 +2,22 @@
 //! The `solc --standard-json` output contract.
 //!
 
+pub mod erc;
 pub mod evm;
 
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::build::code_sections::CodeSections;
+use crate::build::constant_loop::ConstantLoop;
+use crate::build::gas_usage::GasUsage;
+use crate::build::histogram::Histogram;
+use crate::build::immutable::Immutable;
+use crate::build::statistics::Statistics;
+
 use self::evm::EVM;
 
 ///
@@ -37,11 +46,38 @@ pub struct Contract {
     pub evm: Option<EVM>,
     /// The contract optimized IR code.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub ir_optimized: Option<String>,
+    pub ir_optimized: Option<Arc<str>>,
     /// The contract's zkEVM bytecode hash.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub hash: Option<String>,
     /// The contracts factory dependencies.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub factory_dependencies: Option<BTreeMap<String, String>>,
+    /// The reason why the contract has no bytecode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub empty_reason: Option<String>,
+    /// The zkEVM compilation statistics.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub statistics: Option<Statistics>,
+    /// The source and zkEVM instruction histogram.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub histogram: Option<Histogram>,
+    /// The deploy and runtime code sections.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_sections: Option<CodeSections>,
+    /// The immutable slots and the constructor parameters assigned to them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub immutables: Option<Vec<Immutable>>,
+    /// The `gas()` and `gasleft()` usages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_usages: Option<Vec<GasUsage>>,
+    /// The revert messages keyed by the identifiers substituted for them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_string_ids: Option<BTreeMap<String, String>>,
+    /// The constant-sized loops with their estimated unrolling tradeoffs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constant_loops: Option<Vec<ConstantLoop>>,
+    /// The other `solc` contract output fields, preserved as is.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
@@ -0,0 +1,58 @@
+//!
+//! The diagnostics terminal format.
+//!
+
+use std::io::IsTerminal;
+use std::str::FromStr;
+
+///
+/// The diagnostics terminal format.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// The source snippets with carets and labels, colored if the terminal supports it.
+    #[default]
+    Human,
+    /// The `solc` formatted messages as is, intended for machine parsing.
+    Raw,
+}
+
+impl Format {
+    ///
+    /// Whether the diagnostics printed to stderr must be colored.
+    ///
+    /// The colors are disabled if stderr is not a terminal or the `NO_COLOR` variable is set.
+    ///
+    pub fn is_colored(&self) -> bool {
+        match self {
+            Self::Human => {
+                std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+            }
+            Self::Raw => false,
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "human" => Ok(Self::Human),
+            "raw" => Ok(Self::Raw),
+            string => anyhow::bail!(
+                "Unknown error format `{}`. Supported formats: `human`, `raw`",
+                string
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Human => write!(f, "human"),
+            Self::Raw => write!(f, "raw"),
+        }
+    }
+}
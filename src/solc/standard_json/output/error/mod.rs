@@ -2,13 +2,20 @@
 //! The `solc --standard-json` output error.
 //!
 
+pub mod format;
+pub mod render;
+pub mod secondary_source_location;
 pub mod source_location;
 
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::solc::standard_json::output::contract::erc::Erc;
+
+use self::secondary_source_location::SecondarySourceLocation;
 use self::source_location::SourceLocation;
 
 ///
@@ -29,8 +36,14 @@ pub struct Error {
     pub severity: String,
     /// The error location data.
     pub source_location: Option<SourceLocation>,
+    /// The related locations, e.g. the previous declaration of a duplicate identifier.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary_source_locations: Option<Vec<SecondarySourceLocation>>,
     /// The error type.
     pub r#type: String,
+    /// The other `solc` error fields, preserved as is.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
 impl Error {
@@ -55,7 +68,9 @@ impl Error {
             message,
             severity: "warning".to_owned(),
             source_location: src.map(SourceLocation::from_str).and_then(Result::ok),
+            secondary_source_locations: None,
             r#type: "Warning".to_owned(),
+            other: BTreeMap::new(),
         }
     }
 
@@ -83,7 +98,9 @@ impl Error {
             message,
             severity: "warning".to_owned(),
             source_location: src.map(SourceLocation::from_str).and_then(Result::ok),
+            secondary_source_locations: None,
             r#type: "Warning".to_owned(),
+            other: BTreeMap::new(),
         }
     }
 
@@ -110,7 +127,9 @@ impl Error {
             message,
             severity: "warning".to_owned(),
             source_location: src.map(SourceLocation::from_str).and_then(Result::ok),
+            secondary_source_locations: None,
             r#type: "Warning".to_owned(),
+            other: BTreeMap::new(),
         }
     }
 
@@ -135,7 +154,9 @@ impl Error {
             message,
             severity: "warning".to_owned(),
             source_location: src.map(SourceLocation::from_str).and_then(Result::ok),
+            secondary_source_locations: None,
             r#type: "Warning".to_owned(),
+            other: BTreeMap::new(),
         }
     }
 
@@ -161,7 +182,9 @@ impl Error {
             message,
             severity: "warning".to_owned(),
             source_location: src.map(SourceLocation::from_str).and_then(Result::ok),
+            secondary_source_locations: None,
             r#type: "Warning".to_owned(),
+            other: BTreeMap::new(),
         }
     }
 
@@ -186,7 +209,9 @@ impl Error {
             message,
             severity: "warning".to_owned(),
             source_location: src.map(SourceLocation::from_str).and_then(Result::ok),
+            secondary_source_locations: None,
             r#type: "Warning".to_owned(),
+            other: BTreeMap::new(),
         }
     }
 
@@ -211,7 +236,9 @@ impl Error {
             message,
             severity: "warning".to_owned(),
             source_location: src.map(SourceLocation::from_str).and_then(Result::ok),
+            secondary_source_locations: None,
             r#type: "Warning".to_owned(),
+            other: BTreeMap::new(),
         }
     }
 
@@ -233,14 +260,341 @@ impl Error {
             message,
             severity: "error".to_owned(),
             source_location: src.map(SourceLocation::from_str).and_then(Result::ok),
+            secondary_source_locations: None,
+            r#type: "Error".to_owned(),
+            other: BTreeMap::new(),
+        }
+    }
+
+    ///
+    /// Returns the identical bytecode warning.
+    ///
+    /// Each contract is labeled with a note at its definition, if its location is known.
+    ///
+    pub fn message_duplicate_bytecode(
+        hash: &str,
+        contracts: &[(String, Option<SourceLocation>)],
+    ) -> Self {
+        let message = format!(
+            "Warning: Contracts {} have identical bytecode with hash `{}`. Consider deduplicating their deployments.",
+            contracts
+                .iter()
+                .map(|(path, _location)| format!("`{path}`"))
+                .collect::<Vec<String>>()
+                .join(", "),
+            hash,
+        );
+
+        let mut error = Self {
+            component: "general".to_owned(),
+            error_code: None,
+            formatted_message: message.clone(),
+            message,
+            severity: "warning".to_owned(),
+            source_location: None,
+            secondary_source_locations: None,
+            r#type: "Warning".to_owned(),
+            other: BTreeMap::new(),
+        };
+        for (path, location) in contracts.iter() {
+            let message = format!("Contract `{path}` is defined here");
+            let location = match location {
+                Some(location) => SecondarySourceLocation::new(
+                    location.file.to_owned(),
+                    location.start,
+                    location.end,
+                    message,
+                ),
+                None => {
+                    let file = path
+                        .rsplit_once(':')
+                        .map(|(file, _)| file)
+                        .unwrap_or(path.as_str());
+                    SecondarySourceLocation::new(file.to_owned(), -1, -1, message)
+                }
+            };
+            error.push_note(location);
+        }
+        error
+    }
+
+    ///
+    /// Returns the warning about the mutant, whose instructions have not been found.
+    ///
+    pub fn message_mutant_not_applied(name: &str) -> Self {
+        let message = format!(
+            "Warning: Mutant `{name}` has not been built, as no matching instructions have been found at its source code location.",
+        );
+
+        Self {
+            component: "general".to_owned(),
+            error_code: None,
+            formatted_message: message.clone(),
+            message,
+            severity: "warning".to_owned(),
+            source_location: None,
+            secondary_source_locations: None,
+            r#type: "Warning".to_owned(),
+            other: BTreeMap::new(),
+        }
+    }
+
+    ///
+    /// Returns the warning about the contract skipped by the build, with the `reason`.
+    ///
+    /// The contract is labeled at its definition, if its location is known.
+    ///
+    pub fn message_skipped(path: &str, reason: &str, location: Option<SourceLocation>) -> Self {
+        let message = format!("Warning: Contract `{path}` has been skipped, as {reason}");
+
+        Self {
+            component: "general".to_owned(),
+            error_code: None,
+            formatted_message: message.clone(),
+            message,
+            severity: "warning".to_owned(),
+            source_location: location,
+            secondary_source_locations: None,
+            r#type: "Warning".to_owned(),
+            other: BTreeMap::new(),
+        }
+    }
+
+    ///
+    /// Returns the note about the contract recompiled on a build cache miss.
+    ///
+    /// The `changed_sources` are empty if the contract is built for the first time or the
+    /// settings changed.
+    ///
+    pub fn message_recompiled(path: &str, changed_sources: &[String]) -> Self {
+        let message = if changed_sources.is_empty() {
+            format!("Info: Contract `{path}` has been recompiled.")
+        } else {
+            format!(
+                "Info: Contract `{path}` has been recompiled, as its sources {} have changed.",
+                changed_sources
+                    .iter()
+                    .map(|source| format!("`{source}`"))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        };
+
+        Self {
+            component: "general".to_owned(),
+            error_code: None,
+            formatted_message: message.clone(),
+            message,
+            severity: "info".to_owned(),
+            source_location: None,
+            secondary_source_locations: None,
+            r#type: "Info".to_owned(),
+            other: BTreeMap::new(),
+        }
+    }
+
+    ///
+    /// Returns the warning about the unsupported `solc` version forced with `--force`.
+    ///
+    pub fn message_unsupported_solc_version(
+        version: &semver::Version,
+        last_supported_version: &semver::Version,
+    ) -> Self {
+        let lines = [
+            format!("Warning: `solc` v{version} is newer than the last supported v{last_supported_version}, and is"),
+            "only allowed by `--force`. The compiler may not be aware of the IR changes it introduces, so"
+                .to_owned(),
+            "the bytecode may be incorrect. Do not deploy it to production without thorough testing. The"
+                .to_owned(),
+            "version is recorded into the contract metadata as `unsupported_solc_version`.".to_owned(),
+        ];
+        let border = "─".repeat(98);
+        let mut message = format!("\n┌{border}┐\n");
+        for line in lines.iter() {
+            message.push_str(format!("│ {line:<96} │\n").as_str());
+        }
+        message.push_str(format!("└{border}┘").as_str());
+
+        Self {
+            component: "general".to_owned(),
+            error_code: None,
+            formatted_message: message.clone(),
+            message,
+            severity: "warning".to_owned(),
+            source_location: None,
+            secondary_source_locations: None,
+            r#type: "Warning".to_owned(),
+            other: BTreeMap::new(),
+        }
+    }
+
+    ///
+    /// Returns the warning about the contract `path`, which claims the ERC `standard` by
+    /// implementing its identifying functions, but has the `violations` of its interface.
+    ///
+    pub fn message_erc_nonconformance(path: &str, standard: Erc, violations: &[String]) -> Self {
+        let message = format!(
+            "Warning: Contract `{path}` looks like an {standard} implementation, but does not conform to the standard:\n{}",
+            violations
+                .iter()
+                .map(|violation| format!("  - {violation}"))
+                .collect::<Vec<String>>()
+                .join("\n"),
+        );
+
+        Self {
+            component: "general".to_owned(),
+            error_code: Some(standard.code().to_owned()),
+            formatted_message: message.clone(),
+            message,
+            severity: "warning".to_owned(),
+            source_location: None,
+            secondary_source_locations: None,
+            r#type: "Warning".to_owned(),
+            other: BTreeMap::new(),
+        }
+    }
+
+    ///
+    /// Returns the warning about the legacy or unknown input field.
+    ///
+    pub fn message_input_field(description: &str) -> Self {
+        let message = format!("Warning: {description}");
+
+        Self {
+            component: "general".to_owned(),
+            error_code: None,
+            formatted_message: message.clone(),
+            message,
+            severity: "warning".to_owned(),
+            source_location: None,
+            secondary_source_locations: None,
+            r#type: "Warning".to_owned(),
+            other: BTreeMap::new(),
+        }
+    }
+
+    ///
+    /// Returns the error of the compiler itself, which has prevented producing the output.
+    ///
+    pub fn message_compiler_failure(description: &str) -> Self {
+        let message = format!("Error: {description}");
+
+        Self {
+            component: "general".to_owned(),
+            error_code: None,
+            formatted_message: message.clone(),
+            message,
+            severity: "error".to_owned(),
+            source_location: None,
+            secondary_source_locations: None,
+            r#type: "Exception".to_owned(),
+            other: BTreeMap::new(),
+        }
+    }
+
+    ///
+    /// Returns the warning about the source with CRLF line endings or a UTF-8 BOM.
+    ///
+    pub fn message_unnormalized_source(path: &str) -> Self {
+        let message = format!(
+            "Warning: Source `{path}` contains CRLF line endings or a UTF-8 BOM, which affect its hash and the contract metadata hash. \
+            Consider enabling the source normalization to get identical metadata across platforms.",
+        );
+
+        Self {
+            component: "general".to_owned(),
+            error_code: None,
+            formatted_message: message.clone(),
+            message,
+            severity: "warning".to_owned(),
+            source_location: None,
+            secondary_source_locations: None,
+            r#type: "Warning".to_owned(),
+            other: BTreeMap::new(),
+        }
+    }
+
+    ///
+    /// Returns the strict mode error about a construct translated with an Era-specific approximation.
+    ///
+    pub fn message_strict_approximation(
+        construct: &str,
+        approximation: &str,
+        src: Option<&str>,
+    ) -> Self {
+        let message = format!(
+            "Error: `{construct}` relies on the {approximation}, which is an Era-specific approximation rejected in strict mode.",
+        );
+
+        Self {
+            component: "general".to_owned(),
+            error_code: None,
+            formatted_message: message.clone(),
+            message,
+            severity: "error".to_owned(),
+            source_location: src.map(SourceLocation::from_str).and_then(Result::ok),
+            secondary_source_locations: None,
             r#type: "Error".to_owned(),
+            other: BTreeMap::new(),
         }
     }
 
+    ///
+    /// Returns the strict gas mode error about a `gas()` or `gasleft()` usage.
+    ///
+    pub fn message_strict_gas(construct: &str, src: Option<&str>) -> Self {
+        let message = format!(
+            "Error: `{construct}` depends on the gas accounting, which is measured in ergs on EraVM and rejected in strict gas mode.",
+        );
+
+        Self {
+            component: "general".to_owned(),
+            error_code: None,
+            formatted_message: message.clone(),
+            message,
+            severity: "error".to_owned(),
+            source_location: src.map(SourceLocation::from_str).and_then(Result::ok),
+            secondary_source_locations: None,
+            r#type: "Error".to_owned(),
+            other: BTreeMap::new(),
+        }
+    }
+
+    ///
+    /// Promotes the warning to an error.
+    ///
+    pub fn promote(&mut self) {
+        self.severity = "error".to_owned();
+        self.r#type = "Error".to_owned();
+        for message in [&mut self.message, &mut self.formatted_message] {
+            if let Some(rest) = message.strip_prefix("Warning") {
+                *message = format!("Error{rest}");
+            }
+        }
+    }
+
+    ///
+    /// Adds a note at the related location, which is also appended to the formatted message.
+    ///
+    pub fn push_note(&mut self, location: SecondarySourceLocation) {
+        self.formatted_message
+            .push_str(format!("\n{location}\n").as_str());
+        self.secondary_source_locations
+            .get_or_insert_with(Vec::new)
+            .push(location);
+    }
+
     ///
     /// Appends the contract path to the message..
     ///
+    /// The source location file index is replaced with the path, as in the `solc` messages.
+    ///
     pub fn push_contract_path(&mut self, path: &str) {
+        if let Some(source_location) = self.source_location.as_mut() {
+            source_location.file = path.to_owned();
+        }
+
         self.formatted_message
             .push_str(format!("\n--> {path}\n").as_str());
     }
@@ -0,0 +1,278 @@
+//!
+//! The diagnostics terminal renderer.
+//!
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use colored::Colorize;
+
+use super::format::Format;
+use super::Error;
+
+///
+/// The diagnostics terminal renderer.
+///
+/// Renders the diagnostics in the style of `rustc`, with source code snippets and carets.
+///
+#[derive(Debug)]
+pub struct Renderer<'a> {
+    /// The source code files, keyed by path.
+    sources: &'a BTreeMap<String, Arc<str>>,
+    /// The output format.
+    format: Format,
+    /// Whether the output is colored.
+    is_colored: bool,
+}
+
+impl<'a> Renderer<'a> {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(sources: &'a BTreeMap<String, Arc<str>>, format: Format) -> Self {
+        Self {
+            sources,
+            format,
+            is_colored: format.is_colored(),
+        }
+    }
+
+    ///
+    /// Renders the `error`.
+    ///
+    pub fn render(&self, error: &Error) -> String {
+        if let Format::Raw = self.format {
+            return error.formatted_message.to_owned();
+        }
+
+        let severity = error.severity.as_str();
+        let mut output = String::with_capacity(256);
+
+        let message = error
+            .message
+            .strip_prefix("Warning: ")
+            .or_else(|| error.message.strip_prefix("Error: "))
+            .unwrap_or(error.message.as_str());
+        let title = match error.error_code.as_deref() {
+            Some(code) => format!("{severity}[{code}]"),
+            None => severity.to_owned(),
+        };
+        output.push_str(
+            format!(
+                "{}{}\n",
+                self.paint(title.as_str(), severity),
+                self.paint(format!(": {message}").as_str(), "bold")
+            )
+            .as_str(),
+        );
+
+        if let Some(location) = error.source_location.as_ref() {
+            self.push_location(
+                &mut output,
+                location.file.as_str(),
+                location.start,
+                location.end,
+                severity,
+            );
+        }
+        for location in error.secondary_source_locations.iter().flatten() {
+            output.push_str(
+                format!(
+                    "{}: {}\n",
+                    self.paint("note", "note"),
+                    location.message.as_str()
+                )
+                .as_str(),
+            );
+            self.push_location(
+                &mut output,
+                location.file.as_str(),
+                location.start,
+                location.end,
+                "note",
+            );
+        }
+
+        output
+    }
+
+    ///
+    /// Appends the location header and the source code snippet to the `output`.
+    ///
+    fn push_location(
+        &self,
+        output: &mut String,
+        file: &str,
+        start: isize,
+        end: isize,
+        style: &str,
+    ) {
+        let source = match self.sources.get(file) {
+            Some(source)
+                if start >= 0
+                    && end >= 0
+                    && source.is_char_boundary(start as usize)
+                    && source.is_char_boundary((end as usize).min(source.len())) =>
+            {
+                source.as_ref()
+            }
+            _ => {
+                output.push_str(format!("  {} {file}\n", self.paint("-->", "gutter")).as_str());
+                return;
+            }
+        };
+
+        let start = start as usize;
+        let end = (end.max(start as isize) as usize).min(source.len());
+        let line_start = source[..start]
+            .rfind('\n')
+            .map(|index| index + 1)
+            .unwrap_or(0);
+        let line_end = source[start..]
+            .find('\n')
+            .map(|index| start + index)
+            .unwrap_or(source.len());
+        let line_number = source[..start].matches('\n').count() + 1;
+        let column = source[line_start..start].chars().count() + 1;
+        let line = &source[line_start..line_end];
+        let gutter_width = line_number.to_string().len();
+
+        let caret_offset = line[..start - line_start]
+            .chars()
+            .map(|character| if character == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        let caret_length = source[start..end.min(line_end)].chars().count().max(1);
+
+        output.push_str(
+            format!(
+                "{:gutter_width$}{} {file}:{line_number}:{column}\n",
+                "",
+                self.paint("-->", "gutter"),
+            )
+            .as_str(),
+        );
+        output.push_str(format!("{:gutter_width$} {}\n", "", self.paint("|", "gutter")).as_str());
+        output.push_str(
+            format!(
+                "{} {} {line}\n",
+                self.paint(line_number.to_string().as_str(), "gutter"),
+                self.paint("|", "gutter"),
+            )
+            .as_str(),
+        );
+        output.push_str(
+            format!(
+                "{:gutter_width$} {} {caret_offset}{}\n",
+                "",
+                self.paint("|", "gutter"),
+                self.paint("^".repeat(caret_length).as_str(), style),
+            )
+            .as_str(),
+        );
+    }
+
+    ///
+    /// Paints the `text` with the `style` color, if the colors are enabled.
+    ///
+    fn paint(&self, text: &str, style: &str) -> String {
+        if !self.is_colored {
+            return text.to_owned();
+        }
+
+        match style {
+            "error" => text.red().bold().to_string(),
+            "warning" => text.yellow().bold().to_string(),
+            "info" => text.cyan().bold().to_string(),
+            "note" => text.green().bold().to_string(),
+            "gutter" => text.blue().bold().to_string(),
+            "bold" => text.bold().to_string(),
+            _ => text.to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    use super::Format;
+    use super::Renderer;
+    use crate::solc::standard_json::output::error::source_location::SourceLocation;
+    use crate::solc::standard_json::output::error::Error;
+
+    #[test]
+    fn snippet() {
+        let sources = BTreeMap::from([(
+            "Test.sol".to_owned(),
+            Arc::from("contract Test {\n    function f() public { uint x; }\n}\n"),
+        )]);
+        let renderer = Renderer {
+            sources: &sources,
+            format: Format::Human,
+            is_colored: false,
+        };
+        let error = Error {
+            component: "general".to_owned(),
+            error_code: Some("2072".to_owned()),
+            formatted_message: String::new(),
+            message: "Unused local variable.".to_owned(),
+            severity: "warning".to_owned(),
+            source_location: Some(SourceLocation {
+                file: "Test.sol".to_owned(),
+                start: 42,
+                end: 48,
+            }),
+            secondary_source_locations: None,
+            r#type: "Warning".to_owned(),
+            other: BTreeMap::new(),
+        };
+
+        assert_eq!(
+            renderer.render(&error),
+            "warning[2072]: Unused local variable.\n --> Test.sol:2:27\n  |\n2 |     function f() public { uint x; }\n  |                           ^^^^^^\n"
+        );
+    }
+
+    #[test]
+    fn note_location() {
+        let sources = BTreeMap::from([(
+            "Test.sol".to_owned(),
+            Arc::from("contract A {}\ncontract B {}\n"),
+        )]);
+        let renderer = Renderer {
+            sources: &sources,
+            format: Format::Human,
+            is_colored: false,
+        };
+        let error = Error::message_duplicate_bytecode(
+            "0x00",
+            &[
+                (
+                    "Test.sol:A".to_owned(),
+                    Some(SourceLocation {
+                        file: "Test.sol".to_owned(),
+                        start: 0,
+                        end: 13,
+                    }),
+                ),
+                (
+                    "Test.sol:B".to_owned(),
+                    Some(SourceLocation {
+                        file: "Test.sol".to_owned(),
+                        start: 14,
+                        end: 27,
+                    }),
+                ),
+            ],
+        );
+
+        let rendered = renderer.render(&error);
+        assert!(rendered.contains(
+            "note: Contract `Test.sol:A` is defined here\n --> Test.sol:1:1\n  |\n1 | contract A {}\n  | ^^^^^^^^^^^^^\n"
+        ));
+        assert!(rendered.contains(
+            "note: Contract `Test.sol:B` is defined here\n --> Test.sol:2:1\n  |\n2 | contract B {}\n  | ^^^^^^^^^^^^^\n"
+        ));
+    }
+}
@@ -0,0 +1,49 @@
+//!
+//! The `solc --standard-json` output error secondary source location.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The `solc --standard-json` output error secondary source location.
+///
+/// Labels a related location, e.g. the previous declaration of a duplicate identifier.
+///
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SecondarySourceLocation {
+    /// The source file path.
+    pub file: String,
+    /// The start location.
+    pub start: isize,
+    /// The end location.
+    pub end: isize,
+    /// The label message.
+    #[serde(default)]
+    pub message: String,
+}
+
+impl SecondarySourceLocation {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(file: String, start: isize, end: isize, message: String) -> Self {
+        Self {
+            file,
+            start,
+            end,
+            message,
+        }
+    }
+}
+
+impl std::fmt::Display for SecondarySourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Note: {}\n --> {}", self.message, self.file)?;
+        if self.start >= 0 && self.end >= 0 {
+            write!(f, ":{}:{}", self.start, self.end)?;
+        }
+        Ok(())
+    }
+}
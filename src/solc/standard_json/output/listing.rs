@@ -0,0 +1,209 @@
+//!
+//! The `solc --standard-json` output contract listing.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+///
+/// The contract, library, or interface found by the `solc` analysis stage.
+///
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Listing {
+    /// The full contract path.
+    pub path: String,
+    /// The source file path.
+    pub source: String,
+    /// The contract name.
+    pub name: String,
+    /// The contract kind, i.e. `contract`, `library`, or `interface`.
+    pub kind: String,
+    /// Whether the contract is abstract.
+    #[serde(rename = "abstract")]
+    pub is_abstract: bool,
+    /// Whether the contract can be deployed on its own.
+    pub deployable: bool,
+    /// The full paths of the contracts created directly by this one, either with `new` or with
+    /// the `type(C).creationCode` and `type(C).runtimeCode` expressions.
+    pub dependencies: BTreeSet<String>,
+}
+
+impl Listing {
+    ///
+    /// Lists the AST `definitions`, which are keyed by the full contract path.
+    ///
+    pub fn from_definitions(definitions: &[(String, &serde_json::Value)]) -> Vec<Self> {
+        let paths: BTreeMap<u64, &str> = definitions
+            .iter()
+            .filter_map(|(path, node)| {
+                node.get("id")
+                    .and_then(|id| id.as_u64())
+                    .map(|id| (id, path.as_str()))
+            })
+            .collect();
+
+        definitions
+            .iter()
+            .map(|(path, node)| {
+                let (source, name) = path.rsplit_once(':').expect("Always valid");
+                let kind = node
+                    .get("contractKind")
+                    .and_then(|kind| kind.as_str())
+                    .unwrap_or("contract")
+                    .to_owned();
+                let is_abstract = node
+                    .get("abstract")
+                    .and_then(|is_abstract| is_abstract.as_bool())
+                    .unwrap_or_default();
+
+                let mut ids = BTreeSet::new();
+                Self::collect_dependencies(node, &mut ids);
+                let dependencies = ids
+                    .into_iter()
+                    .filter_map(|id| paths.get(&id))
+                    .filter(|dependency| **dependency != path.as_str())
+                    .map(|dependency| dependency.to_string())
+                    .collect();
+
+                Self {
+                    path: path.to_owned(),
+                    source: source.to_owned(),
+                    name: name.to_owned(),
+                    deployable: kind == "contract" && !is_abstract,
+                    kind,
+                    is_abstract,
+                    dependencies,
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// Collects the AST IDs of the contracts created by the `node` subtree.
+    ///
+    fn collect_dependencies(node: &serde_json::Value, ids: &mut BTreeSet<u64>) {
+        match node {
+            serde_json::Value::Object(object) => {
+                match object
+                    .get("nodeType")
+                    .and_then(|node_type| node_type.as_str())
+                {
+                    Some("NewExpression") => {
+                        if let Some(id) = object
+                            .get("typeName")
+                            .and_then(|type_name| type_name.get("referencedDeclaration"))
+                            .and_then(|id| id.as_u64())
+                        {
+                            ids.insert(id);
+                        }
+                    }
+                    Some("MemberAccess") => {
+                        if let Some(id) = Self::type_code_access(object) {
+                            ids.insert(id);
+                        }
+                    }
+                    _ => {}
+                }
+                for value in object.values() {
+                    Self::collect_dependencies(value, ids);
+                }
+            }
+            serde_json::Value::Array(array) => {
+                for value in array.iter() {
+                    Self::collect_dependencies(value, ids);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    ///
+    /// Returns the AST ID of `C` if the member access is `type(C).creationCode` or
+    /// `type(C).runtimeCode`.
+    ///
+    fn type_code_access(object: &serde_json::Map<String, serde_json::Value>) -> Option<u64> {
+        let member_name = object.get("memberName")?.as_str()?;
+        if !["creationCode", "runtimeCode"].contains(&member_name) {
+            return None;
+        }
+
+        let call = object.get("expression")?;
+        if call.get("nodeType")?.as_str()? != "FunctionCall"
+            || call.get("expression")?.get("name")?.as_str()? != "type"
+        {
+            return None;
+        }
+        call.get("arguments")?
+            .as_array()?
+            .first()?
+            .get("referencedDeclaration")?
+            .as_u64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Listing;
+
+    #[test]
+    fn from_definitions() {
+        let factory = serde_json::json!({
+            "id": 1,
+            "nodeType": "ContractDefinition",
+            "contractKind": "contract",
+            "abstract": false,
+            "nodes": [{
+                "nodeType": "FunctionDefinition",
+                "body": { "statements": [
+                    { "nodeType": "NewExpression", "typeName": { "referencedDeclaration": 2 } },
+                    {
+                        "nodeType": "MemberAccess",
+                        "memberName": "creationCode",
+                        "expression": {
+                            "nodeType": "FunctionCall",
+                            "expression": { "nodeType": "Identifier", "name": "type" },
+                            "arguments": [{ "nodeType": "Identifier", "referencedDeclaration": 3 }]
+                        }
+                    },
+                    { "nodeType": "NewExpression", "typeName": { "nodeType": "ArrayTypeName" } }
+                ]}
+            }]
+        });
+        let base = serde_json::json!({
+            "id": 2,
+            "nodeType": "ContractDefinition",
+            "contractKind": "contract",
+            "abstract": true
+        });
+        let library = serde_json::json!({
+            "id": 3,
+            "nodeType": "ContractDefinition",
+            "contractKind": "library",
+            "abstract": false
+        });
+        let definitions = vec![
+            ("Factory.sol:Factory".to_owned(), &factory),
+            ("Base.sol:Base".to_owned(), &base),
+            ("Base.sol:Library".to_owned(), &library),
+        ];
+
+        let listings = Listing::from_definitions(definitions.as_slice());
+        assert_eq!(listings[0].source, "Factory.sol");
+        assert!(listings[0].deployable);
+        assert_eq!(
+            listings[0]
+                .dependencies
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<&str>>(),
+            vec!["Base.sol:Base", "Base.sol:Library"]
+        );
+        assert!(!listings[1].deployable);
+        assert!(listings[1].is_abstract);
+        assert_eq!(listings[2].kind, "library");
+        assert!(!listings[2].deployable);
+    }
+}
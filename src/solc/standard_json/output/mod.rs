@@ -4,26 +4,38 @@
 
 pub mod contract;
 pub mod error;
+pub mod listing;
 pub mod source;
 
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
 use serde::Deserialize;
 use serde::Serialize;
 use sha3::Digest;
 
+use crate::build::gas_usage::GasUsage;
+use crate::build::immutable::Immutable;
+use crate::build::source_hash::SourceHash;
+use crate::debug::filter::Filter as DebugFilter;
 use crate::evmla::assembly::instruction::Instruction;
 use crate::evmla::assembly::Assembly;
 use crate::project::contract::ir::IR as ProjectContractIR;
 use crate::project::contract::Contract as ProjectContract;
 use crate::project::Project;
 use crate::solc::pipeline::Pipeline as SolcPipeline;
+use crate::solc::standard_json::input::settings::empty_contracts::EmptyContracts;
+use crate::solc::standard_json::input::settings::selection::file::flag::Flag as SolcStandardJsonInputSettingsSelectionFileFlag;
 use crate::solc::version::Version as SolcVersion;
+use crate::yul::builtin_versions::BuiltinVersions as YulBuiltinVersions;
 use crate::yul::lexer::Lexer;
 use crate::yul::parser::statement::object::Object;
 
+use self::contract::erc::Erc;
 use self::contract::Contract;
+use self::error::source_location::SourceLocation;
 use self::error::Error as SolcStandardJsonOutputError;
+use self::listing::Listing;
 use self::source::Source;
 
 ///
@@ -49,25 +61,180 @@ pub struct Output {
     /// The `zksolc` compiler version.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub zk_version: Option<String>,
+    /// The other `solc` output fields, preserved as is.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
 impl Output {
+    ///
+    /// Returns the immutables declared by the contracts and their base contracts, keyed by the
+    /// full contract path.
+    ///
+    pub fn get_immutables(&self) -> BTreeMap<String, Vec<Immutable>> {
+        let contract_definitions = self.contract_definitions();
+        let definitions: BTreeMap<u64, &serde_json::Value> = contract_definitions
+            .iter()
+            .filter_map(|(_path, node)| Some((node.get("id")?.as_u64()?, *node)))
+            .collect();
+
+        let mut immutables = BTreeMap::new();
+        for (path, node) in contract_definitions.into_iter() {
+            let contract_immutables = Immutable::from_contract_definition(node, &definitions);
+            if !contract_immutables.is_empty() {
+                immutables.insert(path, contract_immutables);
+            }
+        }
+        immutables
+    }
+
+    ///
+    /// Returns the keccak256 hashes of the sources compiled by `solc`, including the resolved
+    /// imports, as recorded in the contract metadata.
+    ///
+    pub fn get_source_keccak256_hashes(&self) -> BTreeMap<String, String> {
+        let mut hashes = BTreeMap::new();
+        for contract in self
+            .contracts
+            .iter()
+            .flat_map(|files| files.values())
+            .flat_map(|contracts| contracts.values())
+        {
+            let metadata = match contract
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.as_str())
+                .and_then(|metadata| serde_json::from_str::<serde_json::Value>(metadata).ok())
+            {
+                Some(metadata) => metadata,
+                None => continue,
+            };
+            for (name, source) in metadata
+                .get("sources")
+                .and_then(|sources| sources.as_object())
+                .into_iter()
+                .flatten()
+            {
+                if let Some(hash) = source.get("keccak256").and_then(|hash| hash.as_str()) {
+                    hashes.insert(name.to_owned(), hash.to_owned());
+                }
+            }
+        }
+        hashes
+    }
+
+    ///
+    /// Returns the `gas()` and `gasleft()` usages of the contracts, keyed by the full contract path.
+    ///
+    pub fn get_gas_usages(&self) -> BTreeMap<String, Vec<GasUsage>> {
+        let mut gas_usages = BTreeMap::new();
+        for (path, node) in self.contract_definitions().into_iter() {
+            let contract_gas_usages = GasUsage::from_contract_definition(node);
+            if !contract_gas_usages.is_empty() {
+                gas_usages.insert(path, contract_gas_usages);
+            }
+        }
+        gas_usages
+    }
+
+    ///
+    /// Appends the errors for the `gas()` and `gasleft()` usages.
+    ///
+    pub fn check_strict_gas(&mut self, gas_usages: &BTreeMap<String, Vec<GasUsage>>) {
+        let mut messages = Vec::new();
+        for (path, usages) in gas_usages.iter() {
+            for usage in usages.iter() {
+                let mut message = SolcStandardJsonOutputError::message_strict_gas(
+                    usage.construct.as_str(),
+                    usage.src.as_deref(),
+                );
+                message.push_contract_path(path.as_str());
+                messages.push(message);
+            }
+        }
+        self.errors.get_or_insert_with(Vec::new).extend(messages);
+    }
+
+    ///
+    /// Returns the AST contract definitions, keyed by the full contract path.
+    ///
+    fn contract_definitions(&self) -> Vec<(String, &serde_json::Value)> {
+        let mut definitions = Vec::new();
+        for (path, source) in self.sources.iter().flatten() {
+            let nodes = match source
+                .ast
+                .as_ref()
+                .and_then(|ast| ast.get("nodes"))
+                .and_then(|nodes| nodes.as_array())
+            {
+                Some(nodes) => nodes,
+                None => continue,
+            };
+            for node in nodes.iter() {
+                if node
+                    .get("nodeType")
+                    .and_then(|node_type| node_type.as_str())
+                    != Some("ContractDefinition")
+                {
+                    continue;
+                }
+                let name = match node.get("name").and_then(|name| name.as_str()) {
+                    Some(name) => name,
+                    None => continue,
+                };
+                definitions.push((format!("{path}:{name}"), node));
+            }
+        }
+        definitions
+    }
+
+    ///
+    /// Lists the contracts, libraries, and interfaces found in the AST.
+    ///
+    pub fn list_contracts(&self) -> Vec<Listing> {
+        Listing::from_definitions(self.contract_definitions().as_slice())
+    }
+
+    ///
+    /// Returns the source location of the contract definition at the full `path`.
+    ///
+    /// The file index of the AST location is replaced with the source path.
+    ///
+    pub fn contract_location(&self, path: &str) -> Option<SourceLocation> {
+        let (file, _name) = path.rsplit_once(':')?;
+        let definitions = self.contract_definitions();
+        let (_path, node) = definitions
+            .iter()
+            .find(|(definition_path, _node)| definition_path == path)?;
+        let mut location = node
+            .get("src")
+            .and_then(|src| src.as_str())
+            .and_then(|src| src.parse::<SourceLocation>().ok())?;
+        location.file = file.to_owned();
+        Some(location)
+    }
+
     ///
     /// Converts the `solc` JSON output into a convenient project.
     ///
+    /// The errors of all contracts, from the dependency processing, the Yul dumps, and the IR
+    /// extraction, are collected and reported together.
+    ///
     pub fn try_to_project(
         &mut self,
-        source_code_files: BTreeMap<String, String>,
+        source_code_files: BTreeMap<String, Arc<str>>,
         libraries: BTreeMap<String, BTreeMap<String, String>>,
         pipeline: SolcPipeline,
         version: &semver::Version,
         debug_config: Option<&compiler_llvm_context::DebugConfig>,
+        debug_filter: &DebugFilter,
     ) -> anyhow::Result<Project> {
-        if let SolcPipeline::EVMLA = pipeline {
-            self.preprocess_dependencies()?;
-        }
+        let mut errors = match pipeline {
+            SolcPipeline::EVMLA => self.preprocess_dependencies(),
+            SolcPipeline::Yul => BTreeMap::new(),
+        };
 
-        let files = match self.contracts.as_ref() {
+        let files = match self.contracts.as_mut() {
             Some(files) => files,
             None => {
                 anyhow::bail!(
@@ -81,48 +248,45 @@ impl Output {
         };
         let mut project_contracts = BTreeMap::new();
 
-        for (path, contracts) in files.iter() {
-            for (name, contract) in contracts.iter() {
+        for (path, contracts) in files.iter_mut() {
+            for (name, contract) in contracts.iter_mut() {
                 let full_path = format!("{path}:{name}");
+                if errors.contains_key(full_path.as_str()) {
+                    continue;
+                }
+                let mut contract_errors = Vec::new();
 
-                let source = match pipeline {
-                    SolcPipeline::Yul => {
-                        let ir_optimized = match contract.ir_optimized.to_owned() {
-                            Some(ir_optimized) => ir_optimized,
-                            None => continue,
-                        };
-                        if ir_optimized.is_empty() {
-                            continue;
-                        }
+                if let Err(error) =
+                    Self::dump_yul(full_path.as_str(), contract, debug_config, debug_filter)
+                {
+                    contract_errors
+                        .push(format!("Contract `{full_path}` Yul dumping error: {error}"));
+                }
 
-                        if let Some(debug_config) = debug_config {
-                            debug_config.dump_yul(full_path.as_str(), ir_optimized.as_str())?;
+                let source =
+                    match Self::contract_ir(full_path.as_str(), contract, pipeline, version) {
+                        Ok(source) => source,
+                        Err(error) => {
+                            contract_errors.push(error.to_string());
+                            None
                         }
+                    };
 
-                        let mut lexer = Lexer::new(ir_optimized.to_owned());
-                        let object = Object::parse(&mut lexer, None).map_err(|error| {
-                            anyhow::anyhow!("Contract `{}` parsing error: {:?}", full_path, error)
-                        })?;
+                let source_code = source_code_files.get(path.as_str());
+                if source.is_some() && source_code.is_none() {
+                    contract_errors.push(format!(
+                        "Contract `{full_path}` source code for path `{path}` not found"
+                    ));
+                }
 
-                        ProjectContractIR::new_yul(ir_optimized.to_owned(), object)
-                    }
-                    SolcPipeline::EVMLA => {
-                        let evm = contract.evm.as_ref();
-                        let assembly = match evm.and_then(|evm| evm.assembly.to_owned()) {
-                            Some(assembly) => assembly.to_owned(),
-                            None => continue,
-                        };
-                        let extra_metadata = evm
-                            .and_then(|evm| evm.extra_metadata.to_owned())
-                            .unwrap_or_default();
-
-                        ProjectContractIR::new_evmla(assembly, extra_metadata)
-                    }
+                if !contract_errors.is_empty() {
+                    errors.insert(full_path, contract_errors);
+                    continue;
+                }
+                let (source, source_code) = match (source, source_code) {
+                    (Some(source), Some(source_code)) => (source, source_code),
+                    _ => continue,
                 };
-
-                let source_code = source_code_files
-                    .get(path.as_str())
-                    .ok_or_else(|| anyhow::anyhow!("Source code for path `{}` not found", path))?;
                 let source_hash = sha3::Keccak256::digest(source_code.as_bytes()).into();
 
                 let project_contract = ProjectContract::new(
@@ -136,6 +300,18 @@ impl Output {
             }
         }
 
+        if !errors.is_empty() {
+            anyhow::bail!(
+                "{} contract(s) could not be processed:\n{}",
+                errors.len(),
+                errors
+                    .into_values()
+                    .flatten()
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            );
+        }
+
         Ok(Project::new(
             version.to_owned(),
             project_contracts,
@@ -143,6 +319,90 @@ impl Output {
         ))
     }
 
+    ///
+    /// Dumps the Yul of the contract at `full_path`, if it is selected by the debug configuration.
+    ///
+    fn dump_yul(
+        full_path: &str,
+        contract: &Contract,
+        debug_config: Option<&compiler_llvm_context::DebugConfig>,
+        debug_filter: &DebugFilter,
+    ) -> anyhow::Result<()> {
+        let ir_optimized = match contract.ir_optimized.as_deref() {
+            Some(ir_optimized) if !ir_optimized.is_empty() => ir_optimized,
+            _ => return Ok(()),
+        };
+        let debug_config = match debug_config {
+            Some(debug_config) => debug_config,
+            None => return Ok(()),
+        };
+
+        if let Some(debug_config) =
+            crate::debug::contract_config(debug_config, debug_filter, full_path)?
+        {
+            debug_config.dump_yul(full_path, ir_optimized)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Extracts the IR of the contract at `full_path` for the `pipeline`.
+    ///
+    /// Returns `None` if the contract has no IR, e.g. if it is an interface or abstract.
+    ///
+    /// The EVM legacy assembly is checked by building its Ethereal IR, so the assembly that cannot
+    /// be translated is reported here along with the other contracts.
+    ///
+    fn contract_ir(
+        full_path: &str,
+        contract: &Contract,
+        pipeline: SolcPipeline,
+        version: &semver::Version,
+    ) -> anyhow::Result<Option<ProjectContractIR>> {
+        let ir = match pipeline {
+            SolcPipeline::Yul => {
+                let ir_optimized = match contract.ir_optimized.clone() {
+                    Some(ir_optimized) => ir_optimized,
+                    None => return Ok(None),
+                };
+                if ir_optimized.is_empty() {
+                    return Ok(None);
+                }
+
+                let mut lexer = Lexer::new(ir_optimized.clone());
+                let object = Object::parse(&mut lexer, None).map_err(|error| {
+                    anyhow::anyhow!("Contract `{}` parsing error: {:?}", full_path, error)
+                })?;
+                YulBuiltinVersions::check(&object, version).map_err(|error| {
+                    anyhow::anyhow!("Contract `{}` builtin error: {}", full_path, error)
+                })?;
+
+                ProjectContractIR::new_yul(
+                    ir_optimized,
+                    object,
+                    Some(SolcStandardJsonInputSettingsSelectionFileFlag::Yul.to_string()),
+                )
+            }
+            SolcPipeline::EVMLA => {
+                let evm = contract.evm.as_ref();
+                let mut assembly = match evm.and_then(|evm| evm.assembly.to_owned()) {
+                    Some(assembly) => assembly.to_owned(),
+                    None => return Ok(None),
+                };
+                let extra_metadata = evm
+                    .and_then(|evm| evm.extra_metadata.to_owned())
+                    .unwrap_or_default();
+                assembly.extra_metadata = Some(extra_metadata.clone());
+                assembly.ethereal_ir(version).map_err(|error| {
+                    anyhow::anyhow!("Contract `{}` assembly error: {}", full_path, error)
+                })?;
+
+                ProjectContractIR::new_evmla(assembly, extra_metadata)
+            }
+        };
+        Ok(Some(ir))
+    }
+
     ///
     /// Traverses the AST and returns the list of additional errors and warnings.
     ///
@@ -178,13 +438,190 @@ impl Output {
         Ok(())
     }
 
+    ///
+    /// Traverses the AST and appends the strict mode errors for the constructs translated with
+    /// Era-specific approximations.
+    ///
+    pub fn check_strict(&mut self) {
+        let sources = match self.sources.as_ref() {
+            Some(sources) => sources,
+            None => return,
+        };
+
+        let mut messages = Vec::new();
+        for (path, source) in sources.iter() {
+            if let Some(ast) = source.ast.as_ref() {
+                let mut strict_messages = Source::get_strict_messages(ast);
+                for message in strict_messages.iter_mut() {
+                    message.push_contract_path(path.as_str());
+                }
+                messages.extend(strict_messages);
+            }
+        }
+
+        self.errors.get_or_insert_with(Vec::new).extend(messages);
+    }
+
+    ///
+    /// Checks the contracts claiming the ERC-20, ERC-721, or ERC-1155 standards for conformance
+    /// to their interfaces, and adds the warnings about the violations.
+    ///
+    /// The ABI is taken from the `solc` metadata, if the `abi` output is not selected.
+    ///
+    pub fn check_erc(&mut self) {
+        let mut messages = Vec::new();
+        for (path, contracts) in self.contracts.iter().flatten() {
+            for (name, contract) in contracts.iter() {
+                let abi = match contract.abi.clone().or_else(|| {
+                    contract
+                        .metadata
+                        .as_ref()
+                        .and_then(|metadata| metadata.as_str())
+                        .and_then(|metadata| {
+                            serde_json::from_str::<serde_json::Value>(metadata).ok()
+                        })
+                        .and_then(|mut metadata| {
+                            metadata
+                                .pointer_mut("/output/abi")
+                                .map(serde_json::Value::take)
+                        })
+                }) {
+                    Some(abi) => abi,
+                    None => continue,
+                };
+
+                let full_path = format!("{path}:{name}");
+                for (standard, violations) in Erc::check(&abi).into_iter() {
+                    if violations.is_empty() {
+                        continue;
+                    }
+                    let mut message = SolcStandardJsonOutputError::message_erc_nonconformance(
+                        full_path.as_str(),
+                        standard,
+                        violations.as_slice(),
+                    );
+                    message.push_contract_path(path.as_str());
+                    messages.push(message);
+                }
+            }
+        }
+
+        self.errors.get_or_insert_with(Vec::new).extend(messages);
+    }
+
+    ///
+    /// Sets the source code hashes of the corresponding sources.
+    ///
+    pub fn set_source_hashes(&mut self, source_hashes: BTreeMap<String, SourceHash>) {
+        let sources = match self.sources.as_mut() {
+            Some(sources) => sources,
+            None => return,
+        };
+
+        for (path, hash) in source_hashes.into_iter() {
+            if let Some(source) = sources.get_mut(path.as_str()) {
+                source.keccak256 = Some(hash.keccak256);
+                source.sha256 = Some(hash.sha256);
+            }
+        }
+    }
+
+    ///
+    /// Moves the `solc` EVM bytecode of all contracts to a separate field.
+    ///
+    /// Must be called before writing the zkEVM build to the output.
+    ///
+    pub fn preserve_evm_bytecode(&mut self) {
+        let files = match self.contracts.as_mut() {
+            Some(files) => files,
+            None => return,
+        };
+
+        for (_path, contracts) in files.iter_mut() {
+            for (_name, contract) in contracts.iter_mut() {
+                if let Some(evm) = contract.evm.as_mut() {
+                    evm.preserve_evm_bytecode();
+                }
+            }
+        }
+    }
+
+    ///
+    /// Returns the `solc` EVM bytecode of all contracts, keyed by the full contract path.
+    ///
+    pub fn get_evm_bytecode(&self) -> BTreeMap<String, String> {
+        let mut evm_bytecode = BTreeMap::new();
+
+        let files = match self.contracts.as_ref() {
+            Some(files) => files,
+            None => return evm_bytecode,
+        };
+        for (path, contracts) in files.iter() {
+            for (name, contract) in contracts.iter() {
+                if let Some(object) = contract
+                    .evm
+                    .as_ref()
+                    .and_then(|evm| evm.bytecode.as_ref())
+                    .map(|bytecode| bytecode.object.to_owned())
+                {
+                    evm_bytecode.insert(format!("{path}:{name}"), object);
+                }
+            }
+        }
+
+        evm_bytecode
+    }
+
+    ///
+    /// Removes or marks the contracts without bytecode, depending on the `policy`.
+    ///
+    /// The contracts are considered empty if they have not been compiled to zkEVM bytecode.
+    ///
+    pub fn process_empty_contracts(&mut self, policy: EmptyContracts) {
+        let files = match self.contracts.as_mut() {
+            Some(files) => files,
+            None => return,
+        };
+
+        match policy {
+            EmptyContracts::Keep => {}
+            EmptyContracts::Omit => {
+                for (_path, contracts) in files.iter_mut() {
+                    contracts.retain(|_name, contract| contract.hash.is_some());
+                }
+                files.retain(|_path, contracts| !contracts.is_empty());
+            }
+            EmptyContracts::Mark => {
+                for (path, contracts) in files.iter_mut() {
+                    for (name, contract) in contracts.iter_mut() {
+                        if contract.hash.is_some() {
+                            continue;
+                        }
+
+                        let reason = self
+                            .sources
+                            .as_ref()
+                            .and_then(|sources| sources.get(path.as_str()))
+                            .map(|source| source.empty_contract_reason(name.as_str()))
+                            .unwrap_or("unknown");
+                        contract.empty_reason = Some(reason.to_owned());
+                    }
+                }
+            }
+        }
+    }
+
     ///
     /// The pass, which replaces with dependency indexes with actual data.
     ///
-    fn preprocess_dependencies(&mut self) -> anyhow::Result<()> {
+    /// Returns the errors of the contracts whose dependencies could not be processed, by their
+    /// full paths.
+    ///
+    fn preprocess_dependencies(&mut self) -> BTreeMap<String, Vec<String>> {
+        let mut errors = BTreeMap::new();
         let files = match self.contracts.as_mut() {
             Some(files) => files,
-            None => return Ok(()),
+            None => return errors,
         };
         let mut hash_path_mapping = BTreeMap::new();
 
@@ -213,15 +650,19 @@ impl Output {
                 };
 
                 let full_path = format!("{path}:{name}");
-                Self::preprocess_dependency_level(
+                if let Err(error) = Self::preprocess_dependency_level(
                     full_path.as_str(),
                     assembly,
                     &hash_path_mapping,
-                )?;
+                ) {
+                    let error =
+                        format!("Contract `{full_path}` dependencies processing error: {error}");
+                    errors.insert(full_path, vec![error]);
+                }
             }
         }
 
-        Ok(())
+        errors
     }
 
     ///
@@ -261,3 +702,250 @@ impl Output {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::debug::filter::Filter as DebugFilter;
+    use crate::project::contract::ir::IR as ProjectContractIR;
+    use crate::solc::pipeline::Pipeline as SolcPipeline;
+
+    use super::Output;
+
+    #[test]
+    fn round_trip() {
+        let json = serde_json::json!({
+            "contracts": {
+                "Test.sol": {
+                    "Test": {
+                        "abi": [],
+                        "irOptimizedAst": { "nodeType": "YulObject" },
+                        "evm": {
+                            "legacyAssembly": null,
+                            "assembly": null,
+                            "bytecode": { "object": "00", "opcodes": "STOP" },
+                            "gasEstimates": { "creation": {} }
+                        }
+                    }
+                }
+            },
+            "sources": {
+                "Test.sol": { "id": 0, "ast": null, "experimentalFlag": true }
+            },
+            "errors": [{
+                "component": "general",
+                "errorCode": null,
+                "formattedMessage": "Warning",
+                "message": "Warning",
+                "severity": "warning",
+                "sourceLocation": null,
+                "type": "Warning",
+                "customField": 1
+            }],
+            "customOutput": "preserved"
+        });
+        let mut output: Output = serde_json::from_value(json.clone()).expect("Always valid");
+        assert_eq!(serde_json::to_value(&output).expect("Always valid"), json);
+
+        output
+            .contracts
+            .as_mut()
+            .and_then(|files| files.get_mut("Test.sol"))
+            .and_then(|contracts| contracts.get_mut("Test"))
+            .and_then(|contract| contract.evm.as_mut())
+            .expect("Always exists")
+            .modify(String::new(), "00".to_owned());
+        let json = serde_json::to_value(&output).expect("Always valid");
+        assert_eq!(
+            json.pointer("/contracts/Test.sol/Test/evm/gasEstimates"),
+            None
+        );
+        assert_eq!(
+            json.pointer("/contracts/Test.sol/Test/irOptimizedAst/nodeType"),
+            Some(&serde_json::json!("YulObject"))
+        );
+    }
+
+    #[test]
+    fn try_to_project_yul_source() {
+        let json = serde_json::json!({
+            "contracts": {
+                "Test.sol": {
+                    "Test": { "irOptimized": "object \"Test\" { code { } }" }
+                }
+            }
+        });
+        let mut output: Output = serde_json::from_value(json).expect("Always valid");
+        let source_code_files = BTreeMap::from([("Test.sol".to_owned(), "".into())]);
+
+        let project = output
+            .try_to_project(
+                source_code_files,
+                BTreeMap::new(),
+                SolcPipeline::Yul,
+                &semver::Version::new(0, 8, 20),
+                None,
+                &DebugFilter::default(),
+            )
+            .expect("Always valid");
+
+        match project.contracts["Test.sol:Test"].ir {
+            ProjectContractIR::Yul(ref yul) => {
+                assert_eq!(yul.solc_output.as_deref(), Some("irOptimized"))
+            }
+            _ => panic!("Always Yul"),
+        }
+    }
+
+    #[test]
+    fn try_to_project_errors() {
+        let json = serde_json::json!({
+            "contracts": {
+                "Test.sol": {
+                    "First": { "irOptimized": "object \"First\" {" },
+                    "Second": { "irOptimized": "object \"Second\" {" },
+                    "Interface": { "irOptimized": "" }
+                }
+            }
+        });
+        let mut output: Output = serde_json::from_value(json).expect("Always valid");
+        let source_code_files = BTreeMap::from([("Test.sol".to_owned(), "".into())]);
+
+        let error = output
+            .try_to_project(
+                source_code_files,
+                BTreeMap::new(),
+                SolcPipeline::Yul,
+                &semver::Version::new(0, 8, 20),
+                None,
+                &DebugFilter::default(),
+            )
+            .expect_err("Always invalid")
+            .to_string();
+
+        assert!(error.starts_with("2 contract(s) could not be processed"));
+        assert!(error.contains("Contract `Test.sol:First` parsing error"));
+        assert!(error.contains("Contract `Test.sol:Second` parsing error"));
+    }
+
+    #[test]
+    fn try_to_project_dump_errors() {
+        let json = serde_json::json!({
+            "contracts": {
+                "Test.sol": {
+                    "First": { "irOptimized": "object \"First\" {" },
+                    "Second": { "irOptimized": "object \"Second\" { code { } }" }
+                }
+            }
+        });
+        let mut output: Output = serde_json::from_value(json).expect("Always valid");
+        let source_code_files = BTreeMap::from([("Test.sol".to_owned(), "".into())]);
+        let output_file = std::env::temp_dir().join(format!(
+            "zksolc-dump-errors-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        std::fs::write(output_file.as_path(), "").expect("Always valid");
+        let debug_config = compiler_llvm_context::DebugConfig::new(output_file.clone());
+
+        let error = output
+            .try_to_project(
+                source_code_files,
+                BTreeMap::new(),
+                SolcPipeline::Yul,
+                &semver::Version::new(0, 8, 20),
+                Some(&debug_config),
+                &DebugFilter::default(),
+            )
+            .expect_err("Always invalid")
+            .to_string();
+        std::fs::remove_file(output_file).expect("Always valid");
+
+        assert!(error.starts_with("2 contract(s) could not be processed"));
+        assert!(error.contains("Contract `Test.sol:First` Yul dumping error"));
+        assert!(error.contains("Contract `Test.sol:First` parsing error"));
+        assert!(error.contains("Contract `Test.sol:Second` Yul dumping error"));
+    }
+
+    #[test]
+    fn try_to_project_assembly_errors() {
+        let json = serde_json::json!({
+            "contracts": {
+                "Test.sol": {
+                    "First": { "evm": { "legacyAssembly": { ".code": [{ "name": "STOP" }] } } },
+                    "Second": { "evm": { "legacyAssembly": { ".code": [{ "name": "STOP" }] } } },
+                    "Interface": { "evm": { "legacyAssembly": null } }
+                }
+            }
+        });
+        let mut output: Output = serde_json::from_value(json).expect("Always valid");
+        let source_code_files = BTreeMap::from([("Test.sol".to_owned(), "".into())]);
+
+        let error = output
+            .try_to_project(
+                source_code_files,
+                BTreeMap::new(),
+                SolcPipeline::EVMLA,
+                &semver::Version::new(0, 8, 20),
+                None,
+                &DebugFilter::default(),
+            )
+            .expect_err("Always invalid")
+            .to_string();
+
+        assert!(error.starts_with("2 contract(s) could not be processed"));
+        assert!(error.contains("Contract `Test.sol:First` assembly error"));
+        assert!(error.contains("Contract `Test.sol:Second` assembly error"));
+    }
+
+    #[test]
+    fn contract_location() {
+        let json = serde_json::json!({
+            "sources": {
+                "Test.sol": {
+                    "id": 0,
+                    "ast": {
+                        "nodeType": "SourceUnit",
+                        "nodes": [{
+                            "nodeType": "ContractDefinition",
+                            "name": "Test",
+                            "src": "25:16:0"
+                        }]
+                    }
+                }
+            }
+        });
+        let output: Output = serde_json::from_value(json).expect("Always valid");
+
+        let location = output
+            .contract_location("Test.sol:Test")
+            .expect("Always exists");
+        assert_eq!(location.file, "Test.sol");
+        assert_eq!(location.start, 25);
+        assert_eq!(location.end, 41);
+        assert!(output.contract_location("Test.sol:Missing").is_none());
+    }
+
+    #[test]
+    fn source_keccak256_hashes() {
+        let metadata = serde_json::json!({
+            "sources": {
+                "Test.sol": { "keccak256": "0x01" },
+                "lib/Base.sol": { "keccak256": "0x02" }
+            }
+        });
+        let json = serde_json::json!({
+            "contracts": {
+                "Test.sol": {
+                    "Test": { "metadata": metadata.to_string() }
+                }
+            }
+        });
+        let output: Output = serde_json::from_value(json).expect("Always valid");
+
+        let hashes = output.get_source_keccak256_hashes();
+        assert_eq!(hashes.len(), 2);
+        assert_eq!(hashes["lib/Base.sol"], "0x02");
+    }
+}
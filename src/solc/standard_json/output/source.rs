@@ -2,6 +2,8 @@
 //! The `solc --standard-json` output source.
 //!
 
+use std::collections::BTreeMap;
+
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -19,9 +21,27 @@ pub struct Source {
     pub id: usize,
     /// The source code AST.
     pub ast: Option<serde_json::Value>,
+    /// The source code keccak256 hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keccak256: Option<String>,
+    /// The source code sha256 hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// The other `solc` source output fields, preserved as is.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
 impl Source {
+    /// The gas accounting approximation description.
+    const APPROXIMATION_GAS: &'static str = "gas accounting";
+
+    /// The code introspection approximation description.
+    const APPROXIMATION_CODE: &'static str = "code introspection";
+
+    /// The precompile approximation description.
+    const APPROXIMATION_PRECOMPILE: &'static str = "precompile emulation";
+
     ///
     /// Checks the AST node for the `ecrecover` function usage.
     ///
@@ -384,6 +404,82 @@ impl Source {
         messages
     }
 
+    ///
+    /// Checks the AST node for the constructs translated with Era-specific approximations.
+    ///
+    pub fn check_strict_approximation(
+        ast: &serde_json::Value,
+    ) -> Option<SolcStandardJsonOutputError> {
+        let ast = ast.as_object()?;
+
+        let (construct, approximation) =
+            match ast.get("nodeType")?.as_str()? {
+                "Identifier" => match ast.get("name")?.as_str()? {
+                    "gasleft" => ("gasleft", Self::APPROXIMATION_GAS),
+                    name @ ("ecrecover" | "sha256" | "ripemd160") => {
+                        (name, Self::APPROXIMATION_PRECOMPILE)
+                    }
+                    _ => return None,
+                },
+                "MemberAccess" => {
+                    let expression_type = ast
+                        .get("expression")?
+                        .get("typeDescriptions")?
+                        .get("typeIdentifier")?
+                        .as_str()?;
+                    if !expression_type.starts_with("t_address") {
+                        return None;
+                    }
+                    match ast.get("memberName")?.as_str()? {
+                        "send" => ("<address>.send", Self::APPROXIMATION_GAS),
+                        "transfer" => ("<address>.transfer", Self::APPROXIMATION_GAS),
+                        "code" => ("<address>.code", Self::APPROXIMATION_CODE),
+                        "codehash" => ("<address>.codehash", Self::APPROXIMATION_CODE),
+                        _ => return None,
+                    }
+                }
+                "YulFunctionCall" => match ast.get("functionName")?.get("name")?.as_str()? {
+                    "gas" => ("gas", Self::APPROXIMATION_GAS),
+                    name @ ("codesize" | "codecopy" | "extcodesize" | "extcodecopy"
+                    | "extcodehash") => (name, Self::APPROXIMATION_CODE),
+                    _ => return None,
+                },
+                _ => return None,
+            };
+
+        Some(SolcStandardJsonOutputError::message_strict_approximation(
+            construct,
+            approximation,
+            ast.get("src")?.as_str(),
+        ))
+    }
+
+    ///
+    /// Returns the strict mode errors for the constructs translated with Era-specific approximations.
+    ///
+    pub fn get_strict_messages(ast: &serde_json::Value) -> Vec<SolcStandardJsonOutputError> {
+        let mut messages = Vec::new();
+        if let Some(message) = Self::check_strict_approximation(ast) {
+            messages.push(message);
+        }
+
+        match ast {
+            serde_json::Value::Array(array) => {
+                for element in array.iter() {
+                    messages.extend(Self::get_strict_messages(element));
+                }
+            }
+            serde_json::Value::Object(object) => {
+                for (_key, value) in object.iter() {
+                    messages.extend(Self::get_strict_messages(value));
+                }
+            }
+            _ => {}
+        }
+
+        messages
+    }
+
     ///
     /// Returns the name of the last contract.
     ///
@@ -408,4 +504,35 @@ impl Source {
             .last()
             .ok_or_else(|| anyhow::anyhow!("The last contract not found in the AST"))
     }
+
+    ///
+    /// Returns the reason why the contract `name` has no bytecode.
+    ///
+    pub fn empty_contract_reason(&self, name: &str) -> &'static str {
+        let node = self
+            .ast
+            .as_ref()
+            .and_then(|ast| ast.get("nodes"))
+            .and_then(|nodes| nodes.as_array())
+            .and_then(|nodes| {
+                nodes.iter().find(|node| {
+                    node.get("nodeType")
+                        .and_then(|node_type| node_type.as_str())
+                        == Some("ContractDefinition")
+                        && node.get("name").and_then(|name| name.as_str()) == Some(name)
+                })
+            });
+        let node = match node {
+            Some(node) => node,
+            None => return "unknown",
+        };
+
+        if node.get("contractKind").and_then(|kind| kind.as_str()) == Some("interface") {
+            return "interface";
+        }
+        if node.get("abstract").and_then(|value| value.as_bool()) == Some(true) {
+            return "abstract";
+        }
+        "unknown"
+    }
 }
@@ -0,0 +1,63 @@
+//!
+//! The compilation target.
+//!
+
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+///
+/// The compilation target.
+///
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum Target {
+    /// The EraVM target.
+    #[default]
+    #[serde(rename = "eravm")]
+    EraVM,
+    /// The EVM target.
+    #[serde(rename = "evm")]
+    EVM,
+}
+
+impl Target {
+    /// All the targets, including the ones not supported by the current compiler build.
+    pub const ALL: [Self; 2] = [Self::EraVM, Self::EVM];
+
+    ///
+    /// Checks whether the target is supported by the current compiler build.
+    ///
+    pub fn validate(&self) -> anyhow::Result<()> {
+        match self {
+            Self::EraVM => Ok(()),
+            Self::EVM => anyhow::bail!("The `{}` target is not supported yet", self),
+        }
+    }
+}
+
+impl FromStr for Target {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "eravm" => Ok(Self::EraVM),
+            "evm" => Ok(Self::EVM),
+            string => anyhow::bail!(
+                "Unknown target `{}`. Supported targets: `eravm`, `evm`",
+                string
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EraVM => write!(f, "eravm"),
+            Self::EVM => write!(f, "evm"),
+        }
+    }
+}
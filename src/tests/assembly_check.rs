@@ -0,0 +1,53 @@
+//!
+//! The Solidity compiler unit tests for the zkEVM assembly verification pass.
+//!
+
+#![cfg(test)]
+
+use std::collections::BTreeMap;
+
+use crate::build::assembly_check::AssemblyCheck;
+use crate::solc::pipeline::Pipeline as SolcPipeline;
+
+const SOURCE_CODE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Test {
+    uint256[] values;
+
+    error Empty();
+
+    function push(uint256 value) public {
+        require(value != 0, "Zero value");
+        values.push(value);
+    }
+
+    function get(uint256 index) public view returns(uint256) {
+        if (values.length == 0) {
+            revert Empty();
+        }
+        return values[index] / index;
+    }
+}
+"#;
+
+fn check(pipeline: SolcPipeline) {
+    let build = super::build_solidity_with(SOURCE_CODE, BTreeMap::new(), pipeline, |_| {})
+        .expect("Test failure");
+    let contract = build.contracts.get("test.sol:Test").expect("Always exists");
+    assert_eq!(
+        AssemblyCheck::new(contract.build.assembly_text.as_str()).violations,
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn yul() {
+    check(SolcPipeline::Yul);
+}
+
+#[test]
+fn evmla() {
+    check(SolcPipeline::EVMLA);
+}
@@ -0,0 +1,64 @@
+//!
+//! The Solidity compiler unit tests for the standard JSON batch mode.
+//!
+
+#![cfg(test)]
+
+use std::path::PathBuf;
+
+use crate::format_version::FormatVersion;
+use crate::solc::Compiler as SolcCompiler;
+
+#[test]
+fn batch() {
+    compiler_llvm_context::initialize_target();
+    let _ = crate::process::EXECUTABLE.set(PathBuf::from(crate::r#const::DEFAULT_EXECUTABLE_NAME));
+
+    let input = serde_json::json!({
+        "language": "Solidity",
+        "sources": {
+            "Test.sol": {
+                "content": "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\n\ncontract Test {}\n",
+            },
+        },
+        "settings": {
+            "optimizer": { "enabled": true, "mode": "3" },
+            "outputSelection": { "*": { "*": ["abi"] } },
+        },
+    })
+    .to_string();
+    let lines = format!("{input}\n\n{{ invalid\n{input}\n");
+
+    let mut output = Vec::new();
+    crate::standard_json_batch_output(
+        lines.as_bytes(),
+        &mut output,
+        &mut SolcCompiler::new("solc".to_owned()),
+        false,
+        false,
+        false,
+        false,
+        None,
+        None,
+        vec![],
+        None,
+        true,
+        FormatVersion::default(),
+    )
+    .expect("Test failure");
+
+    let output = String::from_utf8(output).expect("Always valid");
+    let outputs: Vec<serde_json::Value> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("Always valid"))
+        .collect();
+    assert_eq!(outputs.len(), 3);
+    assert!(outputs[0]
+        .pointer("/contracts/Test.sol/Test/hash")
+        .is_some());
+    assert!(outputs[1]
+        .pointer("/errors/0/message")
+        .and_then(|message| message.as_str())
+        .is_some());
+    assert_eq!(outputs[2], outputs[0]);
+}
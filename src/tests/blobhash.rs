@@ -0,0 +1,25 @@
+//!
+//! The Solidity compiler unit tests for the blob hashes.
+//!
+
+#![cfg(test)]
+
+#[test]
+fn blobhash_yul() {
+    let source_code = r#"
+object "BlobHash" {
+    code {
+        datacopy(0, dataoffset("BlobHash_deployed"), datasize("BlobHash_deployed"))
+        return(0, datasize("BlobHash_deployed"))
+    }
+    object "BlobHash_deployed" {
+        code {
+            mstore(0, blobhash(calldataload(0)))
+            return(0, 32)
+        }
+    }
+}
+    "#;
+
+    super::build_yul(source_code).expect("Test failure");
+}
@@ -0,0 +1,86 @@
+//!
+//! The Solidity compiler unit tests for the EVM legacy assembly debug locations.
+//!
+
+#![cfg(test)]
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::solc::pipeline::Pipeline as SolcPipeline;
+
+const SOURCE_CODE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Test {
+    function main(uint256 a, uint256 b) public pure returns(uint256) {
+        return a * b + 1;
+    }
+}
+"#;
+
+///
+/// Builds the test contract with the IRs dumped to a temporary directory, and returns its
+/// bytecode and unoptimized LLVM IR.
+///
+fn build(debug_locations: bool) -> (Vec<u8>, String) {
+    let output_directory = std::env::temp_dir().join(format!(
+        "zksolc-debug-locations-{}-{}",
+        std::process::id(),
+        rand::random::<u64>()
+    ));
+    let build = super::build_solidity_with_options(
+        SOURCE_CODE,
+        BTreeMap::new(),
+        SolcPipeline::EVMLA,
+        |project| project.evmla_debug_locations = debug_locations,
+        false,
+        Some(compiler_llvm_context::DebugConfig::new(
+            output_directory.clone(),
+        )),
+    )
+    .expect("Test failure");
+    let bytecode = build.contracts["test.sol:Test"].build.bytecode.to_owned();
+
+    let contract_directory =
+        output_directory.join(crate::debug::contract_directory("test.sol:Test"));
+    let llvm_ir = std::fs::read_to_string(unoptimized_llvm_ir_path(contract_directory.as_path()))
+        .expect("Always valid");
+    std::fs::remove_dir_all(output_directory).expect("Always valid");
+
+    (bytecode, llvm_ir)
+}
+
+///
+/// Returns the path of the unoptimized LLVM IR dump in the contract debug subdirectory.
+///
+fn unoptimized_llvm_ir_path(contract_directory: &Path) -> PathBuf {
+    std::fs::read_dir(contract_directory)
+        .expect("Always valid")
+        .map(|entry| entry.expect("Always valid").path())
+        .find(|path| path.to_string_lossy().ends_with(".unoptimized.ll"))
+        .expect("Always exists")
+}
+
+#[test]
+fn attached() {
+    let (_bytecode, llvm_ir) = build(true);
+
+    assert_eq!(llvm_ir.matches("!DICompileUnit(").count(), 1);
+    assert!(llvm_ir.contains("!DIFile(filename: \"test.sol:Test:deploy\""));
+    assert!(llvm_ir.contains("!DIFile(filename: \"test.sol:Test:runtime\""));
+    assert!(llvm_ir.contains("!DILocation(line: "));
+    assert!(llvm_ir.contains(", !dbg !"));
+}
+
+#[test]
+fn independent_of_debug_output() {
+    let (reference_bytecode, llvm_ir) = build(false);
+    assert!(!llvm_ir.contains("!dbg"));
+    assert!(!llvm_ir.contains("!DICompileUnit("));
+
+    let (bytecode, _llvm_ir) = build(true);
+    assert_eq!(reference_bytecode, bytecode);
+}
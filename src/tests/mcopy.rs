@@ -0,0 +1,51 @@
+//!
+//! The Solidity compiler unit tests for the memory copying.
+//!
+
+#![cfg(test)]
+
+#[test]
+fn mcopy_overlapping() {
+    let source_code = r#"
+object "MemoryCopy" {
+    code {
+        datacopy(0, dataoffset("MemoryCopy_deployed"), datasize("MemoryCopy_deployed"))
+        return(0, datasize("MemoryCopy_deployed"))
+    }
+    object "MemoryCopy_deployed" {
+        code {
+            let size := calldataload(0)
+            calldatacopy(0, 32, size)
+            mcopy(7, 0, size)
+            mcopy(0, 7, size)
+            return(0, add(size, 7))
+        }
+    }
+}
+    "#;
+
+    super::build_yul(source_code).expect("Test failure");
+}
+
+#[test]
+fn mcopy_constant() {
+    let source_code = r#"
+object "MemoryCopy" {
+    code {
+        datacopy(0, dataoffset("MemoryCopy_deployed"), datasize("MemoryCopy_deployed"))
+        return(0, datasize("MemoryCopy_deployed"))
+    }
+    object "MemoryCopy_deployed" {
+        code {
+            mstore(0, 0x0102030405060708091011121314151617181920212223242526272829303132)
+            mcopy(64, 0, 32)
+            mcopy(96, 64, 5)
+            mcopy(0, 0, 0)
+            return(64, 64)
+        }
+    }
+}
+    "#;
+
+    super::build_yul(source_code).expect("Test failure");
+}
@@ -438,3 +438,45 @@ contract StorageFunctionPointerExample {
     )
     .expect("Test failure"));
 }
+
+#[test]
+fn strict_gasleft() {
+    let source_code = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract GasleftExample {
+    function remaining() public view returns (uint256) {
+        return gasleft();
+    }
+}
+    "#;
+
+    assert!(super::check_solidity_strict_error(
+        source_code,
+        "Error: `gasleft` relies on the gas accounting",
+        SolcPipeline::Yul,
+    )
+    .expect("Test failure"));
+}
+
+#[test]
+fn strict_codehash() {
+    let source_code = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract CodehashExample {
+    function hash(address target) public view returns (bytes32) {
+        return target.codehash;
+    }
+}
+    "#;
+
+    assert!(super::check_solidity_strict_error(
+        source_code,
+        "Error: `<address>.codehash` relies on the code introspection",
+        SolcPipeline::Yul,
+    )
+    .expect("Test failure"));
+}
@@ -0,0 +1,55 @@
+//!
+//! The Solidity compiler unit tests for the metadata hash placement.
+//!
+
+#![cfg(test)]
+
+use std::collections::BTreeMap;
+
+use crate::build::metadata_hash_placement::MetadataHashPlacement;
+use crate::solc::pipeline::Pipeline as SolcPipeline;
+
+const SOURCE_CODE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Test {
+    function main(uint256 a) public pure returns(uint256) {
+        return a ^ 0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef;
+    }
+}
+"#;
+
+#[test]
+fn constant_section() {
+    for pipeline in [SolcPipeline::Yul, SolcPipeline::EVMLA] {
+        let build = super::build_solidity_with_options(
+            SOURCE_CODE,
+            BTreeMap::new(),
+            pipeline,
+            |project| project.metadata_hash_placement = MetadataHashPlacement::ConstantSection,
+            true,
+            None,
+        )
+        .expect("Test failure");
+        let assembly_text = build.contracts["test.sol:Test"]
+            .build
+            .assembly_text
+            .as_str();
+
+        let lines: Vec<&str> = assembly_text.lines().collect();
+        let index = lines
+            .iter()
+            .position(|line| line.trim() == format!("{}:", MetadataHashPlacement::LABEL))
+            .expect("Always exists");
+        assert!(lines[index - 1].contains(MetadataHashPlacement::SECTION_CONSTANTS));
+        assert_eq!(
+            lines
+                .iter()
+                .filter(|line| line.contains(MetadataHashPlacement::SECTION_CONSTANTS))
+                .count(),
+            1,
+            "The hash cell must be placed into the existing constant section"
+        );
+    }
+}
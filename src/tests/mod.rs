@@ -4,26 +4,68 @@
 
 #![cfg(test)]
 
+mod assembly_check;
+mod batch;
+mod blobhash;
+mod debug_locations;
 mod libraries;
+mod mcopy;
 mod messages;
+mod metadata_hash_placement;
+mod mutation;
+mod push0;
+mod revert_strings;
 mod runtime_code;
+mod stack_spilling;
 mod unsupported_opcodes;
+mod verify_bundle;
 
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+use crate::build::Build;
+use crate::debug::filter::Filter as DebugFilter;
 use crate::project::Project;
 use crate::solc::pipeline::Pipeline as SolcPipeline;
 use crate::solc::standard_json::input::settings::optimizer::Optimizer as SolcStandardJsonInputSettingsOptimizer;
 use crate::solc::standard_json::input::settings::selection::Selection as SolcStandardJsonInputSettingsSelection;
 use crate::solc::standard_json::input::Input as SolcStandardJsonInput;
 use crate::solc::Compiler as SolcCompiler;
+use crate::target::Target;
+use crate::yul::entry_objects::EntryObjects as YulEntryObjects;
 
 pub fn build_solidity(
     source_code: &str,
     libraries: BTreeMap<String, BTreeMap<String, String>>,
     pipeline: SolcPipeline,
 ) -> anyhow::Result<()> {
+    build_solidity_with(source_code, libraries, pipeline, |_| {})?;
+    Ok(())
+}
+
+pub fn build_solidity_with<F>(
+    source_code: &str,
+    libraries: BTreeMap<String, BTreeMap<String, String>>,
+    pipeline: SolcPipeline,
+    configure: F,
+) -> anyhow::Result<Build>
+where
+    F: FnOnce(&mut Project),
+{
+    build_solidity_with_options(source_code, libraries, pipeline, configure, false, None)
+}
+
+pub fn build_solidity_with_options<F>(
+    source_code: &str,
+    libraries: BTreeMap<String, BTreeMap<String, String>>,
+    pipeline: SolcPipeline,
+    configure: F,
+    include_metadata_hash: bool,
+    debug_config: Option<compiler_llvm_context::DebugConfig>,
+) -> anyhow::Result<Build>
+where
+    F: FnOnce(&mut Project),
+{
     inkwell::support::enable_llvm_pretty_stack_trace();
     compiler_llvm_context::initialize_target();
     let _ = crate::process::EXECUTABLE.set(PathBuf::from(crate::r#const::DEFAULT_EXECUTABLE_NAME));
@@ -43,22 +85,25 @@ pub fn build_solidity(
     let mut solc = SolcCompiler::new("solc".to_owned());
     let mut output = solc.standard_json(input, pipeline, None, vec![], None)?;
 
-    let project = output.try_to_project(
+    let mut project = output.try_to_project(
         sources,
         libraries,
         pipeline,
         &SolcCompiler::LAST_SUPPORTED_VERSION,
         None,
+        &DebugFilter::default(),
     )?;
-    let _build = project.compile(
+    configure(&mut project);
+    project.compile(
         optimizer_settings,
+        Target::EraVM,
         false,
+        include_metadata_hash,
         false,
         zkevm_assembly::RunningVmEncodingMode::Production,
         None,
-    )?;
-
-    Ok(())
+        debug_config,
+    )
 }
 
 pub fn check_solidity_warning(
@@ -89,19 +134,56 @@ pub fn check_solidity_warning(
     Ok(contains_warning)
 }
 
+pub fn check_solidity_strict_error(
+    source_code: &str,
+    error_substring: &str,
+    pipeline: SolcPipeline,
+) -> anyhow::Result<bool> {
+    let mut sources = BTreeMap::new();
+    sources.insert("test.sol".to_string(), source_code.to_string());
+    let input = SolcStandardJsonInput::try_from_sources(
+        sources.clone(),
+        BTreeMap::new(),
+        SolcStandardJsonInputSettingsSelection::new_required(pipeline),
+        SolcStandardJsonInputSettingsOptimizer::new(true, None),
+        None,
+        pipeline == SolcPipeline::Yul,
+    )?;
+
+    let mut solc = SolcCompiler::new("solc".to_owned());
+    let mut output = solc.standard_json(input, pipeline, None, vec![], None)?;
+    output.check_strict();
+    let contains_error = output
+        .errors
+        .ok_or_else(|| anyhow::anyhow!("Solidity compiler messages not found"))?
+        .iter()
+        .any(|error| {
+            error.severity.as_str() == "error" && error.formatted_message.contains(error_substring)
+        });
+
+    Ok(contains_error)
+}
+
 pub fn build_yul(source_code: &str) -> anyhow::Result<()> {
     inkwell::support::enable_llvm_pretty_stack_trace();
     compiler_llvm_context::initialize_target();
     let optimizer_settings = compiler_llvm_context::OptimizerSettings::none();
 
-    let project =
-        Project::try_from_yul_string(PathBuf::from("test.yul").as_path(), source_code, None)?;
+    let project = Project::try_from_yul_string(
+        PathBuf::from("test.yul").as_path(),
+        source_code,
+        &YulEntryObjects::default(),
+        None,
+    )?;
     let _build = project.compile(
         optimizer_settings,
+        Target::EraVM,
+        false,
         false,
         false,
         zkevm_assembly::RunningVmEncodingMode::Production,
         None,
+        None,
     )?;
 
     Ok(())
@@ -0,0 +1,91 @@
+//!
+//! The Solidity compiler unit tests for the mutation testing.
+//!
+
+#![cfg(test)]
+
+use std::collections::BTreeMap;
+
+use crate::build::Build;
+use crate::evmla::assembly::instruction::name::Name as InstructionName;
+use crate::project::mutation::Mutant;
+use crate::solc::pipeline::Pipeline as SolcPipeline;
+use crate::target::Target;
+
+const SOURCE_CODE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Child {
+    function add(uint256 a, uint256 b) public pure returns(uint256) {
+        unchecked { return a + b; }
+    }
+}
+
+contract Parent {
+    function deploy() public returns(address) {
+        return address(new Child());
+    }
+}
+
+contract Other {
+    function get() public pure returns(uint256) {
+        return 42;
+    }
+}
+"#;
+
+#[test]
+fn only_mutated_and_dependents_recompiled() {
+    let mut project = None;
+    let base = super::build_solidity_with(
+        SOURCE_CODE,
+        BTreeMap::new(),
+        SolcPipeline::EVMLA,
+        |base_project| project = Some(base_project.clone()),
+    )
+    .expect("Test failure");
+    let mut project = project.expect("Always exists");
+
+    let begin = SOURCE_CODE.find("a + b").expect("Always exists") as isize;
+    let mutant = Mutant {
+        name: "add-to-sub".to_owned(),
+        path: "test.sol".to_owned(),
+        begin,
+        end: begin + "a + b".len() as isize,
+        from: InstructionName::ADD,
+        to: InstructionName::SUB,
+    };
+    let mutated = mutant
+        .apply(&mut project, &BTreeMap::from([("test.sol".to_owned(), 0)]))
+        .expect("Test failure");
+    assert_eq!(
+        mutated.keys().cloned().collect::<Vec<String>>(),
+        vec!["test.sol:Child".to_owned()]
+    );
+
+    let mutant_build = project
+        .compile_mutant(
+            &base,
+            &mutated.into_keys().collect(),
+            compiler_llvm_context::OptimizerSettings::none(),
+            Target::EraVM,
+            false,
+            false,
+        )
+        .expect("Test failure");
+    let bytecode = |build: &Build, path: &str| build.contracts[path].build.bytecode.to_owned();
+    assert_ne!(
+        bytecode(&base, "test.sol:Child"),
+        bytecode(&mutant_build, "test.sol:Child")
+    );
+    assert_ne!(
+        bytecode(&base, "test.sol:Parent"),
+        bytecode(&mutant_build, "test.sol:Parent"),
+        "The dependent contract must be recompiled with the mutated dependency hash"
+    );
+    assert_eq!(
+        bytecode(&base, "test.sol:Other"),
+        bytecode(&mutant_build, "test.sol:Other")
+    );
+}
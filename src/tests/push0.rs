@@ -0,0 +1,87 @@
+//!
+//! The Solidity compiler unit tests for the zero constants in EVM legacy assembly.
+//!
+
+#![cfg(test)]
+
+use std::collections::BTreeMap;
+
+use crate::evmla::assembly::instruction::name::Name as InstructionName;
+use crate::evmla::assembly::Assembly;
+use crate::solc::pipeline::Pipeline as SolcPipeline;
+use crate::solc::standard_json::input::settings::optimizer::Optimizer as SolcStandardJsonInputSettingsOptimizer;
+use crate::solc::standard_json::input::settings::selection::Selection as SolcStandardJsonInputSettingsSelection;
+use crate::solc::standard_json::input::Input as SolcStandardJsonInput;
+use crate::solc::Compiler as SolcCompiler;
+
+const SOURCE_CODE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Test {
+    uint256 value;
+
+    function reset() public {
+        value = 0;
+    }
+}
+"#;
+
+///
+/// Collects the instructions of the assembly and its nested assemblies.
+///
+fn instructions(assembly: &Assembly) -> Vec<(InstructionName, Option<String>)> {
+    let mut instructions: Vec<(InstructionName, Option<String>)> = assembly
+        .code
+        .iter()
+        .flatten()
+        .map(|instruction| (instruction.name.clone(), instruction.value.clone()))
+        .collect();
+    for data in assembly.data.iter().flat_map(|data| data.values()) {
+        if let Some(assembly) = data.get_assembly() {
+            instructions.extend(instructions(assembly));
+        }
+    }
+    instructions
+}
+
+///
+/// `solc` v0.8.20 and newer default to the `shanghai` EVM version, where the zero constants are
+/// assembled into `PUSH0`. The legacy assembly JSON still lists them as `PUSH` with the zero
+/// value, so they need no special handling.
+///
+#[test]
+fn zero_push() {
+    let mut sources = BTreeMap::new();
+    sources.insert("test.sol".to_owned(), SOURCE_CODE.to_owned());
+    let input = SolcStandardJsonInput::try_from_sources(
+        sources.clone(),
+        BTreeMap::new(),
+        SolcStandardJsonInputSettingsSelection::new_required(SolcPipeline::EVMLA),
+        SolcStandardJsonInputSettingsOptimizer::new(true, None),
+        None,
+        false,
+    )
+    .expect("Test failure");
+
+    let mut solc = SolcCompiler::new("solc".to_owned());
+    assert!(solc.version().expect("Test failure").default >= SolcCompiler::LAST_SUPPORTED_VERSION);
+    let output = solc
+        .standard_json(input, SolcPipeline::EVMLA, None, vec![], None)
+        .expect("Test failure");
+    let assembly = output
+        .contracts
+        .as_ref()
+        .and_then(|contracts| contracts.get("test.sol"))
+        .and_then(|contracts| contracts.get("Test"))
+        .and_then(|contract| contract.evm.as_ref())
+        .and_then(|evm| evm.assembly.as_ref())
+        .expect("Always exists");
+
+    let instructions = instructions(assembly);
+    assert!(instructions
+        .iter()
+        .any(|(name, value)| *name == InstructionName::PUSH && value.as_deref() == Some("0")));
+
+    super::build_solidity(SOURCE_CODE, BTreeMap::new(), SolcPipeline::EVMLA).expect("Test failure");
+}
@@ -0,0 +1,172 @@
+//!
+//! The Solidity compiler unit tests for the revert string identifiers.
+//!
+
+#![cfg(test)]
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use sha3::Digest;
+
+use crate::debug::filter::Filter as DebugFilter;
+use crate::project::contract::ir::IR as ProjectContractIR;
+use crate::project::Project;
+use crate::solc::pipeline::Pipeline as SolcPipeline;
+use crate::solc::standard_json::input::settings::optimizer::Optimizer as SolcStandardJsonInputSettingsOptimizer;
+use crate::solc::standard_json::input::settings::selection::Selection as SolcStandardJsonInputSettingsSelection;
+use crate::solc::standard_json::input::Input as SolcStandardJsonInput;
+use crate::solc::Compiler as SolcCompiler;
+use crate::target::Target;
+use crate::yul::lexer::token::lexeme::literal::integer::Integer as IntegerLiteral;
+use crate::yul::lexer::token::lexeme::literal::Literal as LexicalLiteral;
+use crate::yul::parser::statement::expression::Expression;
+use crate::yul::parser::statement::Statement;
+use crate::yul::revert_strings::RevertStringIds;
+
+const SOURCE_CODE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Test {
+    event Note(string note);
+
+    address owner;
+
+    function transfer() public {
+        require(msg.sender == owner, "Ownable: caller is not the owner of this contract");
+        emit Note("This note is emitted instead of being reverted with");
+    }
+}
+"#;
+
+const MESSAGE: &str = "Ownable: caller is not the owner of this contract";
+
+///
+/// Returns the Yul pipeline project compiled by `solc` with its optimizer enabled or not.
+///
+fn project(solc_optimizer: bool) -> Project {
+    let mut sources = BTreeMap::new();
+    sources.insert("test.sol".to_string(), SOURCE_CODE.to_string());
+    let input = SolcStandardJsonInput::try_from_sources(
+        sources.clone(),
+        BTreeMap::new(),
+        SolcStandardJsonInputSettingsSelection::new_required(SolcPipeline::Yul),
+        SolcStandardJsonInputSettingsOptimizer::new(solc_optimizer, None),
+        None,
+        true,
+    )
+    .expect("Test failure");
+
+    let mut solc = SolcCompiler::new("solc".to_owned());
+    let mut output = solc
+        .standard_json(input, SolcPipeline::Yul, None, vec![], None)
+        .expect("Test failure");
+    output
+        .try_to_project(
+            sources,
+            BTreeMap::new(),
+            SolcPipeline::Yul,
+            &SolcCompiler::LAST_SUPPORTED_VERSION,
+            None,
+            &DebugFilter::default(),
+        )
+        .expect("Test failure")
+}
+
+///
+/// Substitutes the revert strings of the project contract, checking the patched encoding, and
+/// builds it, checking the reported messages.
+///
+fn check(solc_optimizer: bool) {
+    compiler_llvm_context::initialize_target();
+    let _ = crate::process::EXECUTABLE.set(PathBuf::from(crate::r#const::DEFAULT_EXECUTABLE_NAME));
+
+    let hash = hex::encode(sha3::Keccak256::digest(MESSAGE.as_bytes()));
+    let id = hash[..RevertStringIds::ID_LENGTH * 2].to_owned();
+
+    let mut project = project(solc_optimizer);
+    let mut object = match project.contracts["test.sol:Test"].ir {
+        ProjectContractIR::Yul(ref yul) => yul.object.clone(),
+        _ => panic!("The Yul IR is expected"),
+    };
+    let ids = RevertStringIds::substitute(&mut object).expect("Test failure");
+    if !solc_optimizer {
+        assert_eq!(
+            ids.clone().into_iter().collect::<Vec<(String, String)>>(),
+            vec![(id.clone(), MESSAGE.to_owned())]
+        );
+    }
+    for (substituted_id, message) in ids.iter() {
+        assert_eq!(substituted_id, &id);
+        assert_eq!(message, MESSAGE);
+    }
+
+    let encode_prefix = format!("abi_encode_t_stringliteral_{hash}");
+    let runtime = object.inner_object.as_ref().expect("Always exists");
+    for statement in runtime.code.block.statements.iter() {
+        let function = match statement {
+            Statement::FunctionDefinition(function)
+                if function.identifier.starts_with(encode_prefix.as_str()) =>
+            {
+                function
+            }
+            _ => continue,
+        };
+        let encoded: Vec<String> = function
+            .body
+            .statements
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::Assignment(assignment) => match assignment.initializer {
+                    Expression::FunctionCall(ref call) => match call.arguments.get(1) {
+                        Some(Expression::Literal(literal)) => match literal.inner {
+                            LexicalLiteral::Integer(IntegerLiteral::Decimal { ref inner }) => {
+                                Some(inner.to_owned())
+                            }
+                            _ => None,
+                        },
+                        _ => None,
+                    },
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            encoded,
+            vec![
+                RevertStringIds::ID_LENGTH.to_string(),
+                compiler_common::BYTE_LENGTH_FIELD.to_string(),
+            ],
+            "The encoded length must be that of the identifier, and the size must be one word"
+        );
+    }
+
+    project.revert_string_ids = true;
+    let build = project
+        .compile(
+            compiler_llvm_context::OptimizerSettings::none(),
+            Target::EraVM,
+            false,
+            false,
+            false,
+            zkevm_assembly::RunningVmEncodingMode::Production,
+            None,
+            None,
+        )
+        .expect("Test failure");
+    let contract = build.contracts.get("test.sol:Test").expect("Always exists");
+    assert!(!contract.build.bytecode.is_empty());
+    assert_eq!(contract.revert_string_ids.as_ref(), Some(&ids));
+}
+
+#[test]
+fn solc_optimizer_disabled() {
+    check(false);
+}
+
+#[test]
+fn solc_optimizer_enabled() {
+    check(true);
+}
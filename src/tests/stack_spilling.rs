@@ -0,0 +1,63 @@
+//!
+//! The Solidity compiler unit tests for the EVM legacy assembly stack spilling.
+//!
+
+#![cfg(test)]
+
+use std::collections::BTreeMap;
+
+use crate::solc::pipeline::Pipeline as SolcPipeline;
+
+const SOURCE_CODE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Test {
+    function main(uint256 a, uint256 b, uint256 c, uint256 d, uint256 e, uint256 f) public pure returns(uint256) {
+        uint256 g = a + b;
+        uint256 h = c * d;
+        uint256 i = e - f;
+        uint256 j = g ^ h;
+        uint256 k = h | i;
+        return g + h + i + j + k;
+    }
+}
+"#;
+
+#[test]
+fn spilled() {
+    let reference =
+        super::build_solidity_with(SOURCE_CODE, BTreeMap::new(), SolcPipeline::EVMLA, |_| {})
+            .expect("Test failure");
+    let build = super::build_solidity_with(
+        SOURCE_CODE,
+        BTreeMap::new(),
+        SolcPipeline::EVMLA,
+        |project| project.evmla_stack_spill_threshold = Some(2),
+    )
+    .expect("Test failure");
+    let contract = build.contracts.get("test.sol:Test").expect("Always exists");
+    assert!(!contract.build.bytecode.is_empty());
+    assert_ne!(
+        reference.contracts["test.sol:Test"].build.bytecode, contract.build.bytecode,
+        "The spilled slots must be kept in memory"
+    );
+}
+
+#[test]
+fn threshold_above_stack_size() {
+    let reference =
+        super::build_solidity_with(SOURCE_CODE, BTreeMap::new(), SolcPipeline::EVMLA, |_| {})
+            .expect("Test failure");
+    let build = super::build_solidity_with(
+        SOURCE_CODE,
+        BTreeMap::new(),
+        SolcPipeline::EVMLA,
+        |project| project.evmla_stack_spill_threshold = Some(1024),
+    )
+    .expect("Test failure");
+    assert_eq!(
+        reference.contracts["test.sol:Test"].build.bytecode,
+        build.contracts["test.sol:Test"].build.bytecode,
+    );
+}
@@ -164,3 +164,18 @@ fn selfdestruct_yul() {
     super::build_solidity(SELFDESTRUCT_TEST_SOURCE, BTreeMap::new(), SolcPipeline::Yul)
         .expect("Test failure");
 }
+
+#[test]
+#[should_panic(expected = "The `TSTORE` instruction is not supported")]
+fn tstore_yul() {
+    let source_code = r#"
+object "TransientStorage" {
+    code {
+        tstore(0, 1)
+        return(0, 0)
+    }
+}
+    "#;
+
+    super::build_yul(source_code).expect("Test failure");
+}
@@ -0,0 +1,103 @@
+//!
+//! The Solidity compiler unit tests for the verification bundles.
+//!
+
+#![cfg(test)]
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::build::verify_bundle::VerifyBundle;
+use crate::debug::filter::Filter as DebugFilter;
+use crate::solc::pipeline::Pipeline as SolcPipeline;
+use crate::solc::Compiler as SolcCompiler;
+
+#[test]
+fn write_and_replay() {
+    compiler_llvm_context::initialize_target();
+    let _ = crate::process::EXECUTABLE.set(PathBuf::from(crate::r#const::DEFAULT_EXECUTABLE_NAME));
+
+    let input = serde_json::json!({
+        "language": "Solidity",
+        "sources": {
+            "contracts/Test.sol": {
+                "content": "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\n\ninterface ITest {}\n\ncontract Test is ITest {\n    function get() public pure returns (uint256) {\n        return 42;\n    }\n}\n",
+            },
+        },
+        "settings": {
+            "optimizer": { "enabled": true, "mode": "3" },
+            "outputSelection": { "*": { "*": ["abi"] } },
+        },
+    });
+
+    let mut solc = SolcCompiler::new("solc".to_owned());
+    let solc_version = solc.version().expect("Test failure").default;
+    let output = crate::standard_json_output(
+        serde_json::to_vec(&input).expect("Always valid").as_slice(),
+        &mut solc,
+        false,
+        false,
+        false,
+        false,
+        None,
+        None,
+        vec![],
+        None,
+        true,
+        None,
+        DebugFilter::default(),
+    )
+    .expect("Test failure");
+    let output = serde_json::to_value(&output).expect("Always valid");
+
+    let bundles =
+        VerifyBundle::new_all(&input, &output, &solc_version, false, false).expect("Test failure");
+    assert_eq!(
+        bundles
+            .iter()
+            .map(|bundle| bundle.contract.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["contracts/Test.sol:Test"]
+    );
+
+    let directory = std::env::temp_dir().join(format!(
+        "zksolc-verify-bundle-{}-{}",
+        std::process::id(),
+        rand::random::<u64>()
+    ));
+    let path = bundles[0].write(directory.as_path()).expect("Test failure");
+    let bundle = VerifyBundle::try_from_path(path.as_path());
+    std::fs::remove_dir_all(directory.as_path()).expect("Always valid");
+
+    let mut bundle = bundle.expect("Test failure");
+    let replay = bundle.replay(&mut solc).expect("Test failure");
+    assert!(replay.matches);
+    assert_eq!(replay.expected_bytecode_hash, bundles[0].bytecode_hash);
+
+    bundle.bytecode_hash = format!("0x{}", "00".repeat(32));
+    let replay = bundle.replay(&mut solc).expect("Test failure");
+    assert!(!replay.matches);
+}
+
+#[test]
+fn from_build() {
+    let source_code = "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\n\ncontract Test {\n    function get() public pure returns (uint256) {\n        return 42;\n    }\n}\n";
+    let build =
+        super::build_solidity_with(source_code, BTreeMap::new(), SolcPipeline::EVMLA, |_| {})
+            .expect("Test failure");
+
+    let mut solc = SolcCompiler::new("solc".to_owned());
+    let solc_version = solc.version().expect("Test failure").default;
+    let sources = BTreeMap::from([("test.sol".to_owned(), source_code.to_owned())]);
+    let settings = serde_json::json!({
+        "optimizer": { "enabled": true, "mode": "0" },
+        "metadata": { "bytecodeHash": "none" },
+    });
+    let bundles =
+        VerifyBundle::new_all_from_build(&build, &sources, settings, &solc_version, true, false);
+    assert_eq!(bundles.len(), 1);
+    assert_eq!(bundles[0].contract, "test.sol:Test");
+
+    let replay = bundles[0].replay(&mut solc).expect("Test failure");
+    assert!(replay.matches);
+}
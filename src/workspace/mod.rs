@@ -0,0 +1,221 @@
+//!
+//! The multi-project workspace.
+//!
+
+pub mod project;
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::debug::filter::Filter as DebugFilter;
+use crate::format_version::FormatVersion;
+use crate::solc::Compiler as SolcCompiler;
+
+use self::project::Project;
+
+///
+/// The multi-project workspace.
+///
+/// Compiles several independent standard JSON projects in one invocation. The `solc`
+/// executables are shared by the projects using them, so their versions are queried once,
+/// and the contracts of all projects are compiled by the same worker process pool.
+///
+/// Example:
+/// ```json
+/// {
+///   "projects": [
+///     { "name": "core", "input": "core/input.json", "outputDirectory": "core/out", "solc": "solc-0.8.20" },
+///     { "name": "legacy", "input": "legacy/input.json", "outputDirectory": "legacy/out", "solc": "solc-0.7.6" }
+///   ]
+/// }
+/// ```
+///
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Workspace {
+    /// The projects compiled in the order of declaration.
+    pub projects: Vec<Project>,
+    /// The workspace config directory.
+    #[serde(skip)]
+    pub directory: PathBuf,
+}
+
+impl Workspace {
+    /// The standard JSON output file name written to the project output directory.
+    pub const OUTPUT_FILE_NAME: &'static str = "output.json";
+
+    ///
+    /// A shortcut constructor from the config file.
+    ///
+    pub fn try_from_path(path: &Path) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(path).map_err(|error| {
+            anyhow::anyhow!("Workspace config {:?} opening error: {}", path, error)
+        })?;
+        let mut workspace: Self =
+            serde_json::from_reader(std::io::BufReader::new(file)).map_err(|error| {
+                anyhow::anyhow!("Workspace config {:?} parsing error: {}", path, error)
+            })?;
+        workspace.directory = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        let mut names = std::collections::BTreeSet::new();
+        for project in workspace.projects.iter() {
+            if !names.insert(project.name.as_str()) {
+                anyhow::bail!("Workspace project `{}` is declared twice", project.name);
+            }
+        }
+
+        Ok(workspace)
+    }
+
+    ///
+    /// Compiles all projects, writing their standard JSON outputs.
+    ///
+    /// A failing project does not prevent the others from being compiled, and all failures
+    /// are reported at the end.
+    ///
+    pub fn compile(
+        self,
+        allow_network: bool,
+        ipfs_gateway: Option<String>,
+        format_version: FormatVersion,
+    ) -> anyhow::Result<()> {
+        let mut solc_compilers: BTreeMap<String, SolcCompiler> = BTreeMap::new();
+        let mut failures = Vec::new();
+
+        for project in self.projects.iter() {
+            let executable = project.solc_executable(self.directory.as_path());
+            let solc = solc_compilers
+                .entry(executable.clone())
+                .or_insert_with(|| SolcCompiler::new(executable));
+
+            match self.compile_project(
+                project,
+                solc,
+                allow_network,
+                ipfs_gateway.clone(),
+                format_version,
+            ) {
+                Ok(errors) if errors == 0 => {
+                    eprintln!("Project `{}` compiled successfully.", project.name);
+                }
+                Ok(errors) => {
+                    eprintln!(
+                        "Project `{}` compiled with {} error(s).",
+                        project.name, errors
+                    );
+                    failures.push(project.name.as_str());
+                }
+                Err(error) => {
+                    eprintln!("Project `{}` compiling error: {}", project.name, error);
+                    failures.push(project.name.as_str());
+                }
+            }
+        }
+
+        if !failures.is_empty() {
+            anyhow::bail!("Workspace project(s) failed: {}", failures.join(", "));
+        }
+        Ok(())
+    }
+
+    ///
+    /// Compiles a project, returning the number of errors in its standard JSON output.
+    ///
+    fn compile_project(
+        &self,
+        project: &Project,
+        solc: &mut SolcCompiler,
+        allow_network: bool,
+        ipfs_gateway: Option<String>,
+        format_version: FormatVersion,
+    ) -> anyhow::Result<usize> {
+        let input_path = self.directory.join(project.input.as_path());
+        let input = std::fs::File::open(input_path.as_path()).map_err(|error| {
+            anyhow::anyhow!("Input file {:?} opening error: {}", input_path, error)
+        })?;
+
+        let solc_output = crate::standard_json_output(
+            input,
+            solc,
+            project.force_evmla,
+            project.system_mode,
+            false,
+            allow_network,
+            ipfs_gateway,
+            project.base_path(self.directory.as_path()),
+            project.include_paths(self.directory.as_path()),
+            project.allow_paths(self.directory.as_path()),
+            false,
+            None,
+            DebugFilter::default(),
+        )?;
+
+        let output_directory = self.directory.join(project.output_directory.as_path());
+        std::fs::create_dir_all(output_directory.as_path()).map_err(|error| {
+            anyhow::anyhow!(
+                "Output directory {:?} creating error: {}",
+                output_directory,
+                error
+            )
+        })?;
+        let output_path = output_directory.join(Self::OUTPUT_FILE_NAME);
+        let json =
+            serde_json::to_vec(&format_version.to_value(&solc_output)).expect("Always valid");
+        std::fs::write(output_path.as_path(), json).map_err(|error| {
+            anyhow::anyhow!("Output file {:?} writing error: {}", output_path, error)
+        })?;
+
+        let errors = solc_output
+            .errors
+            .iter()
+            .flatten()
+            .filter(|error| error.severity.as_str() == "error")
+            .count();
+        Ok(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Workspace;
+
+    #[test]
+    fn config_directory() {
+        let directory = std::env::temp_dir().join(format!(
+            "zksolc-workspace-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        std::fs::create_dir_all(directory.as_path()).expect("Always valid");
+        let config = directory.join("workspace.json");
+        std::fs::write(
+            config.as_path(),
+            r#"{ "projects": [ { "name": "core", "input": "core.json", "outputDirectory": "out", "solc": "bin/solc", "allowPaths": "vendor" } ] }"#,
+        )
+        .expect("Always valid");
+
+        let workspace = Workspace::try_from_path(config.as_path()).expect("Always valid");
+        let project = &workspace.projects[0];
+        assert_eq!(workspace.directory, directory);
+        assert_eq!(
+            project.solc_executable(workspace.directory.as_path()),
+            directory.join("bin/solc").to_string_lossy()
+        );
+        assert_eq!(
+            project.allow_paths(workspace.directory.as_path()),
+            Some(directory.join("vendor").to_string_lossy().to_string())
+        );
+
+        std::fs::write(
+            config.as_path(),
+            r#"{ "projects": [ { "name": "core", "input": "a.json", "outputDirectory": "a" }, { "name": "core", "input": "b.json", "outputDirectory": "b" } ] }"#,
+        )
+        .expect("Always valid");
+        assert!(Workspace::try_from_path(config.as_path()).is_err());
+
+        std::fs::remove_dir_all(directory).expect("Always valid");
+    }
+}
@@ -0,0 +1,168 @@
+//!
+//! The workspace project.
+//!
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::solc::Compiler as SolcCompiler;
+
+///
+/// The workspace project.
+///
+/// The relative paths are resolved against the workspace config directory.
+///
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Project {
+    /// The project name, used in the diagnostics.
+    pub name: String,
+    /// The standard JSON input file, which specifies the sources and settings.
+    pub input: PathBuf,
+    /// The directory where the standard JSON output is written.
+    pub output_directory: PathBuf,
+    /// The `solc` executable. A bare name is looked up in `${PATH}`, which is also where the
+    /// default one is found.
+    #[serde(default)]
+    pub solc: Option<String>,
+    /// Whether to force the EVM legacy assembly pipeline.
+    #[serde(default, rename = "forceEVMLA")]
+    pub force_evmla: bool,
+    /// Whether to enable the system contract compilation mode.
+    #[serde(default)]
+    pub system_mode: bool,
+    /// The `solc` base path.
+    #[serde(default)]
+    pub base_path: Option<String>,
+    /// The `solc` include paths.
+    #[serde(default)]
+    pub include_paths: Vec<String>,
+    /// The `solc` allowed paths, separated by commas.
+    #[serde(default)]
+    pub allow_paths: Option<String>,
+}
+
+impl Project {
+    ///
+    /// Returns the `solc` executable, resolving it against the workspace config `directory` if
+    /// it is a path.
+    ///
+    pub fn solc_executable(&self, directory: &Path) -> String {
+        match self.solc.as_deref() {
+            Some(solc) if Path::new(solc).components().count() > 1 => {
+                Self::resolve(directory, solc)
+            }
+            Some(solc) => solc.to_owned(),
+            None => SolcCompiler::DEFAULT_EXECUTABLE_NAME.to_owned(),
+        }
+    }
+
+    ///
+    /// Returns the `solc` base path resolved against the workspace config `directory`.
+    ///
+    pub fn base_path(&self, directory: &Path) -> Option<String> {
+        self.base_path
+            .as_deref()
+            .map(|path| Self::resolve(directory, path))
+    }
+
+    ///
+    /// Returns the `solc` include paths resolved against the workspace config `directory`.
+    ///
+    pub fn include_paths(&self, directory: &Path) -> Vec<String> {
+        self.include_paths
+            .iter()
+            .map(|path| Self::resolve(directory, path.as_str()))
+            .collect()
+    }
+
+    ///
+    /// Returns the `solc` allowed paths resolved against the workspace config `directory`.
+    ///
+    pub fn allow_paths(&self, directory: &Path) -> Option<String> {
+        self.allow_paths.as_deref().map(|paths| {
+            paths
+                .split(',')
+                .filter(|path| !path.is_empty())
+                .map(|path| Self::resolve(directory, path))
+                .collect::<Vec<String>>()
+                .join(",")
+        })
+    }
+
+    ///
+    /// Resolves the `path` against the workspace config `directory`.
+    ///
+    /// The absolute paths are returned as is.
+    ///
+    fn resolve(directory: &Path, path: &str) -> String {
+        directory.join(path).to_string_lossy().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use super::Project;
+
+    fn project(solc: Option<&str>, allow_paths: Option<&str>) -> Project {
+        Project {
+            name: "test".to_owned(),
+            input: PathBuf::from("input.json"),
+            output_directory: PathBuf::from("out"),
+            solc: solc.map(str::to_owned),
+            force_evmla: false,
+            system_mode: false,
+            base_path: Some("contracts".to_owned()),
+            include_paths: vec!["lib".to_owned()],
+            allow_paths: allow_paths.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn solc_executable() {
+        let directory = Path::new("workspace");
+
+        assert_eq!(
+            project(Some("bin/solc"), None).solc_executable(directory),
+            Path::new("workspace")
+                .join("bin/solc")
+                .to_string_lossy()
+                .to_string()
+        );
+        assert_eq!(
+            project(Some("solc-0.8.20"), None).solc_executable(directory),
+            "solc-0.8.20"
+        );
+        assert_eq!(project(None, None).solc_executable(directory), "solc");
+    }
+
+    #[test]
+    fn paths() {
+        let directory = Path::new("workspace");
+        let absolute = std::env::temp_dir().join("shared");
+        let allow_paths = format!("vendor,{}", absolute.to_string_lossy());
+        let project = project(None, Some(allow_paths.as_str()));
+
+        assert_eq!(
+            project.allow_paths(directory),
+            Some(format!(
+                "{},{}",
+                directory.join("vendor").to_string_lossy(),
+                absolute.to_string_lossy()
+            ))
+        );
+        assert_eq!(
+            project.base_path(directory),
+            Some(directory.join("contracts").to_string_lossy().to_string())
+        );
+        assert_eq!(
+            project.include_paths(directory),
+            vec![directory.join("lib").to_string_lossy().to_string()]
+        );
+    }
+}
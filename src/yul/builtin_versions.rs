@@ -0,0 +1,172 @@
+//!
+//! The Yul builtin `solc` version check.
+//!
+
+use crate::solc::Compiler as SolcCompiler;
+use crate::yul::lexer::token::location::Location;
+use crate::yul::parser::statement::expression::function_call::name::Name;
+use crate::yul::parser::statement::expression::function_call::FunctionCall;
+use crate::yul::parser::statement::object::Object;
+use crate::yul::visitor::Visitor;
+
+///
+/// The Yul builtin `solc` version check.
+///
+/// Rejects the builtins that the `solc` of the project version does not know, like the EVM
+/// legacy assembly translator does for the instructions. Such a builtin indicates a corrupted
+/// or mismatched input, since `solc` could not have emitted or validated it.
+///
+#[derive(Debug)]
+pub struct BuiltinVersions {
+    /// The `solc` version.
+    version: semver::Version,
+    /// The builtins unknown to the `solc` version, with their locations.
+    unknown: Vec<(Location, &'static str, semver::Version)>,
+}
+
+impl BuiltinVersions {
+    ///
+    /// Checks the builtins of the `object` against the `solc` `version`.
+    ///
+    /// All the unknown builtins are reported at once.
+    ///
+    pub fn check(object: &Object, version: &semver::Version) -> anyhow::Result<()> {
+        let mut check = Self {
+            version: version.to_owned(),
+            unknown: Vec::new(),
+        };
+        check.visit_object(object);
+
+        if !check.unknown.is_empty() {
+            anyhow::bail!(
+                "{}",
+                check
+                    .unknown
+                    .into_iter()
+                    .map(|(location, name, first_version)| format!(
+                        "{location} The `{name}` builtin is not available in `solc` before v{first_version}, found v{version}"
+                    ))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            );
+        }
+        Ok(())
+    }
+
+    ///
+    /// Returns the builtin name and the first `solc` version it is available in, if the builtin
+    /// is version-dependent.
+    ///
+    fn first_version(name: &Name) -> Option<(&'static str, semver::Version)> {
+        match name {
+            Name::BlobHash => Some(("blobhash", SolcCompiler::FIRST_BLOBHASH_VERSION)),
+            Name::MCopy => Some(("mcopy", SolcCompiler::FIRST_MCOPY_VERSION)),
+            Name::TLoad => Some(("tload", SolcCompiler::FIRST_TRANSIENT_STORAGE_VERSION)),
+            Name::TStore => Some(("tstore", SolcCompiler::FIRST_TRANSIENT_STORAGE_VERSION)),
+            _ => None,
+        }
+    }
+}
+
+impl Visitor for BuiltinVersions {
+    fn visit_function_call(&mut self, call: &FunctionCall) {
+        if let Some((name, first_version)) = Self::first_version(&call.name) {
+            if self.version < first_version {
+                self.unknown.push((call.location, name, first_version));
+            }
+        }
+        crate::yul::visitor::walk_function_call(self, call);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solc::Compiler as SolcCompiler;
+    use crate::yul::lexer::Lexer;
+    use crate::yul::parser::statement::object::Object;
+
+    use super::BuiltinVersions;
+
+    const SOURCE_CODE: &str = r#"
+object "Test" {
+    code {
+        {
+            return(0, 0)
+        }
+    }
+    object "Test_deployed" {
+        code {
+            {
+                mcopy(0, 32, 64)
+                return(0, 64)
+            }
+        }
+    }
+}
+    "#;
+
+    #[test]
+    fn unknown() {
+        let mut lexer = Lexer::new(SOURCE_CODE);
+        let object = Object::parse(&mut lexer, None).expect("Always valid");
+
+        let error = BuiltinVersions::check(&object, &SolcCompiler::LAST_SUPPORTED_VERSION)
+            .expect_err("Always fails")
+            .to_string();
+        assert!(error.contains("The `mcopy` builtin is not available in `solc` before v0.8.24"));
+    }
+
+    #[test]
+    fn known() {
+        let mut lexer = Lexer::new(SOURCE_CODE);
+        let object = Object::parse(&mut lexer, None).expect("Always valid");
+
+        assert!(BuiltinVersions::check(&object, &SolcCompiler::FIRST_MCOPY_VERSION).is_ok());
+    }
+
+    #[test]
+    fn blobhash() {
+        let input = r#"
+object "Test" {
+    code {
+        {
+            mstore(0, blobhash(0))
+            return(0, 32)
+        }
+    }
+}
+    "#;
+
+        let mut lexer = Lexer::new(input);
+        let object = Object::parse(&mut lexer, None).expect("Always valid");
+
+        let error = BuiltinVersions::check(&object, &SolcCompiler::LAST_SUPPORTED_VERSION)
+            .expect_err("Always fails")
+            .to_string();
+        assert!(error.contains("The `blobhash` builtin is not available in `solc` before v0.8.24"));
+        assert!(BuiltinVersions::check(&object, &SolcCompiler::FIRST_BLOBHASH_VERSION).is_ok());
+    }
+
+    #[test]
+    fn transient_storage() {
+        let input = r#"
+object "Test" {
+    code {
+        {
+            tstore(0, tload(1))
+            return(0, 0)
+        }
+    }
+}
+    "#;
+
+        let mut lexer = Lexer::new(input);
+        let object = Object::parse(&mut lexer, None).expect("Always valid");
+
+        let error = BuiltinVersions::check(&object, &SolcCompiler::LAST_SUPPORTED_VERSION)
+            .expect_err("Always fails")
+            .to_string();
+        assert!(error.contains("The `tstore` builtin is not available in `solc` before v0.8.24"));
+        assert!(error.contains("The `tload` builtin is not available in `solc` before v0.8.24"));
+    }
+}
@@ -0,0 +1,213 @@
+//!
+//! The Yul object data references check.
+//!
+
+use std::collections::BTreeMap;
+
+use crate::yul::lexer::token::lexeme::literal::Literal as LexicalLiteral;
+use crate::yul::lexer::token::location::Location;
+use crate::yul::parser::statement::expression::function_call::name::Name;
+use crate::yul::parser::statement::expression::function_call::FunctionCall;
+use crate::yul::parser::statement::expression::Expression;
+use crate::yul::parser::statement::object::Object;
+use crate::yul::visitor::Visitor;
+
+///
+/// The Yul object data references check.
+///
+/// On EraVM, contracts are not deployed by copying their bytecode into memory. `dataoffset` and
+/// `datasize` are translated to the bytecode hash of the referenced contract, which must be known
+/// to the compiler and published as a factory dependency of the deploying contract.
+///
+/// Thus, an object may reference itself, its runtime code, its nested objects, and the contracts
+/// compiled in the same project. The other references are reported with an explanation, instead
+/// of a generic lookup failure in the middle of the code generation.
+///
+#[derive(Debug)]
+pub struct DataReferences;
+
+///
+/// The `dataoffset` or `datasize` reference.
+///
+#[derive(Debug)]
+struct Reference {
+    /// The location.
+    location: Location,
+    /// The builtin name.
+    builtin: &'static str,
+    /// The referenced object identifier.
+    identifier: String,
+}
+
+impl DataReferences {
+    /// The runtime code object identifier suffix used by `solc`.
+    const RUNTIME_SUFFIX: &'static str = "_deployed";
+
+    ///
+    /// Checks the data references of the deploy and runtime code of the `object`.
+    ///
+    /// `project_identifiers` are the identifiers of the contracts compiled in the same project,
+    /// which are resolved as factory dependencies even if they are not nested into the `object`.
+    ///
+    pub fn check(
+        object: &Object,
+        project_identifiers: &BTreeMap<String, String>,
+    ) -> anyhow::Result<()> {
+        let mut collector = ReferenceCollector::default();
+        collector.visit_block(&object.code.block);
+        if let Some(runtime) = object.inner_object.as_ref() {
+            collector.visit_block(&runtime.code.block);
+        }
+
+        let mut errors = Vec::new();
+        for reference in collector.references.into_iter() {
+            let identifier = reference.identifier.as_str();
+            if identifier == object.identifier
+                || object
+                    .inner_object
+                    .as_ref()
+                    .map(|runtime| runtime.identifier == identifier)
+                    .unwrap_or_default()
+                || object.factory_dependencies.contains(identifier)
+                || project_identifiers.contains_key(identifier)
+            {
+                continue;
+            }
+
+            let message = match identifier.strip_suffix(Self::RUNTIME_SUFFIX) {
+                Some(contract) => format!(
+                    "{} `{}(\"{}\")` references the runtime code of the foreign object `{}`. \
+                     EraVM deploys contracts as a whole by their bytecode hashes, so the \
+                     runtime code cannot be addressed separately. Reference `{}` instead.",
+                    reference.location, reference.builtin, identifier, contract, contract,
+                ),
+                None => format!(
+                    "{} `{}(\"{}\")` references the object `{}`, which is neither the object \
+                     `{}`, its runtime code, nor one of its nested objects, and is not compiled \
+                     in the same project. EraVM deploys contracts by their bytecode hashes, so \
+                     every deployed contract must be a factory dependency of the deploying one: \
+                     nest the `{}` object into `{}`, or compile it in the same project.",
+                    reference.location,
+                    reference.builtin,
+                    identifier,
+                    identifier,
+                    object.identifier,
+                    identifier,
+                    object.identifier,
+                ),
+            };
+            errors.push(message);
+        }
+
+        if !errors.is_empty() {
+            anyhow::bail!("{}", errors.join("\n"));
+        }
+        Ok(())
+    }
+}
+
+///
+/// Collects the `dataoffset` and `datasize` references.
+///
+#[derive(Debug, Default)]
+struct ReferenceCollector {
+    /// The collected references.
+    references: Vec<Reference>,
+}
+
+impl Visitor for ReferenceCollector {
+    fn visit_function_call(&mut self, call: &FunctionCall) {
+        let builtin = match call.name {
+            Name::DataOffset => Some("dataoffset"),
+            Name::DataSize => Some("datasize"),
+            _ => None,
+        };
+        if let (Some(builtin), Some(Expression::Literal(literal))) =
+            (builtin, call.arguments.first())
+        {
+            if let LexicalLiteral::String(ref string) = literal.inner {
+                self.references.push(Reference {
+                    location: call.location,
+                    builtin,
+                    identifier: string.inner.to_owned(),
+                });
+            }
+        }
+        crate::yul::visitor::walk_function_call(self, call);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::DataReferences;
+    use crate::yul::lexer::Lexer;
+    use crate::yul::parser::statement::object::Object;
+
+    fn object(reference: &str) -> Object {
+        let input = format!(
+            r#"
+object "Test" {{
+    code {{
+        {{
+            let size := datasize("{reference}")
+            codecopy(0, dataoffset("Test_deployed"), datasize("Test_deployed"))
+            return(0, size)
+        }}
+    }}
+    object "Test_deployed" {{
+        code {{
+            {{
+                let offset := dataoffset("Dependency")
+                return(0, 0)
+            }}
+        }}
+        object "Dependency" {{
+            code {{
+                {{
+                    return(0, 0)
+                }}
+            }}
+        }}
+    }}
+}}
+    "#
+        );
+        let mut lexer = Lexer::new(input);
+        Object::parse(&mut lexer, None).expect("Always valid")
+    }
+
+    #[test]
+    fn nested() {
+        let object = object("Dependency");
+        assert!(DataReferences::check(&object, &BTreeMap::new()).is_ok());
+    }
+
+    #[test]
+    fn project() {
+        let object = object("Foreign");
+        let mut project_identifiers = BTreeMap::new();
+        project_identifiers.insert("Foreign".to_owned(), "Foreign.sol:Foreign".to_owned());
+        assert!(DataReferences::check(&object, &project_identifiers).is_ok());
+    }
+
+    #[test]
+    fn foreign() {
+        let object = object("Foreign");
+        let error = DataReferences::check(&object, &BTreeMap::new())
+            .expect_err("Always fails")
+            .to_string();
+        assert!(error.contains("`datasize(\"Foreign\")`"));
+        assert!(error.contains("factory dependency"));
+    }
+
+    #[test]
+    fn foreign_runtime() {
+        let object = object("Foreign_deployed");
+        let error = DataReferences::check(&object, &BTreeMap::new())
+            .expect_err("Always fails")
+            .to_string();
+        assert!(error.contains("Reference `Foreign` instead"));
+    }
+}
@@ -0,0 +1,380 @@
+//!
+//! The Yul deploy and runtime entry objects selection.
+//!
+
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+
+use crate::yul::lexer::token::lexeme::keyword::Keyword;
+use crate::yul::lexer::token::lexeme::literal::Literal as LexicalLiteral;
+use crate::yul::lexer::token::lexeme::symbol::Symbol;
+use crate::yul::lexer::token::lexeme::Lexeme;
+use crate::yul::lexer::token::location::Location;
+use crate::yul::lexer::token::Token;
+use crate::yul::lexer::Lexer;
+use crate::yul::object_paths::ObjectPaths;
+use crate::yul::parser::statement::code::Code;
+use crate::yul::parser::statement::expression::function_call::name::Name;
+use crate::yul::parser::statement::expression::function_call::FunctionCall;
+use crate::yul::parser::statement::expression::Expression;
+use crate::yul::parser::statement::object::Object;
+use crate::yul::visitor::VisitorMut;
+
+///
+/// The Yul deploy and runtime entry objects selection.
+///
+/// By default, the upper-level object is the deploy code, and its nested `<name>_deployed`
+/// object is the runtime code, as generated by `solc`. Hand-written Yul projects may select
+/// any object as the deploy code, and any of its nested objects as the runtime code. The
+/// selected objects are renamed to follow the `solc` convention, including their references
+/// in `dataoffset` and `datasize`, and the other nested objects are treated as factory
+/// dependencies.
+///
+#[derive(Debug, Default, Clone)]
+pub struct EntryObjects {
+    /// The deploy code object identifier.
+    pub deploy: Option<String>,
+    /// The runtime code object identifier.
+    pub runtime: Option<String>,
+}
+
+///
+/// The Yul object parsed without assuming the `solc` naming convention.
+///
+#[derive(Debug)]
+struct RawObject {
+    /// The location.
+    location: Location,
+    /// The identifier.
+    identifier: String,
+    /// The code.
+    code: Code,
+    /// The nested objects.
+    objects: Vec<RawObject>,
+}
+
+impl EntryObjects {
+    /// The runtime code object identifier suffix used by `solc`.
+    pub const RUNTIME_SUFFIX: &'static str = "_deployed";
+
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(deploy: Option<String>, runtime: Option<String>) -> Self {
+        Self { deploy, runtime }
+    }
+
+    ///
+    /// Whether the `solc` naming convention is used.
+    ///
+    pub fn is_default(&self) -> bool {
+        self.deploy.is_none() && self.runtime.is_none()
+    }
+
+    ///
+    /// Parses the Yul source code, selecting the deploy and runtime objects.
+    ///
+    pub fn parse(&self, source_code: &str) -> anyhow::Result<Object> {
+        let mut lexer = Lexer::new(source_code.to_owned());
+        if self.is_default() {
+            return Object::parse(&mut lexer, None).map_err(|error| anyhow::anyhow!("{}", error));
+        }
+
+        let object = RawObject::parse(&mut lexer)?;
+        let deploy = match self.deploy.as_deref() {
+            Some(identifier) => object.find(identifier).ok_or_else(|| {
+                anyhow::anyhow!("The deploy code object `{}` is not found", identifier)
+            })?,
+            None => object,
+        };
+        let deploy_identifier = deploy.identifier.clone();
+        if deploy_identifier.ends_with(Self::RUNTIME_SUFFIX) {
+            anyhow::bail!(
+                "The deploy code object `{}` must not have the `{}` suffix",
+                deploy_identifier,
+                Self::RUNTIME_SUFFIX
+            );
+        }
+
+        let runtime_identifier = self
+            .runtime
+            .clone()
+            .unwrap_or_else(|| format!("{deploy_identifier}{}", Self::RUNTIME_SUFFIX));
+        let runtime_target = format!("{deploy_identifier}{}", Self::RUNTIME_SUFFIX);
+
+        let RawObject {
+            location,
+            identifier,
+            mut code,
+            objects,
+        } = deploy;
+        let mut runtime = None;
+        let mut factory_dependencies = HashSet::new();
+        for object in objects.into_iter() {
+            if object.identifier == runtime_identifier && runtime.is_none() {
+                runtime = Some(object);
+            } else if object.identifier == runtime_target {
+                anyhow::bail!(
+                    "The object `{}` conflicts with the selected runtime code object `{}`",
+                    object.identifier,
+                    runtime_identifier
+                );
+            } else {
+                factory_dependencies.insert(object.identifier);
+            }
+        }
+        let mut runtime = runtime.ok_or_else(|| {
+            anyhow::anyhow!(
+                "The runtime code object `{}` is not found in the deploy code object `{}`",
+                runtime_identifier,
+                identifier
+            )
+        })?;
+
+        let mut renaming = ReferenceRenaming {
+            from: runtime_identifier.as_str(),
+            to: runtime_target.as_str(),
+        };
+        renaming.visit_block(&mut code.block);
+        renaming.visit_block(&mut runtime.code.block);
+        factory_dependencies.extend(runtime.objects.into_iter().map(|object| object.identifier));
+
+        Ok(Object {
+            location,
+            identifier,
+            code,
+            inner_object: Some(Box::new(Object {
+                location: runtime.location,
+                identifier: runtime_target,
+                code: runtime.code,
+                inner_object: None,
+                factory_dependencies: HashSet::new(),
+            })),
+            factory_dependencies,
+        })
+    }
+}
+
+///
+/// Renames the `from` object references in `dataoffset` and `datasize` to `to`.
+///
+struct ReferenceRenaming<'a> {
+    /// The original object identifier.
+    from: &'a str,
+    /// The new object identifier.
+    to: &'a str,
+}
+
+impl VisitorMut for ReferenceRenaming<'_> {
+    fn visit_function_call(&mut self, call: &mut FunctionCall) {
+        if matches!(call.name, Name::DataOffset | Name::DataSize) {
+            if let Some(Expression::Literal(literal)) = call.arguments.first_mut() {
+                if let LexicalLiteral::String(ref mut string) = literal.inner {
+                    if string.inner == self.from {
+                        string.inner = self.to.to_owned();
+                    }
+                }
+            }
+        }
+        crate::yul::visitor::walk_function_call_mut(self, call);
+    }
+}
+
+impl RawObject {
+    ///
+    /// Parses the object with all its nested objects.
+    ///
+    pub fn parse(lexer: &mut Lexer) -> anyhow::Result<Self> {
+        let location = match lexer.next().map_err(|error| anyhow::anyhow!("{}", error))? {
+            Token {
+                lexeme: Lexeme::Keyword(Keyword::Object),
+                location,
+                ..
+            } => location,
+            token => anyhow::bail!(
+                "{} Expected `object`, found `{}`",
+                token.location,
+                token.lexeme
+            ),
+        };
+        let identifier = match lexer.next().map_err(|error| anyhow::anyhow!("{}", error))? {
+            Token {
+                lexeme: Lexeme::Literal(LexicalLiteral::String(literal)),
+                ..
+            } => literal.inner,
+            token => anyhow::bail!(
+                "{} Expected the object name, found `{}`",
+                token.location,
+                token.lexeme
+            ),
+        };
+        match lexer.next().map_err(|error| anyhow::anyhow!("{}", error))? {
+            Token {
+                lexeme: Lexeme::Symbol(Symbol::BracketCurlyLeft),
+                ..
+            } => {}
+            token => anyhow::bail!("{} Expected `{{`, found `{}`", token.location, token.lexeme),
+        }
+
+        let code = Code::parse(lexer, None).map_err(|error| anyhow::anyhow!("{}", error))?;
+        let mut objects = Vec::new();
+        loop {
+            match lexer.peek().map_err(|error| anyhow::anyhow!("{}", error))? {
+                Token {
+                    lexeme: Lexeme::Symbol(Symbol::BracketCurlyRight),
+                    ..
+                } => {
+                    let _bracket = lexer.next();
+                    break;
+                }
+                Token {
+                    lexeme: Lexeme::Keyword(Keyword::Object),
+                    ..
+                } => objects.push(Self::parse(lexer)?),
+                Token {
+                    lexeme: Lexeme::Identifier(identifier),
+                    ..
+                } if identifier.inner.as_str() == "data" => {
+                    let _data = lexer.next();
+                    let _identifier = lexer.next();
+                    let _metadata = lexer.next();
+                }
+                token => anyhow::bail!(
+                    "{} Expected `object`, `data`, or `}}`, found `{}`",
+                    token.location,
+                    token.lexeme
+                ),
+            }
+        }
+
+        let mut object = Self {
+            location,
+            identifier,
+            code,
+            objects,
+        };
+        let paths = object.paths();
+        ObjectPaths::resolve(&mut object.code.block, object.identifier.as_str(), &paths)
+            .map_err(|error| anyhow::anyhow!("{}", error))?;
+        Ok(object)
+    }
+
+    ///
+    /// Finds the object with the `identifier` among itself and its nested objects.
+    ///
+    pub fn find(self, identifier: &str) -> Option<Self> {
+        if self.identifier == identifier {
+            return Some(self);
+        }
+        self.objects
+            .into_iter()
+            .find_map(|object| object.find(identifier))
+    }
+
+    ///
+    /// Returns the paths of the nested objects relative to this one.
+    ///
+    pub fn paths(&self) -> BTreeSet<String> {
+        self.objects
+            .iter()
+            .flat_map(|object| ObjectPaths::nest(object.identifier.as_str(), &object.paths()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EntryObjects;
+
+    const SOURCE_CODE: &str = r#"
+object "Project" {
+    code {
+        {
+            return(0, 0)
+        }
+    }
+    object "Constructor" {
+        code {
+            {
+                let size := datasize("Main")
+                codecopy(0, dataoffset("Main"), size)
+                return(0, size)
+            }
+        }
+        object "Main" {
+            code {
+                {
+                    return(0, 0)
+                }
+            }
+        }
+        object "Dependency" {
+            code {
+                {
+                    return(0, 0)
+                }
+            }
+        }
+    }
+}
+    "#;
+
+    #[test]
+    fn custom() {
+        let entry_objects =
+            EntryObjects::new(Some("Constructor".to_owned()), Some("Main".to_owned()));
+        let object = entry_objects.parse(SOURCE_CODE).expect("Always valid");
+
+        assert_eq!(object.identifier, "Constructor");
+        let runtime = object.inner_object.as_ref().expect("Always exists");
+        assert_eq!(runtime.identifier, "Constructor_deployed");
+        assert!(object.factory_dependencies.contains("Dependency"));
+        assert!(!object.factory_dependencies.contains("Main"));
+
+        let deploy_code = format!("{:?}", object.code);
+        assert!(deploy_code.contains("\"Constructor_deployed\""));
+        assert!(!deploy_code.contains("\"Main\""));
+    }
+
+    #[test]
+    fn runtime_not_found() {
+        let entry_objects =
+            EntryObjects::new(Some("Constructor".to_owned()), Some("Missing".to_owned()));
+        assert!(entry_objects.parse(SOURCE_CODE).is_err());
+    }
+
+    #[test]
+    fn default_convention() {
+        let entry_objects = EntryObjects::new(Some("Project".to_owned()), None);
+        assert!(entry_objects.parse(SOURCE_CODE).is_err());
+    }
+
+    #[test]
+    fn object_paths() {
+        let entry_objects =
+            EntryObjects::new(Some("Constructor".to_owned()), Some("Main".to_owned()));
+        let source_code = SOURCE_CODE.replace(
+            "let size := datasize(\"Main\")",
+            "let size := add(datasize(\"Main\"), datasize(\"Main.Nested\"))",
+        );
+
+        let error = entry_objects
+            .parse(source_code.as_str())
+            .expect_err("Always fails")
+            .to_string();
+        assert!(error.contains("Main.Nested"));
+
+        let source_code = source_code
+            .replace(
+                "                    return(0, 0)\n                }\n            }\n        }\n    }\n}",
+                "                    return(0, 0)\n                }\n            }\n            object \"Nested\" { code { } }\n        }\n    }\n}",
+            )
+            .replace("Main.Nested", "Dependency.Nested");
+        let object = entry_objects
+            .parse(source_code.as_str())
+            .expect("Always valid");
+        let deploy_code = format!("{:?}", object.code);
+        assert!(deploy_code.contains("\"Nested\""));
+        assert!(!deploy_code.contains("Dependency.Nested"));
+    }
+}
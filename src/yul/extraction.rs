@@ -0,0 +1,287 @@
+//!
+//! The Yul extraction from the `solc` textual output.
+//!
+
+///
+/// The Yul extraction from the `solc` textual output.
+///
+/// The `solc --ir` and `solc --ir-optimized` output wraps the Yul object of each contract into
+/// a `======= <path>:<name> =======` section header and an `IR:` or `Optimized IR:` heading,
+/// and precedes it with the comments depending on the `solc` version: the experimental codegen
+/// warning banner before v0.8.13, and the `/// @use-src` source annotations since. The object
+/// is named after the contract, with the AST node ID suffix in the recent versions and without
+/// it in the older ones. The output can be passed to the Yul mode as is, and the Yul plain
+/// sources are left intact.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Extraction;
+
+///
+/// The Yul object extracted from a `solc` output section.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    /// The full contract path from the section header, if any.
+    pub path: Option<String>,
+    /// The Yul object source code.
+    pub source_code: String,
+}
+
+impl Extraction {
+    /// The section header delimiter.
+    const SECTION_DELIMITER: &'static str = "=======";
+
+    /// The Yul headings, from `--ir-optimized` and `--ir` respectively.
+    const HEADINGS: [&'static str; 2] = ["Optimized IR:", "IR:"];
+
+    ///
+    /// Extracts the Yul objects from the `solc` output.
+    ///
+    /// The sections without the IR, e.g. of interfaces and abstract contracts, are skipped.
+    /// The output without section headers is returned as is, so the locations of the parser
+    /// errors in plain Yul sources are preserved.
+    ///
+    pub fn extract(output: &str) -> anyhow::Result<Vec<Section>> {
+        if !output
+            .lines()
+            .any(|line| Self::section_path(line).is_some())
+        {
+            return Ok(vec![Section {
+                path: None,
+                source_code: output.to_owned(),
+            }]);
+        }
+
+        let mut sections = Vec::new();
+        let mut lines = output.lines().peekable();
+        while let Some(line) = lines.next() {
+            let path = match Self::section_path(line) {
+                Some(path) => path,
+                None => continue,
+            };
+
+            let mut body = Vec::new();
+            while let Some(line) = lines.next_if(|line| Self::section_path(line).is_none()) {
+                body.push(line);
+            }
+
+            let heading_index = body
+                .iter()
+                .position(|line| Self::HEADINGS.contains(&line.trim()))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "The `solc` output section `{}` has no `{}` heading",
+                        path,
+                        Self::HEADINGS.join("` or `")
+                    )
+                })?;
+            let body = body[heading_index + 1..].join("\n");
+            if body.trim().is_empty() {
+                continue;
+            }
+
+            let source_code = Self::strip_preamble(body.as_str()).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No Yul object found in section `{}`, which may only be preceded by comments, e.g. the experimental codegen warning banner or the `/// @use-src` source annotations",
+                    path
+                )
+            })?;
+            Self::check_identifier(path, source_code)?;
+            sections.push(Section {
+                path: Some(path.to_owned()),
+                source_code: source_code.trim_end().to_owned(),
+            });
+        }
+        Ok(sections)
+    }
+
+    ///
+    /// Extracts the only Yul object from the `solc` output.
+    ///
+    pub fn extract_single(output: &str) -> anyhow::Result<String> {
+        let mut sections = Self::extract(output)?;
+        match sections.len() {
+            1 => Ok(sections.remove(0).source_code),
+            0 => anyhow::bail!("The `solc` output contains no Yul objects"),
+            _ => anyhow::bail!(
+                "The `solc` output contains several Yul objects: {}. Only one is allowed",
+                sections
+                    .iter()
+                    .filter_map(|section| section.path.as_deref())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    ///
+    /// Skips the comments preceding the Yul object, returning `None` if there is no object.
+    ///
+    fn strip_preamble(source_code: &str) -> Option<&str> {
+        let mut rest = source_code.trim_start();
+        loop {
+            if let Some(comment) = rest.strip_prefix("//") {
+                rest = comment
+                    .split_once('\n')
+                    .map(|(_comment, rest)| rest)
+                    .unwrap_or_default()
+                    .trim_start();
+            } else if let Some(comment) = rest.strip_prefix("/*") {
+                rest = comment.split_once("*/")?.1.trim_start();
+            } else {
+                break;
+            }
+        }
+        rest.starts_with("object").then_some(rest)
+    }
+
+    ///
+    /// Checks that the Yul object of the section at `path` is named after its contract, so a
+    /// misaligned section is never compiled as another contract.
+    ///
+    fn check_identifier(path: &str, source_code: &str) -> anyhow::Result<()> {
+        let identifier = source_code
+            .strip_prefix("object")
+            .map(|rest| rest.trim_start())
+            .and_then(|rest| rest.strip_prefix('"'))
+            .and_then(|rest| rest.split_once('"'))
+            .map(|(identifier, _rest)| identifier)
+            .ok_or_else(|| anyhow::anyhow!("The Yul object in section `{}` has no name", path))?;
+        let name = path.rsplit_once(':').map_or(path, |(_path, name)| name);
+
+        let is_named_after_contract = identifier == name
+            || identifier
+                .strip_prefix(name)
+                .and_then(|suffix| suffix.strip_prefix('_'))
+                .map_or(false, |id| {
+                    !id.is_empty() && id.chars().all(|character| character.is_ascii_digit())
+                });
+        if !is_named_after_contract {
+            anyhow::bail!(
+                "The Yul object `{}` in section `{}` is not named after the contract `{}`",
+                identifier,
+                path,
+                name
+            );
+        }
+        Ok(())
+    }
+
+    ///
+    /// Returns the contract path if the line is a section header.
+    ///
+    fn section_path(line: &str) -> Option<&str> {
+        line.trim()
+            .strip_prefix(Self::SECTION_DELIMITER)?
+            .strip_suffix(Self::SECTION_DELIMITER)
+            .map(|path| path.trim())
+            .filter(|path| !path.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Extraction;
+
+    #[test]
+    fn plain() {
+        let source_code = "// SPDX-License-Identifier: MIT\nobject \"Test\" { code { } }\n";
+        let sections = Extraction::extract(source_code).expect("Always valid");
+
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].path, None);
+        assert_eq!(sections[0].source_code, source_code);
+    }
+
+    #[test]
+    fn experimental_banner() {
+        let output = r#"
+======= test.sol:I =======
+IR:
+
+
+======= test.sol:Test =======
+IR:
+/*=====================================================*
+ *                       WARNING                       *
+ *  Solidity to Yul compilation is still EXPERIMENTAL   *
+ *       It can result in LOSS OF FUNDS or worse        *
+ *                !USE AT YOUR OWN RISK!               *
+ *=====================================================*/
+
+
+object "Test_12" {
+    code { }
+    object "Test_12_deployed" {
+        code { }
+    }
+}
+"#;
+        let sections = Extraction::extract(output).expect("Always valid");
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].path.as_deref(), Some("test.sol:Test"));
+        assert!(sections[0].source_code.starts_with("object \"Test_12\" {"));
+        assert!(sections[0].source_code.ends_with('}'));
+    }
+
+    #[test]
+    fn source_annotations() {
+        let output = r#"
+======= test.sol:A =======
+Optimized IR:
+/// @use-src 0:"test.sol"
+object "A_5" {
+    code { }
+}
+
+
+======= test.sol:B =======
+Optimized IR:
+/// @use-src 0:"test.sol"
+object "B_9" {
+    code { }
+}
+"#;
+        let sections = Extraction::extract(output).expect("Always valid");
+        assert_eq!(
+            sections
+                .iter()
+                .map(|section| section.path.as_deref().expect("Always exists"))
+                .collect::<Vec<&str>>(),
+            vec!["test.sol:A", "test.sol:B"]
+        );
+        assert_eq!(sections[1].source_code, "object \"B_9\" {\n    code { }\n}");
+        assert!(Extraction::extract_single(output).is_err());
+    }
+
+    #[test]
+    fn object_naming() {
+        let output = r#"
+======= test.sol:Test =======
+IR:
+object "Test" {
+    code { }
+    object "Test_deployed" {
+        code { }
+    }
+}
+"#;
+        let source_code = Extraction::extract_single(output).expect("Always valid");
+        assert!(source_code.starts_with("object \"Test\" {"));
+
+        let output = output.replace("\"Test", "\"Other_12");
+        let error = Extraction::extract(output.as_str())
+            .expect_err("Always invalid")
+            .to_string();
+        assert!(error.contains("`Other_12` in section `test.sol:Test`"));
+    }
+
+    #[test]
+    fn missing_object() {
+        let output =
+            "======= test.sol:Test =======\nOptimized IR:\n/// @use-src 0:\"test.sol\"\n{ }\n";
+        let error = Extraction::extract(output).expect_err("Always invalid");
+
+        assert!(error.to_string().contains("section `test.sol:Test`"));
+    }
+}
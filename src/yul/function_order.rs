@@ -0,0 +1,215 @@
+//!
+//! The selector-based function ordering.
+//!
+
+use std::collections::BTreeMap;
+
+use crate::yul::lexer::token::lexeme::literal::integer::Integer as IntegerLiteral;
+use crate::yul::lexer::token::lexeme::literal::Literal as LexicalLiteral;
+use crate::yul::parser::statement::block::Block;
+use crate::yul::parser::statement::expression::function_call::name::Name;
+use crate::yul::parser::statement::expression::Expression;
+use crate::yul::parser::statement::object::Object;
+use crate::yul::parser::statement::Statement;
+
+///
+/// The selector-based function ordering.
+///
+/// Sorts the external function dispatcher cases by selector, and the function definitions by
+/// the selectors of the external functions dispatched to them. The other functions follow in the
+/// order of their names without the `solc` AST identifier suffixes, so moving the code around in
+/// the source file does not change the bytecode layout.
+///
+#[derive(Debug)]
+pub struct FunctionOrder;
+
+impl FunctionOrder {
+    /// The selector size in bytes.
+    const SELECTOR_SIZE: usize = 4;
+
+    ///
+    /// Reorders the deploy and runtime code of the `object`.
+    ///
+    pub fn by_selector(object: &mut Object) {
+        Self::reorder(&mut object.code.block);
+        if let Some(runtime) = object.inner_object.as_mut() {
+            Self::reorder(&mut runtime.code.block);
+        }
+    }
+
+    ///
+    /// Reorders the dispatcher cases and the function definitions of the code `block`.
+    ///
+    fn reorder(block: &mut Block) {
+        let mut selectors = BTreeMap::new();
+        Self::sort_dispatchers(block, &mut selectors);
+
+        let mut functions: Vec<Statement> = block
+            .statements
+            .iter()
+            .filter(|statement| matches!(statement, Statement::FunctionDefinition(_)))
+            .cloned()
+            .collect();
+        functions.sort_by_cached_key(|statement| match statement {
+            Statement::FunctionDefinition(function) => {
+                match selectors.get(function.identifier.as_str()) {
+                    Some(selector) => (0, selector.to_owned(), String::new()),
+                    None => (
+                        1,
+                        Self::stable_name(function.identifier.as_str()).to_owned(),
+                        function.identifier.to_owned(),
+                    ),
+                }
+            }
+            _ => unreachable!(),
+        });
+
+        let mut functions = functions.into_iter();
+        for statement in block.statements.iter_mut() {
+            if let Statement::FunctionDefinition(_) = statement {
+                *statement = functions.next().expect("Always exists");
+            }
+        }
+    }
+
+    ///
+    /// Sorts the cases of the selector switches nested into the `block`, collecting the
+    /// selectors keyed by the names of the functions dispatched to.
+    ///
+    fn sort_dispatchers(block: &mut Block, selectors: &mut BTreeMap<String, String>) {
+        for statement in block.statements.iter_mut() {
+            match statement {
+                Statement::Block(block) => Self::sort_dispatchers(block, selectors),
+                Statement::IfConditional(conditional) => {
+                    Self::sort_dispatchers(&mut conditional.block, selectors)
+                }
+                Statement::Switch(switch) => {
+                    let case_selectors: Option<Vec<String>> = switch
+                        .cases
+                        .iter()
+                        .map(|case| Self::selector(&case.literal.inner))
+                        .collect();
+                    let case_selectors = match case_selectors {
+                        Some(case_selectors) if !case_selectors.is_empty() => case_selectors,
+                        _ => continue,
+                    };
+
+                    for (case, selector) in switch.cases.iter().zip(case_selectors.into_iter()) {
+                        for statement in case.block.statements.iter() {
+                            if let Statement::Expression(Expression::FunctionCall(call)) = statement
+                            {
+                                if let Name::UserDefined(ref name) = call.name {
+                                    selectors
+                                        .entry(name.to_owned())
+                                        .or_insert_with(|| selector.clone());
+                                }
+                            }
+                        }
+                    }
+                    switch.cases.sort_by_cached_key(|case| {
+                        Self::selector(&case.literal.inner).unwrap_or_default()
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    ///
+    /// Returns the zero-padded hexadecimal selector, if the `literal` is a 4-byte hexadecimal one.
+    ///
+    fn selector(literal: &LexicalLiteral) -> Option<String> {
+        match literal {
+            LexicalLiteral::Integer(IntegerLiteral::Hexadecimal { inner }) => {
+                let digits = inner.strip_prefix("0x").unwrap_or(inner.as_str());
+                if digits.len() > Self::SELECTOR_SIZE * 2 {
+                    return None;
+                }
+                Some(format!(
+                    "{:0>width$}",
+                    digits.to_lowercase(),
+                    width = Self::SELECTOR_SIZE * 2
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    ///
+    /// Strips the `solc` AST identifier suffix, e.g. `_42` in `fun_transfer_42`.
+    ///
+    fn stable_name(identifier: &str) -> &str {
+        match identifier.rsplit_once('_') {
+            Some((name, suffix))
+                if !suffix.is_empty()
+                    && suffix.chars().all(|character| character.is_ascii_digit()) =>
+            {
+                name
+            }
+            _ => identifier,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::yul::lexer::Lexer;
+    use crate::yul::parser::statement::object::Object;
+    use crate::yul::parser::statement::Statement;
+
+    use super::FunctionOrder;
+
+    #[test]
+    fn by_selector() {
+        let input = r#"
+object "Test" {
+    code {
+        {
+            return(0, 0)
+        }
+    }
+    object "Test_deployed" {
+        code {
+            {
+                let selector := shr(224, calldataload(0))
+                switch selector
+                case 0xa9059cbb { external_fun_transfer_12() }
+                case 0x18160ddd { external_fun_totalSupply_3() }
+                default { }
+                revert(0, 0)
+            }
+            function fun_helper_40() { }
+            function external_fun_transfer_12() { }
+            function external_fun_totalSupply_3() { }
+            function abi_decode_tuple_(headStart, dataEnd) { }
+        }
+    }
+}
+    "#;
+
+        let mut lexer = Lexer::new(input.to_owned());
+        let mut object = Object::parse(&mut lexer, None).expect("Always valid");
+        FunctionOrder::by_selector(&mut object);
+
+        let runtime = object.inner_object.expect("Always exists");
+        let names: Vec<String> = runtime
+            .code
+            .block
+            .statements
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::FunctionDefinition(function) => Some(function.identifier.to_owned()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "external_fun_totalSupply_3",
+                "external_fun_transfer_12",
+                "abi_decode_tuple_",
+                "fun_helper_40",
+            ]
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//!
+//! The Yul immutable keys collector.
+//!
+
+use std::collections::BTreeSet;
+
+use crate::yul::lexer::token::lexeme::literal::Literal as LexicalLiteral;
+use crate::yul::parser::statement::expression::function_call::name::Name;
+use crate::yul::parser::statement::expression::function_call::FunctionCall;
+use crate::yul::parser::statement::expression::Expression;
+use crate::yul::visitor::Visitor;
+
+///
+/// The Yul immutable keys collector.
+///
+/// Collects the keys of the immutables loaded with `loadimmutable` or assigned with
+/// `setimmutable`, which are exactly the ones the code generator allocates slots for.
+///
+#[derive(Debug, Default)]
+pub struct ImmutableKeys {
+    /// The collected keys.
+    pub keys: BTreeSet<String>,
+}
+
+impl Visitor for ImmutableKeys {
+    fn visit_function_call(&mut self, call: &FunctionCall) {
+        let key = match call.name {
+            Name::LoadImmutable => call.arguments.first(),
+            Name::SetImmutable => call.arguments.get(1),
+            _ => None,
+        };
+        if let Some(Expression::Literal(literal)) = key {
+            if let LexicalLiteral::String(ref key) = literal.inner {
+                if key.inner != "library_deploy_address" {
+                    self.keys.insert(key.inner.to_owned());
+                }
+            }
+        }
+        crate::yul::visitor::walk_function_call(self, call);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::yul::lexer::Lexer;
+    use crate::yul::parser::statement::object::Object;
+    use crate::yul::visitor::Visitor;
+
+    use super::ImmutableKeys;
+
+    #[test]
+    fn collected() {
+        let input = r#"
+object "Test" {
+    code {
+        {
+            setimmutable(0, "3", caller())
+            setimmutable(0, "library_deploy_address", address())
+            return(0, 0)
+        }
+    }
+    object "Test_deployed" {
+        code {
+            {
+                mstore(0, add(loadimmutable("3"), loadimmutable("5")))
+                return(0, 32)
+            }
+        }
+    }
+}
+    "#;
+
+        let mut lexer = Lexer::new(input);
+        let object = Object::parse(&mut lexer, None).expect("Always valid");
+        let mut collector = ImmutableKeys::default();
+        collector.visit_object(&object);
+
+        assert_eq!(
+            collector.keys.into_iter().collect::<Vec<String>>(),
+            vec!["3".to_owned(), "5".to_owned()]
+        );
+    }
+}
@@ -8,6 +8,8 @@ pub mod token;
 #[cfg(test)]
 mod tests;
 
+use std::sync::Arc;
+
 use self::error::Error;
 use self::token::lexeme::comment::Comment;
 use self::token::lexeme::identifier::Identifier;
@@ -21,10 +23,12 @@ use self::token::Token;
 ///
 /// The compiler lexer.
 ///
+/// The input is shared, so the source code kept elsewhere, e.g. in the project, is not copied.
+///
 pub struct Lexer {
     /// The input source code.
-    input: String,
-    /// The number of characters processed so far.
+    input: Arc<str>,
+    /// The number of bytes processed so far.
     offset: usize,
     /// The current location.
     location: Location,
@@ -36,11 +40,9 @@ impl Lexer {
     ///
     /// A shortcut constructor.
     ///
-    pub fn new(mut input: String) -> Self {
-        input.push('\n');
-
+    pub fn new<S: Into<Arc<str>>>(input: S) -> Self {
         Self {
-            input,
+            input: input.into(),
             offset: 0,
             location: Location::default(),
             peeked: None,
@@ -78,10 +80,11 @@ impl Lexer {
             }
 
             if let Some(mut token) = StringLiteral::parse(input) {
+                let columns = token.location.column;
                 token.location = self.location;
 
                 self.offset += token.length;
-                self.location.shift_right(token.length);
+                self.location.shift_right(columns);
                 return Ok(token);
             }
 
@@ -109,18 +112,29 @@ impl Lexer {
                 return Ok(token);
             }
 
-            let end = self.input[self.offset..]
-                .find(char::is_whitespace)
-                .unwrap_or(self.input.len());
+            let end = input.find(char::is_whitespace).unwrap_or(input.len());
             return Err(Error::InvalidLexeme {
                 location: self.location,
-                sequence: self.input[self.offset..self.offset + end].to_owned(),
+                sequence: input[..end].to_owned(),
             });
         }
 
+        if self.offset == self.input.len() {
+            // The end of the input is treated as a line break, like a trailing newline.
+            self.location.line += 1;
+            self.location.column = 1;
+            self.offset += 1;
+        }
         Ok(Token::new(self.location, Lexeme::EndOfFile, 0))
     }
 
+    ///
+    /// Returns the byte offset of the end of the last processed token, including the peeked one.
+    ///
+    pub fn offset(&self) -> usize {
+        self.offset.min(self.input.len())
+    }
+
     ///
     /// Peeks the next lexeme without advancing the iterator.
     ///
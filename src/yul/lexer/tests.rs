@@ -3,6 +3,8 @@
 //!
 
 use crate::yul::lexer::error::Error;
+use crate::yul::lexer::token::lexeme::literal::string::String as StringLiteral;
+use crate::yul::lexer::token::lexeme::literal::Literal;
 use crate::yul::lexer::token::lexeme::Lexeme;
 use crate::yul::lexer::token::location::Location;
 use crate::yul::lexer::Lexer;
@@ -89,3 +91,110 @@ object "Test" {
         }
     }
 }
+
+#[test]
+fn string_unicode() {
+    let input = r#""héllo 😀" x"#;
+
+    let mut lexer = Lexer::new(input.to_owned());
+    let token = lexer.next().expect("Always valid");
+    assert_eq!(
+        token.lexeme,
+        Lexeme::Literal(Literal::String(StringLiteral::new(
+            "héllo 😀".to_owned(),
+            false
+        )))
+    );
+    assert_eq!(token.location, Location::new(1, 1));
+
+    let token = lexer.next().expect("Always valid");
+    assert_eq!(token.location, Location::new(1, 11));
+}
+
+#[test]
+fn string_escaped_quote() {
+    let input = r#""a\"ü\\" x"#;
+
+    let mut lexer = Lexer::new(input.to_owned());
+    let token = lexer.next().expect("Always valid");
+    assert_eq!(
+        token.lexeme,
+        Lexeme::Literal(Literal::String(StringLiteral::new(
+            r#"a\"ü\\"#.to_owned(),
+            false
+        )))
+    );
+
+    let token = lexer.next().expect("Always valid");
+    assert_eq!(token.location, Location::new(1, 10));
+}
+
+#[test]
+fn string_unterminated() {
+    let input = r#""abc"#;
+
+    let mut lexer = Lexer::new(input.to_owned());
+    assert_eq!(
+        lexer.next(),
+        Err(Error::InvalidLexeme {
+            location: Location::new(1, 1),
+            sequence: r#""abc"#.to_owned(),
+        })
+    );
+}
+
+#[test]
+fn string_to_bytes_escapes() {
+    let literal = StringLiteral::new(r#"\u00e9\x41\n\t\"\\"#.to_owned(), false);
+    assert_eq!(
+        literal.to_bytes().expect("Always valid"),
+        vec![0xc3, 0xa9, 0x41, 0x0a, 0x09, 0x22, 0x5c]
+    );
+}
+
+#[test]
+fn string_to_bytes_unicode() {
+    let literal = StringLiteral::new("é😀".to_owned(), false);
+    assert_eq!(
+        literal.to_bytes().expect("Always valid"),
+        "é😀".as_bytes().to_vec()
+    );
+}
+
+#[test]
+fn string_to_bytes_hexadecimal() {
+    let literal = StringLiteral::new("12_ab".to_owned(), true);
+    assert_eq!(literal.to_bytes().expect("Always valid"), vec![0x12, 0xab]);
+}
+
+#[test]
+fn invalid_lexeme_at_end() {
+    let input = "let x := #";
+
+    let mut lexer = Lexer::new(input);
+    for _ in 0..3 {
+        lexer.next().expect("Always valid");
+    }
+    assert_eq!(
+        lexer.next(),
+        Err(Error::InvalidLexeme {
+            location: Location::new(1, 10),
+            sequence: "#".to_owned(),
+        })
+    );
+}
+
+#[test]
+fn end_of_file_location() {
+    let input = "x";
+
+    let mut lexer = Lexer::new(input);
+    lexer.next().expect("Always valid");
+    let token = lexer.next().expect("Always valid");
+    assert_eq!(token.lexeme, Lexeme::EndOfFile);
+    assert_eq!(token.location, Location::new(2, 1));
+    assert_eq!(
+        lexer.next().expect("Always valid").location,
+        Location::new(2, 1)
+    );
+}
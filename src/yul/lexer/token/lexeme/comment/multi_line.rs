@@ -28,8 +28,8 @@ impl Comment {
         let length = end_position + Self::END.len();
         let lines = input.matches('\n').count();
         let columns = match input.rfind('\n') {
-            Some(new_line) => end_position - (new_line + 1),
-            None => end_position,
+            Some(new_line) => input[new_line + 1..].chars().count(),
+            None => input.chars().count(),
         };
 
         Token::new(Location::new(lines, columns), Lexeme::Comment, length)
@@ -53,21 +53,25 @@ impl String {
         }
 
         let mut string = std::string::String::new();
+        let mut characters = input[length..].chars();
         loop {
-            if input[length..].starts_with('\\') {
-                string.push(input.chars().nth(length).expect("Always exists"));
-                string.push(input.chars().nth(length + 1).expect("Always exists"));
-                length += 2;
+            let character = characters.next()?;
+
+            if character == '\\' {
+                let escaped = characters.next()?;
+                string.push(character);
+                string.push(escaped);
+                length += character.len_utf8() + escaped.len_utf8();
                 continue;
             }
 
-            if input[length..].starts_with('"') {
-                length += 1;
+            if character == '"' {
+                length += character.len_utf8();
                 break;
             }
 
-            string.push(input.chars().nth(length).expect("Always exists"));
-            length += 1;
+            string.push(character);
+            length += character.len_utf8();
         }
 
         let string = string
@@ -79,11 +83,73 @@ impl String {
         let literal = Self::new(string, is_hex_string);
 
         Some(Token::new(
-            Location::new(0, length),
+            Location::new(0, input[..length].chars().count()),
             Lexeme::Literal(Literal::String(literal)),
             length,
         ))
     }
+
+    ///
+    /// Decodes the string contents into bytes, resolving the escape sequences.
+    ///
+    /// The unicode characters, both raw and escaped with `\u`, are encoded as UTF-8.
+    ///
+    pub fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        if self.is_hexadecimal {
+            return hex::decode(self.inner.replace('_', "")).map_err(|error| {
+                anyhow::anyhow!("Invalid hexadecimal string `{}`: {}", self.inner, error)
+            });
+        }
+
+        let mut bytes = Vec::with_capacity(self.inner.len());
+        let mut characters = self.inner.chars();
+        while let Some(character) = characters.next() {
+            if character != '\\' {
+                let mut buffer = [0u8; 4];
+                bytes.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes());
+                continue;
+            }
+
+            match characters.next() {
+                Some('x') => {
+                    let digits: std::string::String = characters.by_ref().take(2).collect();
+                    let byte =
+                        u8::from_str_radix(digits.as_str(), compiler_common::BASE_HEXADECIMAL)
+                            .map_err(|error| {
+                                anyhow::anyhow!(
+                                    "Invalid escape sequence `\\x{}`: {}",
+                                    digits,
+                                    error
+                                )
+                            })?;
+                    bytes.push(byte);
+                }
+                Some('u') => {
+                    let digits: std::string::String = characters.by_ref().take(4).collect();
+                    let codepoint =
+                        u32::from_str_radix(digits.as_str(), compiler_common::BASE_HEXADECIMAL)
+                            .map_err(|error| {
+                                anyhow::anyhow!("Invalid codepoint `{}`: {}", digits, error)
+                            })?;
+                    let character = char::from_u32(codepoint)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid codepoint {}", codepoint))?;
+                    let mut buffer = [0u8; 4];
+                    bytes.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes());
+                }
+                Some('t') => bytes.push(b'\t'),
+                Some('n') => bytes.push(b'\n'),
+                Some('r') => bytes.push(b'\r'),
+                Some('\n') => {}
+                Some(character) => {
+                    let mut buffer = [0u8; 4];
+                    bytes.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes());
+                }
+                None => anyhow::bail!("Unterminated escape sequence in `{}`", self.inner),
+            }
+        }
+
+        Ok(bytes)
+    }
 }
 
 impl std::fmt::Display for String {
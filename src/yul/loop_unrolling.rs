@@ -0,0 +1,377 @@
+//!
+//! The constant-sized loop unrolling.
+//!
+
+use num::Num;
+use num::ToPrimitive;
+
+use crate::build::constant_loop::ConstantLoop;
+use crate::yul::lexer::token::lexeme::literal::integer::Integer as IntegerLiteral;
+use crate::yul::lexer::token::lexeme::literal::Literal as LexicalLiteral;
+use crate::yul::parser::statement::block::Block;
+use crate::yul::parser::statement::expression::function_call::name::Name;
+use crate::yul::parser::statement::expression::Expression;
+use crate::yul::parser::statement::for_loop::ForLoop;
+use crate::yul::parser::statement::function_definition::FunctionDefinition;
+use crate::yul::parser::statement::object::Object;
+use crate::yul::parser::statement::Statement;
+use crate::yul::visitor::Visitor;
+use crate::yul::visitor::VisitorMut;
+
+///
+/// The constant-sized loop unrolling.
+///
+/// Detects the loops in the form of `for { let i := A } lt(i, B) { i := add(i, C) } { ... }`,
+/// also with the `gt(B, i)` condition and the `add(C, i)` increment, where `A`, `B`, and `C` are
+/// literals. Such a loop is unrolled into a block with the body repeated for each iteration if
+/// the trip count multiplied by the body size does not exceed the threshold.
+///
+/// The loops with `break` or `continue`, nested function definitions, or assignments to the
+/// counter in the body are reported, but never unrolled.
+///
+#[derive(Debug)]
+pub struct LoopUnrolling {
+    /// The maximal trip count multiplied by the body size, if the unrolling is enabled.
+    threshold: Option<usize>,
+    /// The code type being visited, which is either `deploy` or `runtime`.
+    code: &'static str,
+    /// The enclosing function name, if any.
+    function: Option<String>,
+    /// The detected constant-sized loops.
+    loops: Vec<ConstantLoop>,
+}
+
+impl LoopUnrolling {
+    ///
+    /// A shortcut constructor.
+    ///
+    pub fn new(threshold: Option<usize>) -> Self {
+        Self {
+            threshold,
+            code: "deploy",
+            function: None,
+            loops: Vec::new(),
+        }
+    }
+
+    ///
+    /// Unrolls the constant-sized loops in the deploy and runtime code of the `object`,
+    /// returning all the detected ones.
+    ///
+    pub fn apply(mut self, object: &mut Object) -> Vec<ConstantLoop> {
+        self.visit_object(object);
+        self.loops
+    }
+
+    ///
+    /// Reports the `for_loop` if it is constant-sized, and returns its unrolled block if it is
+    /// within the threshold.
+    ///
+    fn unroll(&mut self, for_loop: &ForLoop) -> Option<Block> {
+        let (counter, trip_count) = Self::trip_count(for_loop)?;
+        let body_size = Size::of(&for_loop.body);
+        let is_unrolled = Repeatability::check(&for_loop.body, counter.as_str())
+            && self
+                .threshold
+                .map(|threshold| trip_count.saturating_mul(body_size.max(1)) <= threshold)
+                .unwrap_or_default();
+
+        self.loops.push(ConstantLoop::new(
+            self.code,
+            self.function.clone(),
+            for_loop.location.to_string(),
+            trip_count,
+            body_size,
+            is_unrolled,
+        ));
+        if !is_unrolled {
+            return None;
+        }
+
+        let mut statements = for_loop.initializer.statements.clone();
+        for iteration in 0..trip_count {
+            if iteration > 0 {
+                statements.extend(for_loop.finalizer.statements.iter().cloned());
+            }
+            statements.push(Statement::Block(for_loop.body.clone()));
+        }
+        Some(Block {
+            location: for_loop.location,
+            statements,
+        })
+    }
+
+    ///
+    /// Returns the counter name and the trip count of the `for_loop`, if it is constant-sized.
+    ///
+    fn trip_count(for_loop: &ForLoop) -> Option<(String, usize)> {
+        let (counter, start) = match for_loop.initializer.statements.as_slice() {
+            [Statement::VariableDeclaration(declaration)] if declaration.bindings.len() == 1 => (
+                declaration.bindings[0].inner.to_owned(),
+                Self::literal(declaration.expression.as_ref()?)?,
+            ),
+            _ => return None,
+        };
+
+        let end = match &for_loop.condition {
+            Expression::FunctionCall(call) => match (&call.name, call.arguments.as_slice()) {
+                (Name::Lt, [Expression::Identifier(identifier), end])
+                | (Name::Gt, [end, Expression::Identifier(identifier)])
+                    if identifier.inner == counter =>
+                {
+                    Self::literal(end)?
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let step = match for_loop.finalizer.statements.as_slice() {
+            [Statement::Assignment(assignment)]
+                if assignment.bindings.len() == 1 && assignment.bindings[0].inner == counter =>
+            {
+                match &assignment.initializer {
+                    Expression::FunctionCall(call) => {
+                        match (&call.name, call.arguments.as_slice()) {
+                            (Name::Add, [Expression::Identifier(identifier), step])
+                            | (Name::Add, [step, Expression::Identifier(identifier)])
+                                if identifier.inner == counter =>
+                            {
+                                Self::literal(step)?
+                            }
+                            _ => return None,
+                        }
+                    }
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+
+        if step == num::BigUint::from(0u8) {
+            return None;
+        }
+        if start >= end {
+            return Some((counter, 0));
+        }
+        let field_limit = num::BigUint::from(1u8) << compiler_common::BIT_LENGTH_FIELD;
+        if &end - 1u8 + &step >= field_limit {
+            return None;
+        }
+        let trip_count = (&end - &start + &step - 1u8) / &step;
+        Some((counter, trip_count.to_usize()?))
+    }
+
+    ///
+    /// Returns the value of the integer literal `expression`.
+    ///
+    fn literal(expression: &Expression) -> Option<num::BigUint> {
+        match expression {
+            Expression::Literal(literal) => match &literal.inner {
+                LexicalLiteral::Integer(IntegerLiteral::Decimal { inner }) => {
+                    num::BigUint::from_str_radix(inner.as_str(), compiler_common::BASE_DECIMAL).ok()
+                }
+                LexicalLiteral::Integer(IntegerLiteral::Hexadecimal { inner }) => {
+                    num::BigUint::from_str_radix(
+                        inner.strip_prefix("0x").unwrap_or(inner.as_str()),
+                        compiler_common::BASE_HEXADECIMAL,
+                    )
+                    .ok()
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl VisitorMut for LoopUnrolling {
+    fn visit_object(&mut self, object: &mut Object) {
+        self.code = "deploy";
+        self.visit_block(&mut object.code.block);
+        if let Some(runtime) = object.inner_object.as_mut() {
+            self.code = "runtime";
+            self.visit_block(&mut runtime.code.block);
+        }
+    }
+
+    fn visit_statement(&mut self, statement: &mut Statement) {
+        let unrolled = match statement {
+            Statement::ForLoop(for_loop) => {
+                self.visit_block(&mut for_loop.body);
+                self.unroll(for_loop)
+            }
+            _ => {
+                crate::yul::visitor::walk_statement_mut(self, statement);
+                None
+            }
+        };
+        if let Some(unrolled) = unrolled {
+            *statement = Statement::Block(unrolled);
+        }
+    }
+
+    fn visit_function_definition(&mut self, function: &mut FunctionDefinition) {
+        let enclosing = self.function.replace(function.identifier.to_owned());
+        crate::yul::visitor::walk_function_definition_mut(self, function);
+        self.function = enclosing;
+    }
+}
+
+///
+/// Checks whether a loop body can be repeated, that is, it does not leave the loop early,
+/// define functions, or assign the loop counter.
+///
+/// `break` and `continue` are only checked outside of the nested loops, as those own theirs.
+///
+struct Repeatability<'a> {
+    /// The loop counter name.
+    counter: &'a str,
+    /// The number of the nested loops being visited.
+    depth: usize,
+    /// Whether the body can be repeated.
+    is_repeatable: bool,
+}
+
+impl<'a> Repeatability<'a> {
+    ///
+    /// Whether the loop `body` with the `counter` can be repeated.
+    ///
+    fn check(body: &Block, counter: &'a str) -> bool {
+        let mut repeatability = Self {
+            counter,
+            depth: 0,
+            is_repeatable: true,
+        };
+        repeatability.visit_block(body);
+        repeatability.is_repeatable
+    }
+}
+
+impl Visitor for Repeatability<'_> {
+    fn visit_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Break(_) | Statement::Continue(_) if self.depth == 0 => {
+                self.is_repeatable = false;
+            }
+            Statement::FunctionDefinition(_) => self.is_repeatable = false,
+            Statement::Assignment(assignment)
+                if assignment
+                    .bindings
+                    .iter()
+                    .any(|binding| binding.inner == self.counter) =>
+            {
+                self.is_repeatable = false;
+            }
+            Statement::ForLoop(_) => {
+                self.depth += 1;
+                crate::yul::visitor::walk_statement(self, statement);
+                self.depth -= 1;
+            }
+            _ => crate::yul::visitor::walk_statement(self, statement),
+        }
+    }
+}
+
+///
+/// Counts the statements and expression nodes of a block.
+///
+#[derive(Default)]
+struct Size(usize);
+
+impl Size {
+    ///
+    /// Returns the number of statements and expression nodes in the `block`.
+    ///
+    fn of(block: &Block) -> usize {
+        let mut size = Self::default();
+        size.visit_block(block);
+        size.0
+    }
+}
+
+impl Visitor for Size {
+    fn visit_statement(&mut self, statement: &Statement) {
+        self.0 += 1;
+        crate::yul::visitor::walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        self.0 += 1;
+        crate::yul::visitor::walk_expression(self, expression);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::build::constant_loop::ConstantLoop;
+    use crate::yul::lexer::Lexer;
+    use crate::yul::parser::statement::object::Object;
+
+    use super::LoopUnrolling;
+
+    const SOURCE: &str = r#"
+object "Test" {
+    code {
+        {
+            return(0, 0)
+        }
+    }
+    object "Test_deployed" {
+        code {
+            {
+                let sum := 0
+                for { let i := 0 } lt(i, 3) { i := add(i, 1) } {
+                    sum := add(sum, i)
+                }
+                for { let i := 0x10 } gt(0x20, i) { i := add(4, i) } {
+                    if eq(i, 0x14) { break }
+                }
+                for { let i := 0 } lt(i, n()) { i := add(i, 1) } { }
+                for { let i := 0 } lt(i, 0xffffffffffffffff) { i := add(i, 1) } { }
+                return(0, sum)
+            }
+            function n() -> r { r := 3 }
+        }
+    }
+}
+"#;
+
+    #[test]
+    fn report() {
+        let mut lexer = Lexer::new(SOURCE.to_owned());
+        let mut object = Object::parse(&mut lexer, None).expect("Always valid");
+
+        let loops = LoopUnrolling::new(None).apply(&mut object);
+        assert_eq!(
+            loops
+                .iter()
+                .map(|constant_loop| (constant_loop.trip_count, constant_loop.unrolled))
+                .collect::<Vec<_>>(),
+            vec![(3, false), (4, false), (0xffffffffffffffff, false)]
+        );
+        assert_eq!(
+            loops[2].estimated_size_delta,
+            i64::MAX - 1 - ConstantLoop::CONTROL_SIZE
+        );
+        assert_eq!(loops[2].estimated_ergs_saved, u64::MAX);
+        assert!(loops
+            .iter()
+            .all(|constant_loop| constant_loop.code.as_str() == "runtime"));
+    }
+
+    #[test]
+    fn unroll() {
+        let mut lexer = Lexer::new(SOURCE.to_owned());
+        let mut object = Object::parse(&mut lexer, None).expect("Always valid");
+
+        let loops = LoopUnrolling::new(Some(16)).apply(&mut object);
+        assert_eq!(
+            loops
+                .iter()
+                .map(|constant_loop| (constant_loop.trip_count, constant_loop.unrolled))
+                .collect::<Vec<_>>(),
+            vec![(3, true), (4, false), (0xffffffffffffffff, false)]
+        );
+    }
+}
@@ -2,6 +2,16 @@
 //! The Yul IR compiling tools.
 //!
 
+pub mod builtin_versions;
+pub mod data_references;
+pub mod entry_objects;
 pub mod error;
+pub mod extraction;
+pub mod function_order;
+pub mod immutable_keys;
 pub mod lexer;
+pub mod loop_unrolling;
+pub mod object_paths;
 pub mod parser;
+pub mod revert_strings;
+pub mod visitor;
@@ -0,0 +1,129 @@
+//!
+//! The Yul object paths resolution.
+//!
+
+use std::collections::BTreeSet;
+
+use crate::yul::lexer::token::lexeme::literal::Literal as LexicalLiteral;
+use crate::yul::parser::error::Error as ParserError;
+use crate::yul::parser::statement::block::Block;
+use crate::yul::parser::statement::expression::function_call::name::Name;
+use crate::yul::parser::statement::expression::function_call::FunctionCall;
+use crate::yul::parser::statement::expression::Expression;
+use crate::yul::visitor::VisitorMut;
+
+///
+/// The Yul object paths resolution.
+///
+/// `solc` may address the objects nested deeper than one level with dotted paths, e.g.
+/// `datasize("Test_deployed.Dependency")` in the deploy code of `Test`. The nested objects are
+/// only tracked by their identifiers, so such paths are validated against the object tree and
+/// replaced with the identifier of the last object in the path.
+///
+/// The paths are resolved once, when the object is parsed, as the object tree is only known
+/// to the parser.
+///
+#[derive(Debug)]
+pub struct ObjectPaths<'a> {
+    /// The identifier of the object the code belongs to.
+    object: &'a str,
+    /// The paths of all objects nested into the object, relative to it.
+    paths: &'a BTreeSet<String>,
+    /// The first unknown path error.
+    error: Option<ParserError>,
+}
+
+impl<'a> ObjectPaths<'a> {
+    /// The object path separator.
+    pub const SEPARATOR: char = '.';
+
+    ///
+    /// Whether the `dataoffset` or `datasize` argument is a path to a nested object.
+    ///
+    /// The data section names, such as `.metadata`, are not object paths.
+    ///
+    pub fn is_path(identifier: &str) -> bool {
+        identifier.contains(Self::SEPARATOR)
+            && identifier
+                .split(Self::SEPARATOR)
+                .all(|segment| !segment.is_empty())
+    }
+
+    ///
+    /// Joins the `identifier` of a nested object with the `paths` of its own nested objects.
+    ///
+    pub fn nest(identifier: &str, paths: &BTreeSet<String>) -> Vec<String> {
+        std::iter::once(identifier.to_owned())
+            .chain(
+                paths
+                    .iter()
+                    .map(|path| format!("{identifier}{}{path}", Self::SEPARATOR)),
+            )
+            .collect()
+    }
+
+    ///
+    /// Resolves the object paths in the code `block` of the `object`.
+    ///
+    /// `paths` are the paths of all objects nested into the `object`, relative to it.
+    ///
+    pub fn resolve(
+        block: &mut Block,
+        object: &'a str,
+        paths: &'a BTreeSet<String>,
+    ) -> Result<(), ParserError> {
+        let mut resolution = Self {
+            object,
+            paths,
+            error: None,
+        };
+        resolution.visit_block(block);
+        match resolution.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+impl VisitorMut for ObjectPaths<'_> {
+    fn visit_function_call(&mut self, call: &mut FunctionCall) {
+        if self.error.is_some() {
+            return;
+        }
+        if matches!(call.name, Name::DataOffset | Name::DataSize) {
+            if let Some(Expression::Literal(literal)) = call.arguments.first_mut() {
+                if let LexicalLiteral::String(ref mut string) = literal.inner {
+                    if Self::is_path(string.inner.as_str()) {
+                        if !self.paths.contains(string.inner.as_str()) {
+                            self.error = Some(ParserError::InvalidObjectPath {
+                                location: call.location,
+                                path: string.inner.clone(),
+                                object: self.object.to_owned(),
+                            });
+                            return;
+                        }
+                        string.inner = string
+                            .inner
+                            .rsplit(Self::SEPARATOR)
+                            .next()
+                            .expect("Always exists")
+                            .to_owned();
+                    }
+                }
+            }
+        }
+        crate::yul::visitor::walk_function_call_mut(self, call);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ObjectPaths;
+
+    #[test]
+    fn is_path() {
+        assert!(ObjectPaths::is_path("Test_deployed.Dependency"));
+        assert!(!ObjectPaths::is_path("Test_deployed"));
+        assert!(!ObjectPaths::is_path(".metadata"));
+    }
+}
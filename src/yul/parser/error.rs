@@ -51,4 +51,14 @@ pub enum Error {
         /// The invalid identifier.
         found: String,
     },
+    /// The object path in `dataoffset` or `datasize` does not lead to a nested object.
+    #[error("{location} The object path `{path}` is not found in the object `{object}`")]
+    InvalidObjectPath {
+        /// The invalid path location.
+        location: Location,
+        /// The invalid path.
+        path: String,
+        /// The object whose code contains the path.
+        object: String,
+    },
 }
@@ -10,6 +10,7 @@ use num::ToPrimitive;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::prevrandao::Prevrandao;
 use crate::yul::error::Error;
 use crate::yul::lexer::token::lexeme::symbol::Symbol;
 use crate::yul::lexer::token::lexeme::Lexeme;
@@ -484,6 +485,16 @@ impl FunctionCall {
                 )
                 .map(|_| None)
             }
+            Name::MCopy => {
+                let arguments = self.pop_arguments_llvm::<D, 3>(context)?;
+                crate::evmla::assembly::instruction::mcopy::copy(
+                    context,
+                    arguments[0].into_int_value(),
+                    arguments[1].into_int_value(),
+                    arguments[2].into_int_value(),
+                )
+                .map(|_| None)
+            }
 
             Name::SLoad => {
                 let arguments = self.pop_arguments_llvm::<D, 1>(context)?;
@@ -499,6 +510,16 @@ impl FunctionCall {
                 )
                 .map(|_| None)
             }
+            Name::TLoad => anyhow::bail!(
+                "{} Error {}: The `TLOAD` instruction is not supported, since the transient storage (EIP-1153) is not available on EraVM yet",
+                location,
+                crate::r#const::ERROR_CODE_TRANSIENT_STORAGE,
+            ),
+            Name::TStore => anyhow::bail!(
+                "{} Error {}: The `TSTORE` instruction is not supported, since the transient storage (EIP-1153) is not available on EraVM yet",
+                location,
+                crate::r#const::ERROR_CODE_TRANSIENT_STORAGE,
+            ),
             Name::LoadImmutable => {
                 let mut arguments = self.pop_arguments::<D, 1>(context)?;
                 let key = arguments[0].original.take().ok_or_else(|| {
@@ -944,11 +965,18 @@ impl FunctionCall {
 
                 compiler_llvm_context::contract_context::block_hash(context, index).map(Some)
             }
-            Name::Difficulty | Name::Prevrandao => {
-                compiler_llvm_context::contract_context::difficulty(context).map(Some)
-            }
+            Name::Difficulty | Name::Prevrandao => Prevrandao::translate(context),
             Name::CoinBase => compiler_llvm_context::contract_context::coinbase(context).map(Some),
             Name::BaseFee => compiler_llvm_context::contract_context::basefee(context).map(Some),
+            Name::BlobHash => {
+                let _arguments = self.pop_arguments_llvm::<D, 1>(context)?;
+                Ok(Some(context.field_const(0).as_basic_value_enum()))
+            }
+            Name::BlobBaseFee => anyhow::bail!(
+                "{} Error {}: The `BLOBBASEFEE` instruction is not supported, since EraVM transactions do not carry blobs (EIP-4844), so there is no blob gas market",
+                location,
+                crate::r#const::ERROR_CODE_BLOB_BASE_FEE,
+            ),
             Name::MSize => compiler_llvm_context::contract_context::msize(context).map(Some),
 
             Name::Verbatim {
@@ -78,11 +78,17 @@ pub enum Name {
     MStore,
     /// `mem[p] := v & 0xff` (only modifies a single byte)
     MStore8,
+    /// `mem[t…(t+s)) := mem[f…(f+s))`, with the overlapping ranges allowed
+    MCopy,
 
     /// `storage[p]`
     SLoad,
     /// `storage[p] := v`
     SStore,
+    /// `transientStorage[p]`
+    TLoad,
+    /// `transientStorage[p] := v`
+    TStore,
     /// `loadimmutable` storage read
     LoadImmutable,
     /// `setimmutable` storage write
@@ -222,6 +228,10 @@ pub enum Name {
 
     /// the base fee
     BaseFee,
+    /// versioned hash of the `i`th blob of the transaction, `0` if there is no such blob
+    BlobHash,
+    /// the blob base fee
+    BlobBaseFee,
     /// current position in code
     Pc,
     /// like `codecopy(t, f, s)` but take code at address `a`
@@ -362,9 +372,12 @@ impl From<&str> for Name {
             "mload" => Self::MLoad,
             "mstore" => Self::MStore,
             "mstore8" => Self::MStore8,
+            "mcopy" => Self::MCopy,
 
             "sload" => Self::SLoad,
             "sstore" => Self::SStore,
+            "tload" => Self::TLoad,
+            "tstore" => Self::TStore,
             "loadimmutable" => Self::LoadImmutable,
             "setimmutable" => Self::SetImmutable,
 
@@ -424,6 +437,8 @@ impl From<&str> for Name {
             "prevrandao" => Self::Prevrandao,
             "coinbase" => Self::CoinBase,
             "basefee" => Self::BaseFee,
+            "blobhash" => Self::BlobHash,
+            "blobbasefee" => Self::BlobBaseFee,
             "msize" => Self::MSize,
 
             "callcode" => Self::CallCode,
@@ -2,6 +2,7 @@
 //! The YUL object.
 //!
 
+use std::collections::BTreeSet;
 use std::collections::HashSet;
 
 use serde::Deserialize;
@@ -15,6 +16,7 @@ use crate::yul::lexer::token::lexeme::Lexeme;
 use crate::yul::lexer::token::location::Location;
 use crate::yul::lexer::token::Token;
 use crate::yul::lexer::Lexer;
+use crate::yul::object_paths::ObjectPaths;
 use crate::yul::parser::error::Error as ParserError;
 use crate::yul::parser::statement::code::Code;
 
@@ -42,6 +44,16 @@ impl Object {
     /// The element parser.
     ///
     pub fn parse(lexer: &mut Lexer, initial: Option<Token>) -> Result<Self, Error> {
+        Self::parse_nested(lexer, initial).map(|(object, _paths)| object)
+    }
+
+    ///
+    /// Parses the object, also returning the paths of its nested objects relative to it.
+    ///
+    fn parse_nested(
+        lexer: &mut Lexer,
+        initial: Option<Token>,
+    ) -> Result<(Self, BTreeSet<String>), Error> {
         let token = crate::yul::parser::take_or_next(initial, lexer)?;
 
         let location = match token {
@@ -91,9 +103,10 @@ impl Object {
             }
         }
 
-        let code = Code::parse(lexer, None)?;
+        let mut code = Code::parse(lexer, None)?;
         let mut inner_object = None;
         let mut factory_dependencies = HashSet::new();
+        let mut paths = BTreeSet::new();
 
         if !is_runtime_code {
             inner_object = match lexer.peek()? {
@@ -101,7 +114,7 @@ impl Object {
                     lexeme: Lexeme::Keyword(Keyword::Object),
                     ..
                 } => {
-                    let mut object = Self::parse(lexer, None)?;
+                    let (mut object, object_paths) = Self::parse_nested(lexer, None)?;
 
                     if format!("{identifier}_deployed") != object.identifier {
                         return Err(ParserError::InvalidObjectName {
@@ -112,6 +125,7 @@ impl Object {
                         .into());
                     }
 
+                    paths.extend(ObjectPaths::nest(object.identifier.as_str(), &object_paths));
                     factory_dependencies.extend(object.factory_dependencies.drain());
                     Some(Box::new(object))
                 }
@@ -141,7 +155,11 @@ impl Object {
                     lexeme: Lexeme::Keyword(Keyword::Object),
                     ..
                 } => {
-                    let dependency = Self::parse(lexer, Some(token))?;
+                    let (dependency, dependency_paths) = Self::parse_nested(lexer, Some(token))?;
+                    paths.extend(ObjectPaths::nest(
+                        dependency.identifier.as_str(),
+                        &dependency_paths,
+                    ));
                     factory_dependencies.insert(dependency.identifier);
                 }
                 Token {
@@ -162,13 +180,18 @@ impl Object {
             }
         }
 
-        Ok(Self {
-            location,
-            identifier,
-            code,
-            inner_object,
-            factory_dependencies,
-        })
+        ObjectPaths::resolve(&mut code.block, identifier.as_str(), &paths)?;
+
+        Ok((
+            Self {
+                location,
+                identifier,
+                code,
+                inner_object,
+                factory_dependencies,
+            },
+            paths,
+        ))
     }
 }
 
@@ -400,4 +423,70 @@ object "Test" {
             .into())
         );
     }
+
+    #[test]
+    fn object_path() {
+        let input = r#"
+object "Test" {
+    code {
+        {
+            let size := datasize("Test_deployed.Dependency")
+            return(0, size)
+        }
+    }
+    object "Test_deployed" {
+        code {
+            {
+                return(0, 0)
+            }
+        }
+        object "Dependency" {
+            code {
+                {
+                    return(0, 0)
+                }
+            }
+        }
+    }
+}
+    "#;
+
+        let mut lexer = Lexer::new(input.to_owned());
+        let object = Object::parse(&mut lexer, None).expect("Always valid");
+        assert!(format!("{:?}", object.code).contains("\"Dependency\""));
+        assert!(!format!("{:?}", object.code).contains("Test_deployed.Dependency"));
+    }
+
+    #[test]
+    fn error_invalid_object_path() {
+        let input = r#"
+object "Test" {
+    code {
+        {
+            let size := datasize("Test_deployed.Unknown")
+            return(0, size)
+        }
+    }
+    object "Test_deployed" {
+        code {
+            {
+                return(0, 0)
+            }
+        }
+    }
+}
+    "#;
+
+        let mut lexer = Lexer::new(input.to_owned());
+        let result = Object::parse(&mut lexer, None);
+        assert_eq!(
+            result,
+            Err(Error::InvalidObjectPath {
+                location: Location::new(5, 25),
+                path: "Test_deployed.Unknown".to_owned(),
+                object: "Test".to_owned(),
+            }
+            .into())
+        );
+    }
 }
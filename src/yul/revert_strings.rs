@@ -0,0 +1,477 @@
+//!
+//! The revert string identifiers substitution.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use crate::yul::lexer::token::lexeme::literal::integer::Integer as IntegerLiteral;
+use crate::yul::lexer::token::lexeme::literal::Literal as LexicalLiteral;
+use crate::yul::lexer::Lexer;
+use crate::yul::parser::statement::block::Block;
+use crate::yul::parser::statement::expression::function_call::name::Name;
+use crate::yul::parser::statement::expression::function_call::FunctionCall;
+use crate::yul::parser::statement::expression::Expression;
+use crate::yul::parser::statement::object::Object;
+use crate::yul::parser::statement::Statement;
+
+///
+/// The revert string identifiers substitution.
+///
+/// Replaces the revert strings longer than the identifier with the first 4 bytes of their
+/// keccak256 hash. The `Error(string)` encoding is kept, so the existing decoders return the
+/// 4-byte identifier instead of the message, and the messages are reported keyed by the
+/// hexadecimal identifiers.
+///
+/// `solc` stores each string literal in memory with a `store_literal_in_memory_<hash>` function.
+/// A literal is only substituted if each of its ABI encodings flows into a `revert` argument in
+/// the same block, so the strings returned from functions or emitted in events are left intact.
+///
+#[derive(Debug)]
+pub struct RevertStringIds;
+
+impl RevertStringIds {
+    /// The identifier length in bytes.
+    pub const ID_LENGTH: usize = 4;
+
+    /// The literal storing function name prefix.
+    const STORE_LITERAL_PREFIX: &'static str = "store_literal_in_memory_";
+
+    /// The literal copying function name prefix, used for non-revert strings.
+    const COPY_LITERAL_PREFIX: &'static str = "copy_literal_to_memory_";
+
+    /// The literal encoding function name prefix.
+    const ENCODE_LITERAL_PREFIX: &'static str = "abi_encode_t_stringliteral_";
+
+    /// The literal tuple encoding function name prefix.
+    const ENCODE_TUPLE_PREFIX: &'static str = "abi_encode_tuple_";
+
+    /// The string length encoding function name prefix.
+    const STORE_LENGTH_PREFIX: &'static str = "array_storeLengthForEncoding_";
+
+    ///
+    /// Substitutes the revert strings in the deploy and runtime code of the `object`,
+    /// returning the messages keyed by their identifiers.
+    ///
+    pub fn substitute(object: &mut Object) -> anyhow::Result<BTreeMap<String, String>> {
+        let mut ids = Self::substitute_block(&mut object.code.block)?;
+        if let Some(runtime) = object.inner_object.as_mut() {
+            ids.extend(Self::substitute_block(&mut runtime.code.block)?);
+        }
+        Ok(ids)
+    }
+
+    ///
+    /// Substitutes the revert strings in the functions defined in the code `block`.
+    ///
+    fn substitute_block(block: &mut Block) -> anyhow::Result<BTreeMap<String, String>> {
+        let functions: BTreeSet<String> = block
+            .statements
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::FunctionDefinition(function) => Some(function.identifier.to_owned()),
+                _ => None,
+            })
+            .collect();
+        let mut hashes: BTreeSet<String> = functions
+            .iter()
+            .filter_map(|name| name.strip_prefix(Self::STORE_LITERAL_PREFIX))
+            .filter(|hash| {
+                !functions.contains(format!("{}{hash}", Self::COPY_LITERAL_PREFIX).as_str())
+            })
+            .map(|hash| hash.to_owned())
+            .collect();
+
+        let mut excluded = BTreeSet::new();
+        Self::for_each_block(block, &mut |block| {
+            for (index, statement) in block.statements.iter().enumerate() {
+                for call in Self::statement_calls(statement).into_iter() {
+                    let name = match call.name {
+                        Name::UserDefined(ref name)
+                            if name.starts_with(Self::ENCODE_TUPLE_PREFIX) =>
+                        {
+                            name
+                        }
+                        _ => continue,
+                    };
+                    if Self::flows_into_revert(&block.statements[index..]) {
+                        continue;
+                    }
+                    for hash in hashes.iter() {
+                        if name.contains(hash.as_str()) {
+                            excluded.insert(hash.to_owned());
+                        }
+                    }
+                }
+            }
+        });
+        hashes.retain(|hash| !excluded.contains(hash));
+
+        let mut ids = BTreeMap::new();
+        for hash in hashes.into_iter() {
+            let store_name = format!("{}{hash}", Self::STORE_LITERAL_PREFIX);
+            let encode_prefix = format!("{}{hash}", Self::ENCODE_LITERAL_PREFIX);
+            if functions
+                .iter()
+                .filter(|name| name.starts_with(encode_prefix.as_str()))
+                .count()
+                != 1
+            {
+                continue;
+            }
+
+            let mut message = Vec::new();
+            let mut argument = None;
+            for statement in block.statements.iter() {
+                if let Statement::FunctionDefinition(function) = statement {
+                    if function.identifier == store_name {
+                        message = Self::literal_chunks(&function.body)?;
+                        argument = function
+                            .arguments
+                            .first()
+                            .map(|argument| argument.inner.to_owned());
+                    }
+                }
+            }
+            let argument = match argument {
+                Some(argument) if message.len() > Self::ID_LENGTH => argument,
+                _ => continue,
+            };
+
+            let is_patched = block
+                .statements
+                .iter_mut()
+                .any(|statement| match statement {
+                    Statement::FunctionDefinition(function)
+                        if function.identifier.starts_with(encode_prefix.as_str()) =>
+                    {
+                        Self::patch_length(&mut function.body)
+                    }
+                    _ => false,
+                });
+            if !is_patched {
+                continue;
+            }
+
+            let id = hash[..Self::ID_LENGTH * 2].to_owned();
+            let body = Self::parse_block(
+                format!(
+                    "{{ mstore({argument}, shl({}, 0x{id})) }}",
+                    compiler_common::BIT_LENGTH_FIELD - Self::ID_LENGTH * 8
+                )
+                .as_str(),
+            )?;
+            for statement in block.statements.iter_mut() {
+                if let Statement::FunctionDefinition(function) = statement {
+                    if function.identifier == store_name {
+                        function.body.statements = body.statements.clone();
+                    }
+                }
+            }
+            ids.insert(id, String::from_utf8_lossy(message.as_slice()).to_string());
+        }
+
+        Ok(ids)
+    }
+
+    ///
+    /// Concatenates the string literal chunks stored by the `store_literal_in_memory_*` function.
+    ///
+    fn literal_chunks(body: &Block) -> anyhow::Result<Vec<u8>> {
+        let mut message = Vec::new();
+        for statement in body.statements.iter() {
+            if let Statement::Expression(Expression::FunctionCall(call)) = statement {
+                if let Some(Expression::Literal(literal)) = call.arguments.get(1) {
+                    if let LexicalLiteral::String(ref string) = literal.inner {
+                        message.extend(string.to_bytes()?);
+                    }
+                }
+            }
+        }
+        Ok(message)
+    }
+
+    ///
+    /// Replaces the encoded length with that of the identifier, and the encoded size with one word.
+    ///
+    /// Returns `false` without changes if the function does not match the `solc` encoding pattern.
+    ///
+    fn patch_length(body: &mut Block) -> bool {
+        let mut length = None;
+        let mut size = None;
+        for (index, statement) in body.statements.iter().enumerate() {
+            if let Statement::Assignment(assignment) = statement {
+                if let Expression::FunctionCall(ref call) = assignment.initializer {
+                    match call.name {
+                        Name::UserDefined(ref name)
+                            if name.starts_with(Self::STORE_LENGTH_PREFIX) =>
+                        {
+                            length = Some(index)
+                        }
+                        Name::Add => size = Some(index),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        let (length, size) = match (length, size) {
+            (Some(length), Some(size)) => (length, size),
+            _ => return false,
+        };
+
+        for (index, value) in [
+            (length, Self::ID_LENGTH),
+            (size, compiler_common::BYTE_LENGTH_FIELD),
+        ] {
+            if let Statement::Assignment(ref mut assignment) = body.statements[index] {
+                if let Expression::FunctionCall(ref mut call) = assignment.initializer {
+                    if let Some(Expression::Literal(literal)) = call.arguments.get_mut(1) {
+                        literal.inner = LexicalLiteral::Integer(IntegerLiteral::Decimal {
+                            inner: value.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    ///
+    /// Calls `f` for the `block` and all blocks nested into it, including the function bodies.
+    ///
+    fn for_each_block(block: &Block, f: &mut dyn FnMut(&Block)) {
+        f(block);
+        for statement in block.statements.iter() {
+            match statement {
+                Statement::Block(block) => Self::for_each_block(block, f),
+                Statement::FunctionDefinition(function) => Self::for_each_block(&function.body, f),
+                Statement::IfConditional(conditional) => {
+                    Self::for_each_block(&conditional.block, f)
+                }
+                Statement::Switch(switch) => {
+                    for case in switch.cases.iter() {
+                        Self::for_each_block(&case.block, f);
+                    }
+                    if let Some(default) = switch.default.as_ref() {
+                        Self::for_each_block(default, f);
+                    }
+                }
+                Statement::ForLoop(for_loop) => {
+                    Self::for_each_block(&for_loop.initializer, f);
+                    Self::for_each_block(&for_loop.finalizer, f);
+                    Self::for_each_block(&for_loop.body, f);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    ///
+    /// Returns the function calls in the `statement`, excluding the nested blocks.
+    ///
+    fn statement_calls(statement: &Statement) -> Vec<&FunctionCall> {
+        let mut calls = Vec::new();
+        let expression = match statement {
+            Statement::Expression(expression) => Some(expression),
+            Statement::VariableDeclaration(declaration) => declaration.expression.as_ref(),
+            Statement::Assignment(assignment) => Some(&assignment.initializer),
+            Statement::IfConditional(conditional) => Some(&conditional.condition),
+            Statement::Switch(switch) => Some(&switch.expression),
+            _ => None,
+        };
+        if let Some(expression) = expression {
+            Self::collect_calls(expression, &mut calls);
+        }
+        calls
+    }
+
+    ///
+    /// Checks whether the value computed by the first of the `statements` flows into a `revert`
+    /// argument, either directly or via the variables it is assigned to, which are then used by
+    /// one of the following `statements`.
+    ///
+    fn flows_into_revert(statements: &[Statement]) -> bool {
+        let bindings = match statements.first() {
+            Some(Statement::Expression(Expression::FunctionCall(call)))
+                if call.name == Name::Revert =>
+            {
+                return true;
+            }
+            Some(Statement::VariableDeclaration(declaration)) => declaration.bindings.as_slice(),
+            Some(Statement::Assignment(assignment)) => assignment.bindings.as_slice(),
+            _ => return false,
+        };
+        statements[1..].iter().any(|statement| match statement {
+            Statement::Expression(Expression::FunctionCall(call)) if call.name == Name::Revert => {
+                call.arguments.iter().any(|argument| {
+                    bindings
+                        .iter()
+                        .any(|binding| Self::references(argument, binding.inner.as_str()))
+                })
+            }
+            _ => false,
+        })
+    }
+
+    ///
+    /// Checks whether the `expression` references the variable with the specified `name`.
+    ///
+    fn references(expression: &Expression, name: &str) -> bool {
+        match expression {
+            Expression::Identifier(identifier) => identifier.inner == name,
+            Expression::FunctionCall(call) => call
+                .arguments
+                .iter()
+                .any(|argument| Self::references(argument, name)),
+            Expression::Literal(_) => false,
+        }
+    }
+
+    ///
+    /// Collects the function calls in the `expression`, including the nested ones.
+    ///
+    fn collect_calls<'a>(expression: &'a Expression, calls: &mut Vec<&'a FunctionCall>) {
+        if let Expression::FunctionCall(call) = expression {
+            calls.push(call);
+            for argument in call.arguments.iter() {
+                Self::collect_calls(argument, calls);
+            }
+        }
+    }
+
+    ///
+    /// Parses the Yul block.
+    ///
+    fn parse_block(code: &str) -> anyhow::Result<Block> {
+        let mut lexer = Lexer::new(code.to_owned());
+        Block::parse(&mut lexer, None)
+            .map_err(|error| anyhow::anyhow!("Revert string identifier parsing error: {:?}", error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::yul::lexer::Lexer;
+    use crate::yul::parser::statement::object::Object;
+    use crate::yul::parser::statement::Statement;
+
+    use super::RevertStringIds;
+
+    #[test]
+    fn substitute() {
+        let input = r#"
+object "Test" {
+    code {
+        {
+            return(0, 0)
+        }
+    }
+    object "Test_deployed" {
+        code {
+            {
+                if iszero(callvalue()) {
+                    let memPtr := mload(64)
+                    mstore(memPtr, shl(229, 4594637))
+                    let end := abi_encode_tuple_t_stringliteral_a1b2c3d4e5f6__to_t_string_memory_ptr__fromStack(add(memPtr, 4))
+                    revert(memPtr, sub(end, memPtr))
+                }
+                let memPos := mload(64)
+                let end := abi_encode_tuple_t_stringliteral_f6e5d4c3b2a1__to_t_string_memory_ptr__fromStack(memPos)
+                log1(memPos, sub(end, memPos), 0)
+                return(0, 0)
+            }
+            function store_literal_in_memory_a1b2c3d4e5f6(memPtr) {
+                mstore(add(memPtr, 0), "Ownable: caller is not the owne")
+                mstore(add(memPtr, 31), "r")
+            }
+            function abi_encode_t_stringliteral_a1b2c3d4e5f6_to_t_string_memory_ptr_fromStack(pos) -> end {
+                pos := array_storeLengthForEncoding_t_string_memory_ptr_fromStack(pos, 32)
+                store_literal_in_memory_a1b2c3d4e5f6(pos)
+                end := add(pos, 32)
+            }
+            function store_literal_in_memory_f6e5d4c3b2a1(memPtr) {
+                mstore(add(memPtr, 0), "Transferred to the new owner")
+            }
+            function abi_encode_t_stringliteral_f6e5d4c3b2a1_to_t_string_memory_ptr_fromStack(pos) -> end {
+                pos := array_storeLengthForEncoding_t_string_memory_ptr_fromStack(pos, 28)
+                store_literal_in_memory_f6e5d4c3b2a1(pos)
+                end := add(pos, 32)
+            }
+        }
+    }
+}
+    "#;
+
+        let mut lexer = Lexer::new(input.to_owned());
+        let mut object = Object::parse(&mut lexer, None).expect("Always valid");
+
+        let ids = RevertStringIds::substitute(&mut object).expect("Always valid");
+        let runtime = object.inner_object.as_ref().expect("Always exists");
+        let store_body = runtime
+            .code
+            .block
+            .statements
+            .iter()
+            .find_map(|statement| match statement {
+                Statement::FunctionDefinition(function)
+                    if function.identifier == "store_literal_in_memory_a1b2c3d4e5f6" =>
+                {
+                    Some(&function.body)
+                }
+                _ => None,
+            })
+            .expect("Always exists");
+        assert_eq!(store_body.statements.len(), 1);
+        assert_eq!(
+            ids.into_iter().collect::<Vec<(String, String)>>(),
+            vec![(
+                "a1b2c3d4".to_owned(),
+                "Ownable: caller is not the owner".to_owned()
+            )]
+        );
+    }
+
+    #[test]
+    fn shared_with_event() {
+        let input = r#"
+object "Test" {
+    code {
+        {
+            return(0, 0)
+        }
+    }
+    object "Test_deployed" {
+        code {
+            {
+                let memPos := mload(64)
+                let end := abi_encode_tuple_t_stringliteral_a1b2c3d4e5f6__to_t_string_memory_ptr__fromStack(memPos)
+                log1(memPos, sub(end, memPos), 0)
+                if iszero(callvalue()) {
+                    let memPtr := mload(64)
+                    mstore(memPtr, shl(229, 4594637))
+                    let end_1 := abi_encode_tuple_t_stringliteral_a1b2c3d4e5f6__to_t_string_memory_ptr__fromStack(add(memPtr, 4))
+                    revert(memPtr, sub(end_1, memPtr))
+                }
+                revert(0, 0)
+            }
+            function store_literal_in_memory_a1b2c3d4e5f6(memPtr) {
+                mstore(add(memPtr, 0), "Ownable: caller is not the owne")
+                mstore(add(memPtr, 31), "r")
+            }
+            function abi_encode_t_stringliteral_a1b2c3d4e5f6_to_t_string_memory_ptr_fromStack(pos) -> end {
+                pos := array_storeLengthForEncoding_t_string_memory_ptr_fromStack(pos, 32)
+                store_literal_in_memory_a1b2c3d4e5f6(pos)
+                end := add(pos, 32)
+            }
+        }
+    }
+}
+    "#;
+
+        let mut lexer = Lexer::new(input.to_owned());
+        let mut object = Object::parse(&mut lexer, None).expect("Always valid");
+
+        let ids = RevertStringIds::substitute(&mut object).expect("Always valid");
+        assert!(ids.is_empty());
+    }
+}
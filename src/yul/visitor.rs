@@ -0,0 +1,429 @@
+//!
+//! The Yul AST visitor.
+//!
+
+use crate::yul::parser::statement::block::Block;
+use crate::yul::parser::statement::expression::function_call::FunctionCall;
+use crate::yul::parser::statement::expression::Expression;
+use crate::yul::parser::statement::function_definition::FunctionDefinition;
+use crate::yul::parser::statement::object::Object;
+use crate::yul::parser::statement::Statement;
+
+///
+/// The Yul AST visitor.
+///
+/// Lets the analysis tools traverse the Yul AST, e.g. from the `Plugin::visit_yul` hook, without
+/// matching every node. Each method visits the children of its node by default, so an
+/// implementation only overrides the nodes it is interested in, calling the corresponding `walk_*`
+/// function to keep traversing the children.
+///
+pub trait Visitor {
+    ///
+    /// Visits the `object` and its inner object.
+    ///
+    fn visit_object(&mut self, object: &Object) {
+        walk_object(self, object);
+    }
+
+    ///
+    /// Visits the `block` statements.
+    ///
+    fn visit_block(&mut self, block: &Block) {
+        walk_block(self, block);
+    }
+
+    ///
+    /// Visits the `statement` and its nested blocks and expressions.
+    ///
+    fn visit_statement(&mut self, statement: &Statement) {
+        walk_statement(self, statement);
+    }
+
+    ///
+    /// Visits the `function` body.
+    ///
+    fn visit_function_definition(&mut self, function: &FunctionDefinition) {
+        walk_function_definition(self, function);
+    }
+
+    ///
+    /// Visits the `expression` and its subexpressions.
+    ///
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression);
+    }
+
+    ///
+    /// Visits the `call` arguments.
+    ///
+    fn visit_function_call(&mut self, call: &FunctionCall) {
+        walk_function_call(self, call);
+    }
+}
+
+///
+/// Visits the code block of the `object` and its inner object.
+///
+pub fn walk_object<V>(visitor: &mut V, object: &Object)
+where
+    V: Visitor + ?Sized,
+{
+    visitor.visit_block(&object.code.block);
+    if let Some(inner_object) = object.inner_object.as_deref() {
+        visitor.visit_object(inner_object);
+    }
+}
+
+///
+/// Visits the statements of the `block`.
+///
+pub fn walk_block<V>(visitor: &mut V, block: &Block)
+where
+    V: Visitor + ?Sized,
+{
+    for statement in block.statements.iter() {
+        visitor.visit_statement(statement);
+    }
+}
+
+///
+/// Visits the nested blocks and expressions of the `statement`.
+///
+pub fn walk_statement<V>(visitor: &mut V, statement: &Statement)
+where
+    V: Visitor + ?Sized,
+{
+    match statement {
+        Statement::Object(object) => visitor.visit_object(object),
+        Statement::Code(code) => visitor.visit_block(&code.block),
+        Statement::Block(block) => visitor.visit_block(block),
+        Statement::Expression(expression) => visitor.visit_expression(expression),
+        Statement::FunctionDefinition(function) => visitor.visit_function_definition(function),
+        Statement::VariableDeclaration(declaration) => {
+            if let Some(expression) = declaration.expression.as_ref() {
+                visitor.visit_expression(expression);
+            }
+        }
+        Statement::Assignment(assignment) => visitor.visit_expression(&assignment.initializer),
+        Statement::IfConditional(conditional) => {
+            visitor.visit_expression(&conditional.condition);
+            visitor.visit_block(&conditional.block);
+        }
+        Statement::Switch(switch) => {
+            visitor.visit_expression(&switch.expression);
+            for case in switch.cases.iter() {
+                visitor.visit_block(&case.block);
+            }
+            if let Some(default) = switch.default.as_ref() {
+                visitor.visit_block(default);
+            }
+        }
+        Statement::ForLoop(for_loop) => {
+            visitor.visit_block(&for_loop.initializer);
+            visitor.visit_expression(&for_loop.condition);
+            visitor.visit_block(&for_loop.finalizer);
+            visitor.visit_block(&for_loop.body);
+        }
+        Statement::Continue(_) | Statement::Break(_) | Statement::Leave(_) => {}
+    }
+}
+
+///
+/// Visits the body of the `function`.
+///
+pub fn walk_function_definition<V>(visitor: &mut V, function: &FunctionDefinition)
+where
+    V: Visitor + ?Sized,
+{
+    visitor.visit_block(&function.body);
+}
+
+///
+/// Visits the function call of the `expression`, if any.
+///
+pub fn walk_expression<V>(visitor: &mut V, expression: &Expression)
+where
+    V: Visitor + ?Sized,
+{
+    if let Expression::FunctionCall(call) = expression {
+        visitor.visit_function_call(call);
+    }
+}
+
+///
+/// Visits the arguments of the `call`.
+///
+pub fn walk_function_call<V>(visitor: &mut V, call: &FunctionCall)
+where
+    V: Visitor + ?Sized,
+{
+    for argument in call.arguments.iter() {
+        visitor.visit_expression(argument);
+    }
+}
+
+///
+/// The Yul AST mutable visitor.
+///
+/// Lets the transformations rewrite the Yul AST in place, e.g. from the `Plugin::visit_yul` hook,
+/// in the same order as `Visitor`. Each method visits the children of its node by default,
+/// calling the corresponding `walk_*_mut` function.
+///
+pub trait VisitorMut {
+    ///
+    /// Visits the `object` and its inner object.
+    ///
+    fn visit_object(&mut self, object: &mut Object) {
+        walk_object_mut(self, object);
+    }
+
+    ///
+    /// Visits the `block` statements.
+    ///
+    fn visit_block(&mut self, block: &mut Block) {
+        walk_block_mut(self, block);
+    }
+
+    ///
+    /// Visits the `statement` and its nested blocks and expressions.
+    ///
+    fn visit_statement(&mut self, statement: &mut Statement) {
+        walk_statement_mut(self, statement);
+    }
+
+    ///
+    /// Visits the `function` body.
+    ///
+    fn visit_function_definition(&mut self, function: &mut FunctionDefinition) {
+        walk_function_definition_mut(self, function);
+    }
+
+    ///
+    /// Visits the `expression` and its subexpressions.
+    ///
+    fn visit_expression(&mut self, expression: &mut Expression) {
+        walk_expression_mut(self, expression);
+    }
+
+    ///
+    /// Visits the `call` arguments.
+    ///
+    fn visit_function_call(&mut self, call: &mut FunctionCall) {
+        walk_function_call_mut(self, call);
+    }
+}
+
+///
+/// Visits the code block of the `object` and its inner object.
+///
+pub fn walk_object_mut<V>(visitor: &mut V, object: &mut Object)
+where
+    V: VisitorMut + ?Sized,
+{
+    visitor.visit_block(&mut object.code.block);
+    if let Some(inner_object) = object.inner_object.as_deref_mut() {
+        visitor.visit_object(inner_object);
+    }
+}
+
+///
+/// Visits the statements of the `block`.
+///
+pub fn walk_block_mut<V>(visitor: &mut V, block: &mut Block)
+where
+    V: VisitorMut + ?Sized,
+{
+    for statement in block.statements.iter_mut() {
+        visitor.visit_statement(statement);
+    }
+}
+
+///
+/// Visits the nested blocks and expressions of the `statement`.
+///
+pub fn walk_statement_mut<V>(visitor: &mut V, statement: &mut Statement)
+where
+    V: VisitorMut + ?Sized,
+{
+    match statement {
+        Statement::Object(object) => visitor.visit_object(object),
+        Statement::Code(code) => visitor.visit_block(&mut code.block),
+        Statement::Block(block) => visitor.visit_block(block),
+        Statement::Expression(expression) => visitor.visit_expression(expression),
+        Statement::FunctionDefinition(function) => visitor.visit_function_definition(function),
+        Statement::VariableDeclaration(declaration) => {
+            if let Some(expression) = declaration.expression.as_mut() {
+                visitor.visit_expression(expression);
+            }
+        }
+        Statement::Assignment(assignment) => visitor.visit_expression(&mut assignment.initializer),
+        Statement::IfConditional(conditional) => {
+            visitor.visit_expression(&mut conditional.condition);
+            visitor.visit_block(&mut conditional.block);
+        }
+        Statement::Switch(switch) => {
+            visitor.visit_expression(&mut switch.expression);
+            for case in switch.cases.iter_mut() {
+                visitor.visit_block(&mut case.block);
+            }
+            if let Some(default) = switch.default.as_mut() {
+                visitor.visit_block(default);
+            }
+        }
+        Statement::ForLoop(for_loop) => {
+            visitor.visit_block(&mut for_loop.initializer);
+            visitor.visit_expression(&mut for_loop.condition);
+            visitor.visit_block(&mut for_loop.finalizer);
+            visitor.visit_block(&mut for_loop.body);
+        }
+        Statement::Continue(_) | Statement::Break(_) | Statement::Leave(_) => {}
+    }
+}
+
+///
+/// Visits the body of the `function`.
+///
+pub fn walk_function_definition_mut<V>(visitor: &mut V, function: &mut FunctionDefinition)
+where
+    V: VisitorMut + ?Sized,
+{
+    visitor.visit_block(&mut function.body);
+}
+
+///
+/// Visits the function call of the `expression`, if any.
+///
+pub fn walk_expression_mut<V>(visitor: &mut V, expression: &mut Expression)
+where
+    V: VisitorMut + ?Sized,
+{
+    if let Expression::FunctionCall(call) = expression {
+        visitor.visit_function_call(call);
+    }
+}
+
+///
+/// Visits the arguments of the `call`.
+///
+pub fn walk_function_call_mut<V>(visitor: &mut V, call: &mut FunctionCall)
+where
+    V: VisitorMut + ?Sized,
+{
+    for argument in call.arguments.iter_mut() {
+        visitor.visit_expression(argument);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::yul::lexer::Lexer;
+    use crate::yul::parser::statement::expression::function_call::name::Name;
+    use crate::yul::parser::statement::expression::function_call::FunctionCall;
+    use crate::yul::parser::statement::function_definition::FunctionDefinition;
+    use crate::yul::parser::statement::object::Object;
+
+    use super::Visitor;
+    use super::VisitorMut;
+
+    ///
+    /// Counts the `sstore` calls, and collects the function names.
+    ///
+    #[derive(Default)]
+    struct Counter {
+        /// The `sstore` calls.
+        sstores: usize,
+        /// The function names.
+        functions: Vec<String>,
+    }
+
+    impl Visitor for Counter {
+        fn visit_function_definition(&mut self, function: &FunctionDefinition) {
+            self.functions.push(function.identifier.to_owned());
+            super::walk_function_definition(self, function);
+        }
+
+        fn visit_function_call(&mut self, call: &FunctionCall) {
+            if call.name == Name::SStore {
+                self.sstores += 1;
+            }
+            super::walk_function_call(self, call);
+        }
+    }
+
+    #[test]
+    fn traversal() {
+        let input = r#"
+object "Test" {
+    code {
+        {
+            sstore(0, 1)
+            return(0, 0)
+        }
+    }
+    object "Test_deployed" {
+        code {
+            {
+                for { let i := 0 } lt(i, 2) { i := add(i, 1) } {
+                    if callvalue() { set(i) }
+                }
+                return(0, 0)
+            }
+            function set(slot) { sstore(slot, add(sload(slot), 1)) }
+        }
+    }
+}
+    "#;
+
+        let mut lexer = Lexer::new(input.to_owned());
+        let object = Object::parse(&mut lexer, None).expect("Always valid");
+        let mut counter = Counter::default();
+        counter.visit_object(&object);
+
+        assert_eq!(counter.sstores, 2);
+        assert_eq!(counter.functions, vec!["set".to_owned()]);
+    }
+
+    ///
+    /// Replaces the `sstore` calls with `tstore`.
+    ///
+    struct Transient;
+
+    impl VisitorMut for Transient {
+        fn visit_function_call(&mut self, call: &mut FunctionCall) {
+            if call.name == Name::SStore {
+                call.name = Name::TStore;
+            }
+            super::walk_function_call_mut(self, call);
+        }
+    }
+
+    #[test]
+    fn mutation() {
+        let input = r#"
+object "Test" {
+    code {
+        {
+            sstore(0, 1)
+            return(0, 0)
+        }
+    }
+    object "Test_deployed" {
+        code {
+            {
+                if callvalue() { sstore(1, sload(1)) }
+                return(0, 0)
+            }
+        }
+    }
+}
+    "#;
+
+        let mut lexer = Lexer::new(input.to_owned());
+        let mut object = Object::parse(&mut lexer, None).expect("Always valid");
+        Transient.visit_object(&mut object);
+
+        let mut counter = Counter::default();
+        counter.visit_object(&object);
+        assert_eq!(counter.sstores, 0);
+    }
+}
@@ -2,6 +2,7 @@
 //! Solidity to zkEVM compiler arguments.
 //!
 
+use std::path::Path;
 use std::path::PathBuf;
 
 use structopt::StructOpt;
@@ -21,6 +22,22 @@ pub struct Arguments {
     #[structopt(long = "version")]
     pub version: bool,
 
+    /// Print the EVM legacy assembly instructions supported and rejected by the translator, and exit.
+    #[structopt(long = "print-supported-instructions", hidden = true)]
+    pub print_supported_instructions: bool,
+
+    /// Print the JSON map of the supported features, e.g. the languages, standard JSON input keys,
+    /// output selection flags, and instruction policies, and exit.
+    /// Lets the tooling detect the features instead of comparing the compiler versions.
+    #[structopt(long = "capabilities")]
+    pub capabilities: bool,
+
+    /// Run only the `solc` analysis stage, print the JSON list of the contracts, libraries, and
+    /// interfaces found with their deployability and direct creation dependencies, and exit.
+    /// Only available in the default Solidity mode.
+    #[structopt(long = "list-contracts")]
+    pub list_contracts: bool,
+
     /// Specify the input file paths.
     /// Multiple Solidity files can be passed in the default Solidity mode.
     /// Yul and LLVM IR modes currently support only a single file.
@@ -51,8 +68,22 @@ pub struct Arguments {
     #[structopt(long = "overwrite")]
     pub overwrite: bool,
 
+    /// Set the format version of the emitted JSON artifacts. Available arguments: `1`, `2`.
+    /// Version 1, the default one, is the unversioned format of older releases, kept for the existing parsers.
+    /// Version 2 stamps the artifacts with the `formatVersion` field and must be selected explicitly.
+    #[structopt(long = "format-version")]
+    pub format_version: Option<String>,
+
+    /// Set the file naming of the output directory. Available arguments: `zksolc`, `solc`.
+    /// `zksolc`, the default one, names the artifacts after the source file and contract, e.g. `Test.sol:Test.zbin`.
+    /// `solc` mimics `solc -o`: `Test.bin` with the hexadecimal bytecode, `Test.abi`, and `Test_meta.json`.
+    /// Requires `--output-dir`.
+    #[structopt(long = "output-layout")]
+    pub output_layout: Option<String>,
+
     /// Set the optimization parameter -O[0 | 1 | 2 | 3 | s | z].
     /// Use `3` for best performance and `z` for minimal size.
+    /// Can be set with the `ZKSOLC_OPTIMIZATION` environment variable.
     #[structopt(short = "O", long = "optimization")]
     pub optimization: Option<char>,
 
@@ -63,6 +94,7 @@ pub struct Arguments {
     pub disable_solc_optimizer: bool,
 
     /// Specify the path to the `solc` executable. By default, the one in `${PATH}` is used.
+    /// Can be set with the `ZKSOLC_SOLC` environment variable.
     /// Yul mode: `solc` is used for source code validation, as `zksolc` itself assumes that the input Yul is valid.
     /// LLVM IR mode: `solc` is unused.
     #[structopt(long = "solc")]
@@ -83,12 +115,71 @@ pub struct Arguments {
     #[structopt(long = "standard-json")]
     pub standard_json: bool,
 
+    /// Read a standard JSON input per line from stdin until it is closed, and write an output per line to stdout.
+    /// The `solc` version, worker processes, and outputs of the inputs with inline sources are reused across the inputs,
+    /// whereas `solc` itself is still run once per input.
+    /// Only supported in standard JSON mode.
+    #[structopt(long = "batch")]
+    pub batch: bool,
+
+    /// Delegate the compilation to the compile daemon of the current user, if it is running.
+    /// Only the inputs with all the sources inline are delegated, if the daemon uses the same `--solc` value.
+    /// Only supported in standard JSON mode. Start the daemon with `zksolc daemon`.
+    #[structopt(long = "daemon")]
+    pub daemon: bool,
+
+    /// Write the verification bundle of each compiled contract into the specified directory.
+    /// The bundle pins the compiler versions, flags, and standard JSON input, and is replayed with `zksolc replay`.
+    /// Only supported in standard JSON mode without batching, with all the sources inline.
+    /// In the default Solidity mode, use `--emit verify-bundles` with `--output-dir`.
+    #[structopt(long = "verify-bundles", parse(from_os_str))]
+    pub verify_bundles: Option<PathBuf>,
+
+    /// Allow fetching the standard JSON input sources specified by `http://` and IPFS URLs in their `urls`.
+    /// Such sources must specify the `keccak256` hash, which is verified after fetching.
+    /// HTTPS is not supported, so use a plain HTTP mirror or a local proxy terminating TLS.
+    /// The imports are not resolved by URL, so the imported sources must be listed in the input as well.
+    #[structopt(long = "allow-network")]
+    pub allow_network: bool,
+
+    /// Set the HTTP gateway used to fetch the IPFS sources, e.g. `http://127.0.0.1:8080` of a local IPFS node.
+    /// The IPFS sources are rejected if it is not set.
+    #[structopt(long = "ipfs-gateway")]
+    pub ipfs_gateway: Option<String>,
+
+    /// Set the number of parallel compilation jobs. By default, the number of logical CPUs is used.
+    /// Can be set with the `ZKSOLC_JOBS` environment variable.
+    /// The environment variables are overridden by the flags passed explicitly.
+    #[structopt(short = "j", long = "jobs")]
+    pub jobs: Option<usize>,
+
+    /// Forbid the file system access outside of the explicitly specified paths.
+    /// Requires the `--solc` executable path instead of looking it up in `${PATH}`, and `--base-path` in Solidity modes.
+    /// The sources are only read from the input files and the base, include, and allowed paths,
+    /// and nothing is written outside of the output, debug output, artifact store, and verification bundle directories.
+    /// Network access is not allowed.
+    #[structopt(long = "hermetic")]
+    pub hermetic: bool,
+
     /// Switch to Yul mode.
-    /// Only one input Yul file is allowed.
+    /// Only one input Yul file is allowed. The output of `solc --ir-optimized` with a single
+    /// contract is also accepted.
     /// Cannot be used with combined and standard JSON modes.
     #[structopt(long = "yul")]
     pub yul: bool,
 
+    /// Set the Yul object used as the deploy code.
+    /// By default, the upper-level object is used.
+    /// Only for Yul mode.
+    #[structopt(long = "yul-deploy-object")]
+    pub yul_deploy_object: Option<String>,
+
+    /// Set the nested Yul object used as the runtime code.
+    /// By default, the nested `<deploy>_deployed` object is used.
+    /// Only for Yul mode.
+    #[structopt(long = "yul-runtime-object")]
+    pub yul_runtime_object: Option<String>,
+
     /// Switch to LLVM IR mode.
     /// Only one input LLVM IR file is allowed.
     /// Cannot be used with combined and standard JSON modes.
@@ -101,12 +192,57 @@ pub struct Arguments {
     #[structopt(long = "zkasm")]
     pub zkasm: bool,
 
+    /// Set the compilation target. Available arguments: `eravm`, `evm`.
+    /// Only `eravm` is currently supported, which is the default.
+    #[structopt(long = "target")]
+    pub target: Option<String>,
+
     /// Forcibly switch to EVM legacy assembly pipeline.
     /// It is useful for older revisions of `solc` 0.8, where Yul was considered highly experimental
     /// and contained more bugs than today.
     #[structopt(long = "force-evmla")]
     pub force_evmla: bool,
 
+    /// Allow `solc` versions newer than the last supported one, e.g. patch releases the compiler
+    /// has not been updated for yet. The compiler may not be aware of the IR changes in such
+    /// versions, so a warning is emitted and the version is recorded into the contract metadata.
+    #[structopt(long = "force")]
+    pub force: bool,
+
+    /// Spill the deepest stack slots of EVM legacy assembly functions to memory, if the function
+    /// stack size exceeds the specified threshold. Mitigates the register pressure caused by deep stack juggling.
+    /// The spilling is never enabled automatically, as the stack juggling is not detected, so the
+    /// threshold must be set manually for the affected contracts.
+    /// In standard JSON mode, use the `evmlaStackSpillThreshold` setting.
+    #[structopt(long = "evmla-stack-spill-threshold")]
+    pub evmla_stack_spill_threshold: Option<usize>,
+
+    /// Instrument the external function with the specified signature, e.g. `transfer(address,uint256)`,
+    /// with a reentrancy guard. Can be specified multiple times. Only supported in the Yul pipeline.
+    /// The `view` and `pure` functions are rejected, as the lock is written to the storage.
+    /// In standard JSON mode, use the `reentrancyGuard` setting.
+    #[structopt(long = "reentrancy-guard")]
+    pub reentrancy_guard: Vec<String>,
+
+    /// Order the external function dispatch and the emitted functions by selector instead of
+    /// the source order, so moving the code around does not change the bytecode.
+    /// Only supported in the Yul pipeline. In standard JSON mode, use the `orderBySelector` setting.
+    #[structopt(long = "order-by-selector")]
+    pub order_by_selector: bool,
+
+    /// Reject the constructs translated with Era-specific approximations of the gas accounting,
+    /// code introspection, and precompiles, instead of compiling them silently.
+    /// Only available in the Solidity modes. In standard JSON mode, use the `strict` setting.
+    #[structopt(long = "strict")]
+    pub strict: bool,
+
+    /// Check the contracts implementing the identifying functions of ERC-20, ERC-721, or ERC-1155
+    /// for conformance to the standard interface, reporting the missing or mistyped functions and
+    /// events as warnings coded with the standard name, e.g. `ERC20`.
+    /// Only available in the Solidity modes. In standard JSON mode, use the `ercChecks` setting.
+    #[structopt(long = "erc-checks")]
+    pub erc_checks: bool,
+
     /// Enable system contract compilation mode.
     /// In this mode zkEVM extensions are enabled. For example, calls to addresses `0xFFFF` and below
     /// are substituted by special zkEVM instructions.
@@ -120,6 +256,13 @@ pub struct Arguments {
     #[structopt(long = "metadata-hash")]
     pub metadata_hash: Option<String>,
 
+    /// Set where the metadata hash is placed in the bytecode.
+    /// Available arguments: `appended` (the last word, the default), `constant-section` (the first constant).
+    /// Only available in the default Solidity and combined JSON modes.
+    /// In standard JSON mode, use the `metadata.hashPlacement` setting.
+    #[structopt(long = "metadata-hash-placement")]
+    pub metadata_hash_placement: Option<String>,
+
     /// Output zkEVM assembly of the contracts.
     #[structopt(long = "asm")]
     pub output_assembly: bool,
@@ -128,11 +271,171 @@ pub struct Arguments {
     #[structopt(long = "bin")]
     pub output_binary: bool,
 
+    /// Write the bytecode to a content-addressed store at the specified directory.
+    /// Objects are named after their bytecode hashes, and `index.json` maps contract paths to them.
+    /// In the default Solidity mode, the store is also used as the incremental build cache: only
+    /// the contracts whose transitive sources or settings changed are recompiled and reported.
+    /// In the default Solidity mode, can be set with the `ZKSOLC_CACHE_DIR` environment variable.
+    #[structopt(long = "artifact-store")]
+    pub artifact_store: Option<PathBuf>,
+
+    /// Push new artifact store objects to the HTTP cache at the specified URL.
+    /// Only `http://` endpoints are supported. Requires `--artifact-store`.
+    #[structopt(long = "artifact-cache-url")]
+    pub artifact_cache_url: Option<String>,
+
+    /// Output the keccak256 hashes of the source code files, as they appear in the metadata, and their sha256 hashes.
+    /// Only available in the default Solidity mode. In standard JSON mode, use the `outputSourceHashes` setting.
+    #[structopt(long = "source-hashes")]
+    pub output_source_hashes: bool,
+
+    /// Write the keccak256 hashes of the input files and all resolved imports to the lockfile.
+    /// Only available in the default Solidity mode.
+    #[structopt(long = "lockfile", parse(from_os_str))]
+    pub lockfile: Option<PathBuf>,
+
+    /// Fail if the sources differ from the ones pinned in the lockfile instead of updating it.
+    /// The files are checked before compiling, and the compiled sources are checked after it.
+    /// Requires `--lockfile`.
+    #[structopt(long = "locked")]
+    pub locked: bool,
+
+    /// Sign the artifacts with the hex-encoded secp256k1 private key from the specified file.
+    /// Writes `manifest.json` with the provenance statement of the sources and toolchain versions,
+    /// and its detached signature `manifest.json.sig`. Requires `--output-dir`.
+    #[structopt(long = "sign-key", parse(from_os_str))]
+    pub sign_key: Option<PathBuf>,
+
+    /// Replace CRLF line endings with LF and remove UTF-8 BOMs in the source code files.
+    /// Makes the source and metadata hashes identical across Windows and Unix checkouts.
+    /// In standard JSON mode, use the `normalizeSources` setting.
+    #[structopt(long = "normalize-sources")]
+    pub normalize_sources: bool,
+
+    /// Promote the warnings to errors and fail the build if any are emitted.
+    /// Optionally, only the `solc` warnings with the specified codes are promoted, e.g. `--warnings-as-errors 2072,5667`.
+    /// Unlike the numeric `solc` codes, the ERC conformance warnings of `--erc-checks` are coded with the
+    /// standard names `ERC20`, `ERC721`, and `ERC1155`, e.g. `--warnings-as-errors 2072,ERC20`.
+    /// In standard JSON mode, use the `warningsAsErrors` setting.
+    #[structopt(long = "warnings-as-errors", min_values = 0, use_delimiter = true)]
+    pub warnings_as_errors: Option<Vec<String>>,
+
+    /// Set the diagnostics format. Available arguments: `human`, `raw`.
+    /// `human`, the default one, shows the source code snippets, colored unless `NO_COLOR` is set or stderr is not a terminal.
+    /// `raw` prints the `solc` formatted messages as is, for machine parsing.
+    /// Standard JSON mode always outputs the raw messages in JSON.
+    #[structopt(long = "error-format")]
+    pub error_format: Option<String>,
+
+    /// Additionally build the contracts in the `3`, `s`, and `z` optimization modes, except the
+    /// one already selected, and recommend a mode per contract based on the bytecode size.
+    /// The runtime cost is not measured.
+    /// Only available in the default Solidity mode.
+    #[structopt(long = "recommend-optimization")]
+    pub recommend_optimization: bool,
+
+    /// Set the compile time budget per contract in seconds.
+    /// The contracts exceeding it are skipped and reported with the stages finished in time and
+    /// the suggested settings, along with the contracts depending on them, and the rest of the
+    /// build is completed.
+    /// Only available in the default Solidity mode.
+    /// In standard JSON mode, use the `contractTimeBudget` setting.
+    #[structopt(long = "contract-time-budget")]
+    pub contract_time_budget: Option<u64>,
+
+    /// Report the large constants, e.g. revert message chunks and hashes, duplicated in the constant
+    /// pools of several contracts. Such constants are candidates for factoring into a shared library.
+    /// Only available in the default Solidity mode.
+    #[structopt(long = "report-shared-constants")]
+    pub report_shared_constants: bool,
+
+    /// Compile each contract twice and check that the bytecode is identical.
+    /// Useful for detecting nondeterminism that breaks contract verification.
+    #[structopt(long = "determinism-check")]
+    pub determinism_check: bool,
+
+    /// Output the `solc` EVM bytecode of the contracts along with that of zkEVM.
+    /// Only available in the default Solidity mode together with `--bin`.
+    #[structopt(long = "evm-bytecode")]
+    pub output_evm_bytecode: bool,
+
+    /// Emit the additional artifacts, separated by commas.
+    /// Supported artifacts: `yul`, the Yul code produced by `solc` and consumed by zksolc,
+    /// `metadata`, the metadata JSON whose hash is appended to the bytecode, `archive`,
+    /// the compressed `artifacts.zkbuild` tarball of the artifacts written by this run with the build manifest,
+    /// which requires `--output-dir` and includes the manifest signature if `--sign-key` is set,
+    /// `sources`, the input files and resolved imports for publishing, written to the `sources`
+    /// subdirectory of `--output-dir` with their SPDX license identifiers listed in `licenses.json`,
+    /// `calldata`, the example calldata of each function with the default argument values,
+    /// keyed by the function signatures, and `verify-bundles`, the verification bundle of each contract
+    /// replayed with `zksolc replay`, written next to its artifacts, which requires `--output-dir`.
+    /// The archive always includes the verification bundles.
+    /// Only available in the default Solidity mode.
+    #[structopt(long = "emit", use_delimiter = true)]
+    pub emit: Vec<String>,
+
+    /// Prepend the text of the specified file to each emitted source as line comments.
+    /// The SPDX license identifier line of each source is kept first. The banner changes the source
+    /// hashes, so such sources cannot be used for the metadata-based verification. Requires `--emit sources`.
+    #[structopt(long = "source-banner", parse(from_os_str))]
+    pub source_banner: Option<PathBuf>,
+
+    /// Enable the output flags of a named preset, keeping the flags set explicitly.
+    /// `verify`: `--bin`, `--source-hashes`, `--emit metadata`, and `--determinism-check`.
+    /// `dev`: `--bin` with `-O1`, unless the optimization level is specified.
+    /// `audit`: all IRs dumped to the `debug` subdirectory of `--output-dir`, `--asm`, `--bin`,
+    /// `--evm-bytecode`, `--histogram`, and `--emit yul,metadata`.
+    /// Only available in the default Solidity mode.
+    #[structopt(long = "preset")]
+    pub preset: Option<String>,
+
+    /// Build the mutants described by the specified JSON file along with the original contracts,
+    /// sharing the `solc` stage. Each mutant replaces the EVM instructions at a Solidity source code location.
+    /// The mutant builds are written to the `mutants` subdirectory of the output directory.
+    /// Only the mutated contracts and their factory dependency dependents are recompiled for each mutant.
+    /// Only available in the default Solidity mode with the EVM legacy assembly pipeline.
+    #[structopt(long = "mutation-spec")]
+    pub mutation_specification: Option<PathBuf>,
+
+    /// Output the histograms of the source EVM instructions and produced zkEVM instructions as JSON.
+    /// Only available in the default Solidity, Yul, LLVM IR and zkEVM assembly modes.
+    /// In standard JSON mode, use the `outputHistogram` setting.
+    #[structopt(long = "histogram")]
+    pub output_histogram: bool,
+
     /// Dump all IRs to files in the specified directory.
+    /// In the EVM legacy assembly pipeline, the mapping of the block tags to the generated functions
+    /// and LLVM basic blocks is written to `02_<contract>.llvm_blocks.json` in the contract subdirectory
+    /// along with the Ethereal IR, and is listed in the index. It can be correlated with the LLVM IR dumps,
+    /// but not with the renumbered zkEVM assembly labels.
     /// Only for testing and debugging.
     #[structopt(long = "debug-output-dir")]
     pub debug_output_directory: Option<PathBuf>,
 
+    /// Only dump the IRs of the specified contracts, matched by name, full path, or path suffix.
+    /// Can be specified multiple times. Requires `--debug-output-dir`.
+    #[structopt(long = "debug-only")]
+    pub debug_only: Vec<String>,
+
+    /// Only dump the IRs of the specified stages, separated by commas.
+    /// Supported stages: `yul`, `evmla`, `ethir`, `trace`, `llvm`, `asm`. Requires `--debug-output-dir`.
+    #[structopt(long = "debug-stages", use_delimiter = true)]
+    pub debug_stages: Vec<String>,
+
+    /// Trace the translation of the EVM legacy assembly blocks with the specified tag.
+    /// Each instruction is logged with the stack state before and after it, and the LLVM IR it produces.
+    /// The trace is written to the contract debug output subdirectory. Requires `--debug-output-dir`.
+    /// Only for testing and debugging.
+    #[structopt(long = "evmla-trace")]
+    pub evmla_trace_tag: Option<usize>,
+
+    /// Attach the EVM legacy assembly instruction indexes to LLVM IR as debug locations.
+    /// The line is the instruction index in the EVMLA dump plus one, and the file is `<path>:deploy` or `<path>:runtime`,
+    /// so the LLVM IR, the assembly, and the backend errors can be traced back to the legacy assembly.
+    /// Only for testing and debugging.
+    #[structopt(long = "evmla-debug-locations")]
+    pub evmla_debug_locations: bool,
+
     /// Set the verify-each option in LLVM.
     /// Only for testing and debugging.
     #[structopt(long = "llvm-verify-each")]
@@ -147,6 +450,11 @@ pub struct Arguments {
     /// Only for usage from within the compiler.
     #[structopt(long = "recursive-process")]
     pub recursive_process: bool,
+
+    /// Run this process recursively and provide JSON input lines to compile contracts until
+    /// the input is closed. Only for usage from within the compiler.
+    #[structopt(long = "recursive-process-persistent", hidden = true)]
+    pub recursive_process_persistent: bool,
 }
 
 impl Default for Arguments {
@@ -156,6 +464,24 @@ impl Default for Arguments {
 }
 
 impl Arguments {
+    /// The Yul artifact name for `--emit`.
+    pub const EMIT_YUL: &'static str = "yul";
+
+    /// The metadata artifact name for `--emit`.
+    pub const EMIT_METADATA: &'static str = "metadata";
+
+    /// The archive artifact name for `--emit`.
+    pub const EMIT_ARCHIVE: &'static str = "archive";
+
+    /// The source bundle artifact name for `--emit`.
+    pub const EMIT_SOURCES: &'static str = "sources";
+
+    /// The example calldata artifact name for `--emit`.
+    pub const EMIT_CALLDATA: &'static str = "calldata";
+
+    /// The verification bundles artifact name for `--emit`.
+    pub const EMIT_VERIFY_BUNDLES: &'static str = "verify-bundles";
+
     ///
     /// A shortcut constructor.
     ///
@@ -172,7 +498,19 @@ impl Arguments {
             anyhow::bail!("No other options are allowed while getting the compiler version.");
         }
 
-        if self.recursive_process && std::env::args().count() > 2 {
+        if self.print_supported_instructions && std::env::args().count() > 2 {
+            anyhow::bail!(
+                "No other options are allowed while printing the supported instructions."
+            );
+        }
+
+        if self.capabilities && std::env::args().count() > 2 {
+            anyhow::bail!("No other options are allowed while printing the capabilities.");
+        }
+
+        if (self.recursive_process || self.recursive_process_persistent)
+            && std::env::args().count() > 2
+        {
             anyhow::bail!("No other options are allowed in recursive mode.");
         }
 
@@ -213,12 +551,43 @@ impl Arguments {
             if self.force_evmla {
                 anyhow::bail!("EVM legacy assembly mode is not supported in Yul, LLVM IR and zkEVM assembly modes.");
             }
+            if self.evmla_stack_spill_threshold.is_some() {
+                anyhow::bail!("EVM legacy assembly stack spilling is not supported in Yul, LLVM IR and zkEVM assembly modes.");
+            }
+            if self.evmla_trace_tag.is_some() {
+                anyhow::bail!("EVM legacy assembly translation trace is not supported in Yul, LLVM IR and zkEVM assembly modes.");
+            }
+            if self.evmla_debug_locations {
+                anyhow::bail!("EVM legacy assembly debug locations are not supported in Yul, LLVM IR and zkEVM assembly modes.");
+            }
+            if !self.reentrancy_guard.is_empty() {
+                anyhow::bail!("The reentrancy guard is not supported in Yul, LLVM IR and zkEVM assembly modes.");
+            }
+            if self.order_by_selector {
+                anyhow::bail!("The selector-based function ordering is not supported in Yul, LLVM IR and zkEVM assembly modes.");
+            }
+            if self.strict {
+                anyhow::bail!(
+                    "Strict mode is not supported in Yul, LLVM IR and zkEVM assembly modes."
+                );
+            }
+            if self.erc_checks {
+                anyhow::bail!(
+                    "ERC checks are not supported in Yul, LLVM IR and zkEVM assembly modes."
+                );
+            }
 
             if self.disable_solc_optimizer {
                 anyhow::bail!("Disabling the solc optimizer is not supported in Yul, LLVM IR and zkEVM assembly modes.");
             }
         }
 
+        if (self.yul_deploy_object.is_some() || self.yul_runtime_object.is_some()) && !self.yul {
+            anyhow::bail!(
+                "Selecting the deploy and runtime objects is only supported in Yul mode."
+            );
+        }
+
         if self.llvm_ir || self.zkasm {
             if self.solc.is_some() {
                 anyhow::bail!("`solc` is not used in LLVM IR and zkEVM assembly modes.");
@@ -237,6 +606,279 @@ impl Arguments {
             }
         }
 
+        if self.output_evm_bytecode {
+            if self.yul
+                || self.llvm_ir
+                || self.zkasm
+                || self.combined_json.is_some()
+                || self.standard_json
+            {
+                anyhow::bail!("EVM bytecode output is only supported in the default Solidity mode. In standard JSON mode, use the `outputEvmBytecode` setting.");
+            }
+            if !self.output_binary {
+                anyhow::bail!("EVM bytecode output requires the `--bin` flag.");
+            }
+        }
+
+        for artifact in self.emit.iter() {
+            if artifact.as_str() != Self::EMIT_YUL
+                && artifact.as_str() != Self::EMIT_METADATA
+                && artifact.as_str() != Self::EMIT_ARCHIVE
+                && artifact.as_str() != Self::EMIT_SOURCES
+                && artifact.as_str() != Self::EMIT_CALLDATA
+                && artifact.as_str() != Self::EMIT_VERIFY_BUNDLES
+            {
+                anyhow::bail!(
+                    "Unknown artifact `{}`. Supported artifacts: `{}`, `{}`, `{}`, `{}`, `{}`, `{}`.",
+                    artifact,
+                    Self::EMIT_YUL,
+                    Self::EMIT_METADATA,
+                    Self::EMIT_ARCHIVE,
+                    Self::EMIT_SOURCES,
+                    Self::EMIT_CALLDATA,
+                    Self::EMIT_VERIFY_BUNDLES
+                );
+            }
+        }
+        if self
+            .emit
+            .iter()
+            .any(|artifact| artifact.as_str() == Self::EMIT_VERIFY_BUNDLES)
+            && self.output_directory.is_none()
+        {
+            anyhow::bail!("The verification bundles require the `--output-dir` option.");
+        }
+        if self
+            .emit
+            .iter()
+            .any(|artifact| artifact.as_str() == Self::EMIT_ARCHIVE)
+            && self.output_directory.is_none()
+        {
+            anyhow::bail!("The artifact archive requires the `--output-dir` option.");
+        }
+        let output_sources = self
+            .emit
+            .iter()
+            .any(|artifact| artifact.as_str() == Self::EMIT_SOURCES);
+        if output_sources && self.output_directory.is_none() {
+            anyhow::bail!("The source bundle requires the `--output-dir` option.");
+        }
+        if self.source_banner.is_some() && !output_sources {
+            anyhow::bail!("The source banner requires the `--emit sources` option.");
+        }
+        if !self.emit.is_empty()
+            && (self.yul
+                || self.llvm_ir
+                || self.zkasm
+                || self.combined_json.is_some()
+                || self.standard_json)
+        {
+            anyhow::bail!("Emitting additional artifacts is only supported in the default Solidity mode. In standard JSON mode, use the `irOptimized` output selection.");
+        }
+
+        if self.preset.is_some()
+            && (self.yul
+                || self.llvm_ir
+                || self.zkasm
+                || self.combined_json.is_some()
+                || self.standard_json)
+        {
+            anyhow::bail!("Presets are only supported in the default Solidity mode.");
+        }
+
+        if self.mutation_specification.is_some() {
+            if self.yul
+                || self.llvm_ir
+                || self.zkasm
+                || self.combined_json.is_some()
+                || self.standard_json
+            {
+                anyhow::bail!("Mutation testing is only supported in the default Solidity mode.");
+            }
+            if self.output_directory.is_none() {
+                anyhow::bail!("Mutation testing requires the `--output-dir` option.");
+            }
+        }
+
+        if self.output_histogram && (self.combined_json.is_some() || self.standard_json) {
+            anyhow::bail!("Instruction histogram output is not supported in combined JSON mode. In standard JSON mode, use the `outputHistogram` setting.");
+        }
+
+        if (!self.debug_only.is_empty()
+            || !self.debug_stages.is_empty()
+            || self.evmla_trace_tag.is_some())
+            && self.debug_output_directory.is_none()
+        {
+            anyhow::bail!(
+                "Debug dump filters and translation trace require the `--debug-output-dir` option."
+            );
+        }
+
+        if self.batch && !self.standard_json {
+            anyhow::bail!("Batch mode is only supported in standard JSON mode.");
+        }
+        if self.batch && self.debug_output_directory.is_some() {
+            anyhow::bail!("Debug output directory cannot be used in batch mode.");
+        }
+        if self.daemon && (!self.standard_json || self.batch) {
+            anyhow::bail!(
+                "The daemon delegation is only supported in standard JSON mode without batching."
+            );
+        }
+        if self.verify_bundles.is_some() && (!self.standard_json || self.batch) {
+            anyhow::bail!(
+                "The verification bundles are only supported in standard JSON mode without batching. In the default Solidity mode, use `--emit verify-bundles`."
+            );
+        }
+
+        if (self.allow_network || self.ipfs_gateway.is_some()) && !self.standard_json {
+            anyhow::bail!("Fetching sources by URLs is only supported in standard JSON mode.");
+        }
+        if self.ipfs_gateway.is_some() && !self.allow_network {
+            anyhow::bail!("The IPFS gateway requires the `--allow-network` flag.");
+        }
+
+        if self.metadata_hash_placement.is_some()
+            && (self.yul || self.llvm_ir || self.zkasm || self.standard_json)
+        {
+            anyhow::bail!(
+                "The metadata hash placement is only supported in the default Solidity and combined JSON modes."
+            );
+        }
+
+        if self.jobs == Some(0) {
+            anyhow::bail!("The number of jobs must be positive.");
+        }
+
+        if self.hermetic {
+            if !self.llvm_ir && !self.zkasm {
+                match self.solc.as_deref() {
+                    Some(solc) if Path::new(solc).components().count() > 1 => {}
+                    _ => anyhow::bail!("Hermetic mode requires the `--solc` executable path, as `solc` is not looked up in `${{PATH}}`."),
+                }
+            }
+            if self.allow_network
+                || self.ipfs_gateway.is_some()
+                || self.artifact_cache_url.is_some()
+            {
+                anyhow::bail!("Network access is not allowed in hermetic mode.");
+            }
+            if self.base_path.is_none() && !self.yul && !self.llvm_ir && !self.zkasm {
+                anyhow::bail!("Hermetic mode requires the `--base-path` option, as `solc` resolves the imports relative to the current directory otherwise.");
+            }
+        }
+
+        if self.warnings_as_errors.is_some()
+            && (self.yul || self.llvm_ir || self.zkasm || self.standard_json)
+        {
+            anyhow::bail!("Promoting warnings to errors is only supported in the default Solidity and combined JSON modes. In standard JSON mode, use the `warningsAsErrors` setting.");
+        }
+
+        if self.list_contracts {
+            if self.yul
+                || self.llvm_ir
+                || self.zkasm
+                || self.combined_json.is_some()
+                || self.standard_json
+            {
+                anyhow::bail!("Contract discovery is only supported in the default Solidity mode.");
+            }
+            if self.input_files.is_empty() {
+                anyhow::bail!("Contract discovery requires the input files.");
+            }
+        }
+
+        if self.recommend_optimization
+            && (self.yul
+                || self.llvm_ir
+                || self.zkasm
+                || self.combined_json.is_some()
+                || self.standard_json)
+        {
+            anyhow::bail!(
+                "Optimization mode recommendation is only supported in the default Solidity mode."
+            );
+        }
+
+        if let Some(contract_time_budget) = self.contract_time_budget {
+            if self.yul
+                || self.llvm_ir
+                || self.zkasm
+                || self.combined_json.is_some()
+                || self.standard_json
+            {
+                anyhow::bail!(
+                    "The contract time budget is only supported in the default Solidity mode. In standard JSON mode, use the `contractTimeBudget` setting."
+                );
+            }
+            if contract_time_budget == 0 {
+                anyhow::bail!("The contract time budget must be positive.");
+            }
+        }
+
+        if self.report_shared_constants
+            && (self.yul
+                || self.llvm_ir
+                || self.zkasm
+                || self.combined_json.is_some()
+                || self.standard_json)
+        {
+            anyhow::bail!(
+                "Shared constants report is only supported in the default Solidity mode."
+            );
+        }
+
+        if self.error_format.is_some()
+            && (self.yul || self.llvm_ir || self.zkasm || self.standard_json)
+        {
+            anyhow::bail!("The diagnostics format is only supported in the default Solidity and combined JSON modes.");
+        }
+
+        if self.normalize_sources && (self.yul || self.llvm_ir || self.zkasm || self.standard_json)
+        {
+            anyhow::bail!("Source normalization is only supported in the default Solidity and combined JSON modes.");
+        }
+
+        if self.output_source_hashes
+            && (self.yul
+                || self.llvm_ir
+                || self.zkasm
+                || self.combined_json.is_some()
+                || self.standard_json)
+        {
+            anyhow::bail!("Source hashes output is only supported in the default Solidity mode.");
+        }
+
+        if self.lockfile.is_some()
+            && (self.yul
+                || self.llvm_ir
+                || self.zkasm
+                || self.combined_json.is_some()
+                || self.standard_json)
+        {
+            anyhow::bail!("The lockfile is only supported in the default Solidity mode.");
+        }
+        if self.locked && self.lockfile.is_none() {
+            anyhow::bail!("The `--locked` flag requires the `--lockfile` option.");
+        }
+
+        if self.output_layout.is_some() && self.output_directory.is_none() {
+            anyhow::bail!("The output layout requires the `--output-dir` option.");
+        }
+
+        if self.sign_key.is_some() && self.output_directory.is_none() {
+            anyhow::bail!("Artifact signing requires the `--output-dir` option.");
+        }
+
+        if self.artifact_cache_url.is_some() && self.artifact_store.is_none() {
+            anyhow::bail!("The artifact cache URL requires the `--artifact-store` option.");
+        }
+        if self.artifact_store.is_some() && (self.combined_json.is_some() || self.standard_json) {
+            anyhow::bail!(
+                "The artifact store is not supported in combined and standard JSON modes."
+            );
+        }
+
         if self.combined_json.is_some() {
             if self.output_assembly || self.output_binary {
                 anyhow::bail!(
@@ -276,6 +918,38 @@ impl Arguments {
             if self.metadata_hash.is_some() {
                 anyhow::bail!("Metadata hash mode must specified in standard JSON input settings.");
             }
+            if self.target.is_some() {
+                anyhow::bail!("Target must be specified in standard JSON input settings.");
+            }
+            if self.evmla_stack_spill_threshold.is_some() {
+                anyhow::bail!("EVM legacy assembly stack spilling threshold must be specified in standard JSON input settings.");
+            }
+            if self.evmla_trace_tag.is_some() {
+                anyhow::bail!(
+                    "EVM legacy assembly translation trace is not supported in standard JSON mode."
+                );
+            }
+            if self.evmla_debug_locations {
+                anyhow::bail!(
+                    "EVM legacy assembly debug locations are not supported in standard JSON mode."
+                );
+            }
+            if !self.reentrancy_guard.is_empty() {
+                anyhow::bail!(
+                    "Reentrancy guard signatures must be specified in standard JSON input settings."
+                );
+            }
+            if self.order_by_selector {
+                anyhow::bail!(
+                    "The selector-based function ordering must be specified in standard JSON input settings."
+                );
+            }
+            if self.strict {
+                anyhow::bail!("Strict mode must be specified in standard JSON input settings.");
+            }
+            if self.erc_checks {
+                anyhow::bail!("ERC checks must be specified in standard JSON input settings.");
+            }
         }
 
         Ok(())
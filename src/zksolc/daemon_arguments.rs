@@ -0,0 +1,41 @@
+//!
+//! Solidity to zkEVM compiler `daemon` subcommand arguments.
+//!
+
+use structopt::StructOpt;
+
+///
+/// Runs zksolc as a local compile daemon.
+///
+/// While it is running, the `zksolc --standard-json --daemon` invocations of the same version and
+/// user delegate the compilation of the inputs with all the sources inline to it, unless
+/// `--hermetic` or `--debug-output-dir` is passed, or the invocation uses another `solc`.
+///
+/// Example: zksolc daemon
+///
+#[derive(Debug, StructOpt)]
+#[structopt(name = "The zkEVM Solidity compile daemon")]
+pub struct DaemonArguments {
+    /// Specify the path to the `solc` executable. By default, the one in `${PATH}` is used.
+    /// Only the invocations passing the same `--solc` value are delegated to the daemon.
+    #[structopt(long = "solc")]
+    pub solc: Option<String>,
+}
+
+impl Default for DaemonArguments {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DaemonArguments {
+    /// The subcommand name.
+    pub const SUBCOMMAND: &'static str = "daemon";
+
+    ///
+    /// Parses the arguments following the subcommand name.
+    ///
+    pub fn new() -> Self {
+        Self::from_iter(std::env::args().skip(1))
+    }
+}
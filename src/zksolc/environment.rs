@@ -0,0 +1,192 @@
+//!
+//! Solidity to zkEVM compiler environment variables.
+//!
+
+use std::path::PathBuf;
+
+use crate::arguments::Arguments;
+
+///
+/// The environment variable configuration layer.
+///
+/// The variables take precedence over the defaults and presets, but not over the flags passed
+/// explicitly. They are ignored in the modes where the corresponding flags are not supported,
+/// so the same environment can be shared by all invocations in a CI job.
+///
+#[derive(Debug)]
+pub struct Environment;
+
+impl Environment {
+    /// The `solc` executable path variable.
+    pub const SOLC: &'static str = "ZKSOLC_SOLC";
+
+    /// The optimization mode variable.
+    pub const OPTIMIZATION: &'static str = "ZKSOLC_OPTIMIZATION";
+
+    /// The incremental build cache directory variable.
+    ///
+    /// Sets the artifact store only in the default Solidity mode, where the store is used as the
+    /// incremental build cache. In the other modes the store only collects the bytecode, so it is
+    /// not enabled implicitly.
+    pub const CACHE_DIR: &'static str = "ZKSOLC_CACHE_DIR";
+
+    /// The number of compilation jobs variable.
+    pub const JOBS: &'static str = "ZKSOLC_JOBS";
+
+    ///
+    /// Sets the options not passed explicitly from the environment variables.
+    ///
+    pub fn apply(arguments: &mut Arguments) -> anyhow::Result<()> {
+        Self::apply_from(arguments, |name| std::env::var(name))
+    }
+
+    ///
+    /// Sets the options not passed explicitly from the variables returned by `variable`.
+    ///
+    fn apply_from<F>(arguments: &mut Arguments, variable: F) -> anyhow::Result<()>
+    where
+        F: Fn(&str) -> Result<String, std::env::VarError>,
+    {
+        let get = |name: &str| Self::get(name, variable(name));
+
+        let is_solc_used = !arguments.llvm_ir && !arguments.zkasm;
+        let is_json = arguments.standard_json || arguments.combined_json.is_some();
+        let is_solidity = is_solc_used && !arguments.yul && !is_json;
+
+        if is_solc_used && arguments.solc.is_none() {
+            arguments.solc = get(Self::SOLC)?;
+        }
+
+        if !arguments.zkasm && !arguments.standard_json && arguments.optimization.is_none() {
+            if let Some(optimization) = get(Self::OPTIMIZATION)? {
+                let mut characters = optimization.chars();
+                match (characters.next(), characters.next()) {
+                    (Some(mode), None) => arguments.optimization = Some(mode),
+                    _ => anyhow::bail!(
+                        "Environment variable `{}` must be a single character, found `{}`.",
+                        Self::OPTIMIZATION,
+                        optimization
+                    ),
+                }
+            }
+        }
+
+        if is_solidity && arguments.artifact_store.is_none() {
+            arguments.artifact_store = get(Self::CACHE_DIR)?.map(PathBuf::from);
+        }
+
+        if arguments.jobs.is_none() {
+            if let Some(jobs) = get(Self::JOBS)? {
+                arguments.jobs = Some(jobs.parse().map_err(|error| {
+                    anyhow::anyhow!(
+                        "Environment variable `{}` value `{}` is invalid: {}",
+                        Self::JOBS,
+                        jobs,
+                        error
+                    )
+                })?);
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the variable `name` value, treating the empty one as unset.
+    ///
+    fn get(
+        name: &str,
+        value: Result<String, std::env::VarError>,
+    ) -> anyhow::Result<Option<String>> {
+        match value {
+            Ok(value) if value.is_empty() => Ok(None),
+            Ok(value) => Ok(Some(value)),
+            Err(std::env::VarError::NotPresent) => Ok(None),
+            Err(error) => anyhow::bail!("Environment variable `{}` is invalid: {}", name, error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    use structopt::StructOpt;
+
+    use crate::arguments::Arguments;
+
+    use super::Environment;
+
+    fn apply(arguments: &[&str], variables: &[(&str, &str)]) -> anyhow::Result<Arguments> {
+        let variables: BTreeMap<&str, &str> = variables.iter().copied().collect();
+        let mut arguments = Arguments::from_iter(["zksolc"].iter().chain(arguments.iter()));
+        Environment::apply_from(&mut arguments, |name| {
+            variables
+                .get(name)
+                .map(|value| value.to_string())
+                .ok_or(std::env::VarError::NotPresent)
+        })?;
+        Ok(arguments)
+    }
+
+    #[test]
+    fn precedence() {
+        let variables = [
+            (Environment::SOLC, "/usr/bin/solc"),
+            (Environment::OPTIMIZATION, "z"),
+            (Environment::JOBS, "4"),
+        ];
+
+        let arguments = apply(&["Test.sol"], &variables).expect("Always valid");
+        assert_eq!(arguments.solc, Some("/usr/bin/solc".to_owned()));
+        assert_eq!(arguments.optimization, Some('z'));
+        assert_eq!(arguments.jobs, Some(4));
+
+        let arguments = apply(
+            &[
+                "Test.sol",
+                "--solc",
+                "solc-0.8.20",
+                "-O",
+                "3",
+                "--jobs",
+                "2",
+            ],
+            &variables,
+        )
+        .expect("Always valid");
+        assert_eq!(arguments.solc, Some("solc-0.8.20".to_owned()));
+        assert_eq!(arguments.optimization, Some('3'));
+        assert_eq!(arguments.jobs, Some(2));
+    }
+
+    #[test]
+    fn cache_dir() {
+        let variables = [(Environment::CACHE_DIR, "cache")];
+
+        let arguments = apply(&["Test.sol"], &variables).expect("Always valid");
+        assert_eq!(arguments.artifact_store, Some(PathBuf::from("cache")));
+
+        let arguments =
+            apply(&["Test.sol", "--artifact-store", "store"], &variables).expect("Always valid");
+        assert_eq!(arguments.artifact_store, Some(PathBuf::from("store")));
+
+        for mode in [&["--yul", "Test.yul"][..], &["--standard-json"][..]] {
+            let arguments = apply(mode, &variables).expect("Always valid");
+            assert!(arguments.artifact_store.is_none());
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let arguments = apply(&["Test.sol"], &[(Environment::SOLC, "")]).expect("Always valid");
+        assert!(arguments.solc.is_none());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(apply(&["Test.sol"], &[(Environment::OPTIMIZATION, "3z")]).is_err());
+        assert!(apply(&["Test.sol"], &[(Environment::JOBS, "many")]).is_err());
+    }
+}
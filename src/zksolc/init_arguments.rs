@@ -0,0 +1,50 @@
+//!
+//! Solidity to zkEVM compiler `init` subcommand arguments.
+//!
+
+use std::path::PathBuf;
+
+use structopt::StructOpt;
+
+///
+/// Scaffolds a minimal zkEVM Solidity project.
+///
+/// Example: zksolc init ./my-project
+///
+#[derive(Debug, StructOpt)]
+#[structopt(name = "The zkEVM Solidity project scaffolding")]
+pub struct InitArguments {
+    /// The project directory. Defaults to the current one.
+    #[structopt(parse(from_os_str))]
+    pub directory: Option<PathBuf>,
+
+    /// The project name. Defaults to the directory name.
+    #[structopt(long = "name")]
+    pub name: Option<String>,
+
+    /// Use the recommended settings for system contracts.
+    #[structopt(long = "system-mode")]
+    pub is_system_mode: bool,
+
+    /// Overwrite the existing files.
+    #[structopt(long = "overwrite")]
+    pub overwrite: bool,
+}
+
+impl Default for InitArguments {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InitArguments {
+    /// The subcommand name.
+    pub const SUBCOMMAND: &'static str = "init";
+
+    ///
+    /// Parses the arguments following the subcommand name.
+    ///
+    pub fn new() -> Self {
+        Self::from_iter(std::env::args().skip(1))
+    }
+}
@@ -3,10 +3,27 @@
 //!
 
 pub mod arguments;
+pub mod daemon_arguments;
+pub mod environment;
+pub mod init_arguments;
+pub mod preset;
+pub mod replay_arguments;
+pub mod serve_arguments;
+pub mod workspace_arguments;
 
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
 use self::arguments::Arguments;
+use self::daemon_arguments::DaemonArguments;
+use self::environment::Environment;
+use self::init_arguments::InitArguments;
+use self::preset::Preset;
+use self::replay_arguments::ReplayArguments;
+use self::serve_arguments::ServeArguments;
+use self::workspace_arguments::WorkspaceArguments;
 
 /// The rayon worker stack size.
 const RAYON_WORKER_STACK_SIZE: usize = 16 * 1024 * 1024;
@@ -32,7 +49,139 @@ fn main() {
 /// The auxiliary `main` function to facilitate the `?` error conversion operator.
 ///
 fn main_inner() -> anyhow::Result<()> {
+    if std::env::args().nth(1).as_deref() == Some(ServeArguments::SUBCOMMAND) {
+        let arguments = ServeArguments::new();
+        let mut server = compiler_solidity::Server::try_new(
+            arguments.host,
+            arguments.port,
+            arguments.solc.unwrap_or_else(|| {
+                compiler_solidity::SolcCompiler::DEFAULT_EXECUTABLE_NAME.to_owned()
+            }),
+            compiler_solidity::ServerLimits::new(
+                arguments.max_request_size,
+                arguments.request_timeout,
+                arguments.max_concurrent_requests,
+                arguments.max_concurrent_jobs,
+                arguments.max_queued_jobs,
+                arguments.max_queue_size,
+                arguments.rate_limit,
+            ),
+        )?;
+        server.sandbox = compiler_solidity::SolcStandardJsonInputSettingsSandbox {
+            memory_limit: arguments.sandbox_memory_limit,
+            cpu_time_limit: arguments.sandbox_cpu_time_limit,
+            file_size_limit: arguments.sandbox_file_size_limit,
+        };
+        return server.run();
+    }
+
+    if std::env::args().nth(1).as_deref() == Some(DaemonArguments::SUBCOMMAND) {
+        let arguments = DaemonArguments::new();
+
+        rayon::ThreadPoolBuilder::new()
+            .stack_size(RAYON_WORKER_STACK_SIZE)
+            .build_global()
+            .expect("Thread pool configuration failure");
+        inkwell::support::enable_llvm_pretty_stack_trace();
+        compiler_llvm_context::initialize_target();
+
+        let solc = compiler_solidity::SolcCompiler::new(arguments.solc.unwrap_or_else(|| {
+            compiler_solidity::SolcCompiler::DEFAULT_EXECUTABLE_NAME.to_owned()
+        }));
+        return compiler_solidity::Daemon::new(solc).run();
+    }
+
+    if std::env::args().nth(1).as_deref() == Some(InitArguments::SUBCOMMAND) {
+        let arguments = InitArguments::new();
+        let directory = arguments.directory.unwrap_or_else(|| PathBuf::from("."));
+        let name = match arguments.name {
+            Some(name) => name,
+            None => directory
+                .canonicalize()
+                .ok()
+                .and_then(|path| {
+                    path.file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                })
+                .unwrap_or_else(|| "project".to_owned()),
+        };
+        let scaffold = compiler_solidity::Scaffold::new(name, arguments.is_system_mode);
+        for path in scaffold
+            .write(directory.as_path(), arguments.overwrite)?
+            .into_iter()
+        {
+            eprintln!("Created {path:?}");
+        }
+        eprintln!(
+            "Build the project with `zksolc {} {}` from {:?}.",
+            WorkspaceArguments::SUBCOMMAND,
+            compiler_solidity::Scaffold::WORKSPACE_FILE_NAME,
+            directory
+        );
+        return Ok(());
+    }
+
+    if std::env::args().nth(1).as_deref() == Some(ReplayArguments::SUBCOMMAND) {
+        let arguments = ReplayArguments::new();
+        let bundle =
+            compiler_solidity::BuildVerifyBundle::try_from_path(arguments.bundle.as_path())?;
+        let mut solc = compiler_solidity::SolcCompiler::new(arguments.solc.unwrap_or_else(|| {
+            compiler_solidity::SolcCompiler::DEFAULT_EXECUTABLE_NAME.to_owned()
+        }));
+
+        rayon::ThreadPoolBuilder::new()
+            .stack_size(RAYON_WORKER_STACK_SIZE)
+            .build_global()
+            .expect("Thread pool configuration failure");
+        inkwell::support::enable_llvm_pretty_stack_trace();
+        compiler_llvm_context::initialize_target();
+
+        let replay = bundle.replay(&mut solc)?;
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&replay).expect("Always valid")
+        );
+        if !replay.matches {
+            anyhow::bail!(
+                "The contract `{}` bytecode hash {} does not match the expected {}",
+                replay.contract,
+                replay.actual_bytecode_hash,
+                replay.expected_bytecode_hash
+            );
+        }
+        return Ok(());
+    }
+
+    if std::env::args().nth(1).as_deref() == Some(WorkspaceArguments::SUBCOMMAND) {
+        let arguments = WorkspaceArguments::new();
+        if arguments.ipfs_gateway.is_some() && !arguments.allow_network {
+            anyhow::bail!("The IPFS gateway requires the `--allow-network` flag.");
+        }
+        let format_version = match arguments.format_version {
+            Some(format_version) => {
+                compiler_solidity::FormatVersion::from_str(format_version.as_str())?
+            }
+            None => compiler_solidity::FormatVersion::default(),
+        };
+
+        rayon::ThreadPoolBuilder::new()
+            .stack_size(RAYON_WORKER_STACK_SIZE)
+            .build_global()
+            .expect("Thread pool configuration failure");
+
+        let workspace = compiler_solidity::Workspace::try_from_path(arguments.config.as_path())?;
+        return workspace.compile(
+            arguments.allow_network,
+            arguments.ipfs_gateway,
+            format_version,
+        );
+    }
+
     let mut arguments = Arguments::new();
+    Environment::apply(&mut arguments)?;
+    if let Some(preset) = arguments.preset.as_deref() {
+        Preset::from_str(preset)?.apply(&mut arguments)?;
+    }
     arguments.validate()?;
 
     if arguments.version {
@@ -45,8 +194,26 @@ fn main_inner() -> anyhow::Result<()> {
         return Ok(());
     }
 
-    rayon::ThreadPoolBuilder::new()
-        .stack_size(RAYON_WORKER_STACK_SIZE)
+    if arguments.print_supported_instructions {
+        print!("{}", compiler_solidity::EVMLAInstructionSupport::report());
+        return Ok(());
+    }
+
+    if arguments.capabilities {
+        let capabilities = compiler_solidity::Capabilities::new();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&capabilities).expect("Always valid")
+        );
+        return Ok(());
+    }
+
+    let mut thread_pool_builder =
+        rayon::ThreadPoolBuilder::new().stack_size(RAYON_WORKER_STACK_SIZE);
+    if let Some(jobs) = arguments.jobs {
+        thread_pool_builder = thread_pool_builder.num_threads(jobs);
+    }
+    thread_pool_builder
         .build_global()
         .expect("Thread pool configuration failure");
     inkwell::support::enable_llvm_pretty_stack_trace();
@@ -55,7 +222,14 @@ fn main_inner() -> anyhow::Result<()> {
     if arguments.recursive_process {
         return compiler_solidity::run_process();
     }
+    if arguments.recursive_process_persistent {
+        return compiler_solidity::run_process_persistent();
+    }
 
+    let debug_filter = compiler_solidity::DebugFilter::try_new(
+        std::mem::take(&mut arguments.debug_only),
+        std::mem::take(&mut arguments.debug_stages),
+    )?;
     let debug_config = match arguments.debug_output_directory {
         Some(debug_output_directory) => {
             std::fs::create_dir_all(debug_output_directory.as_path())?;
@@ -74,6 +248,7 @@ fn main_inner() -> anyhow::Result<()> {
         compiler_solidity::SolcCompiler::new(arguments.solc.unwrap_or_else(|| {
             compiler_solidity::SolcCompiler::DEFAULT_EXECUTABLE_NAME.to_owned()
         }));
+    solc.force = arguments.force;
 
     let mut optimizer_settings = match arguments.optimization {
         Some(mode) => compiler_llvm_context::OptimizerSettings::try_from_cli(mode)?,
@@ -82,6 +257,11 @@ fn main_inner() -> anyhow::Result<()> {
     optimizer_settings.is_verify_each_enabled = arguments.llvm_verify_each;
     optimizer_settings.is_debug_logging_enabled = arguments.llvm_debug_logging;
 
+    let target = match arguments.target {
+        Some(target) => compiler_solidity::Target::from_str(target.as_str())?,
+        None => compiler_solidity::Target::default(),
+    };
+
     let include_metadata_hash = match arguments.metadata_hash {
         Some(metadata_hash) => {
             let metadata = compiler_llvm_context::MetadataHash::from_str(metadata_hash.as_str())?;
@@ -89,39 +269,185 @@ fn main_inner() -> anyhow::Result<()> {
         }
         None => true,
     };
+    let metadata_hash_placement = match arguments.metadata_hash_placement.take() {
+        Some(metadata_hash_placement) => {
+            compiler_solidity::MetadataHashPlacement::from_str(metadata_hash_placement.as_str())?
+        }
+        None => compiler_solidity::MetadataHashPlacement::default(),
+    };
 
-    let build = if arguments.yul {
+    let format_version = match arguments.format_version {
+        Some(format_version) => {
+            compiler_solidity::FormatVersion::from_str(format_version.as_str())?
+        }
+        None => compiler_solidity::FormatVersion::default(),
+    };
+
+    let output_layout = match arguments.output_layout {
+        Some(ref output_layout) => {
+            compiler_solidity::OutputLayout::from_str(output_layout.as_str())?
+        }
+        None => compiler_solidity::OutputLayout::default(),
+    };
+
+    let error_format = match arguments.error_format {
+        Some(error_format) => compiler_solidity::ErrorFormat::from_str(error_format.as_str())?,
+        None => compiler_solidity::ErrorFormat::default(),
+    };
+
+    let artifact_store = match arguments.artifact_store.take() {
+        Some(artifact_store) => Some(compiler_solidity::BuildStore::new(
+            artifact_store,
+            arguments.artifact_cache_url.as_deref(),
+        )?),
+        None => None,
+    };
+
+    let sign_key = match arguments.sign_key.as_ref() {
+        Some(path) => Some(compiler_solidity::BuildManifest::read_key(path.as_path())?),
+        None => None,
+    };
+    let source_banner = match arguments.source_banner.as_ref() {
+        Some(path) => Some(compiler_solidity::BuildSourceBundle::read_banner(
+            path.as_path(),
+        )?),
+        None => None,
+    };
+
+    if arguments.list_contracts {
+        let contracts = compiler_solidity::list_contracts(
+            arguments.input_files.as_slice(),
+            &mut solc,
+            error_format,
+            arguments.base_path,
+            arguments.include_paths,
+            arguments.allow_paths,
+        )?;
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&contracts).expect("Always valid")
+        );
+        return Ok(());
+    }
+
+    let output_archive = arguments
+        .emit
+        .iter()
+        .any(|artifact| artifact.as_str() == Arguments::EMIT_ARCHIVE);
+    let verify_bundle_settings = if output_archive
+        || arguments
+            .emit
+            .iter()
+            .any(|artifact| artifact.as_str() == Arguments::EMIT_VERIFY_BUNDLES)
+    {
+        let mut settings = serde_json::json!({
+            "optimizer": { "enabled": !arguments.disable_solc_optimizer },
+            "metadata": { "hashPlacement": metadata_hash_placement.to_string() },
+            "libraries": compiler_solidity::SolcStandardJsonInputSettings::parse_libraries(
+                arguments.libraries.clone(),
+            )?,
+            "target": target.to_string(),
+            "normalizeSources": arguments.normalize_sources,
+            "orderBySelector": arguments.order_by_selector,
+        });
+        if let Some(mode) = arguments.optimization {
+            settings["optimizer"]["mode"] = serde_json::Value::from(mode.to_string());
+        }
+        if !include_metadata_hash {
+            settings["metadata"]["bytecodeHash"] = serde_json::Value::from("none");
+        }
+        if let Some(threshold) = arguments.evmla_stack_spill_threshold {
+            settings["evmlaStackSpillThreshold"] = serde_json::Value::from(threshold);
+        }
+        if !arguments.reentrancy_guard.is_empty() {
+            settings["reentrancyGuard"] = serde_json::json!(arguments.reentrancy_guard);
+        }
+        Some(settings)
+    } else {
+        None
+    };
+
+    let source_base_path = arguments.base_path.clone();
+    let source_include_paths = arguments.include_paths.clone();
+    let locked = match arguments.lockfile.as_ref() {
+        Some(lockfile_path) if arguments.locked => {
+            let lockfile = compiler_solidity::Lockfile::try_from_path(lockfile_path.as_path())?;
+            lockfile.check_files(source_base_path.as_deref(), source_include_paths.as_slice())?;
+            Some(lockfile)
+        }
+        _ => None,
+    };
+    let mut build = if arguments.yul {
         compiler_solidity::yul(
             arguments.input_files.as_slice(),
             &mut solc,
             optimizer_settings,
+            target,
             arguments.is_system_mode,
             include_metadata_hash,
+            arguments.determinism_check,
+            arguments.output_histogram,
+            compiler_solidity::YulEntryObjects::new(
+                arguments.yul_deploy_object.clone(),
+                arguments.yul_runtime_object.clone(),
+            ),
             debug_config,
+            debug_filter,
         )
     } else if arguments.llvm_ir {
         compiler_solidity::llvm_ir(
             arguments.input_files.as_slice(),
             optimizer_settings,
+            target,
             arguments.is_system_mode,
             include_metadata_hash,
+            arguments.determinism_check,
+            arguments.output_histogram,
             debug_config,
+            debug_filter,
         )
     } else if arguments.zkasm {
         compiler_solidity::zkasm(
             arguments.input_files.as_slice(),
+            target,
             include_metadata_hash,
+            arguments.determinism_check,
+            arguments.output_histogram,
             debug_config,
+            debug_filter,
         )
+    } else if arguments.standard_json && arguments.batch {
+        compiler_solidity::standard_json_batch(
+            &mut solc,
+            arguments.force_evmla,
+            arguments.is_system_mode,
+            arguments.determinism_check,
+            arguments.allow_network,
+            arguments.ipfs_gateway,
+            arguments.base_path,
+            arguments.include_paths,
+            arguments.allow_paths,
+            arguments.hermetic,
+            format_version,
+        )?;
+        return Ok(());
     } else if arguments.standard_json {
         compiler_solidity::standard_json(
             &mut solc,
             arguments.force_evmla,
             arguments.is_system_mode,
+            arguments.determinism_check,
+            arguments.allow_network,
+            arguments.ipfs_gateway,
             arguments.base_path,
             arguments.include_paths,
             arguments.allow_paths,
+            arguments.hermetic,
+            arguments.daemon,
             debug_config,
+            debug_filter,
+            arguments.verify_bundles,
+            format_version,
         )?;
         return Ok(());
     } else if let Some(format) = arguments.combined_json {
@@ -132,14 +458,31 @@ fn main_inner() -> anyhow::Result<()> {
             &mut solc,
             !arguments.disable_solc_optimizer,
             optimizer_settings,
+            target,
             arguments.force_evmla,
             arguments.is_system_mode,
             include_metadata_hash,
+            metadata_hash_placement,
+            arguments.determinism_check,
+            arguments.normalize_sources,
+            arguments.evmla_stack_spill_threshold,
+            arguments.evmla_trace_tag,
+            arguments.evmla_debug_locations,
+            arguments.reentrancy_guard,
+            arguments.order_by_selector,
+            arguments.strict,
+            arguments.erc_checks,
+            arguments
+                .warnings_as_errors
+                .map(compiler_solidity::SolcStandardJsonInputSettingsWarningsAsErrors::new),
+            error_format,
             arguments.base_path,
             arguments.include_paths,
             arguments.allow_paths,
             debug_config,
+            debug_filter,
             arguments.output_directory,
+            format_version,
             arguments.overwrite,
         )?;
         return Ok(());
@@ -150,31 +493,230 @@ fn main_inner() -> anyhow::Result<()> {
             &mut solc,
             !arguments.disable_solc_optimizer,
             optimizer_settings,
+            target,
             arguments.force_evmla,
             arguments.is_system_mode,
             include_metadata_hash,
+            metadata_hash_placement,
+            arguments.determinism_check,
+            arguments.output_evm_bytecode,
+            arguments
+                .emit
+                .iter()
+                .any(|artifact| artifact.as_str() == Arguments::EMIT_YUL),
+            arguments.output_histogram,
+            arguments.normalize_sources,
+            arguments.evmla_stack_spill_threshold,
+            arguments.evmla_trace_tag,
+            arguments.evmla_debug_locations,
+            arguments.reentrancy_guard,
+            arguments.order_by_selector,
+            arguments.contract_time_budget.map(Duration::from_secs),
+            arguments.strict,
+            arguments.erc_checks,
+            match arguments.mutation_specification {
+                Some(path) => Some(compiler_solidity::MutationSpecification::try_from_path(
+                    path.as_path(),
+                )?),
+                None => None,
+            },
+            arguments
+                .warnings_as_errors
+                .map(compiler_solidity::SolcStandardJsonInputSettingsWarningsAsErrors::new),
+            error_format,
+            arguments.recommend_optimization,
             arguments.base_path,
             arguments.include_paths,
             arguments.allow_paths,
+            artifact_store.as_ref(),
             debug_config,
+            debug_filter,
         )
     }?;
 
+    if let Some(lockfile_path) = arguments.lockfile.as_ref() {
+        let lockfile = compiler_solidity::Lockfile::new(build.compiled_sources.clone());
+        match locked {
+            Some(locked) => locked.check(&lockfile)?,
+            None => lockfile.write(lockfile_path.as_path())?,
+        }
+    }
+
+    for (path, skipped) in build.skipped.iter() {
+        eprintln!("Warning: Skipped `{path}`: {skipped}");
+    }
+
+    if arguments.report_shared_constants {
+        for constant in build.shared_constants().into_iter() {
+            eprintln!("{constant}");
+        }
+    }
+
+    if let Some(artifact_store) = artifact_store.as_ref() {
+        artifact_store.write(&build)?;
+    }
+
+    let output_metadata = arguments
+        .emit
+        .iter()
+        .any(|artifact| artifact.as_str() == Arguments::EMIT_METADATA);
+    let output_calldata = arguments
+        .emit
+        .iter()
+        .any(|artifact| artifact.as_str() == Arguments::EMIT_CALLDATA);
     if let Some(output_directory) = arguments.output_directory {
         std::fs::create_dir_all(&output_directory)?;
 
-        build.write_to_directory(
+        let mut artifacts = Vec::new();
+        for (name, mutant) in std::mem::take(&mut build.mutants).into_iter() {
+            let mut mutant_directory = output_directory.clone();
+            mutant_directory.push(compiler_solidity::Build::MUTANTS_DIRECTORY_NAME);
+            mutant_directory.push(name);
+            std::fs::create_dir_all(&mutant_directory)?;
+
+            artifacts.extend(mutant.write_to_directory(
+                &mutant_directory,
+                arguments.output_assembly,
+                arguments.output_binary,
+                arguments.output_histogram,
+                output_metadata,
+                output_calldata,
+                format_version,
+                output_layout,
+                arguments.overwrite,
+            )?);
+        }
+
+        if arguments.output_source_hashes {
+            artifacts.extend(build.write_source_hashes_to_directory(
+                &output_directory,
+                format_version,
+                arguments.overwrite,
+            )?);
+        }
+        let source_hashes = std::mem::take(&mut build.source_hashes);
+        let source_names = std::mem::take(&mut build.source_names);
+        let source_code = std::mem::take(&mut build.source_code);
+        let compiled_sources = std::mem::take(&mut build.compiled_sources);
+        let output_sources = arguments
+            .emit
+            .iter()
+            .any(|artifact| artifact.as_str() == Arguments::EMIT_SOURCES);
+        let source_bundle = if output_sources || verify_bundle_settings.is_some() {
+            Some(compiler_solidity::BuildSourceBundle::try_new(
+                source_names.as_slice(),
+                &source_code,
+                &compiled_sources,
+                source_base_path.as_deref(),
+                source_include_paths.as_slice(),
+            )?)
+        } else {
+            None
+        };
+        let verify_bundles = match (verify_bundle_settings, source_bundle.as_ref()) {
+            (Some(settings), Some(source_bundle)) => {
+                compiler_solidity::BuildVerifyBundle::new_all_from_build(
+                    &build,
+                    &source_bundle.sources,
+                    settings,
+                    &solc.version()?.default,
+                    arguments.force_evmla,
+                    arguments.is_system_mode,
+                )
+            }
+            _ => vec![],
+        };
+        artifacts.extend(build.write_to_directory(
             &output_directory,
             arguments.output_assembly,
             arguments.output_binary,
+            arguments.output_histogram,
+            output_metadata,
+            output_calldata,
+            format_version,
+            output_layout,
             arguments.overwrite,
-        )?;
+        )?);
+
+        if let Some(source_bundle) = source_bundle.as_ref().filter(|_| output_sources) {
+            artifacts.extend(source_bundle.write(
+                &output_directory,
+                source_banner.as_deref(),
+                arguments.overwrite,
+            )?);
+        }
+        for verify_bundle in verify_bundles.iter() {
+            artifacts.push(verify_bundle.write(&output_directory)?);
+        }
+
+        if sign_key.is_some() || output_archive {
+            let mut toolchain = BTreeMap::new();
+            toolchain.insert("zksolc".to_owned(), env!("CARGO_PKG_VERSION").to_owned());
+            toolchain.insert(
+                "llvm".to_owned(),
+                inkwell::support::get_commit_id().to_string(),
+            );
+            if !arguments.llvm_ir && !arguments.zkasm {
+                toolchain.insert("solc".to_owned(), solc.version()?.long);
+            }
+            let mut parameters = BTreeMap::new();
+            parameters.insert(
+                "metadataHash".to_owned(),
+                if include_metadata_hash {
+                    "keccak256"
+                } else {
+                    "none"
+                }
+                .to_owned(),
+            );
+            parameters.insert(
+                "metadataHashPlacement".to_owned(),
+                metadata_hash_placement.to_string(),
+            );
+            let manifest = compiler_solidity::BuildManifest::try_new(
+                &output_directory,
+                artifacts.as_slice(),
+                &source_hashes
+                    .into_iter()
+                    .map(|(path, hash)| (path, hash.keccak256))
+                    .collect(),
+                parameters,
+                toolchain,
+                sign_key.as_ref(),
+            )?;
+            manifest.write(&output_directory, sign_key.as_ref())?;
+
+            if output_archive {
+                let archive_path =
+                    compiler_solidity::BuildArchive::write(&output_directory, &manifest)?;
+                eprintln!("Artifact archive written to {archive_path:?}.");
+            }
+        }
 
         eprintln!(
             "Compiler run successful. Artifact(s) can be found in directory {output_directory:?}."
         );
-    } else if arguments.output_assembly || arguments.output_binary {
+    } else if arguments.output_assembly
+        || arguments.output_binary
+        || arguments.output_source_hashes
+        || arguments.output_histogram
+        || !arguments.emit.is_empty()
+    {
+        if arguments.output_source_hashes {
+            for (path, hash) in build.source_hashes.iter() {
+                println!("Source `{path}` keccak256: {}", hash.keccak256);
+                println!("Source `{path}` sha256: {}", hash.sha256);
+            }
+        }
         for (path, contract) in build.contracts.into_iter() {
+            let calldata = if output_calldata {
+                contract
+                    .abi()
+                    .map(|abi| compiler_solidity::BuildCalldata::from_abi(&abi))
+            } else {
+                None
+            };
+
             if arguments.output_assembly {
                 println!(
                     "Contract `{}` assembly:\n\n{}",
@@ -188,6 +730,31 @@ fn main_inner() -> anyhow::Result<()> {
                     hex::encode(contract.build.bytecode)
                 );
             }
+            if let Some(evm_bytecode) = contract.evm_bytecode {
+                println!("Contract `{path}` EVM bytecode: 0x{evm_bytecode}");
+            }
+            if let Some(yul) = contract.yul {
+                println!("Contract `{path}` Yul:\n\n{yul}");
+            }
+            if output_metadata {
+                println!("Contract `{}` metadata: {}", path, contract.metadata_json);
+            }
+            if let Some(calldata) = calldata {
+                println!("Contract `{path}` calldata:");
+                for (signature, calldata) in calldata.into_iter() {
+                    println!("{signature}: {calldata}");
+                }
+            }
+            if arguments.output_histogram {
+                if let Some(histogram) = contract.histogram {
+                    println!(
+                        "Contract `{}` instruction histogram:\n\n{}",
+                        path,
+                        serde_json::to_string_pretty(&format_version.to_value(&histogram))
+                            .expect("Always valid")
+                    );
+                }
+            }
         }
     } else {
         eprintln!("Compiler run successful. No output requested. Use --asm and --bin flags.");
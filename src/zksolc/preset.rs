@@ -0,0 +1,168 @@
+//!
+//! Solidity to zkEVM compiler output presets.
+//!
+
+use std::str::FromStr;
+
+use crate::arguments::Arguments;
+
+///
+/// The named preset expanding to the output flags.
+///
+/// The flags set explicitly are kept, and the preset only enables the ones it needs.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// The artifacts needed for the source code verification: bytecode, metadata, and source
+    /// hashes, with the determinism check.
+    Verify,
+    /// The bytecode only, built quickly with the minimal optimizations.
+    Dev,
+    /// All the intermediate representations, assembly, EVM bytecode, and instruction histograms,
+    /// for auditing the translation.
+    Audit,
+}
+
+impl Preset {
+    /// The debug output subdirectory of the `audit` preset.
+    pub const AUDIT_DEBUG_DIRECTORY_NAME: &'static str = "debug";
+
+    ///
+    /// Enables the flags of the preset.
+    ///
+    pub fn apply(self, arguments: &mut Arguments) -> anyhow::Result<()> {
+        match self {
+            Self::Verify => {
+                arguments.output_binary = true;
+                arguments.output_source_hashes = true;
+                arguments.determinism_check = true;
+                Self::emit(arguments, Arguments::EMIT_METADATA);
+            }
+            Self::Dev => {
+                arguments.output_binary = true;
+                arguments.optimization.get_or_insert('1');
+            }
+            Self::Audit => {
+                let output_directory = arguments.output_directory.as_ref().ok_or_else(|| {
+                    anyhow::anyhow!("The `audit` preset requires the `--output-dir` option.")
+                })?;
+                arguments
+                    .debug_output_directory
+                    .get_or_insert_with(|| output_directory.join(Self::AUDIT_DEBUG_DIRECTORY_NAME));
+                arguments.output_assembly = true;
+                arguments.output_binary = true;
+                arguments.output_evm_bytecode = true;
+                arguments.output_histogram = true;
+                Self::emit(arguments, Arguments::EMIT_YUL);
+                Self::emit(arguments, Arguments::EMIT_METADATA);
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Adds the artifact to the emitted ones, if it is not there yet.
+    ///
+    fn emit(arguments: &mut Arguments, artifact: &str) {
+        if !arguments.emit.iter().any(|emitted| emitted == artifact) {
+            arguments.emit.push(artifact.to_owned());
+        }
+    }
+}
+
+impl FromStr for Preset {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "verify" => Ok(Self::Verify),
+            "dev" => Ok(Self::Dev),
+            "audit" => Ok(Self::Audit),
+            string => anyhow::bail!(
+                "Unknown preset `{}`. Supported presets: `verify`, `dev`, `audit`.",
+                string
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use structopt::StructOpt;
+
+    use crate::arguments::Arguments;
+
+    use super::Preset;
+
+    fn arguments(preset: &str, extra: &[&str]) -> Arguments {
+        let mut arguments = Arguments::from_iter(
+            ["zksolc", "Test.sol", "--preset", preset]
+                .iter()
+                .chain(extra.iter()),
+        );
+        Preset::from_str(preset)
+            .expect("Always valid")
+            .apply(&mut arguments)
+            .expect("Always valid");
+        arguments.validate().expect("Always valid");
+        arguments
+    }
+
+    #[test]
+    fn verify() {
+        let arguments = arguments("verify", &["--output-dir", "build"]);
+        assert!(arguments.output_binary);
+        assert!(arguments.output_source_hashes);
+        assert!(arguments.determinism_check);
+        assert_eq!(arguments.emit, vec![Arguments::EMIT_METADATA.to_owned()]);
+    }
+
+    #[test]
+    fn dev() {
+        let arguments = arguments("dev", &[]);
+        assert!(arguments.output_binary);
+        assert_eq!(arguments.optimization, Some('1'));
+
+        let arguments = self::arguments("dev", &["-O", "3"]);
+        assert_eq!(arguments.optimization, Some('3'));
+    }
+
+    #[test]
+    fn audit() {
+        let arguments = arguments("audit", &["--output-dir", "build", "--emit", "metadata"]);
+        assert_eq!(
+            arguments.debug_output_directory,
+            Some(PathBuf::from("build").join(Preset::AUDIT_DEBUG_DIRECTORY_NAME))
+        );
+        assert!(arguments.output_assembly);
+        assert!(arguments.output_binary);
+        assert!(arguments.output_evm_bytecode);
+        assert!(arguments.output_histogram);
+        assert!(!arguments.recommend_optimization);
+        assert_eq!(
+            arguments.emit,
+            vec![
+                Arguments::EMIT_METADATA.to_owned(),
+                Arguments::EMIT_YUL.to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn audit_without_output_directory() {
+        let mut arguments = Arguments::from_iter(["zksolc", "Test.sol", "--preset", "audit"]);
+        let error = Preset::Audit
+            .apply(&mut arguments)
+            .expect_err("Always invalid");
+        assert!(error.to_string().contains("--output-dir"));
+    }
+
+    #[test]
+    fn unknown() {
+        let error = Preset::from_str("release").expect_err("Always invalid");
+        assert!(error.to_string().contains("Unknown preset `release`"));
+    }
+}
@@ -0,0 +1,44 @@
+//!
+//! Solidity to zkEVM compiler `replay` subcommand arguments.
+//!
+
+use std::path::PathBuf;
+
+use structopt::StructOpt;
+
+///
+/// Reproduces the build pinned by a verification bundle and compares the contract bytecode hash
+/// to the expected one.
+///
+/// Example: zksolc replay verify-bundle.json --solc ./solc-0.8.20
+///
+#[derive(Debug, StructOpt)]
+#[structopt(name = "The zkEVM Solidity verification bundle replay")]
+pub struct ReplayArguments {
+    /// The verification bundle JSON file.
+    #[structopt(parse(from_os_str))]
+    pub bundle: PathBuf,
+
+    /// Specify the path to the `solc` executable of the bundle version.
+    /// By default, the one in `${PATH}` is used.
+    #[structopt(long = "solc")]
+    pub solc: Option<String>,
+}
+
+impl Default for ReplayArguments {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReplayArguments {
+    /// The subcommand name.
+    pub const SUBCOMMAND: &'static str = "replay";
+
+    ///
+    /// Parses the arguments following the subcommand name.
+    ///
+    pub fn new() -> Self {
+        Self::from_iter(std::env::args().skip(1))
+    }
+}
@@ -0,0 +1,89 @@
+//!
+//! Solidity to zkEVM compiler `serve` subcommand arguments.
+//!
+
+use structopt::StructOpt;
+
+///
+/// Runs zksolc as a long-lived compile server.
+///
+/// Example: zksolc serve --port 3050 --solc ./solc-0.8.20
+///
+#[derive(Debug, StructOpt)]
+#[structopt(name = "The zkEVM Solidity compile server")]
+pub struct ServeArguments {
+    /// The listening TCP port.
+    #[structopt(long = "port")]
+    pub port: u16,
+
+    /// The listening address. Defaults to `127.0.0.1`, so the server is not exposed to the network
+    /// unless explicitly requested.
+    #[structopt(long = "host", default_value = "127.0.0.1")]
+    pub host: String,
+
+    /// Specify the path to the `solc` executable. By default, the one in `${PATH}` is used.
+    #[structopt(long = "solc")]
+    pub solc: Option<String>,
+
+    /// The maximal request body size in bytes.
+    #[structopt(long = "max-request-size")]
+    pub max_request_size: Option<usize>,
+
+    /// The maximal request handling time in seconds. The compilation is killed on exceeding it.
+    #[structopt(long = "request-timeout")]
+    pub request_timeout: Option<u64>,
+
+    /// The maximal number of requests handled concurrently. The excess requests are rejected.
+    #[structopt(long = "max-concurrent-requests")]
+    pub max_concurrent_requests: Option<usize>,
+
+    /// The number of queued jobs, which are compiled concurrently.
+    /// Both the synchronous and the `/jobs` compilations are queued.
+    #[structopt(long = "max-concurrent-jobs")]
+    pub max_concurrent_jobs: Option<usize>,
+
+    /// The maximal number of pending jobs in the queue. The excess submissions are rejected.
+    #[structopt(long = "max-queued-jobs")]
+    pub max_queued_jobs: Option<usize>,
+
+    /// The maximal total size of the queued inputs and the kept outputs in bytes.
+    /// The oldest outputs are evicted first, and the excess submissions are rejected.
+    #[structopt(long = "max-queue-size")]
+    pub max_queue_size: Option<usize>,
+
+    /// The maximal number of requests per client address per minute. Unlimited by default.
+    #[structopt(long = "rate-limit")]
+    pub rate_limit: Option<usize>,
+
+    /// The sandboxed `solc` address space limit in megabytes.
+    /// All requests are compiled in the sandbox, overriding their `sandbox` setting, so the
+    /// sources must be specified inline, and `solc` is run in an empty temporary directory.
+    #[structopt(long = "sandbox-memory-limit")]
+    pub sandbox_memory_limit: Option<u64>,
+
+    /// The sandboxed `solc` CPU time limit in seconds.
+    #[structopt(long = "sandbox-cpu-time-limit")]
+    pub sandbox_cpu_time_limit: Option<u64>,
+
+    /// The sandboxed `solc` written file size limit in megabytes.
+    #[structopt(long = "sandbox-file-size-limit")]
+    pub sandbox_file_size_limit: Option<u64>,
+}
+
+impl Default for ServeArguments {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServeArguments {
+    /// The subcommand name.
+    pub const SUBCOMMAND: &'static str = "serve";
+
+    ///
+    /// Parses the arguments following the subcommand name.
+    ///
+    pub fn new() -> Self {
+        Self::from_iter(std::env::args().skip(1))
+    }
+}
@@ -0,0 +1,50 @@
+//!
+//! Solidity to zkEVM compiler `workspace` subcommand arguments.
+//!
+
+use std::path::PathBuf;
+
+use structopt::StructOpt;
+
+///
+/// Compiles all projects of a workspace config.
+///
+/// Example: zksolc workspace ./zksolc-workspace.json
+///
+#[derive(Debug, StructOpt)]
+#[structopt(name = "The zkEVM Solidity workspace compiler")]
+pub struct WorkspaceArguments {
+    /// The workspace config file.
+    #[structopt(parse(from_os_str))]
+    pub config: PathBuf,
+
+    /// Allow fetching the standard JSON input sources specified by `http://` and IPFS URLs.
+    #[structopt(long = "allow-network")]
+    pub allow_network: bool,
+
+    /// Set the HTTP gateway used to fetch the IPFS sources.
+    #[structopt(long = "ipfs-gateway")]
+    pub ipfs_gateway: Option<String>,
+
+    /// Set the output format version.
+    #[structopt(long = "format-version")]
+    pub format_version: Option<String>,
+}
+
+impl Default for WorkspaceArguments {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorkspaceArguments {
+    /// The subcommand name.
+    pub const SUBCOMMAND: &'static str = "workspace";
+
+    ///
+    /// Parses the arguments following the subcommand name.
+    ///
+    pub fn new() -> Self {
+        Self::from_iter(std::env::args().skip(1))
+    }
+}